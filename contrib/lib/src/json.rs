@@ -18,7 +18,7 @@ use std::ops::{Deref, DerefMut};
 use std::io::{self, Read};
 use std::iter::FromIterator;
 
-use rocket::request::Request;
+use rocket::request::{Request, GuardError};
 use rocket::outcome::Outcome::*;
 use rocket::data::{Outcome, Transform, Transform::*, Transformed, Data, FromData};
 use rocket::response::{self, Responder, content};
@@ -156,6 +156,11 @@ impl<'a, T: Deserialize<'a>> FromData<'a> for Json<T> {
             }
         }
     }
+
+    #[inline(always)]
+    fn guard_error(error: Self::Error) -> GuardError {
+        GuardError::from_debug(&error)
+    }
 }
 
 /// Serializes the wrapped value into JSON. Returns a response with Content-Type
@@ -17,7 +17,7 @@
 use std::io::Read;
 use std::ops::{Deref, DerefMut};
 
-use rocket::request::Request;
+use rocket::request::{Request, GuardError};
 use rocket::outcome::Outcome::*;
 use rocket::data::{Outcome, Transform, Transform::*, Transformed, Data, FromData};
 use rocket::response::{self, Responder, content};
@@ -145,6 +145,11 @@ impl<'a, T: Deserialize<'a>> FromData<'a> for MsgPack<T> {
             }
         }
     }
+
+    #[inline(always)]
+    fn guard_error(error: Self::Error) -> GuardError {
+        GuardError::new(error)
+    }
 }
 
 /// Serializes the wrapped value into MessagePack. Returns a response with
@@ -0,0 +1,435 @@
+use std::any::TypeId;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use rocket::{Rocket, Route, Request, Data, ParamInfo};
+use rocket::handler::{Handler, Outcome};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::response::content::Content;
+use rocket::http::{Method, MediaType, ContentType};
+use rocket::http::route::{Source, Kind as SegmentKind};
+
+use serde_json::{json, Map, Value};
+
+use crate::openapi::OpenApiSchema;
+
+/// The version of the OpenAPI specification documents are written in.
+const OPENAPI_VERSION: &str = "3.0.3";
+
+/// The functions of a registered [`OpenApiSchema`] implementation.
+#[derive(Clone, Copy)]
+struct SchemaFns {
+    schema: fn() -> Value,
+    media_type: fn() -> Option<MediaType>,
+    required: fn() -> bool,
+}
+
+impl SchemaFns {
+    fn of<T: OpenApiSchema>() -> SchemaFns {
+        SchemaFns { schema: T::schema, media_type: T::media_type, required: T::required }
+    }
+}
+
+/// A document served by an attached `OpenApi` fairing.
+#[derive(Default)]
+struct Served {
+    /// The number of routes the document describes.
+    routes: usize,
+    document: String,
+}
+
+/// Generates an OpenAPI 3 document describing the routes of a `Rocket`
+/// instance and optionally serves it.
+///
+/// See the [module level docs](crate::openapi) for usage details.
+#[derive(Clone)]
+pub struct OpenApi {
+    title: String,
+    version: String,
+    description: Option<String>,
+    schemas: HashMap<TypeId, SchemaFns>,
+    serve: Option<String>,
+    served: Arc<RwLock<Served>>,
+}
+
+macro_rules! register {
+    ($api:expr, $($T:ty),*) => ($(
+        $api = $api.schema::<$T>().schema::<Option<$T>>();
+    )*)
+}
+
+impl OpenApi {
+    /// Returns a new `OpenApi` for a document with the title `title` and API
+    /// version `version`. The schemas of strings, numbers, `bool`, `PathBuf`,
+    /// and `Option`s of these are registered.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::openapi::OpenApi;
+    ///
+    /// let api = OpenApi::new("Todo API", "1.0.0");
+    /// ```
+    pub fn new<T: Into<String>, V: Into<String>>(title: T, version: V) -> OpenApi {
+        let mut api = OpenApi {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            schemas: HashMap::new(),
+            serve: None,
+            served: Arc::default(),
+        };
+
+        register!(api, String, &'static str, &'static rocket::http::RawStr,
+            std::path::PathBuf, bool, i8, i16, i32, i64, isize, u8, u16, u32, u64,
+            usize, f32, f64);
+
+        api
+    }
+
+    /// Sets the description of the API to `description`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::openapi::OpenApi;
+    ///
+    /// let api = OpenApi::new("Todo API", "1.0.0").description("Manages todos.");
+    /// ```
+    pub fn description<D: Into<String>>(mut self, description: D) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Registers the schema of the type `T`. Parameters, data guards, and
+    /// responders of type `T` are described using `T`'s [`OpenApiSchema`]
+    /// implementation. Types are matched exactly: to describe a handler that
+    /// returns `Option<Json<User>>`, register `Option<Json<User>>`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::openapi::{OpenApi, OpenApiSchema};
+    /// use rocket_contrib::json::Json;
+    /// use serde_json::json;
+    ///
+    /// struct Task { description: String }
+    ///
+    /// impl OpenApiSchema for Task {
+    ///     fn schema() -> serde_json::Value {
+    ///         json!({ "type": "object", "properties": { "description": String::schema() } })
+    ///     }
+    /// }
+    ///
+    /// let api = OpenApi::new("Todo API", "1.0.0")
+    ///     .schema::<Json<Task>>()
+    ///     .schema::<Option<Json<Task>>>();
+    /// ```
+    pub fn schema<T: OpenApiSchema + 'static>(mut self) -> Self {
+        self.schemas.insert(TypeId::of::<T>(), SchemaFns::of::<T>());
+        self
+    }
+
+    /// Serves the document at `path` when `self` is attached to a `Rocket`
+    /// instance. The document is written in YAML if `path` ends in `.yaml` or
+    /// `.yml` and in JSON otherwise.
+    ///
+    /// The document is generated when `self` is attached and generated anew
+    /// at launch if routes were mounted since, so that it describes every
+    /// route. A `Rocket` instance that isn't launched, like that of a
+    /// [`Client`](rocket::local::Client), serves the document describing the
+    /// routes mounted before `self` was attached.
+    ///
+    /// # Panics
+    ///
+    /// Attaching `self` panics if `path` is not a valid route path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket_contrib::openapi::OpenApi;
+    ///
+    /// let api = OpenApi::new("Todo API", "1.0.0").serve("/openapi.json");
+    /// ```
+    pub fn serve<P: Into<String>>(mut self, path: P) -> Self {
+        self.serve = Some(path.into());
+        self
+    }
+
+    /// Returns the document describing the routes of `rocket`.
+    ///
+    /// Every route with a standard HTTP method is described. Path and query
+    /// parameters, data guards, and responders are described by the schemas
+    /// registered for their types; a type without a registered schema has an
+    /// empty schema, and a responder without one has no described content. A
+    /// warning is logged for each such type. Request guards and route hosts are
+    /// not described.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket_contrib::openapi::OpenApi;
+    ///
+    /// #[get("/hello/<name>")]
+    /// fn hello(name: String) -> String {
+    ///     format!("Hello, {}!", name)
+    /// }
+    ///
+    /// let rocket = rocket::ignite().mount("/", routes![hello]);
+    /// let document = OpenApi::new("Hello", "1.0.0").document(&rocket);
+    ///
+    /// let operation = &document["paths"]["/hello/{name}"]["get"];
+    /// assert_eq!(operation["operationId"], "hello");
+    /// assert_eq!(operation["parameters"][0]["in"], "path");
+    /// assert_eq!(operation["parameters"][0]["schema"]["type"], "string");
+    /// ```
+    pub fn document(&self, rocket: &Rocket) -> Value {
+        self.describe(rocket.routes())
+    }
+
+    /// Returns the document describing `routes`.
+    fn describe<'r, I: Iterator<Item = &'r Route>>(&self, routes: I) -> Value {
+        let mut paths = Map::new();
+        let mut operation_ids = HashSet::new();
+        let mut missing = BTreeSet::new();
+        for route in routes {
+            let method = match route.method {
+                Method::Get | Method::Put | Method::Post | Method::Delete | Method::Options
+                    | Method::Head | Method::Patch | Method::Trace => route.method.as_str(),
+                _ => continue,
+            };
+
+            let path = paths.entry(openapi_path(route)).or_insert_with(|| json!({}));
+            let method = method.to_ascii_lowercase();
+            if path.get(&method).is_none() {
+                missing.extend(self.missing_schemas(route));
+                let mut operation = self.operation(route);
+                if let Some(name) = route.name.filter(|name| operation_ids.insert(*name)) {
+                    operation["operationId"] = json!(name);
+                }
+
+                path[method] = operation;
+            }
+        }
+
+        if !missing.is_empty() {
+            warn!("OpenAPI: types without a registered schema are left undescribed:");
+            for name in missing {
+                warn_!("{}", name);
+            }
+        }
+
+        let mut info = json!({ "title": self.title, "version": self.version });
+        if let Some(ref description) = self.description {
+            info["description"] = json!(description);
+        }
+
+        json!({ "openapi": OPENAPI_VERSION, "info": info, "paths": paths })
+    }
+
+    /// Returns the document describing the routes of `rocket` as JSON. See
+    /// [`OpenApi::document()`].
+    pub fn to_json(&self, rocket: &Rocket) -> String {
+        serde_json::to_string_pretty(&self.document(rocket))
+            .expect("a JSON value is always serializable")
+    }
+
+    /// Returns the document describing the routes of `rocket` as YAML. See
+    /// [`OpenApi::document()`].
+    pub fn to_yaml(&self, rocket: &Rocket) -> String {
+        serde_yaml::to_string(&self.document(rocket))
+            .expect("a JSON value is always serializable")
+    }
+
+    /// Renders the document describing `routes` in the format of the served
+    /// document at `path`.
+    fn render<'r, I: Iterator<Item = &'r Route>>(&self, path: &str, routes: I) -> String {
+        let document = self.describe(routes);
+        match is_yaml(path) {
+            true => serde_yaml::to_string(&document)
+                .expect("a JSON value is always serializable"),
+            false => serde_json::to_string_pretty(&document)
+                .expect("a JSON value is always serializable"),
+        }
+    }
+
+    fn schema_fns(&self, id: TypeId) -> Option<SchemaFns> {
+        self.schemas.get(&id).cloned()
+    }
+
+    /// Returns the names of the types of the parameters, data guard, and
+    /// responder of `route` that have no registered schema.
+    fn missing_schemas<'r>(&'r self, route: &'r Route) -> impl Iterator<Item = &'static str> + 'r {
+        let signature = route.signature.iter();
+        let params = signature.clone()
+            .flat_map(|s| s.params.iter())
+            .filter(|p| p.source == Source::Path || p.source == Source::Query
+                || p.source == Source::Data)
+            .map(|p| p.ty);
+
+        params.chain(signature.filter_map(|s| s.output))
+            .filter(move |ty| self.schema_fns(ty.id).is_none())
+            .map(|ty| ty.name)
+    }
+
+    fn operation(&self, route: &Route) -> Value {
+        let params = route.signature.iter().flat_map(|s| s.params.iter());
+        let parameters: Vec<_> = params.clone()
+            .filter_map(|param| self.parameter(param))
+            .collect();
+
+        // A route's format is the request's `Content-Type` for methods with
+        // payloads and its `Accept` otherwise.
+        let (request_format, response_format) = match route.method.supports_payload() {
+            true => (route.format.as_ref(), None),
+            false => (None, route.format.as_ref()),
+        };
+
+        let mut operation = json!({ "parameters": parameters });
+        if let Some(data) = params.clone().find(|p| p.source == Source::Data) {
+            let content = self.content(data.ty.id, request_format)
+                .unwrap_or_else(|| content(&MediaType::Binary, json!({})));
+
+            operation["requestBody"] = json!({ "required": true, "content": content });
+        }
+
+        let mut response = json!({ "description": "Success" });
+        let output = route.signature.as_ref().and_then(|s| s.output);
+        if let Some(content) = output.and_then(|ty| self.content(ty.id, response_format)) {
+            response["content"] = content;
+        }
+
+        operation["responses"] = json!({ "200": response });
+        operation
+    }
+
+    fn parameter(&self, param: &ParamInfo) -> Option<Value> {
+        let location = match param.source {
+            Source::Path => "path",
+            Source::Query => "query",
+            _ => return None,
+        };
+
+        let schema_fns = self.schema_fns(param.ty.id);
+        let schema = schema_fns.map_or_else(|| json!({}), |s| (s.schema)());
+        let required = param.source == Source::Path || schema_fns.map_or(true, |s| (s.required)());
+        let mut parameter = json!({
+            "name": param.name,
+            "in": location,
+            "required": required,
+            "schema": schema,
+        });
+
+        // The fields of a trailing query parameter are query parameters.
+        if param.source == Source::Query && param.kind == SegmentKind::Multi {
+            parameter["style"] = json!("form");
+            parameter["explode"] = json!(true);
+        }
+
+        Some(parameter)
+    }
+
+    fn content(&self, id: TypeId, format: Option<&MediaType>) -> Option<Value> {
+        let schema_fns = self.schema_fns(id)?;
+        let media_type = (schema_fns.media_type)()
+            .or_else(|| format.cloned())
+            .unwrap_or(MediaType::Plain);
+
+        Some(content(&media_type, (schema_fns.schema)()))
+    }
+}
+
+/// Returns a media type object for `media_type` with the schema `schema`.
+fn content(media_type: &MediaType, schema: Value) -> Value {
+    let mut content = Map::new();
+    let name = format!("{}/{}", media_type.top(), media_type.sub());
+    content.insert(name, json!({ "schema": schema }));
+    Value::Object(content)
+}
+
+/// Returns the path of `route` in OpenAPI's format: `<param>` and `<param..>`
+/// segments are written `{param}`.
+fn openapi_path(route: &Route) -> String {
+    let mut path = String::new();
+    for segment in route.uri.segments() {
+        path.push('/');
+        if segment.starts_with('<') && segment.ends_with('>') {
+            let name = segment[1..segment.len() - 1].trim_end_matches("..");
+            path.push_str(&format!("{{{}}}", name));
+        } else {
+            path.push_str(segment);
+        }
+    }
+
+    if path.is_empty() {
+        path.push('/');
+    }
+
+    path
+}
+
+/// Returns `true` if the document served at `path` is written in YAML.
+fn is_yaml(path: &str) -> bool {
+    path.ends_with(".yaml") || path.ends_with(".yml")
+}
+
+/// Serves a generated document.
+#[derive(Clone)]
+struct DocumentHandler {
+    content_type: ContentType,
+    served: Arc<RwLock<Served>>,
+}
+
+impl Handler for DocumentHandler {
+    fn handle<'r>(&self, req: &'r Request<'_>, _: Data) -> Outcome<'r> {
+        let document = self.served.read().expect("document lock").document.clone();
+        Outcome::from(req, Content(self.content_type.clone(), document))
+    }
+}
+
+impl Fairing for OpenApi {
+    fn info(&self) -> Info {
+        Info { name: "OpenAPI", kind: Kind::Attach | Kind::Launch }
+    }
+
+    fn on_attach(&self, rocket: Rocket) -> Result<Rocket, Rocket> {
+        let path = match self.serve {
+            Some(ref path) => path,
+            None => return Ok(rocket),
+        };
+
+        let routes = rocket.routes().count();
+        let document = self.render(path, rocket.routes());
+        *self.served.write().expect("document lock") = Served { routes, document };
+
+        let content_type = match is_yaml(path) {
+            true => ContentType::new("application", "yaml"),
+            false => ContentType::JSON,
+        };
+
+        let handler = DocumentHandler { content_type, served: self.served.clone() };
+        Ok(rocket.mount("/", vec![Route::new(Method::Get, path, handler)]))
+    }
+
+    fn on_launch(&self, rocket: &Rocket) {
+        let path = match self.serve {
+            Some(ref path) => path,
+            None => return,
+        };
+
+        // Routes are never removed, so the document is current unless routes
+        // other than the document's own were mounted after attaching.
+        let mut served = self.served.write().expect("document lock");
+        if rocket.routes().count() == served.routes + 1 {
+            return;
+        }
+
+        let routes = rocket.routes()
+            .filter(|r| !(r.method == Method::Get && r.uri.path() == path && r.name.is_none()));
+
+        served.document = self.render(path, routes);
+        served.routes = rocket.routes().count() - 1;
+    }
+}
@@ -0,0 +1,97 @@
+//! OpenAPI 3 document generation from route attributes.
+//!
+//! [`OpenApi`] generates an [OpenAPI 3] document describing the routes mounted
+//! on a `Rocket` instance. Routes generated by codegen carry the
+//! [`Signature`](rocket::Signature) of their handler, so the document is always
+//! in sync with the code: every route is described by its method, path, path
+//! and query parameters, data guard, format, and responder. The schemas of the
+//! types involved are provided by implementations of [`OpenApiSchema`].
+//!
+//! [OpenAPI 3]: https://swagger.io/specification/
+//!
+//! # Enabling
+//!
+//! This module is only available when the `openapi` feature is enabled. Enable
+//! it in `Cargo.toml` as follows:
+//!
+//! ```toml
+//! [dependencies.rocket_contrib]
+//! version = "0.5.0-dev"
+//! default-features = false
+//! features = ["openapi"]
+//! ```
+//!
+//! # Usage
+//!
+//! Implement [`OpenApiSchema`] for the types of your parameters, data guards,
+//! and responders that don't already implement it, register them with
+//! [`OpenApi::schema()`], and attach the `OpenApi` fairing. Types are matched
+//! exactly; a warning is logged for every type without a registered schema
+//! when the document is generated. With [`OpenApi::serve()`], the document is
+//! served from a route at the given path and describes the routes mounted at
+//! launch:
+//!
+//! ```rust
+//! # #![feature(proc_macro_hygiene)]
+//! # #[macro_use] extern crate rocket;
+//! use rocket_contrib::json::Json;
+//! use rocket_contrib::openapi::{OpenApi, OpenApiSchema};
+//! use serde_json::json;
+//!
+//! struct Task {
+//!     description: String,
+//! }
+//!
+//! # impl serde::Serialize for Task {
+//! #     fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+//! #         serde::Serialize::serialize(&self.description, s)
+//! #     }
+//! # }
+//! #
+//! # impl<'de> serde::Deserialize<'de> for Task {
+//! #     fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Task, D::Error> {
+//! #         <String as serde::Deserialize>::deserialize(d).map(|description| Task { description })
+//! #     }
+//! # }
+//! #
+//! impl OpenApiSchema for Task {
+//!     fn schema() -> serde_json::Value {
+//!         json!({ "type": "object", "properties": { "description": String::schema() } })
+//!     }
+//! }
+//!
+//! #[get("/<id>")]
+//! fn task(id: usize) -> Option<Json<Task>> {
+//!     /* .. */
+//!     # None
+//! }
+//!
+//! #[post("/", data = "<task>")]
+//! fn new_task(task: Json<Task>) -> String {
+//!     /* .. */
+//!     # "".into()
+//! }
+//!
+//! fn main() {
+//!     let api = OpenApi::new("Todo API", "1.0.0")
+//!         .schema::<Json<Task>>()
+//!         .schema::<Option<Json<Task>>>()
+//!         .serve("/openapi.yaml");
+//!
+//!     # if false {
+//!     rocket::ignite()
+//!         .mount("/tasks", routes![task, new_task])
+//!         .attach(api)
+//!         .launch();
+//!     # }
+//! }
+//! ```
+//!
+//! The document can also be generated directly with [`OpenApi::document()`],
+//! [`OpenApi::to_json()`], or [`OpenApi::to_yaml()`].
+
+mod schema;
+mod document;
+
+pub use self::schema::OpenApiSchema;
+pub use self::document::OpenApi;
@@ -0,0 +1,159 @@
+use std::path::PathBuf;
+
+use rocket::http::{MediaType, RawStr};
+use rocket::request::{Form, LenientForm};
+
+use serde_json::{json, Value};
+
+/// Trait implemented by parameter, data guard, and responder types that can be
+/// described in an OpenAPI document.
+///
+/// The [`schema()`](OpenApiSchema::schema()) of a type is a [JSON Schema]
+/// object describing its values. For data guards and responders, the
+/// [`media_type()`](OpenApiSchema::media_type()) is the media type of the
+/// request or response body. A type is only described in a document if it is
+/// registered with [`OpenApi::schema()`](crate::openapi::OpenApi::schema()).
+///
+/// Implementations are provided for strings, numbers, `bool`, `PathBuf`,
+/// `Option<T>`, `Result<T, E>`, `Form<T>`, `LenientForm<T>`, `Json<T>` and,
+/// when the `msgpack` feature is enabled, `MsgPack<T>`.
+///
+/// [JSON Schema]: https://swagger.io/specification/#schema-object
+///
+/// # Example
+///
+/// ```rust
+/// use rocket_contrib::openapi::OpenApiSchema;
+/// use serde_json::json;
+///
+/// struct User {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// impl OpenApiSchema for User {
+///     fn schema() -> serde_json::Value {
+///         json!({
+///             "type": "object",
+///             "required": ["name", "age"],
+///             "properties": {
+///                 "name": String::schema(),
+///                 "age": u8::schema(),
+///             }
+///         })
+///     }
+/// }
+/// ```
+pub trait OpenApiSchema {
+    /// Returns the JSON Schema object describing values of this type.
+    fn schema() -> Value;
+
+    /// Returns the media type of a request or response body of this type.
+    /// When `None`, the default, the route's format is used if it has one and
+    /// `text/plain` otherwise.
+    fn media_type() -> Option<MediaType> {
+        None
+    }
+
+    /// Returns `true` if a query parameter of this type must be present in
+    /// requests. The default is `true`.
+    fn required() -> bool {
+        true
+    }
+}
+
+macro_rules! impl_schema {
+    ($($T:ty => $schema:tt),* $(,)?) => ($(
+        impl OpenApiSchema for $T {
+            fn schema() -> Value {
+                json!($schema)
+            }
+        }
+    )*)
+}
+
+impl_schema! {
+    String => { "type": "string" },
+    &str => { "type": "string" },
+    &RawStr => { "type": "string" },
+    PathBuf => { "type": "string" },
+    bool => { "type": "boolean" },
+    i8 => { "type": "integer", "format": "int32" },
+    i16 => { "type": "integer", "format": "int32" },
+    i32 => { "type": "integer", "format": "int32" },
+    i64 => { "type": "integer", "format": "int64" },
+    isize => { "type": "integer", "format": "int64" },
+    u8 => { "type": "integer", "format": "int32", "minimum": 0 },
+    u16 => { "type": "integer", "format": "int32", "minimum": 0 },
+    u32 => { "type": "integer", "format": "int64", "minimum": 0 },
+    u64 => { "type": "integer", "format": "int64", "minimum": 0 },
+    usize => { "type": "integer", "format": "int64", "minimum": 0 },
+    f32 => { "type": "number", "format": "float" },
+    f64 => { "type": "number", "format": "double" },
+}
+
+impl<T: OpenApiSchema> OpenApiSchema for Option<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn media_type() -> Option<MediaType> {
+        T::media_type()
+    }
+
+    fn required() -> bool {
+        false
+    }
+}
+
+/// Describes the success value only.
+impl<T: OpenApiSchema, E> OpenApiSchema for Result<T, E> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn media_type() -> Option<MediaType> {
+        T::media_type()
+    }
+}
+
+impl<T: OpenApiSchema> OpenApiSchema for Form<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn media_type() -> Option<MediaType> {
+        Some(MediaType::Form)
+    }
+}
+
+impl<T: OpenApiSchema> OpenApiSchema for LenientForm<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn media_type() -> Option<MediaType> {
+        Some(MediaType::Form)
+    }
+}
+
+impl<T: OpenApiSchema> OpenApiSchema for crate::json::Json<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn media_type() -> Option<MediaType> {
+        Some(MediaType::JSON)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl<T: OpenApiSchema> OpenApiSchema for crate::msgpack::MsgPack<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+
+    fn media_type() -> Option<MediaType> {
+        Some(MediaType::MsgPack)
+    }
+}
@@ -1,6 +1,6 @@
 use rocket::{Request, State, Outcome};
 use rocket::http::Status;
-use rocket::request::{self, FromRequest};
+use rocket::request::{self, FromRequest, GuardError};
 
 use crate::templates::ContextManager;
 
@@ -101,4 +101,9 @@ impl<'a> FromRequest<'a, '_> for Metadata<'a> {
                 Outcome::Failure((Status::InternalServerError, ()))
             })
     }
+
+    #[inline(always)]
+    fn guard_error(error: Self::Error) -> GuardError {
+        GuardError::new(error)
+    }
 }
@@ -0,0 +1,162 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use]
+#[cfg(feature = "openapi")]
+extern crate rocket;
+
+#[cfg(feature = "openapi")]
+mod openapi_tests {
+    use rocket::Rocket;
+    use rocket::http::{ContentType, Status};
+    use rocket::local::Client;
+    use rocket::request::Form;
+
+    use rocket_contrib::json::Json;
+    use rocket_contrib::openapi::{OpenApi, OpenApiSchema};
+    use serde_json::{json, Value};
+
+    #[derive(FromForm)]
+    struct Login {
+        user: String,
+    }
+
+    impl OpenApiSchema for Login {
+        fn schema() -> Value {
+            json!({ "type": "object", "properties": { "user": String::schema() } })
+        }
+    }
+
+    struct FileInfo(String);
+
+    impl serde::Serialize for FileInfo {
+        fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            s.serialize_str(&self.0)
+        }
+    }
+
+    impl OpenApiSchema for FileInfo {
+        fn schema() -> Value {
+            json!({ "type": "string", "format": "path" })
+        }
+    }
+
+    #[get("/<id>?<verbose>")]
+    fn item(id: usize, verbose: Option<bool>) -> Option<String> {
+        verbose.map(|_| id.to_string())
+    }
+
+    #[get("/files/<path..>")]
+    fn file(path: std::path::PathBuf) -> Json<FileInfo> {
+        Json(FileInfo(path.display().to_string()))
+    }
+
+    #[get("/latest", format = "json")]
+    fn latest() -> Option<String> {
+        None
+    }
+
+    #[post("/login", data = "<login>")]
+    fn login(login: Form<Login>) -> String {
+        login.into_inner().user
+    }
+
+    #[put("/", rank = 2)]
+    fn unnamed() -> impl rocket::response::Responder<'static> {
+        "unnamed"
+    }
+
+    fn api() -> OpenApi {
+        OpenApi::new("Test", "0.1.0")
+            .description("A test API.")
+            .schema::<Form<Login>>()
+            .schema::<Json<FileInfo>>()
+    }
+
+    fn rocket() -> Rocket {
+        rocket::ignite()
+            .mount("/items", routes![item, file, latest])
+            .mount("/", routes![login, unnamed])
+    }
+
+    #[test]
+    fn document_describes_routes() {
+        let document = api().document(&rocket());
+        assert_eq!(document["openapi"], "3.0.3");
+        assert_eq!(document["info"]["title"], "Test");
+        assert_eq!(document["info"]["description"], "A test API.");
+
+        let item = &document["paths"]["/items/{id}"]["get"];
+        assert_eq!(item["operationId"], "item");
+        assert_eq!(item["parameters"][0], json!({
+            "name": "id", "in": "path", "required": true,
+            "schema": { "type": "integer", "format": "int64", "minimum": 0 }
+        }));
+
+        assert_eq!(item["parameters"][1], json!({
+            "name": "verbose", "in": "query", "required": false,
+            "schema": { "type": "boolean" }
+        }));
+
+        let content = &item["responses"]["200"]["content"];
+        assert_eq!(content["text/plain"]["schema"], json!({ "type": "string" }));
+        assert!(item.get("requestBody").is_none());
+    }
+
+    #[test]
+    fn document_describes_bodies_and_formats() {
+        let document = api().document(&rocket());
+        let file = &document["paths"]["/items/files/{path}"]["get"];
+        assert_eq!(file["parameters"][0]["name"], "path");
+        let content = &file["responses"]["200"]["content"];
+        let schema = json!({ "type": "string", "format": "path" });
+        assert_eq!(content["application/json"]["schema"], schema);
+
+        let latest = &document["paths"]["/items/latest"]["get"];
+        let content = &latest["responses"]["200"]["content"];
+        assert_eq!(content["application/json"]["schema"], json!({ "type": "string" }));
+
+        let login = &document["paths"]["/login"]["post"];
+        assert_eq!(login["parameters"], json!([]));
+        let body = &login["requestBody"]["content"]["application/x-www-form-urlencoded"];
+        assert_eq!(body["schema"]["properties"]["user"], json!({ "type": "string" }));
+
+        let unnamed = &document["paths"]["/"]["put"];
+        assert_eq!(unnamed["operationId"], "unnamed");
+        assert!(unnamed["responses"]["200"].get("content").is_none());
+    }
+
+    #[test]
+    fn document_is_served() {
+        let client = Client::new(rocket().attach(api().serve("/openapi.json"))).unwrap();
+        let mut response = client.get("/openapi.json").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+
+        let document: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+        assert_eq!(document, api().document(&rocket()));
+
+        let client = Client::new(rocket().attach(api().serve("/docs/openapi.yaml"))).unwrap();
+        let mut response = client.get("/docs/openapi.yaml").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.body_string().unwrap().contains("openapi: 3.0.3"));
+    }
+
+    #[test]
+    fn served_document_is_regenerated_at_launch() {
+        use rocket::fairing::Fairing;
+
+        let api = api().serve("/openapi.json");
+        let rocket = rocket::ignite()
+            .mount("/", routes![login])
+            .attach(api.clone())
+            .mount("/items", routes![item, file, latest])
+            .mount("/", routes![unnamed]);
+
+        // A `Client` doesn't run launch fairings.
+        api.on_launch(&rocket);
+        let client = Client::new(rocket).unwrap();
+        let mut response = client.get("/openapi.json").dispatch();
+        let document: Value = serde_json::from_str(&response.body_string().unwrap()).unwrap();
+        assert_eq!(document, api().document(&rocket()));
+    }
+}
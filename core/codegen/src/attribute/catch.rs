@@ -1,23 +1,40 @@
 use proc_macro::{TokenStream, Span};
-use devise::{syn, Spanned, Result, FromMeta};
+use devise::{syn, Spanned, Result, FromMeta, MetaItem, ext::PathExt};
 use crate::proc_macro2::TokenStream as TokenStream2;
 
-use crate::http_codegen::Status;
+use crate::http_codegen::{Status, Optional};
 use crate::syn_ext::{syn_to_diag, IdentExt, ReturnTypeExt};
 use self::syn::{Attribute, parse::Parser};
 use crate::{CATCH_FN_PREFIX, CATCH_STRUCT_PREFIX};
 
+/// The status code in a `#[catch]` attribute: `None` for `default`.
+#[derive(Debug)]
+struct Code(Option<Status>);
+
+impl FromMeta for Code {
+    fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
+        if let MetaItem::Path(path) = meta {
+            if path.last_ident().map_or(false, |ident| ident == "default") {
+                return Ok(Code(None));
+            }
+        }
+
+        Ok(Code(Some(Status::from_meta(meta)?)))
+    }
+}
+
 /// The raw, parsed `#[catch(code)]` attribute.
 #[derive(Debug, FromMeta)]
 struct CatchAttribute {
     #[meta(naked)]
-    status: Status
+    status: Code
 }
 
 /// This structure represents the parsed `catch` attribute an associated items.
 struct CatchParams {
-    /// The status associated with the code in the `#[catch(code)]` attribute.
-    status: Status,
+    /// The status associated with the code in the `#[catch(code)]` attribute,
+    /// or `None` for `#[catch(default)]`.
+    status: Option<Status>,
     /// The function that was decorated with the `catch` attribute.
     function: syn::ItemFn,
 }
@@ -35,7 +52,7 @@ fn parse_params(args: TokenStream2, input: TokenStream) -> Result<CatchParams> {
         None => return Err(Span::call_site().error("internal error: bad attribute"))
     };
 
-    Ok(CatchParams { status: attribute.status, function })
+    Ok(CatchParams { status: attribute.status.0, function })
 }
 
 pub fn _catch(args: TokenStream, input: TokenStream) -> Result<TokenStream> {
@@ -47,19 +64,27 @@ pub fn _catch(args: TokenStream, input: TokenStream) -> Result<TokenStream> {
     let mut user_catcher_fn_name = catch.function.sig.ident.clone();
     let generated_struct_name = user_catcher_fn_name.prepend(CATCH_STRUCT_PREFIX);
     let generated_fn_name = user_catcher_fn_name.prepend(CATCH_FN_PREFIX);
-    let (vis, status) = (&catch.function.vis, &catch.status);
-    let status_code = status.0.code;
+    let vis = &catch.function.vis;
+    let status_code = Optional(catch.status.as_ref().map(|status| status.0.code));
 
     // Variables names we'll use and reuse.
-    define_vars_and_mods!(req, catcher, response, Request, Response);
+    define_vars_and_mods!(req, catcher, response, Request, Response, Status);
+
+    // A default catcher responds with the status of the error it's handling.
+    let status = match catch.status {
+        Some(ref status) => quote!(#status),
+        None => quote!(#req.error_status().unwrap_or(#Status::InternalServerError)),
+    };
 
     // Determine the number of parameters that will be passed in.
     let (fn_sig, inputs) = match catch.function.sig.inputs.len() {
         0 => (quote!(fn() -> _), quote!()),
         1 => (quote!(fn(&#Request) -> _), quote!(#req)),
+        2 => (quote!(fn(#Status, &#Request) -> _), quote!(#status, #req)),
         _ => return Err(catch.function.sig.inputs.span()
-                .error("invalid number of arguments: must be zero or one")
-                .help("catchers may optionally take an argument of type `&Request`"))
+                .error("invalid number of arguments: must be zero, one, or two")
+                .help("catchers may optionally take an argument of type `&Request` \
+                      or arguments of type `Status` and `&Request`"))
     };
 
     // Set the span of the function name to point to inputs so that a later type
@@ -10,7 +10,7 @@ use crate::proc_macro_ext::{Diagnostics, StringLit};
 use crate::syn_ext::{syn_to_diag, IdentExt};
 use self::syn::{Attribute, parse::Parser};
 
-use crate::http_codegen::{Method, MediaType, RoutePath, DataSegment, Optional};
+use crate::http_codegen::{Method, MediaType, RoutePath, DataSegment, HostPattern, Optional};
 use crate::attribute::segments::{Source, Kind, Segment};
 use crate::{ROUTE_FN_PREFIX, ROUTE_STRUCT_PREFIX, URI_MACRO_PREFIX, ROCKET_PARAM_PREFIX};
 
@@ -23,6 +23,8 @@ struct RouteAttribute {
     data: Option<SpanWrapped<DataSegment>>,
     format: Option<MediaType>,
     rank: Option<isize>,
+    host: Option<HostPattern>,
+    name: Option<String>,
 }
 
 /// The raw, parsed `#[method]` (e.g, `get`, `put`, `post`, etc.) attribute.
@@ -33,6 +35,8 @@ struct MethodRouteAttribute {
     data: Option<SpanWrapped<DataSegment>>,
     format: Option<MediaType>,
     rank: Option<isize>,
+    host: Option<HostPattern>,
+    name: Option<String>,
 }
 
 /// This structure represents the parsed `route` attribute and associated items.
@@ -78,6 +82,7 @@ fn parse_route(attr: RouteAttribute, function: syn::ItemFn) -> Result<Route> {
         }
     }
 
+    attr.host.as_ref().map(|h| dup_check(&mut segments, h.labels.iter().cloned(), &mut diags));
     dup_check(&mut segments, attr.path.path.iter().cloned(), &mut diags);
     attr.path.query.as_ref().map(|q| dup_check(&mut segments, q.iter().cloned(), &mut diags));
     dup_check(&mut segments, attr.data.clone().map(|s| s.value.0).into_iter(), &mut diags);
@@ -146,9 +151,14 @@ fn param_expr(seg: &Segment, ident: &syn::Ident, ty: &syn::Type) -> TokenStream2
         #Outcome::Forward(#data)
     });
 
+    let raw_str = match seg.source {
+        Source::Host => quote!(#req.raw_host_label(#i)),
+        _ => quote!(#req.raw_segment_str(#i)),
+    };
+
     let expr = match seg.kind {
         Kind::Single => quote_spanned! { span =>
-            match #req.raw_segment_str(#i) {
+            match #raw_str {
                 #_Some(__s) => match <#ty as #request::FromParam>::from_param(__s) {
                     #_Ok(__v) => __v,
                     #_Err(#error) => return #parse_error,
@@ -174,9 +184,10 @@ fn param_expr(seg: &Segment, ident: &syn::Ident, ty: &syn::Type) -> TokenStream2
     }
 }
 
-fn data_expr(ident: &syn::Ident, ty: &syn::Type) -> TokenStream2 {
+fn data_expr(name: &syn::Ident, ident: &syn::Ident, ty: &syn::Type) -> TokenStream2 {
     define_vars_and_mods!(req, data, FromData, Outcome, Transform);
     let span = ident.span().unstable().join(ty.span()).unwrap().into();
+    let name = name.to_string();
     quote_spanned! { span =>
         let __transform = <#ty as #FromData>::transform(#req, #data);
 
@@ -198,7 +209,10 @@ fn data_expr(ident: &syn::Ident, ty: &syn::Type) -> TokenStream2 {
         let #ident: #ty = match <#ty as #FromData>::from_data(#req, __outcome) {
             #Outcome::Success(__d) => __d,
             #Outcome::Forward(__d) => return #Outcome::Forward(__d),
-            #Outcome::Failure((__c, _)) => return #Outcome::Failure(__c),
+            #Outcome::Failure((__c, __e)) => {
+                #req.set_guard_error(<#ty as #FromData>::guard_error(__e).with_param(#name));
+                return #Outcome::Failure(__c);
+            }
         };
     }
 }
@@ -308,15 +322,20 @@ fn query_exprs(route: &Route) -> Option<TokenStream2> {
     })
 }
 
-fn request_guard_expr(ident: &syn::Ident, ty: &syn::Type) -> TokenStream2 {
+fn request_guard_expr(name: &syn::Ident, ident: &syn::Ident, ty: &syn::Type) -> TokenStream2 {
     define_vars_and_mods!(req, data, request, Outcome);
     let span = ident.span().unstable().join(ty.span()).unwrap().into();
+    let name = name.to_string();
     quote_spanned! { span =>
         #[allow(non_snake_case, unreachable_patterns, unreachable_code)]
         let #ident: #ty = match <#ty as #request::FromRequest>::from_request(#req) {
             #Outcome::Success(__v) => __v,
             #Outcome::Forward(_) => return #Outcome::Forward(#data),
-            #Outcome::Failure((__c, _)) => return #Outcome::Failure(__c),
+            #Outcome::Failure((__c, __e)) => {
+                let __e = <#ty as #request::FromRequest>::guard_error(__e);
+                #req.set_guard_error(__e.with_param(#name));
+                return #Outcome::Failure(__c);
+            }
         };
     }
 }
@@ -375,6 +394,79 @@ fn generate_respond_expr(route: &Route) -> TokenStream2 {
     }
 }
 
+/// Returns the name of `ty` as written, without the spaces that separate its
+/// tokens, e.g, `Option<Json<User>>` instead of `Option < Json < User > >`.
+fn type_name(ty: &syn::Type) -> String {
+    let tokens = quote!(#ty).to_string();
+    let chars: Vec<char> = tokens.chars().collect();
+    let mut name = String::with_capacity(tokens.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' {
+            let prev = if i > 0 { chars[i - 1] } else { ' ' };
+            let next = chars.get(i + 1).cloned().unwrap_or(' ');
+            if "<>:&'([".contains(prev) || "<>:,)]".contains(next) {
+                continue;
+            }
+        }
+
+        name.push(c);
+    }
+
+    name
+}
+
+/// Returns `true` if `tokens` contain an `impl` type, which can't be named.
+fn contains_impl(tokens: TokenStream2) -> bool {
+    tokens.into_iter().any(|token| match token {
+        crate::proc_macro2::TokenTree::Ident(ref ident) => ident == "impl",
+        crate::proc_macro2::TokenTree::Group(ref group) => contains_impl(group.stream()),
+        _ => false,
+    })
+}
+
+fn type_info_expr(ty: &syn::Type) -> Option<TokenStream2> {
+    define_vars_and_mods!(StaticTypeInfo);
+    if contains_impl(quote!(#ty)) {
+        return None;
+    }
+
+    let name = type_name(ty);
+    Some(quote!(#StaticTypeInfo { name: #name, id: ::std::any::TypeId::of::<#ty> }))
+}
+
+fn generate_signature(route: &Route) -> (TokenStream2, TokenStream2) {
+    define_vars_and_mods!(StaticParamInfo);
+    let params = route.inputs.iter().filter_map(|(ident, _, ty)| {
+        let (source, kind) = match route.segments.get(&Segment::from(ident)) {
+            Some(seg) => (seg.source, seg.kind),
+            None => (Source::Unknown, Kind::Single),
+        };
+
+        // The `Debug` representations of `Source` and `Kind` are the variants.
+        let span = Span::call_site().into();
+        let source = syn::Ident::new(&format!("{:?}", source), span);
+        let kind = syn::Ident::new(&format!("{:?}", kind), span);
+        let name = ident.to_string();
+        let ty = type_info_expr(ty)?;
+        Some(quote! {
+            #StaticParamInfo {
+                name: #name,
+                source: ::rocket::http::route::Source::#source,
+                kind: ::rocket::http::route::Kind::#kind,
+                ty: #ty,
+            }
+        })
+    });
+
+    let output = match route.function.sig.output {
+        syn::ReturnType::Default => type_info_expr(&syn::parse_quote!(())),
+        syn::ReturnType::Type(_, ref ty) => type_info_expr(&ty.with_stripped_lifetimes()),
+    };
+
+    let output = Optional(output);
+    (quote!(&[#(#params),*]), quote!(#output))
+}
+
 fn codegen_route(route: Route) -> Result<TokenStream> {
     // Generate the declarations for path, data, and request guard parameters.
     let mut data_stmt = None;
@@ -383,16 +475,16 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     for (ident, rocket_ident, ty) in &route.inputs {
         let fn_segment: Segment = ident.into();
         match route.segments.get(&fn_segment) {
-            Some(seg) if seg.source == Source::Path => {
+            Some(seg) if seg.source == Source::Path || seg.source == Source::Host => {
                 parameter_definitions.push(param_expr(seg, rocket_ident, &ty));
             }
             Some(seg) if seg.source == Source::Data => {
                 // the data statement needs to come last, so record it specially
-                data_stmt = Some(data_expr(rocket_ident, &ty));
+                data_stmt = Some(data_expr(ident, rocket_ident, &ty));
             }
             Some(_) => continue, // handle query parameters later
             None => {
-                req_guard_definitions.push(request_guard_expr(rocket_ident, &ty));
+                req_guard_definitions.push(request_guard_expr(ident, rocket_ident, &ty));
             }
         };
     }
@@ -410,9 +502,16 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
     let generated_struct_name = user_handler_fn_name.prepend(ROUTE_STRUCT_PREFIX);
     let generated_internal_uri_macro = generate_internal_uri_macro(&route);
     let generated_respond_expr = generate_respond_expr(&route);
+    let (params, output) = generate_signature(&route);
+
+    let name = match route.attribute.name {
+        Some(ref name) => quote!(#name),
+        None => quote!(stringify!(#user_handler_fn_name)),
+    };
 
     let method = route.attribute.method;
     let path = route.attribute.path.origin.0.to_string();
+    let host = Optional(route.attribute.host.as_ref().map(|h| &h.string));
     let rank = Optional(route.attribute.rank);
     let format = Optional(route.attribute.format);
 
@@ -440,12 +539,15 @@ fn codegen_route(route: Route) -> Result<TokenStream> {
         #[allow(non_upper_case_globals)]
         #vis static #generated_struct_name: #StaticRouteInfo =
             #StaticRouteInfo {
-                name: stringify!(#user_handler_fn_name),
+                name: #name,
                 method: #method,
+                host: #host,
                 path: #path,
                 handler: #generated_fn_name,
                 format: #format,
                 rank: #rank,
+                params: #params,
+                output: #output,
             };
     }.into())
 }
@@ -494,6 +596,8 @@ fn incomplete_route(
         data: method_attribute.data,
         format: method_attribute.format,
         rank: method_attribute.rank,
+        host: method_attribute.host,
+        name: method_attribute.name,
     };
 
     codegen_route(parse_route(attribute, function)?)
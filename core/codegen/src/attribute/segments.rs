@@ -147,3 +147,46 @@ pub fn parse_segments<P: UriPart>(
 
     diags.err_or(segments)
 }
+
+pub fn parse_host_segments(string: &str, span: Span) -> DResult<Vec<Segment>> {
+    let mut segments = vec![];
+    let mut diags = Diagnostics::new();
+
+    for (i, label) in string.split('.').enumerate() {
+        if label.is_empty() {
+            diags.push(span.error("host cannot contain empty labels"));
+            continue;
+        }
+
+        let label_span = subspan(label, string, span);
+
+        match <RouteSegment<'_, Path>>::parse_one(label) {
+            Err(error) => diags.push(into_diagnostic(label, string, span, &error)),
+            Ok(segment) if segment.kind == Kind::Multi => {
+                diags.push(label_span.error("host parameters cannot be multi-segment")
+                    .help("host parameters must be of the form '<param>'"));
+            }
+            Ok(segment) if segment.kind == Kind::Static && !is_valid_host_label(label) => {
+                let diag = label_span.error("invalid host label")
+                    .help("host labels may only contain ASCII letters, digits, and '-'");
+
+                match label.contains(':') {
+                    true => diags.push(diag.note("hosts cannot contain a port")),
+                    false => diags.push(diag),
+                }
+            }
+            Ok(segment) => {
+                let mut seg = Segment::from(segment, label_span);
+                seg.source = Source::Host;
+                seg.index = Some(i);
+                segments.push(seg);
+            }
+        }
+    }
+
+    diags.err_or(segments)
+}
+
+fn is_valid_host_label(label: &str) -> bool {
+    label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
@@ -1,9 +1,10 @@
 use quote::ToTokens;
 use crate::proc_macro2::TokenStream as TokenStream2;
-use devise::{FromMeta, MetaItem, Result, ext::{Split2, PathExt}};
+use devise::{syn, FromMeta, MetaItem, Result, ext::{Split2, PathExt}};
 use crate::http::{self, ext::IntoOwned};
 use crate::http::uri::{Path, Query};
-use crate::attribute::segments::{parse_segments, parse_data_segment, Segment, Kind};
+use crate::attribute::segments::{parse_segments, parse_data_segment, parse_host_segments};
+use crate::attribute::segments::{Segment, Kind};
 
 use crate::proc_macro_ext::StringLit;
 
@@ -25,6 +26,12 @@ pub struct Origin(pub http::uri::Origin<'static>);
 #[derive(Clone, Debug)]
 pub struct DataSegment(pub Segment);
 
+#[derive(Debug)]
+pub struct HostPattern {
+    pub string: String,
+    pub labels: Vec<Segment>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Optional<T>(pub Option<T>);
 
@@ -117,13 +124,10 @@ impl ToTokens for MediaType {
 }
 
 const VALID_METHODS_STR: &str = "`GET`, `PUT`, `POST`, `DELETE`, `HEAD`, \
-    `PATCH`, `OPTIONS`";
+    `PATCH`, `OPTIONS`, a WebDAV method like `PROPFIND`, or an uppercase extension \
+    method like `PURGE`";
 
-const VALID_METHODS: &[http::Method] = &[
-    http::Method::Get, http::Method::Put, http::Method::Post,
-    http::Method::Delete, http::Method::Head, http::Method::Patch,
-    http::Method::Options,
-];
+const INVALID_METHODS: &[http::Method] = &[http::Method::Trace, http::Method::Connect];
 
 impl FromMeta for Method {
     fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
@@ -132,10 +136,14 @@ impl FromMeta for Method {
 
         if let MetaItem::Path(path) = meta {
             if let Some(ident) = path.last_ident() {
-                let method = ident.to_string().parse()
-                    .map_err(|_| span.error("invalid HTTP method").help(&*help_text))?;
-
-                if !VALID_METHODS.contains(&method) {
+                // Hyphenated methods, like `VERSION-CONTROL`, are written with
+                // underscores. Extension methods must be written in uppercase.
+                let name = ident.to_string().replace('_', "-");
+                let method = name.parse::<http::Method>().ok()
+                    .filter(|m| !m.is_extension() || !name.chars().any(|c| c.is_ascii_lowercase()))
+                    .ok_or_else(|| span.error("invalid HTTP method").help(&*help_text))?;
+
+                if INVALID_METHODS.contains(&method) {
                     return Err(span.error("invalid HTTP method for route handlers")
                                .help(&*help_text));
                 }
@@ -152,15 +160,19 @@ impl FromMeta for Method {
 impl ToTokens for Method {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let method_tokens = match self.0 {
-            http::Method::Get => quote!(::rocket::http::Method::Get),
-            http::Method::Put => quote!(::rocket::http::Method::Put),
-            http::Method::Post => quote!(::rocket::http::Method::Post),
-            http::Method::Delete => quote!(::rocket::http::Method::Delete),
-            http::Method::Options => quote!(::rocket::http::Method::Options),
-            http::Method::Head => quote!(::rocket::http::Method::Head),
-            http::Method::Trace => quote!(::rocket::http::Method::Trace),
-            http::Method::Connect => quote!(::rocket::http::Method::Connect),
-            http::Method::Patch => quote!(::rocket::http::Method::Patch),
+            http::Method::Extension(method) => {
+                let bytes = method.raw_bytes().to_vec();
+                let len = method.as_str().len() as u8;
+                quote!(::rocket::http::Method::Extension(
+                    ::rocket::http::ExtensionMethod::from_raw([#(#bytes),*], #len)
+                ))
+            }
+            method => {
+                // The `Debug` representation of a named method is its variant.
+                let span = proc_macro::Span::call_site().into();
+                let variant = syn::Ident::new(&format!("{:?}", method), span);
+                quote!(::rocket::http::Method::#variant)
+            }
         };
 
         tokens.extend(method_tokens);
@@ -206,6 +218,15 @@ impl FromMeta for DataSegment {
     }
 }
 
+impl FromMeta for HostPattern {
+    fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
+        let string = StringLit::from_meta(meta)?;
+        let span = string.subspan(1..(string.len() + 1));
+        let labels = parse_host_segments(&string, span).map_err(|d| d.emit_head())?;
+        Ok(HostPattern { string: string.0.clone(), labels })
+    }
+}
+
 impl FromMeta for RoutePath {
     fn from_meta(meta: MetaItem<'_>) -> Result<Self> {
         let (origin, string) = (Origin::from_meta(meta)?, StringLit::from_meta(meta)?);
@@ -93,8 +93,11 @@ vars_and_mods! {
     Query => rocket::request::Query,
     Request => rocket::Request,
     Response => rocket::response::Response,
+    Status => rocket::http::Status,
     Data => rocket::Data,
     StaticRouteInfo => rocket::StaticRouteInfo,
+    StaticParamInfo => rocket::StaticParamInfo,
+    StaticTypeInfo => rocket::StaticTypeInfo,
     SmallVec => rocket::http::private::SmallVec,
     _Option => ::std::option::Option,
     _Result => ::std::result::Result,
@@ -182,6 +185,25 @@ macro_rules! route_attribute {
         /// }
         /// ```
         ///
+        /// [`route`] also accepts the WebDAV methods, like `PROPFIND` and
+        /// `MKCOL`, and any extension method written in uppercase, like
+        /// `PURGE`. Hyphens in a method's name, as in `VERSION-CONTROL`, are
+        /// written as underscores:
+        ///
+        /// ```rust
+        /// # #![feature(proc_macro_hygiene)]
+        /// # #[macro_use] extern crate rocket;
+        /// #
+        /// #[route(PROPFIND, path = "/<path..>")]
+        /// fn properties(path: std::path::PathBuf) { /* .. */ }
+        ///
+        /// #[route(VERSION_CONTROL, path = "/<path..>")]
+        /// fn version_control(path: std::path::PathBuf) { /* .. */ }
+        ///
+        /// #[route(PURGE, path = "/<path..>")]
+        /// fn purge(path: std::path::PathBuf) { /* .. */ }
+        /// ```
+        ///
         /// [`get`]: attr.get.html
         /// [`put`]: attr.put.html
         /// [`post`]: attr.post.html
@@ -209,11 +231,20 @@ macro_rules! route_attribute {
         /// parameter := 'rank' '=' INTEGER
         ///            | 'format' '=' '"' MEDIA_TYPE '"'
         ///            | 'data' '=' '"' SINGLE_PARAM '"'
+        ///            | 'host' '=' '"' host '"'
+        ///            | 'name' '=' '"' NAME '"'
+        ///
+        /// host := label ('.' label)*
+        ///
+        /// label := HOST_LABEL
+        ///        | SINGLE_PARAM
         ///
         /// SINGLE_PARAM := '<' IDENT '>'
         /// MULTI_PARAM := '<' IDENT '..>'
         ///
         /// URI_SEG := valid, non-percent-encoded HTTP URI segment
+        /// HOST_LABEL := ASCII letters, digits, and '-'
+        /// NAME := any string, the name of the route
         /// MEDIA_TYPE := valid HTTP media type or known shorthand
         ///
         /// INTEGER := unsigned integer, as defined by Rust
@@ -224,6 +255,9 @@ macro_rules! route_attribute {
         ///
         /// ```text
         /// generic-route := METHOD ',' 'path' '=' route
+        ///
+        /// METHOD := a method other than TRACE and CONNECT, case-insensitively,
+        ///           or an uppercase extension method, with '-' written as '_'
         /// ```
         ///
         /// # Typing Requirements
@@ -252,6 +286,7 @@ macro_rules! route_attribute {
         ///
         /// | position | kind        | trait             |
         /// |----------|-------------|-------------------|
+        /// | host     | `<ident>`   | [`FromParam`]     |
         /// | path     | `<ident>`   | [`FromParam`]     |
         /// | path     | `<ident..>` | [`FromSegments`]  |
         /// | query    | `<ident>`   | [`FromFormValue`] |
@@ -262,6 +297,20 @@ macro_rules! route_attribute {
         /// corresponding dynamic parameter is required to implement the
         /// [`FromRequest`] trait.
         ///
+        /// A route with a `host` only matches requests whose `Host` header
+        /// matches it. A dynamic parameter in the host matches any one label
+        /// of the request's host, so the following route matches `GET /` for
+        /// `acme.example.com` and passes `"acme"` as `tenant`:
+        ///
+        /// ```rust
+        /// # #![feature(proc_macro_hygiene)]
+        /// # #[macro_use] extern crate rocket;
+        /// #[get("/", host = "<tenant>.example.com")]
+        /// fn index(tenant: String) -> String {
+        ///     format!("Hello, {}!", tenant)
+        /// }
+        /// ```
+        ///
         /// The return type of the decorated function must implement the
         /// [`Responder`] trait.
         ///
@@ -291,11 +340,11 @@ macro_rules! route_attribute {
         ///            `Failure`. See [`FromRequest` Outcomes] for further
         ///            detail.
         ///
-        ///         2. Path and query parameters from left to right as declared
-        ///            in the function argument list.
+        ///         2. Host, path, and query parameters from left to right as
+        ///            declared in the function argument list.
         ///
-        ///            If a path or query parameter guard fails, the request is
-        ///            forwarded.
+        ///            If a host, path, or query parameter guard fails, the
+        ///            request is forwarded.
         ///
         ///         3. Data parameter, if any.
         ///
@@ -310,9 +359,9 @@ macro_rules! route_attribute {
         ///   2. A static structure used by [`routes!`] to generate a [`Route`].
         ///
         ///      The static structure (and resulting [`Route`]) is populated
-        ///      with the name (the function's name), path, query, rank, and
-        ///      format from the route attribute. The handler is set to the
-        ///      generated handler.
+        ///      with the name (the function's name unless `name` is set),
+        ///      host, path, query, rank, and format from the route attribute.
+        ///      The handler is set to the generated handler.
         ///
         ///   3. A macro used by [`uri!`] to type-check and generate an
         ///      [`Origin`].
@@ -357,21 +406,40 @@ route_attribute!(options => Method::Options);
 /// }
 /// ```
 ///
+/// A catcher declared with `default` in place of a status code handles every
+/// error status without a more specific catcher. It will typically take the
+/// status of the error as its first argument:
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// #
+/// use rocket::Request;
+/// use rocket::http::Status;
+///
+/// #[catch(default)]
+/// fn default(status: Status, req: &Request) -> String {
+///     format!("{} ({})", status, req.uri())
+/// }
+/// ```
+///
 /// # Grammar
 ///
 /// The grammar for the `#[catch]` attributes is defined as:
 ///
 /// ```text
-/// catch := STATUS
+/// catch := STATUS | 'default'
 ///
 /// STATUS := valid HTTP status code (integer in [200, 599])
 /// ```
 ///
 /// # Typing Requirements
 ///
-/// The decorated function must take exactly zero or one argument. If the
-/// decorated function takes an argument, the argument's type must be
-/// [`&Request`].
+/// The decorated function must take exactly zero, one, or two arguments. If the
+/// decorated function takes one argument, the argument's type must be
+/// [`&Request`]. If it takes two arguments, their types must be [`Status`] and
+/// [`&Request`], in that order. The `Status` is the status of the error being
+/// handled.
 ///
 /// The return type of the decorated function must implement the [`Responder`]
 /// trait.
@@ -384,7 +452,8 @@ route_attribute!(options => Method::Options);
 ///
 ///      The generated handler calls the decorated function, passing in the
 ///      [`&Request`] value if requested. The returned value is used to generate
-///      a [`Response`] via the type's [`Responder`] implementation.
+///      a [`Response`] via the type's [`Responder`] implementation. A default
+///      catcher's response has the status of the error being handled.
 ///
 ///   2. A static structure used by [`catchers!`] to generate a [`Catcher`].
 ///
@@ -393,6 +462,7 @@ route_attribute!(options => Method::Options);
 ///      route attribute. The handler is set to the generated handler.
 ///
 /// [`&Request`]: ../rocket/struct.Request.html
+/// [`Status`]: ../rocket/http/struct.Status.html
 /// [`ErrorHandler`]: ../rocket/type.ErrorHandler.html
 /// [`catchers!`]: macro.catchers.html
 /// [`Catcher`]: ../rocket/struct.Catcher.html
@@ -122,3 +122,28 @@ fn test_full_route() {
     assert_eq!(response.body_string().unwrap(), format!("({}, {}, {}, {}, {}, {}) ({})",
             sky, name, "A A", "inside", path, simple, expected_uri));
 }
+
+#[test]
+fn test_route_signature() {
+    use rocket::http::route::{Source, Kind};
+
+    let route: rocket::Route = (&static_rocket_route_info_for_post1).into();
+    let signature = route.signature.unwrap();
+    let params: Vec<_> = signature.params.iter()
+        .map(|p| (p.name, p.source, p.kind, p.ty.name))
+        .collect();
+
+    assert_eq!(params, vec![
+        ("sky", Source::Query, Kind::Single, "usize"),
+        ("name", Source::Path, Kind::Single, "&RawStr"),
+        ("a", Source::Path, Kind::Single, "String"),
+        ("query", Source::Query, Kind::Multi, "Form<Inner<'_>>"),
+        ("path", Source::Path, Kind::Multi, "PathBuf"),
+        ("simple", Source::Data, Kind::Single, "Simple"),
+    ]);
+
+    assert!(signature.params[1].ty.is::<&RawStr>());
+    assert!(signature.params[3].ty.is::<Form<Inner<'static>>>());
+    assert!(signature.params[5].ty.is::<Simple>());
+    assert!(signature.output.unwrap().is::<String>());
+}
@@ -37,7 +37,7 @@ fn e5(_request: &Request) { }
 fn e5(_request: &Request) { }
 
 #[catch(404)]
-fn f3(_request: &Request, other: bool) {
+fn f3(_request: &Request, other: bool, more: bool) {
     //~^ ERROR invalid number of arguments
     //~^^ HELP optionally take an argument
 }
@@ -111,7 +111,7 @@ fn e12() {}
 //~^ HELP method must be one of
 fn f0() {}
 
-#[route(FIX, "/")] //~ ERROR invalid HTTP method
+#[route(fix, "/")] //~ ERROR invalid HTTP method
 //~^ HELP method must be one of
 fn f1() {}
 
@@ -131,4 +131,22 @@ fn m2() {}
 //~^^ HELP identifiers cannot contain
 fn m3() {}
 
+// Check that hosts are well-formed.
+
+#[get("/", host = "example..com")] //~ ERROR empty labels
+fn n0() {}
+
+#[get("/", host = "example.com:8000")] //~ ERROR invalid host label
+//~^ HELP may only contain
+//~^^ NOTE cannot contain a port
+fn n1() {}
+
+#[get("/", host = "<sub..>.example.com")] //~ ERROR multi-segment
+//~^ HELP must be of the form
+fn n2(sub: String) {}
+
+#[get("/", host = "<sub.example.com")] //~ ERROR missing a closing bracket
+//~^ HELP did you mean
+fn n3() {}
+
 fn main() {  }
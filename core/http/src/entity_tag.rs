@@ -0,0 +1,234 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Header;
+use crate::parse::{parse_entity_tag, parse_entity_tags};
+
+/// An entity-tag: an opaque validator of a representation, as sent in the
+/// `ETag` header.
+///
+/// An entity-tag is either strong, indicating that representations with equal
+/// tags are byte-for-byte identical, or weak, indicating only that they are
+/// semantically equivalent. Which comparison applies depends on the header
+/// the tag is received in: `If-None-Match` uses [weak
+/// comparison](EntityTag::weak_eq()) while `If-Match` and `If-Range` use
+/// [strong comparison](EntityTag::strong_eq()).
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::EntityTag;
+///
+/// let etag: EntityTag = "W/\"v1\"".parse().unwrap();
+/// assert!(etag.is_weak());
+/// assert_eq!(etag.tag(), "v1");
+/// assert!(etag.weak_eq(&EntityTag::strong("v1")));
+/// assert!(!etag.strong_eq(&EntityTag::strong("v1")));
+/// ```
+///
+/// `EntityTag` implements `Into<Header>` as an `ETag` header. As such, it can
+/// be used in any context where an `Into<Header>` is expected:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::EntityTag;
+/// use rocket::response::Response;
+///
+/// # #[allow(unused_variables)]
+/// let response = Response::build().header(EntityTag::strong("v1")).finalize();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntityTag {
+    weak: bool,
+    tag: String,
+}
+
+/// Returns `true` if `c` may appear in the opaque part of an entity-tag.
+#[inline]
+pub(crate) fn is_etagc(c: char) -> bool {
+    c == '!' || (c >= '#' && c != '\x7F')
+}
+
+impl EntityTag {
+    fn new(weak: bool, tag: String) -> EntityTag {
+        if let Some(c) = tag.chars().find(|&c| !is_etagc(c)) {
+            panic!("invalid character {:?} in entity-tag {:?}", c, tag);
+        }
+
+        EntityTag { weak, tag }
+    }
+
+    /// Creates a strong entity-tag with the opaque tag `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` contains a `"`, whitespace, or a control character.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let etag = EntityTag::strong("abc");
+    /// assert_eq!(etag.to_string(), "\"abc\"");
+    /// ```
+    #[inline]
+    pub fn strong<S: Into<String>>(tag: S) -> EntityTag {
+        EntityTag::new(false, tag.into())
+    }
+
+    /// Creates a weak entity-tag with the opaque tag `tag`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tag` contains a `"`, whitespace, or a control character.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let etag = EntityTag::weak("abc");
+    /// assert_eq!(etag.to_string(), "W/\"abc\"");
+    /// ```
+    #[inline]
+    pub fn weak<S: Into<String>>(tag: S) -> EntityTag {
+        EntityTag::new(true, tag.into())
+    }
+
+    /// Returns the opaque tag of `self`, without quotes.
+    #[inline(always)]
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns `true` if `self` is a weak entity-tag.
+    #[inline(always)]
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Compares `self` and `other` with the strong comparison function: both
+    /// must be strong and have equal tags.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// assert!(EntityTag::strong("1").strong_eq(&EntityTag::strong("1")));
+    /// assert!(!EntityTag::strong("1").strong_eq(&EntityTag::weak("1")));
+    /// ```
+    #[inline]
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Compares `self` and `other` with the weak comparison function: their
+    /// tags must be equal, regardless of weakness.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// assert!(EntityTag::weak("1").weak_eq(&EntityTag::strong("1")));
+    /// assert!(!EntityTag::weak("1").weak_eq(&EntityTag::weak("2")));
+    /// ```
+    #[inline]
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.tag == other.tag
+    }
+
+    /// Parses a comma-separated list of entity-tags, as sent in the `If-Match`
+    /// and `If-None-Match` headers. The special value `*` is not an
+    /// entity-tag and must be checked for separately.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let tags = EntityTag::parse_list("\"a,b\", W/\"c\"").unwrap();
+    /// assert_eq!(tags, vec![EntityTag::strong("a,b"), EntityTag::weak("c")]);
+    /// ```
+    pub fn parse_list(string: &str) -> Result<Vec<EntityTag>, String> {
+        match string.trim().is_empty() {
+            true => Ok(vec![]),
+            false => parse_entity_tags(string).map_err(|e| e.to_string())
+        }
+    }
+}
+
+impl FromStr for EntityTag {
+    type Err = String;
+
+    #[inline]
+    fn from_str(raw: &str) -> Result<EntityTag, String> {
+        parse_entity_tag(raw.trim()).map_err(|e| e.to_string())
+    }
+}
+
+impl fmt::Display for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/")?;
+        }
+
+        write!(f, "\"{}\"", self.tag)
+    }
+}
+
+/// Creates a new `Header` with name `ETag` and the value set to the HTTP
+/// rendering of this entity-tag.
+impl Into<Header<'static>> for EntityTag {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new("ETag", self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::EntityTag;
+
+    #[test]
+    fn check_parse() {
+        assert_eq!("\"xyzzy\"".parse(), Ok(EntityTag::strong("xyzzy")));
+        assert_eq!("W/\"xyzzy\"".parse(), Ok(EntityTag::weak("xyzzy")));
+        assert_eq!("\"\"".parse(), Ok(EntityTag::strong("")));
+
+        assert!("xyzzy".parse::<EntityTag>().is_err());
+        assert!("\"".parse::<EntityTag>().is_err());
+        assert!("w/\"xyzzy\"".parse::<EntityTag>().is_err());
+        assert!("\"xy\"zy\"".parse::<EntityTag>().is_err());
+        assert!("\"xy zzy\"".parse::<EntityTag>().is_err());
+    }
+
+    #[test]
+    fn check_parse_list() {
+        let list = EntityTag::parse_list("\"xyzzy\", \"r2d2xxxx\",W/\"c3piozzzz\"").unwrap();
+        assert_eq!(list, vec![
+            EntityTag::strong("xyzzy"),
+            EntityTag::strong("r2d2xxxx"),
+            EntityTag::weak("c3piozzzz")
+        ]);
+
+        assert_eq!(EntityTag::parse_list("").unwrap(), vec![]);
+        assert!(EntityTag::parse_list("*").is_err());
+        assert!(EntityTag::parse_list("\"a\" \"b\"").is_err());
+        assert!(EntityTag::parse_list("\"a").is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_invalid_tag_panics() {
+        EntityTag::strong("a\"b");
+    }
+}
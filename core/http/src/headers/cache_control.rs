@@ -0,0 +1,269 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::Header;
+use crate::parse::parse_cache_control;
+use super::{TypedHeader, write_word};
+
+/// A directive in a `Cache-Control` header.
+///
+/// The directives defined by RFC 7234 and RFC 8246 are recognized. Directives
+/// are case-insensitive; unrecognized ones are kept, lowercased, as
+/// [`CacheDirective::Extension`]. So are the field-name forms of `no-cache`
+/// and `private`, as in `private="Set-Cookie"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CacheDirective {
+    /// `max-age=seconds`
+    MaxAge(u64),
+    /// `s-maxage=seconds`
+    SMaxAge(u64),
+    /// `max-stale[=seconds]`
+    MaxStale(Option<u64>),
+    /// `min-fresh=seconds`
+    MinFresh(u64),
+    /// `no-cache`
+    NoCache,
+    /// `no-store`
+    NoStore,
+    /// `no-transform`
+    NoTransform,
+    /// `only-if-cached`
+    OnlyIfCached,
+    /// `must-revalidate`
+    MustRevalidate,
+    /// `proxy-revalidate`
+    ProxyRevalidate,
+    /// `public`
+    Public,
+    /// `private`
+    Private,
+    /// `immutable`
+    Immutable,
+    /// Any other directive, with its optional argument.
+    Extension(String, Option<String>),
+}
+
+impl CacheDirective {
+    pub(crate) fn from_parts(name: String, value: Option<String>) -> Result<Self, String> {
+        use self::CacheDirective::*;
+
+        let seconds = |value: Option<String>| -> Result<u64, String> {
+            let value = value.ok_or_else(|| format!("`{}` requires a value", name))?;
+            value.parse().map_err(|_| format!("invalid `{}` seconds: {:?}", name, value))
+        };
+
+        let directive = match (&*name, &value) {
+            ("max-age", _) => MaxAge(seconds(value)?),
+            ("s-maxage", _) => SMaxAge(seconds(value)?),
+            ("max-stale", &None) => MaxStale(None),
+            ("max-stale", _) => MaxStale(Some(seconds(value)?)),
+            ("min-fresh", _) => MinFresh(seconds(value)?),
+            ("no-cache", &None) => NoCache,
+            ("no-store", &None) => NoStore,
+            ("no-transform", &None) => NoTransform,
+            ("only-if-cached", &None) => OnlyIfCached,
+            ("must-revalidate", &None) => MustRevalidate,
+            ("proxy-revalidate", &None) => ProxyRevalidate,
+            ("public", &None) => Public,
+            ("private", &None) => Private,
+            ("immutable", &None) => Immutable,
+            _ => Extension(name, value),
+        };
+
+        Ok(directive)
+    }
+}
+
+impl fmt::Display for CacheDirective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::CacheDirective::*;
+
+        match *self {
+            MaxAge(secs) => write!(f, "max-age={}", secs),
+            SMaxAge(secs) => write!(f, "s-maxage={}", secs),
+            MaxStale(None) => write!(f, "max-stale"),
+            MaxStale(Some(secs)) => write!(f, "max-stale={}", secs),
+            MinFresh(secs) => write!(f, "min-fresh={}", secs),
+            NoCache => write!(f, "no-cache"),
+            NoStore => write!(f, "no-store"),
+            NoTransform => write!(f, "no-transform"),
+            OnlyIfCached => write!(f, "only-if-cached"),
+            MustRevalidate => write!(f, "must-revalidate"),
+            ProxyRevalidate => write!(f, "proxy-revalidate"),
+            Public => write!(f, "public"),
+            Private => write!(f, "private"),
+            Immutable => write!(f, "immutable"),
+            Extension(ref name, None) => write!(f, "{}", name),
+            Extension(ref name, Some(ref value)) => {
+                write!(f, "{}=", name)?;
+                write_word(f, value)
+            }
+        }
+    }
+}
+
+/// The `Cache-Control` header: directives for caches along the request and
+/// response chain.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use std::time::Duration;
+/// use rocket::http::headers::{CacheControl, CacheDirective};
+///
+/// let cache_control: CacheControl = "public, max-age=3600".parse().unwrap();
+/// assert_eq!(cache_control.max_age(), Some(Duration::from_secs(3600)));
+/// assert!(cache_control.directives().contains(&CacheDirective::Public));
+/// ```
+///
+/// `CacheControl` implements `Into<Header>`, so it can be set on a response:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::response::Response;
+/// use rocket::http::headers::{CacheControl, CacheDirective::*};
+///
+/// let response = Response::build()
+///     .header(CacheControl::new(vec![Public, MaxAge(86400), Immutable]))
+///     .finalize();
+///
+/// let cache_control = response.headers().get_one("Cache-Control");
+/// assert_eq!(cache_control, Some("public, max-age=86400, immutable"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheControl {
+    directives: Vec<CacheDirective>,
+}
+
+impl CacheControl {
+    /// Creates a `Cache-Control` header with the given `directives`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::headers::{CacheControl, CacheDirective};
+    ///
+    /// let cache_control = CacheControl::new(vec![CacheDirective::NoStore]);
+    /// assert_eq!(cache_control.to_string(), "no-store");
+    /// ```
+    #[inline]
+    pub fn new(directives: Vec<CacheDirective>) -> CacheControl {
+        CacheControl { directives }
+    }
+
+    /// Returns the directives in `self`, in order.
+    #[inline(always)]
+    pub fn directives(&self) -> &[CacheDirective] {
+        &self.directives
+    }
+
+    /// Returns the duration of the `max-age` directive, if there is one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use std::time::Duration;
+    /// use rocket::http::headers::CacheControl;
+    ///
+    /// let cache_control: CacheControl = "max-age=60, must-revalidate".parse().unwrap();
+    /// assert_eq!(cache_control.max_age(), Some(Duration::from_secs(60)));
+    ///
+    /// let cache_control: CacheControl = "no-cache".parse().unwrap();
+    /// assert_eq!(cache_control.max_age(), None);
+    /// ```
+    pub fn max_age(&self) -> Option<Duration> {
+        self.directives.iter()
+            .filter_map(|directive| match *directive {
+                CacheDirective::MaxAge(secs) => Some(Duration::from_secs(secs)),
+                _ => None
+            })
+            .next()
+    }
+}
+
+impl TypedHeader for CacheControl {
+    const NAME: &'static str = "Cache-Control";
+}
+
+impl FromStr for CacheControl {
+    type Err = String;
+
+    #[inline]
+    fn from_str(raw: &str) -> Result<CacheControl, String> {
+        parse_cache_control(raw.trim()).map_err(|e| e.to_string())
+    }
+}
+
+impl fmt::Display for CacheControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, directive) in self.directives.iter().enumerate() {
+            if i >= 1 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}", directive)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates a new `Header` with name `Cache-Control` and the value set to the
+/// HTTP rendering of these directives.
+impl Into<Header<'static>> for CacheControl {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new(CacheControl::NAME, self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CacheControl, CacheDirective::*};
+
+    macro_rules! assert_directives {
+        ($string:expr, [$($directive:expr),*]) => ({
+            match $string.parse::<CacheControl>() {
+                Ok(cc) => assert_eq!(cc.directives(), &[$($directive),*]),
+                Err(e) => panic!("{:?} failed to parse: {}", $string, e)
+            }
+        });
+    }
+
+    #[test]
+    fn check_parse() {
+        assert_directives!("no-cache", [NoCache]);
+        assert_directives!("No-Store, MAX-AGE=0", [NoStore, MaxAge(0)]);
+        assert_directives!("max-age=\"60\",s-maxage=30", [MaxAge(60), SMaxAge(30)]);
+        assert_directives!("max-stale, min-fresh=5", [MaxStale(None), MinFresh(5)]);
+        assert_directives!("max-stale=10", [MaxStale(Some(10))]);
+        assert_directives!("public, immutable", [Public, Immutable]);
+        assert_directives!("private=\"Set-Cookie, X-Foo\"",
+            [Extension("private".into(), Some("Set-Cookie, X-Foo".into()))]);
+        assert_directives!("community=\"UCI\"",
+            [Extension("community".into(), Some("UCI".into()))]);
+
+        assert!("".parse::<CacheControl>().is_err());
+        assert!("max-age".parse::<CacheControl>().is_err());
+        assert!("max-age=-1".parse::<CacheControl>().is_err());
+        assert!("max-age=1 0".parse::<CacheControl>().is_err());
+        assert!("no-cache,".parse::<CacheControl>().is_err());
+    }
+
+    #[test]
+    fn check_display() {
+        let cc = CacheControl::new(vec![
+            Private,
+            MaxAge(0),
+            Extension("ext".into(), Some("a b".into())),
+            Extension("token".into(), Some("v".into())),
+        ]);
+
+        assert_eq!(cc.to_string(), "private, max-age=0, ext=\"a b\", token=v");
+        assert_eq!(cc.to_string().parse::<CacheControl>().unwrap(), cc);
+    }
+}
@@ -0,0 +1,245 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::Header;
+use crate::parse::parse_content_disposition;
+use super::{TypedHeader, write_quoted};
+
+/// Returns `true` if `byte` is an `attr-char` from RFC 8187 and so can appear
+/// unencoded in an extended parameter value.
+fn is_attr_char(byte: u8) -> bool {
+    match byte {
+        b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'!' | b'#' | b'$' | b'&' | b'+'
+            | b'-' | b'.' | b'^' | b'_' | b'`' | b'|' | b'~' => true,
+        _ => false
+    }
+}
+
+/// The `Content-Disposition` header (RFC 6266): whether a response is meant
+/// to be displayed inline or downloaded, and under what file name.
+///
+/// Non-ASCII file names are sent in the RFC 8187 `filename*` parameter with an
+/// ASCII `filename` fallback for older clients; [`filename()`] prefers the
+/// former when reading the header.
+///
+/// [`filename()`]: ContentDisposition::filename()
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::headers::ContentDisposition;
+///
+/// let cd: ContentDisposition = "attachment; filename=\"report.pdf\"".parse().unwrap();
+/// assert!(cd.is_attachment());
+/// assert_eq!(cd.filename().unwrap(), "report.pdf");
+///
+/// let cd: ContentDisposition = "attachment; filename*=UTF-8''%e2%82%ac%20rates"
+///     .parse().unwrap();
+/// assert_eq!(cd.filename().unwrap(), "€ rates");
+/// ```
+///
+/// `ContentDisposition` implements `Into<Header>`, so it can be set on a
+/// response:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::response::Response;
+/// use rocket::http::headers::ContentDisposition;
+///
+/// let response = Response::build()
+///     .header(ContentDisposition::attachment().with_filename("naïve.txt"))
+///     .finalize();
+///
+/// let value = response.headers().get_one("Content-Disposition").unwrap();
+/// assert_eq!(value, "attachment; filename=\"na_ve.txt\"; \
+///     filename*=UTF-8''na%C3%AFve.txt");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContentDisposition {
+    pub(crate) kind: String,
+    pub(crate) params: Vec<(String, String)>,
+}
+
+impl ContentDisposition {
+    /// Creates an `inline` disposition with no parameters.
+    #[inline]
+    pub fn inline() -> ContentDisposition {
+        ContentDisposition { kind: "inline".into(), params: vec![] }
+    }
+
+    /// Creates an `attachment` disposition with no parameters.
+    #[inline]
+    pub fn attachment() -> ContentDisposition {
+        ContentDisposition { kind: "attachment".into(), params: vec![] }
+    }
+
+    /// Sets the file name of `self` to `name`, replacing any existing one.
+    ///
+    /// If `name` isn't plain ASCII, it is sent percent-encoded in `filename*`
+    /// and a lossy ASCII version of it is sent in `filename`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::headers::ContentDisposition;
+    ///
+    /// let cd = ContentDisposition::attachment().with_filename("data.csv");
+    /// assert_eq!(cd.to_string(), "attachment; filename=\"data.csv\"");
+    /// assert_eq!(cd.filename().unwrap(), "data.csv");
+    /// ```
+    pub fn with_filename(mut self, name: &str) -> Self {
+        self.params.retain(|&(ref key, _)| key != "filename" && key != "filename*");
+
+        let is_plain = |c: char| c.is_ascii() && !c.is_ascii_control();
+        let fallback: String = name.chars()
+            .map(|c| if is_plain(c) { c } else { '_' })
+            .collect();
+
+        self.params.push(("filename".into(), fallback));
+        if !name.chars().all(is_plain) {
+            let mut encoded = String::from("UTF-8''");
+            for &byte in name.as_bytes() {
+                match is_attr_char(byte) {
+                    true => encoded.push(byte as char),
+                    false => { let _ = write!(encoded, "%{:02X}", byte); }
+                }
+            }
+
+            self.params.push(("filename*".into(), encoded));
+        }
+
+        self
+    }
+
+    /// Returns the disposition type, lowercased: usually `inline` or
+    /// `attachment`.
+    #[inline(always)]
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Returns `true` if the disposition type is `attachment`.
+    #[inline]
+    pub fn is_attachment(&self) -> bool {
+        self.kind == "attachment"
+    }
+
+    /// Returns the raw value of the first parameter named `name`, if any.
+    /// `name` is matched case-insensitively.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter()
+            .find(|&&(ref key, _)| key.eq_ignore_ascii_case(name))
+            .map(|&(_, ref value)| value.as_str())
+    }
+
+    /// Returns the suggested file name. The decoded `filename*` parameter is
+    /// preferred over `filename`. Only the UTF-8 and ISO-8859-1 character sets
+    /// are supported in `filename*`; a value in any other is ignored.
+    ///
+    /// The file name is whatever the sender chose. It must be sanitized before
+    /// being used as a path.
+    pub fn filename(&self) -> Option<Cow<'_, str>> {
+        let extended = self.param("filename*").and_then(|value| {
+            let mut parts = value.splitn(3, '\'');
+            let (charset, _language, encoded) = (parts.next()?, parts.next()?, parts.next()?);
+            let decoded = percent_encoding::percent_decode(encoded.as_bytes());
+            if charset.eq_ignore_ascii_case("UTF-8") {
+                decoded.decode_utf8().ok()
+            } else if charset.eq_ignore_ascii_case("ISO-8859-1") {
+                Some(Cow::Owned(decoded.map(|byte| byte as char).collect()))
+            } else {
+                None
+            }
+        });
+
+        extended.or_else(|| self.param("filename").map(Cow::Borrowed))
+    }
+}
+
+impl TypedHeader for ContentDisposition {
+    const NAME: &'static str = "Content-Disposition";
+}
+
+impl FromStr for ContentDisposition {
+    type Err = String;
+
+    #[inline]
+    fn from_str(raw: &str) -> Result<ContentDisposition, String> {
+        parse_content_disposition(raw.trim()).map_err(|e| e.to_string())
+    }
+}
+
+impl fmt::Display for ContentDisposition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        for &(ref name, ref value) in &self.params {
+            write!(f, "; {}=", name)?;
+            match name.ends_with('*') {
+                true => write!(f, "{}", value)?,
+                false => write_quoted(f, value)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates a new `Header` with name `Content-Disposition` and the value set to
+/// the HTTP rendering of this disposition.
+impl Into<Header<'static>> for ContentDisposition {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new(ContentDisposition::NAME, self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ContentDisposition;
+
+    #[test]
+    fn check_parse() {
+        let cd: ContentDisposition = "INLINE".parse().unwrap();
+        assert_eq!(cd, ContentDisposition::inline());
+        assert_eq!(cd.filename(), None);
+
+        let cd: ContentDisposition = "attachment; FileName=a.txt".parse().unwrap();
+        assert!(cd.is_attachment());
+        assert_eq!(cd.filename().unwrap(), "a.txt");
+
+        let cd: ContentDisposition = "attachment; filename=\"EURO rates\"; \
+            filename*=utf-8''%e2%82%ac%20rates".parse().unwrap();
+        assert_eq!(cd.filename().unwrap(), "€ rates");
+        assert_eq!(cd.param("filename"), Some("EURO rates"));
+
+        let cd: ContentDisposition = "attachment; filename*=iso-8859-1'en'%A3%20rates"
+            .parse().unwrap();
+        assert_eq!(cd.filename().unwrap(), "£ rates");
+
+        let cd: ContentDisposition = "attachment; filename=x; filename*=koi8-r''%C1"
+            .parse().unwrap();
+        assert_eq!(cd.filename().unwrap(), "x");
+
+        assert!("".parse::<ContentDisposition>().is_err());
+        assert!("attachment;".parse::<ContentDisposition>().is_err());
+        assert!("attachment; filename".parse::<ContentDisposition>().is_err());
+        assert!("attachment filename=a".parse::<ContentDisposition>().is_err());
+    }
+
+    #[test]
+    fn check_with_filename() {
+        let cd = ContentDisposition::attachment()
+            .with_filename("old.txt")
+            .with_filename("\"résumé\".pdf");
+
+        assert_eq!(cd.to_string(), "attachment; filename=\"\\\"r_sum_\\\".pdf\"; \
+            filename*=UTF-8''%22r%C3%A9sum%C3%A9%22.pdf");
+
+        let parsed: ContentDisposition = cd.to_string().parse().unwrap();
+        assert_eq!(parsed.filename().unwrap(), "\"résumé\".pdf");
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Header;
+use crate::parse::parse_host;
+use super::TypedHeader;
+
+/// The `Host` header: the host and optional port of the request's target.
+///
+/// The host is lowercased when parsed. An IPv6 address keeps its enclosing
+/// brackets, as in `[::1]`.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::headers::Host;
+///
+/// let host: Host = "API.example.com:8000".parse().unwrap();
+/// assert_eq!(host.host(), "api.example.com");
+/// assert_eq!(host.port(), Some(8000));
+///
+/// let host: Host = "[::1]".parse().unwrap();
+/// assert_eq!(host.host(), "[::1]");
+/// assert_eq!(host.port(), None);
+/// ```
+///
+/// As a request guard, `Host` lets a handler see which name it was reached by:
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::headers::Host;
+///
+/// #[get("/")]
+/// fn index(host: Host) -> String {
+///     format!("Hello from {}!", host.host())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Host {
+    pub(crate) host: String,
+    pub(crate) port: Option<u16>,
+}
+
+impl Host {
+    /// Creates a `Host` header for `host` and, optionally, `port`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `host` isn't a valid URI host.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::headers::Host;
+    ///
+    /// let host = Host::new("rocket.rs", Some(443));
+    /// assert_eq!(host.to_string(), "rocket.rs:443");
+    /// ```
+    pub fn new(host: &str, port: Option<u16>) -> Host {
+        match parse_host(host) {
+            Ok(ref parsed) if parsed.port.is_none() => Host { host: parsed.host.clone(), port },
+            _ => panic!("invalid host: {:?}", host)
+        }
+    }
+
+    /// Returns the host, lowercased.
+    #[inline(always)]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the port, if there is one.
+    #[inline(always)]
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+}
+
+impl TypedHeader for Host {
+    const NAME: &'static str = "Host";
+}
+
+impl FromStr for Host {
+    type Err = String;
+
+    #[inline]
+    fn from_str(raw: &str) -> Result<Host, String> {
+        parse_host(raw.trim()).map_err(|e| e.to_string())
+    }
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.port {
+            Some(port) => write!(f, "{}:{}", self.host, port),
+            None => write!(f, "{}", self.host),
+        }
+    }
+}
+
+/// Creates a new `Header` with name `Host` and the value set to the HTTP
+/// rendering of this host and port.
+impl Into<Header<'static>> for Host {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new(Host::NAME, self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Host;
+
+    #[test]
+    fn check_parse() {
+        assert_eq!("localhost".parse(), Ok(Host::new("localhost", None)));
+        assert_eq!("Example.COM:80".parse(), Ok(Host::new("example.com", Some(80))));
+        assert_eq!("127.0.0.1:8000".parse(), Ok(Host::new("127.0.0.1", Some(8000))));
+        assert_eq!("[2001:db8::7]:443".parse(), Ok(Host::new("[2001:db8::7]", Some(443))));
+
+        assert!("".parse::<Host>().is_err());
+        assert!("example.com:".parse::<Host>().is_err());
+        assert!("example.com:65536".parse::<Host>().is_err());
+        assert!("example.com:80:80".parse::<Host>().is_err());
+        assert!("exa mple.com".parse::<Host>().is_err());
+        assert!("[::1".parse::<Host>().is_err());
+        assert!("user@example.com".parse::<Host>().is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_new_rejects_port() {
+        Host::new("example.com:80", None);
+    }
+}
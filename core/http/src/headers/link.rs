@@ -0,0 +1,248 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Header;
+use crate::parse::parse_link;
+use crate::parse::checkers::is_valid_token;
+use super::{TypedHeader, write_quoted};
+
+/// A single link in a `Link` header: a URI reference and its parameters.
+///
+/// Parameter names are case-insensitive and are stored lowercased.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::headers::LinkValue;
+///
+/// let next = LinkValue::new("/items?page=3").with_param("rel", "next");
+/// assert_eq!(next.uri(), "/items?page=3");
+/// assert_eq!(next.rel(), Some("next"));
+/// assert_eq!(next.to_string(), "</items?page=3>; rel=\"next\"");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LinkValue {
+    pub(crate) uri: String,
+    pub(crate) params: Vec<(String, String)>,
+}
+
+impl LinkValue {
+    /// Creates a link to `uri` with no parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `uri` contains a `>`, which can't be represented in a `Link`
+    /// header.
+    #[inline]
+    pub fn new<S: Into<String>>(uri: S) -> LinkValue {
+        let uri = uri.into();
+        assert!(!uri.contains('>'), "link URI {:?} contains a '>'", uri);
+        LinkValue { uri, params: vec![] }
+    }
+
+    /// Adds the parameter `name` with value `value` to `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` is not a valid HTTP token.
+    #[inline]
+    pub fn with_param<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        let name = name.into();
+        assert!(!name.is_empty() && name.chars().all(is_valid_token),
+            "link parameter name {:?} is not a valid token", name);
+
+        self.params.push((name.to_ascii_lowercase(), value.into()));
+        self
+    }
+
+    /// Returns the URI reference of this link.
+    #[inline(always)]
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Returns the value of the first parameter named `name`, if any. `name` is
+    /// matched case-insensitively.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::headers::LinkValue;
+    ///
+    /// let link = LinkValue::new("/style.css").with_param("rel", "preload");
+    /// assert_eq!(link.param("REL"), Some("preload"));
+    /// assert_eq!(link.param("as"), None);
+    /// ```
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter()
+            .find(|&&(ref key, _)| key.eq_ignore_ascii_case(name))
+            .map(|&(_, ref value)| value.as_str())
+    }
+
+    /// Returns an iterator over the `(name, value)` parameters of this link.
+    #[inline]
+    pub fn params<'a>(&'a self) -> impl Iterator<Item=(&'a str, &'a str)> + 'a {
+        self.params.iter().map(|&(ref key, ref value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Returns the value of the `rel` parameter, if any.
+    #[inline]
+    pub fn rel(&self) -> Option<&str> {
+        self.param("rel")
+    }
+}
+
+impl fmt::Display for LinkValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.uri)?;
+        for &(ref name, ref value) in &self.params {
+            write!(f, "; {}=", name)?;
+            write_quoted(f, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The `Link` header (RFC 8288): typed links from a response to other
+/// resources.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::headers::Link;
+///
+/// let link: Link = "</items?page=1>; rel=prev, </items?page=3>; rel=next".parse().unwrap();
+/// assert_eq!(link.values().len(), 2);
+/// assert_eq!(link.find_rel("next").map(|l| l.uri()), Some("/items?page=3"));
+/// ```
+///
+/// `Link` implements `Into<Header>`, so it can be set on a response:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::response::Response;
+/// use rocket::http::headers::{Link, LinkValue};
+///
+/// let response = Response::build()
+///     .header(Link::new(vec![LinkValue::new("/app.js").with_param("rel", "preload")]))
+///     .finalize();
+///
+/// let link = response.headers().get_one("Link");
+/// assert_eq!(link, Some("</app.js>; rel=\"preload\""));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Link {
+    values: Vec<LinkValue>,
+}
+
+impl Link {
+    /// Creates a `Link` header with the links in `values`.
+    #[inline]
+    pub fn new(values: Vec<LinkValue>) -> Link {
+        Link { values }
+    }
+
+    /// Returns the links in `self`, in order.
+    #[inline(always)]
+    pub fn values(&self) -> &[LinkValue] {
+        &self.values
+    }
+
+    /// Returns the first link whose `rel` parameter contains the relation
+    /// type `rel`. Relation types are compared case-insensitively.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::headers::Link;
+    ///
+    /// let link: Link = "</>; rel=\"start Home\"".parse().unwrap();
+    /// assert_eq!(link.find_rel("home").map(|l| l.uri()), Some("/"));
+    /// assert!(link.find_rel("next").is_none());
+    /// ```
+    pub fn find_rel(&self, rel: &str) -> Option<&LinkValue> {
+        self.values.iter().find(|value| {
+            value.rel().map_or(false, |rels| {
+                rels.split_whitespace().any(|r| r.eq_ignore_ascii_case(rel))
+            })
+        })
+    }
+}
+
+impl TypedHeader for Link {
+    const NAME: &'static str = "Link";
+}
+
+impl FromStr for Link {
+    type Err = String;
+
+    #[inline]
+    fn from_str(raw: &str) -> Result<Link, String> {
+        parse_link(raw.trim()).map_err(|e| e.to_string())
+    }
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, value) in self.values.iter().enumerate() {
+            if i >= 1 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{}", value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates a new `Header` with name `Link` and the value set to the HTTP
+/// rendering of these links.
+impl Into<Header<'static>> for Link {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new(Link::NAME, self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Link, LinkValue};
+
+    #[test]
+    fn check_parse() {
+        let link: Link = "<http://example.com/TheBook/chapter2>; rel=\"previous\"; \
+            title=\"previous chapter\"".parse().unwrap();
+
+        assert_eq!(link.values(), &[
+            LinkValue::new("http://example.com/TheBook/chapter2")
+                .with_param("rel", "previous")
+                .with_param("title", "previous chapter")
+        ]);
+
+        let link: Link = "</a>,</b> ; REL=next".parse().unwrap();
+        assert_eq!(link.values()[0], LinkValue::new("/a"));
+        assert_eq!(link.values()[1].rel(), Some("next"));
+
+        assert!("".parse::<Link>().is_err());
+        assert!("/a".parse::<Link>().is_err());
+        assert!("</a".parse::<Link>().is_err());
+        assert!("</a>; rel".parse::<Link>().is_err());
+        assert!("</a> </b>".parse::<Link>().is_err());
+    }
+
+    #[test]
+    fn check_round_trip() {
+        let link = Link::new(vec![
+            LinkValue::new("/a").with_param("rel", "next").with_param("title", "say \"hi\""),
+            LinkValue::new("https://example.com/"),
+        ]);
+
+        assert_eq!(link.to_string().parse::<Link>().unwrap(), link);
+    }
+}
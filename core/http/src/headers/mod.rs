@@ -0,0 +1,177 @@
+//! Typed representations of common HTTP headers.
+//!
+//! Each type in this module parses and formats the value of one header as
+//! specified by its RFC. All of them implement [`TypedHeader`], which ties a
+//! type to its header's name and parses it out of a [`HeaderMap`], and
+//! `Into<Header>`, so that they can be set on a response:
+//!
+//! ```rust
+//! # extern crate rocket;
+//! use rocket::http::HeaderMap;
+//! use rocket::http::headers::{TypedHeader, CacheControl, CacheDirective};
+//!
+//! let mut headers = HeaderMap::new();
+//! headers.add(CacheControl::new(vec![CacheDirective::NoCache]));
+//! assert_eq!(headers.get_one("Cache-Control"), Some("no-cache"));
+//!
+//! let cache_control = CacheControl::from_headers(&headers).unwrap().unwrap();
+//! assert_eq!(cache_control.directives(), &[CacheDirective::NoCache]);
+//! ```
+//!
+//! In Rocket, each type in this module is also a request guard. The guard
+//! forwards if the header is missing and fails with `400 Bad Request` if it is
+//! malformed.
+
+mod authorization;
+mod cache_control;
+mod vary;
+mod preconditions;
+mod link;
+mod retry_after;
+mod content_disposition;
+mod host;
+mod user_agent;
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{HeaderMap, ContentType, Accept, Range, IfRange, EntityTag};
+use crate::parse::checkers::is_valid_token;
+
+pub use self::authorization::Authorization;
+pub use self::cache_control::{CacheControl, CacheDirective};
+pub use self::vary::Vary;
+pub use self::preconditions::{IfMatch, IfNoneMatch};
+pub use self::link::{Link, LinkValue};
+pub use self::retry_after::RetryAfter;
+pub use self::content_disposition::ContentDisposition;
+pub use self::host::Host;
+pub use self::user_agent::UserAgent;
+
+/// A header with a typed value.
+///
+/// A `TypedHeader` is parsed from the value of the header named
+/// [`NAME`](TypedHeader::NAME) with its `FromStr` implementation and formatted
+/// back into it with its `Display` implementation.
+///
+/// # Implementing
+///
+/// ```rust
+/// # extern crate rocket;
+/// use std::fmt;
+/// use std::str::FromStr;
+///
+/// use rocket::http::Header;
+/// use rocket::http::headers::TypedHeader;
+///
+/// struct DoNotTrack(bool);
+///
+/// impl TypedHeader for DoNotTrack {
+///     const NAME: &'static str = "DNT";
+/// }
+///
+/// impl FromStr for DoNotTrack {
+///     type Err = String;
+///
+///     fn from_str(value: &str) -> Result<Self, String> {
+///         match value {
+///             "0" => Ok(DoNotTrack(false)),
+///             "1" => Ok(DoNotTrack(true)),
+///             _ => Err(format!("invalid DNT value: {:?}", value))
+///         }
+///     }
+/// }
+///
+/// impl fmt::Display for DoNotTrack {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "{}", self.0 as u8)
+///     }
+/// }
+///
+/// impl Into<Header<'static>> for DoNotTrack {
+///     fn into(self) -> Header<'static> {
+///         Header::new(DoNotTrack::NAME, self.to_string())
+///     }
+/// }
+/// ```
+pub trait TypedHeader: FromStr<Err = String> + fmt::Display {
+    /// The name of the header.
+    const NAME: &'static str;
+
+    /// Parses the header out of `headers`. Returns `None` if there is no
+    /// header named [`NAME`](TypedHeader::NAME) in `headers`.
+    ///
+    /// The values of several headers with the same name are joined with commas
+    /// before being parsed, as RFC 7230 section 3.2.2 prescribes for headers
+    /// whose value is a list. For other headers, a repeated header is
+    /// usually malformed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Header, HeaderMap};
+    /// use rocket::http::headers::{TypedHeader, Vary};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.add(Header::new("Vary", "Accept"));
+    /// headers.add(Header::new("Vary", "Accept-Encoding"));
+    ///
+    /// let vary = Vary::from_headers(&headers).unwrap().unwrap();
+    /// assert!(vary.varies_on("accept-encoding"));
+    /// ```
+    fn from_headers(headers: &HeaderMap<'_>) -> Option<Result<Self, String>> {
+        let mut values = headers.get(Self::NAME);
+        let mut value = Cow::Borrowed(values.next()?);
+        for next in values {
+            value.to_mut().push_str(", ");
+            value.to_mut().push_str(next);
+        }
+
+        Some(value.parse())
+    }
+}
+
+impl TypedHeader for ContentType {
+    const NAME: &'static str = "Content-Type";
+}
+
+impl TypedHeader for Accept {
+    const NAME: &'static str = "Accept";
+}
+
+impl TypedHeader for Range {
+    const NAME: &'static str = "Range";
+}
+
+impl TypedHeader for IfRange {
+    const NAME: &'static str = "If-Range";
+}
+
+impl TypedHeader for EntityTag {
+    const NAME: &'static str = "ETag";
+}
+
+/// Writes `value` as a token if it is one, or as a quoted-string otherwise.
+fn write_word(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    if !value.is_empty() && value.chars().all(is_valid_token) {
+        return write!(f, "{}", value);
+    }
+
+    write_quoted(f, value)
+}
+
+/// Writes `value` as a quoted-string.
+fn write_quoted(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            write!(f, "\\")?;
+        }
+
+        write!(f, "{}", c)?;
+    }
+
+    write!(f, "\"")
+}
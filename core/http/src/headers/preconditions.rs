@@ -0,0 +1,191 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Header, EntityTag};
+use crate::parse::{parse_if_match, parse_if_none_match};
+use super::TypedHeader;
+
+fn write_tags(f: &mut fmt::Formatter<'_>, tags: &[EntityTag]) -> fmt::Result {
+    for (i, tag) in tags.iter().enumerate() {
+        if i >= 1 {
+            write!(f, ", ")?;
+        }
+
+        write!(f, "{}", tag)?;
+    }
+
+    Ok(())
+}
+
+/// The `If-Match` header: a precondition that the target resource's current
+/// entity tag matches one of a list.
+///
+/// Per RFC 7232, `If-Match` uses the strong comparison function.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::EntityTag;
+/// use rocket::http::headers::IfMatch;
+///
+/// let if_match: IfMatch = "\"xyzzy\", \"r2d2xxxx\"".parse().unwrap();
+/// assert!(if_match.matches(&EntityTag::strong("r2d2xxxx")));
+/// assert!(!if_match.matches(&EntityTag::weak("xyzzy")));
+///
+/// assert!(IfMatch::Any.matches(&EntityTag::weak("xyzzy")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IfMatch {
+    /// `*`: matches any current representation.
+    Any,
+    /// A list of entity tags.
+    Tags(Vec<EntityTag>),
+}
+
+impl IfMatch {
+    /// Returns `true` if `etag` strongly matches any of the tags in `self`.
+    pub fn matches(&self, etag: &EntityTag) -> bool {
+        match *self {
+            IfMatch::Any => true,
+            IfMatch::Tags(ref tags) => tags.iter().any(|tag| tag.strong_eq(etag)),
+        }
+    }
+}
+
+impl TypedHeader for IfMatch {
+    const NAME: &'static str = "If-Match";
+}
+
+impl FromStr for IfMatch {
+    type Err = String;
+
+    #[inline]
+    fn from_str(raw: &str) -> Result<IfMatch, String> {
+        parse_if_match(raw.trim()).map_err(|e| e.to_string())
+    }
+}
+
+impl fmt::Display for IfMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            IfMatch::Any => write!(f, "*"),
+            IfMatch::Tags(ref tags) => write_tags(f, tags),
+        }
+    }
+}
+
+/// Creates a new `Header` with name `If-Match` and the value set to the HTTP
+/// rendering of this precondition.
+impl Into<Header<'static>> for IfMatch {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new(IfMatch::NAME, self.to_string())
+    }
+}
+
+/// The `If-None-Match` header: a precondition that the target resource's
+/// current entity tag matches none of a list.
+///
+/// Per RFC 7232, `If-None-Match` uses the weak comparison function.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::EntityTag;
+/// use rocket::http::headers::IfNoneMatch;
+///
+/// let if_none_match: IfNoneMatch = "W/\"xyzzy\"".parse().unwrap();
+/// assert!(if_none_match.matches(&EntityTag::strong("xyzzy")));
+/// assert!(!if_none_match.matches(&EntityTag::strong("r2d2xxxx")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IfNoneMatch {
+    /// `*`: matches any current representation.
+    Any,
+    /// A list of entity tags.
+    Tags(Vec<EntityTag>),
+}
+
+impl IfNoneMatch {
+    /// Returns `true` if `etag` weakly matches any of the tags in `self`. The
+    /// precondition fails when this returns `true`.
+    pub fn matches(&self, etag: &EntityTag) -> bool {
+        match *self {
+            IfNoneMatch::Any => true,
+            IfNoneMatch::Tags(ref tags) => tags.iter().any(|tag| tag.weak_eq(etag)),
+        }
+    }
+}
+
+impl TypedHeader for IfNoneMatch {
+    const NAME: &'static str = "If-None-Match";
+}
+
+impl FromStr for IfNoneMatch {
+    type Err = String;
+
+    #[inline]
+    fn from_str(raw: &str) -> Result<IfNoneMatch, String> {
+        parse_if_none_match(raw.trim()).map_err(|e| e.to_string())
+    }
+}
+
+impl fmt::Display for IfNoneMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            IfNoneMatch::Any => write!(f, "*"),
+            IfNoneMatch::Tags(ref tags) => write_tags(f, tags),
+        }
+    }
+}
+
+/// Creates a new `Header` with name `If-None-Match` and the value set to the
+/// HTTP rendering of this precondition.
+impl Into<Header<'static>> for IfNoneMatch {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new(IfNoneMatch::NAME, self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{IfMatch, IfNoneMatch};
+    use crate::EntityTag;
+
+    #[test]
+    fn check_parse() {
+        assert_eq!("*".parse(), Ok(IfMatch::Any));
+        assert_eq!("*".parse(), Ok(IfNoneMatch::Any));
+        assert_eq!("\"a\", W/\"b\"".parse(), Ok(IfMatch::Tags(vec![
+            EntityTag::strong("a"), EntityTag::weak("b")
+        ])));
+
+        assert!("".parse::<IfMatch>().is_err());
+        assert!("a".parse::<IfMatch>().is_err());
+        assert!("*, \"a\"".parse::<IfNoneMatch>().is_err());
+        assert!("\"a\" \"b\"".parse::<IfNoneMatch>().is_err());
+    }
+
+    #[test]
+    fn check_matches() {
+        let if_match: IfMatch = "W/\"a\", \"b\"".parse().unwrap();
+        assert!(!if_match.matches(&EntityTag::strong("a")));
+        assert!(if_match.matches(&EntityTag::strong("b")));
+        assert!(!if_match.matches(&EntityTag::weak("b")));
+
+        let if_none_match: IfNoneMatch = "W/\"a\", \"b\"".parse().unwrap();
+        assert!(if_none_match.matches(&EntityTag::strong("a")));
+        assert!(if_none_match.matches(&EntityTag::weak("b")));
+        assert!(!if_none_match.matches(&EntityTag::weak("c")));
+    }
+
+    #[test]
+    fn check_display() {
+        let if_none_match: IfNoneMatch = "\"a\",W/\"b\"".parse().unwrap();
+        assert_eq!(if_none_match.to_string(), "\"a\", W/\"b\"");
+        assert_eq!(IfMatch::Any.to_string(), "*");
+    }
+}
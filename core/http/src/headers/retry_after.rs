@@ -0,0 +1,141 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::{Header, HttpDate};
+use super::TypedHeader;
+
+/// The `Retry-After` header: how long a client should wait before making a
+/// follow-up request.
+///
+/// `Retry-After` is typically sent with `503 Service Unavailable`, `429 Too
+/// Many Requests`, and redirects. Its value is either an HTTP-date or a delay
+/// in whole seconds.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use std::time::Duration;
+/// use rocket::http::headers::RetryAfter;
+///
+/// let retry: RetryAfter = "120".parse().unwrap();
+/// assert_eq!(retry, RetryAfter::Delay(Duration::from_secs(120)));
+///
+/// let retry: RetryAfter = "Fri, 31 Dec 1999 23:59:59 GMT".parse().unwrap();
+/// assert!(retry.date().is_some());
+/// ```
+///
+/// `RetryAfter` implements `Into<Header>`, so it can be set on a response:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use std::time::Duration;
+/// use rocket::response::Response;
+/// use rocket::http::Status;
+/// use rocket::http::headers::RetryAfter;
+///
+/// let response = Response::build()
+///     .status(Status::ServiceUnavailable)
+///     .header(RetryAfter::Delay(Duration::from_secs(30)))
+///     .finalize();
+///
+/// assert_eq!(response.headers().get_one("Retry-After"), Some("30"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RetryAfter {
+    /// Retry at or after the given date.
+    Date(HttpDate),
+    /// Retry after the given delay. Only whole seconds are sent.
+    Delay(Duration),
+}
+
+impl RetryAfter {
+    /// Returns the date to retry at, if `self` is a `Date`.
+    #[inline]
+    pub fn date(&self) -> Option<HttpDate> {
+        match *self {
+            RetryAfter::Date(date) => Some(date),
+            RetryAfter::Delay(_) => None,
+        }
+    }
+
+    /// Returns the delay to retry after, if `self` is a `Delay`.
+    #[inline]
+    pub fn delay(&self) -> Option<Duration> {
+        match *self {
+            RetryAfter::Date(_) => None,
+            RetryAfter::Delay(delay) => Some(delay),
+        }
+    }
+}
+
+impl TypedHeader for RetryAfter {
+    const NAME: &'static str = "Retry-After";
+}
+
+impl FromStr for RetryAfter {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<RetryAfter, String> {
+        let raw = raw.trim();
+        if !raw.is_empty() && raw.bytes().all(|b| b.is_ascii_digit()) {
+            return raw.parse()
+                .map(|secs| RetryAfter::Delay(Duration::from_secs(secs)))
+                .map_err(|_| format!("Retry-After delay is too large: {}", raw));
+        }
+
+        raw.parse::<HttpDate>()
+            .map(RetryAfter::Date)
+            .map_err(|_| format!("invalid Retry-After value: {:?}", raw))
+    }
+}
+
+impl fmt::Display for RetryAfter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RetryAfter::Date(date) => write!(f, "{}", date),
+            RetryAfter::Delay(delay) => write!(f, "{}", delay.as_secs()),
+        }
+    }
+}
+
+/// Creates a new `Header` with name `Retry-After` and the value set to the
+/// HTTP rendering of this date or delay.
+impl Into<Header<'static>> for RetryAfter {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new(RetryAfter::NAME, self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::RetryAfter;
+    use crate::HttpDate;
+
+    #[test]
+    fn check_parse() {
+        let date = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784111777));
+        assert_eq!("Sun, 06 Nov 1994 08:49:37 GMT".parse(), Ok(RetryAfter::Date(date)));
+        assert_eq!("0".parse(), Ok(RetryAfter::Delay(Duration::from_secs(0))));
+        assert_eq!(" 3600 ".parse(), Ok(RetryAfter::Delay(Duration::from_secs(3600))));
+
+        assert!("".parse::<RetryAfter>().is_err());
+        assert!("-1".parse::<RetryAfter>().is_err());
+        assert!("1.5".parse::<RetryAfter>().is_err());
+        assert!("99999999999999999999999".parse::<RetryAfter>().is_err());
+        assert!("tomorrow".parse::<RetryAfter>().is_err());
+    }
+
+    #[test]
+    fn check_display() {
+        let delay = RetryAfter::Delay(Duration::from_millis(2500));
+        assert_eq!(delay.to_string(), "2");
+
+        let date = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784111777));
+        assert_eq!(RetryAfter::Date(date).to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+    }
+}
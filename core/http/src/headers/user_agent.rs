@@ -0,0 +1,126 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Header;
+use crate::parse::parse_user_agent;
+use super::TypedHeader;
+
+/// The `User-Agent` header: the software making the request.
+///
+/// A `User-Agent` must begin with a `product[/version]` identifier. What
+/// follows it is kept as is.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::headers::UserAgent;
+///
+/// let agent: UserAgent = "curl/7.64.1".parse().unwrap();
+/// assert_eq!(agent.product(), ("curl", Some("7.64.1")));
+///
+/// let agent: UserAgent = "Mozilla/5.0 (X11; Linux x86_64)".parse().unwrap();
+/// assert_eq!(agent.product(), ("Mozilla", Some("5.0")));
+/// assert_eq!(agent.as_str(), "Mozilla/5.0 (X11; Linux x86_64)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UserAgent(pub(crate) String);
+
+impl UserAgent {
+    /// Creates a `User-Agent` header identifying `product`, at `version` if
+    /// one is given.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `product` or `version` aren't valid HTTP tokens.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::headers::UserAgent;
+    ///
+    /// let agent = UserAgent::new("Rocket", Some("0.5"));
+    /// assert_eq!(agent.as_str(), "Rocket/0.5");
+    /// ```
+    pub fn new(product: &str, version: Option<&str>) -> UserAgent {
+        let value = match version {
+            Some(version) => format!("{}/{}", product, version),
+            None => product.to_string(),
+        };
+
+        match parse_user_agent(&value) {
+            Ok(agent) if agent.product() == (product, version) => agent,
+            _ => panic!("invalid User-Agent product: {:?}", value)
+        }
+    }
+
+    /// Returns the full value of the header.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the name and the version, if any, of the first product.
+    pub fn product(&self) -> (&str, Option<&str>) {
+        let product = self.0.split_whitespace().next().unwrap_or("");
+        let mut parts = product.splitn(2, '/');
+        (parts.next().unwrap_or(""), parts.next())
+    }
+}
+
+impl TypedHeader for UserAgent {
+    const NAME: &'static str = "User-Agent";
+}
+
+impl FromStr for UserAgent {
+    type Err = String;
+
+    #[inline]
+    fn from_str(raw: &str) -> Result<UserAgent, String> {
+        parse_user_agent(raw.trim()).map_err(|e| e.to_string())
+    }
+}
+
+impl fmt::Display for UserAgent {
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Creates a new `Header` with name `User-Agent` and the value set to the
+/// value of this `UserAgent`.
+impl Into<Header<'static>> for UserAgent {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new(UserAgent::NAME, self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UserAgent;
+
+    #[test]
+    fn check_parse() {
+        let agent: UserAgent = "Rocket".parse().unwrap();
+        assert_eq!(agent.product(), ("Rocket", None));
+
+        let agent: UserAgent = "  Wget/1.20.3 (linux-gnu)  ".parse().unwrap();
+        assert_eq!(agent.product(), ("Wget", Some("1.20.3")));
+        assert_eq!(agent.as_str(), "Wget/1.20.3 (linux-gnu)");
+
+        assert!("".parse::<UserAgent>().is_err());
+        assert!("/1.0".parse::<UserAgent>().is_err());
+        assert!("curl/".parse::<UserAgent>().is_err());
+        assert!("(compatible)".parse::<UserAgent>().is_err());
+        assert!("curl/7.0(x)".parse::<UserAgent>().is_err());
+    }
+
+    #[test]
+    fn check_new() {
+        assert_eq!(UserAgent::new("curl", None).to_string(), "curl");
+        assert_eq!(UserAgent::new("curl", Some("7.0")).product(), ("curl", Some("7.0")));
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Header;
+use crate::uncased::Uncased;
+use crate::parse::parse_vary;
+use super::TypedHeader;
+
+/// The `Vary` header: the request headers that a response was selected by.
+///
+/// Caches use `Vary` to decide whether a stored response can be reused for a
+/// request. `Vary: *` signals that the response depends on more than request
+/// headers.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::headers::Vary;
+///
+/// let vary: Vary = "Accept-Encoding, Accept-Language".parse().unwrap();
+/// assert!(vary.varies_on("accept-language"));
+/// assert!(!vary.varies_on("Cookie"));
+///
+/// assert!(Vary::Any.varies_on("Cookie"));
+/// ```
+///
+/// `Vary` implements `Into<Header>`, so it can be set on a response:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::response::Response;
+/// use rocket::http::headers::Vary;
+///
+/// let response = Response::build()
+///     .header(Vary::Fields(vec!["Accept".into(), "Cookie".into()]))
+///     .finalize();
+///
+/// assert_eq!(response.headers().get_one("Vary"), Some("Accept, Cookie"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Vary {
+    /// `*`: the response varies on more than the request's headers.
+    Any,
+    /// The names of the request headers the response varies on.
+    Fields(Vec<Uncased<'static>>),
+}
+
+impl Vary {
+    /// Returns `true` if the response varies on the header named `name`.
+    #[inline]
+    pub fn varies_on(&self, name: &str) -> bool {
+        match *self {
+            Vary::Any => true,
+            Vary::Fields(ref fields) => fields.iter().any(|field| field == name),
+        }
+    }
+}
+
+impl TypedHeader for Vary {
+    const NAME: &'static str = "Vary";
+}
+
+impl FromStr for Vary {
+    type Err = String;
+
+    #[inline]
+    fn from_str(raw: &str) -> Result<Vary, String> {
+        parse_vary(raw.trim()).map_err(|e| e.to_string())
+    }
+}
+
+impl fmt::Display for Vary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Vary::Any => write!(f, "*"),
+            Vary::Fields(ref fields) => {
+                for (i, field) in fields.iter().enumerate() {
+                    if i >= 1 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{}", field)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Creates a new `Header` with name `Vary` and the value set to the HTTP
+/// rendering of this `Vary` header.
+impl Into<Header<'static>> for Vary {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new(Vary::NAME, self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Vary;
+
+    #[test]
+    fn check_parse() {
+        assert_eq!("*".parse(), Ok(Vary::Any));
+        assert_eq!("Accept".parse(), Ok(Vary::Fields(vec!["accept".into()])));
+        assert_eq!("Accept ,Origin".parse(),
+            Ok(Vary::Fields(vec!["Accept".into(), "Origin".into()])));
+
+        assert!("".parse::<Vary>().is_err());
+        assert!("*, Accept".parse::<Vary>().is_err());
+        assert!("Accept Origin".parse::<Vary>().is_err());
+        assert!("Accept;".parse::<Vary>().is_err());
+    }
+
+    #[test]
+    fn check_display() {
+        let vary: Vary = "accept,  x-Custom".parse().unwrap();
+        assert_eq!(vary.to_string(), "accept, x-Custom");
+    }
+}
@@ -0,0 +1,259 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use time::OffsetDateTime;
+
+/// A point in time with the one-second resolution of an HTTP-date, as used in
+/// the `Last-Modified`, `If-Modified-Since`, and `If-Unmodified-Since`
+/// headers.
+///
+/// An `HttpDate` is displayed in the preferred IMF-fixdate format, for example
+/// `Sun, 06 Nov 1994 08:49:37 GMT`. Parsing additionally accepts the obsolete
+/// RFC 850 and asctime formats, as recipients are required to.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use rocket::http::HttpDate;
+///
+/// let date = HttpDate::from(UNIX_EPOCH + Duration::from_secs(784111777));
+/// assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+/// assert_eq!("Sunday, 06-Nov-94 08:49:37 GMT".parse(), Ok(date));
+/// assert_eq!("Sun Nov  6 08:49:37 1994".parse(), Ok(date));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HttpDate {
+    /// Seconds since the Unix epoch.
+    timestamp: i64,
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun",
+    "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"
+];
+
+/// Returns the number of days between the Unix epoch and the given date of the
+/// proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = (if year >= 0 { year } else { year - 399 }) / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+fn parse_month(month: &str) -> Option<i64> {
+    MONTHS.iter().position(|&m| m == month).map(|i| i as i64 + 1)
+}
+
+fn parse_number(digits: &str, len: usize) -> Option<i64> {
+    match digits.len() == len && digits.bytes().all(|b| b.is_ascii_digit()) {
+        true => digits.parse().ok(),
+        false => None
+    }
+}
+
+/// Parses an `HH:MM:SS` time of day into seconds since midnight.
+fn parse_time(time: &str) -> Option<i64> {
+    let mut parts = time.split(':');
+    let hour = parse_number(parts.next()?, 2)?;
+    let minute = parse_number(parts.next()?, 2)?;
+    let second = parse_number(parts.next()?, 2)?;
+    if parts.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    // A leap second is folded into the second before it.
+    Some(hour * 3600 + minute * 60 + second.min(59))
+}
+
+impl HttpDate {
+    /// Returns the current time as an `HttpDate`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::HttpDate;
+    ///
+    /// assert!(HttpDate::now() > "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap());
+    /// ```
+    #[inline]
+    pub fn now() -> HttpDate {
+        HttpDate::from(SystemTime::now())
+    }
+
+    /// Returns the number of seconds between the Unix epoch and `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::HttpDate;
+    ///
+    /// let date: HttpDate = "Thu, 01 Jan 1970 00:01:00 GMT".parse().unwrap();
+    /// assert_eq!(date.unix_timestamp(), 60);
+    /// ```
+    #[inline(always)]
+    pub fn unix_timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    fn from_parts(year: i64, month: i64, day: i64, seconds: i64) -> Option<HttpDate> {
+        if day < 1 || day > 31 || year > 9999 {
+            return None;
+        }
+
+        let timestamp = days_from_civil(year, month, day) * 86400 + seconds;
+        Some(HttpDate { timestamp })
+    }
+
+    /// Parses `Sun, 06 Nov 1994 08:49:37 GMT`.
+    fn parse_imf_fixdate(parts: &[&str]) -> Option<HttpDate> {
+        match *parts {
+            [_, day, month, year, time, "GMT"] => HttpDate::from_parts(
+                parse_number(year, 4)?,
+                parse_month(month)?,
+                parse_number(day, 2)?,
+                parse_time(time)?
+            ),
+            _ => None
+        }
+    }
+
+    /// Parses `Sunday, 06-Nov-94 08:49:37 GMT`.
+    fn parse_rfc850(parts: &[&str]) -> Option<HttpDate> {
+        match *parts {
+            [_, date, time, "GMT"] => {
+                let mut date = date.split('-');
+                let day = parse_number(date.next()?, 2)?;
+                let month = parse_month(date.next()?)?;
+                let year = parse_number(date.next()?, 2)?;
+                if date.next().is_some() {
+                    return None;
+                }
+
+                // Two-digit years are assumed to be within fifty years of now.
+                let year = if year < 70 { 2000 + year } else { 1900 + year };
+                HttpDate::from_parts(year, month, day, parse_time(time)?)
+            }
+            _ => None
+        }
+    }
+
+    /// Parses `Sun Nov  6 08:49:37 1994`.
+    fn parse_asctime(parts: &[&str]) -> Option<HttpDate> {
+        match *parts {
+            [_, month, day, time, year] => HttpDate::from_parts(
+                parse_number(year, 4)?,
+                parse_month(month)?,
+                parse_number(day, 1).or_else(|| parse_number(day, 2))?,
+                parse_time(time)?
+            ),
+            _ => None
+        }
+    }
+}
+
+impl From<SystemTime> for HttpDate {
+    /// Converts `time` to an `HttpDate`, truncating it to the second.
+    fn from(time: SystemTime) -> HttpDate {
+        let timestamp = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_secs() as i64,
+            Err(e) => {
+                let before = e.duration();
+                let secs = before.as_secs() as i64;
+                if before.subsec_nanos() > 0 { -secs - 1 } else { -secs }
+            }
+        };
+
+        HttpDate { timestamp }
+    }
+}
+
+impl From<HttpDate> for SystemTime {
+    fn from(date: HttpDate) -> SystemTime {
+        match date.timestamp >= 0 {
+            true => UNIX_EPOCH + Duration::from_secs(date.timestamp as u64),
+            false => UNIX_EPOCH - Duration::from_secs(date.timestamp.abs() as u64),
+        }
+    }
+}
+
+impl FromStr for HttpDate {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<HttpDate, String> {
+        let parts: Vec<_> = raw.split_whitespace().collect();
+        let date = match parts.first() {
+            Some(name) if name.ends_with(',') && name.len() == 4 => {
+                HttpDate::parse_imf_fixdate(&parts)
+            }
+            Some(name) if name.ends_with(',') => HttpDate::parse_rfc850(&parts),
+            Some(_) => HttpDate::parse_asctime(&parts),
+            None => None
+        };
+
+        date.ok_or_else(|| format!("invalid HTTP-date: {:?}", raw))
+    }
+}
+
+impl fmt::Display for HttpDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let date = OffsetDateTime::from_unix_timestamp(self.timestamp);
+        write!(f, "{}", date.format("%a, %d %b %Y %H:%M:%S GMT"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HttpDate;
+
+    macro_rules! assert_date {
+        ($string:expr, $timestamp:expr) => {
+            let date: HttpDate = $string.parse().expect($string);
+            assert_eq!(date.unix_timestamp(), $timestamp);
+        };
+    }
+
+    #[test]
+    fn check_parse() {
+        assert_date!("Sun, 06 Nov 1994 08:49:37 GMT", 784111777);
+        assert_date!("Sunday, 06-Nov-94 08:49:37 GMT", 784111777);
+        assert_date!("Sun Nov  6 08:49:37 1994", 784111777);
+        assert_date!("Thu, 01 Jan 1970 00:00:00 GMT", 0);
+        assert_date!("Wed, 31 Dec 1969 23:59:59 GMT", -1);
+        assert_date!("Tue, 29 Feb 2000 12:00:00 GMT", 951825600);
+        assert_date!("Thursday, 01-Jan-15 00:00:00 GMT", 1420070400);
+        assert_date!("Sat, 31 Dec 2016 23:59:60 GMT", 1483228799);
+    }
+
+    #[test]
+    fn check_parse_invalid() {
+        for invalid in &[
+            "",
+            "Sun, 06 Nov 1994 08:49:37",
+            "Sun, 06 Nov 1994 08:49:37 UTC",
+            "Sun, 6 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Foo 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 24:00:00 GMT",
+            "Sun, 06 Nov 1994 08:49 GMT",
+            "Sunday, 06-Nov-1994 08:49:37 GMT",
+            "Sun Nov 06 08:49:37 94",
+            "1994-11-06T08:49:37Z",
+        ] {
+            assert!(invalid.parse::<HttpDate>().is_err(), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn check_display() {
+        for date in &["Sun, 06 Nov 1994 08:49:37 GMT", "Thu, 01 Jan 1970 00:00:00 GMT"] {
+            assert_eq!(date.parse::<HttpDate>().unwrap().to_string(), *date);
+        }
+    }
+}
@@ -8,6 +8,7 @@
 #[doc(hidden)] pub use hyper::server::Response as Response;
 #[doc(hidden)] pub use hyper::server::Server as Server;
 #[doc(hidden)] pub use hyper::server::Handler as Handler;
+#[doc(hidden)] pub use hyper::server::Listening as Listening;
 
 #[doc(hidden)] pub use hyper::net;
 
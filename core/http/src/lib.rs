@@ -16,6 +16,7 @@
 pub mod hyper;
 pub mod uri;
 pub mod ext;
+pub mod headers;
 
 #[doc(hidden)]
 #[cfg(feature = "tls")]
@@ -35,6 +36,9 @@ mod content_type;
 mod status;
 mod header;
 mod accept;
+mod range;
+mod entity_tag;
+mod http_date;
 mod raw_str;
 mod parse;
 
@@ -53,9 +57,12 @@ pub mod private {
     pub use crate::cookies::{Key, CookieJar};
 }
 
-pub use crate::method::Method;
+pub use crate::method::{Method, ExtensionMethod};
 pub use crate::content_type::ContentType;
 pub use crate::accept::{Accept, QMediaType};
+pub use crate::range::{Range, ByteRange, IfRange};
+pub use crate::entity_tag::EntityTag;
+pub use crate::http_date::HttpDate;
 pub use crate::status::{Status, StatusClass};
 pub use crate::header::{Header, HeaderMap};
 pub use crate::raw_str::RawStr;
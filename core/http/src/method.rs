@@ -2,23 +2,246 @@ use std::fmt;
 use std::str::FromStr;
 
 use crate::{hyper, uncased::uncased_eq};
+use crate::parse::checkers::is_valid_token;
 
 use self::Method::*;
 
-// TODO: Support non-standard methods, here and in codegen.
-
-/// Representation of HTTP methods.
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-pub enum Method {
-    Get,
-    Put,
-    Post,
-    Delete,
-    Options,
-    Head,
-    Trace,
-    Connect,
-    Patch
+macro_rules! define_methods {
+    ($($(#[$attr:meta])* $V:ident ($name:expr, $payload:expr)),+ $(,)*) => {
+        /// Representation of HTTP methods.
+        ///
+        /// Besides the methods of RFC 7231 and `PATCH`, `Method` has a variant
+        /// for each of the methods registered by WebDAV (RFC 4918) and its
+        /// extensions. Any other method is an [`Extension`](Method::Extension).
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        /// # extern crate rocket;
+        /// use rocket::http::Method;
+        ///
+        /// assert_eq!("PROPFIND".parse(), Ok(Method::PropFind));
+        /// assert_eq!("VERSION-CONTROL".parse(), Ok(Method::VersionControl));
+        ///
+        /// let purge: Method = "PURGE".parse().unwrap();
+        /// assert!(purge.is_extension());
+        /// assert_eq!(purge.as_str(), "PURGE");
+        /// ```
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        pub enum Method {
+            $($(#[$attr])* $V,)+
+            /// Any other method. See [`ExtensionMethod`].
+            Extension(ExtensionMethod),
+        }
+
+        impl Method {
+            /// Returns `true` if an HTTP request with the method represented by
+            /// `self` always supports a payload.
+            ///
+            /// The following methods always support payloads:
+            ///
+            ///   * `PUT`, `POST`, `DELETE`, `PATCH`
+            ///   * The WebDAV methods that define a request body, such as
+            ///     `PROPFIND`, `PROPPATCH`, `LOCK`, `REPORT`, and `SEARCH`
+            ///   * Extension methods, whose semantics Rocket doesn't know
+            ///
+            /// The following methods _do not_ always support payloads:
+            ///
+            ///   * `GET`, `HEAD`, `CONNECT`, `TRACE`, `OPTIONS`
+            ///   * `COPY`, `MOVE`, `UNLOCK`, `CHECKOUT`, `UNCHECKOUT`
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            /// # extern crate rocket;
+            /// use rocket::http::Method;
+            ///
+            /// assert_eq!(Method::Get.supports_payload(), false);
+            /// assert_eq!(Method::Post.supports_payload(), true);
+            /// assert_eq!(Method::PropFind.supports_payload(), true);
+            /// assert_eq!(Method::Unlock.supports_payload(), false);
+            /// ```
+            #[inline]
+            pub fn supports_payload(self) -> bool {
+                match self {
+                    $($V => $payload,)+
+                    Extension(_) => true,
+                }
+            }
+
+            /// Returns the string representation of `self`.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            /// # extern crate rocket;
+            /// use rocket::http::Method;
+            ///
+            /// assert_eq!(Method::Get.as_str(), "GET");
+            /// assert_eq!(Method::MkCol.as_str(), "MKCOL");
+            /// ```
+            #[inline]
+            pub fn as_str(&self) -> &str {
+                match *self {
+                    $($V => $name,)+
+                    Extension(ref method) => method.as_str(),
+                }
+            }
+        }
+
+        impl FromStr for Method {
+            type Err = ();
+
+            // According to the RFC, method names are case-sensitive. But some old
+            // clients don't follow this, so we just do a case-insensitive match
+            // here. Extension methods are uppercased for the same reason.
+            fn from_str(s: &str) -> Result<Method, ()> {
+                match s {
+                    $(x if uncased_eq(x, $name) => Ok($V),)+
+                    _ => ExtensionMethod::new(s).map(Extension).ok_or(()),
+                }
+            }
+        }
+    };
+}
+
+define_methods! {
+    /// `GET` (RFC 7231)
+    Get("GET", false),
+    /// `PUT` (RFC 7231)
+    Put("PUT", true),
+    /// `POST` (RFC 7231)
+    Post("POST", true),
+    /// `DELETE` (RFC 7231)
+    Delete("DELETE", true),
+    /// `OPTIONS` (RFC 7231)
+    Options("OPTIONS", false),
+    /// `HEAD` (RFC 7231)
+    Head("HEAD", false),
+    /// `TRACE` (RFC 7231)
+    Trace("TRACE", false),
+    /// `CONNECT` (RFC 7231)
+    Connect("CONNECT", false),
+    /// `PATCH` (RFC 5789)
+    Patch("PATCH", true),
+    /// `PROPFIND` (RFC 4918)
+    PropFind("PROPFIND", true),
+    /// `PROPPATCH` (RFC 4918)
+    PropPatch("PROPPATCH", true),
+    /// `MKCOL` (RFC 4918)
+    MkCol("MKCOL", true),
+    /// `COPY` (RFC 4918)
+    Copy("COPY", false),
+    /// `MOVE` (RFC 4918)
+    Move("MOVE", false),
+    /// `LOCK` (RFC 4918)
+    Lock("LOCK", true),
+    /// `UNLOCK` (RFC 4918)
+    Unlock("UNLOCK", false),
+    /// `VERSION-CONTROL` (RFC 3253)
+    VersionControl("VERSION-CONTROL", true),
+    /// `REPORT` (RFC 3253)
+    Report("REPORT", true),
+    /// `CHECKOUT` (RFC 3253)
+    CheckOut("CHECKOUT", false),
+    /// `CHECKIN` (RFC 3253)
+    CheckIn("CHECKIN", true),
+    /// `UNCHECKOUT` (RFC 3253)
+    Uncheckout("UNCHECKOUT", false),
+    /// `MKWORKSPACE` (RFC 3253)
+    MkWorkspace("MKWORKSPACE", true),
+    /// `UPDATE` (RFC 3253)
+    Update("UPDATE", true),
+    /// `LABEL` (RFC 3253)
+    Label("LABEL", true),
+    /// `MERGE` (RFC 3253)
+    Merge("MERGE", true),
+    /// `BASELINE-CONTROL` (RFC 3253)
+    BaselineControl("BASELINE-CONTROL", true),
+    /// `MKACTIVITY` (RFC 3253)
+    MkActivity("MKACTIVITY", true),
+    /// `ORDERPATCH` (RFC 3648)
+    OrderPatch("ORDERPATCH", true),
+    /// `ACL` (RFC 3744)
+    Acl("ACL", true),
+    /// `MKREDIRECTREF` (RFC 4437)
+    MkRedirectRef("MKREDIRECTREF", true),
+    /// `UPDATEREDIRECTREF` (RFC 4437)
+    UpdateRedirectRef("UPDATEREDIRECTREF", true),
+    /// `MKCALENDAR` (RFC 4791)
+    MkCalendar("MKCALENDAR", true),
+    /// `SEARCH` (RFC 5323)
+    Search("SEARCH", true),
+    /// `BIND` (RFC 5842)
+    Bind("BIND", true),
+    /// `REBIND` (RFC 5842)
+    Rebind("REBIND", true),
+    /// `UNBIND` (RFC 5842)
+    Unbind("UNBIND", true),
+}
+
+/// An HTTP method that isn't one of the named [`Method`] variants, such as
+/// `PURGE`.
+///
+/// An `ExtensionMethod` is any HTTP token of at most
+/// [`MAX_LEN`](ExtensionMethod::MAX_LEN) characters. It is stored uppercased,
+/// inline, so that `Method` remains `Copy`. An `ExtensionMethod` is obtained
+/// by parsing a [`Method`]:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::Method;
+///
+/// match "purge".parse::<Method>() {
+///     Ok(Method::Extension(method)) => assert_eq!(method.as_str(), "PURGE"),
+///     _ => unreachable!(),
+/// }
+///
+/// assert!("BAD METHOD".parse::<Method>().is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExtensionMethod {
+    len: u8,
+    bytes: [u8; ExtensionMethod::MAX_LEN],
+}
+
+impl ExtensionMethod {
+    /// The maximum length, in bytes, of an extension method.
+    pub const MAX_LEN: usize = 32;
+
+    /// Returns an `ExtensionMethod` for the token `name`, uppercased, or
+    /// `None` if `name` isn't a token of at most `MAX_LEN` bytes. Known methods
+    /// aren't checked for: use `Method::from_str()`.
+    fn new(name: &str) -> Option<ExtensionMethod> {
+        if name.is_empty() || name.len() > Self::MAX_LEN || !name.chars().all(is_valid_token) {
+            return None;
+        }
+
+        let mut bytes = [0; Self::MAX_LEN];
+        bytes[..name.len()].copy_from_slice(name.as_bytes());
+        bytes.make_ascii_uppercase();
+        Some(ExtensionMethod { len: name.len() as u8, bytes })
+    }
+
+    /// WARNING: This is unstable! Do not use this method outside of Rocket!
+    #[doc(hidden)]
+    pub const fn from_raw(bytes: [u8; ExtensionMethod::MAX_LEN], len: u8) -> ExtensionMethod {
+        ExtensionMethod { len, bytes }
+    }
+
+    /// WARNING: This is unstable! Do not use this method outside of Rocket!
+    #[doc(hidden)]
+    pub fn raw_bytes(&self) -> [u8; ExtensionMethod::MAX_LEN] {
+        self.bytes
+    }
+
+    /// Returns the name of this method.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // `new()` only accepts ASCII tokens, but `from_raw()` accepts anything.
+        let len = std::cmp::min(self.len as usize, Self::MAX_LEN);
+        std::str::from_utf8(&self.bytes[..len]).unwrap_or("")
+    }
 }
 
 impl Method {
@@ -35,39 +258,11 @@ impl Method {
             hyper::Method::Trace => Some(Trace),
             hyper::Method::Connect => Some(Connect),
             hyper::Method::Patch => Some(Patch),
-            hyper::Method::Extension(_) => None,
-        }
-    }
-
-    /// Returns `true` if an HTTP request with the method represented by `self`
-    /// always supports a payload.
-    ///
-    /// The following methods always support payloads:
-    ///
-    ///   * `PUT`, `POST`, `DELETE`, `PATCH`
-    ///
-    /// The following methods _do not_ always support payloads:
-    ///
-    ///   * `GET`, `HEAD`, `CONNECT`, `TRACE`, `OPTIONS`
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # extern crate rocket;
-    /// use rocket::http::Method;
-    ///
-    /// assert_eq!(Method::Get.supports_payload(), false);
-    /// assert_eq!(Method::Post.supports_payload(), true);
-    /// ```
-    #[inline]
-    pub fn supports_payload(self) -> bool {
-        match self {
-            Put | Post | Delete | Patch => true,
-            Get | Head | Connect | Trace | Options => false,
+            hyper::Method::Extension(ref method) => method.parse().ok(),
         }
     }
 
-    /// Returns the string representation of `self`.
+    /// Returns `true` if `self` is an [`Extension`](Method::Extension) method.
     ///
     /// # Example
     ///
@@ -75,48 +270,84 @@ impl Method {
     /// # extern crate rocket;
     /// use rocket::http::Method;
     ///
-    /// assert_eq!(Method::Get.as_str(), "GET");
+    /// assert!(!Method::Lock.is_extension());
+    /// assert!("PURGE".parse::<Method>().unwrap().is_extension());
     /// ```
     #[inline]
-    pub fn as_str(self) -> &'static str {
-        match self {
-            Get => "GET",
-            Put => "PUT",
-            Post => "POST",
-            Delete => "DELETE",
-            Options => "OPTIONS",
-            Head => "HEAD",
-            Trace => "TRACE",
-            Connect => "CONNECT",
-            Patch => "PATCH",
+    pub fn is_extension(&self) -> bool {
+        match *self {
+            Extension(_) => true,
+            _ => false
         }
     }
 }
 
-impl FromStr for Method {
-    type Err = ();
-
-    // According to the RFC, method names are case-sensitive. But some old
-    // clients don't follow this, so we just do a case-insensitive match here.
-    fn from_str(s: &str) -> Result<Method, ()> {
-        match s {
-            x if uncased_eq(x, Get.as_str()) => Ok(Get),
-            x if uncased_eq(x, Put.as_str()) => Ok(Put),
-            x if uncased_eq(x, Post.as_str()) => Ok(Post),
-            x if uncased_eq(x, Delete.as_str()) => Ok(Delete),
-            x if uncased_eq(x, Options.as_str()) => Ok(Options),
-            x if uncased_eq(x, Head.as_str()) => Ok(Head),
-            x if uncased_eq(x, Trace.as_str()) => Ok(Trace),
-            x if uncased_eq(x, Connect.as_str()) => Ok(Connect),
-            x if uncased_eq(x, Patch.as_str()) => Ok(Patch),
-            _ => Err(()),
-        }
+impl fmt::Display for Method {
+    #[inline(always)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_str().fmt(f)
     }
 }
 
-impl fmt::Display for Method {
+impl fmt::Display for ExtensionMethod {
     #[inline(always)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.as_str().fmt(f)
     }
 }
+
+impl fmt::Debug for ExtensionMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ExtensionMethod").field(&self.as_str()).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Method, Method::*, ExtensionMethod};
+
+    #[test]
+    fn check_parse() {
+        assert_eq!("GET".parse(), Ok(Get));
+        assert_eq!("get".parse(), Ok(Get));
+        assert_eq!("PropFind".parse(), Ok(PropFind));
+        assert_eq!("baseline-control".parse(), Ok(BaselineControl));
+
+        let purge: Method = "Purge".parse().unwrap();
+        assert_eq!(purge.as_str(), "PURGE");
+        assert_eq!(purge, "PURGE".parse().unwrap());
+
+        let long = "X".repeat(ExtensionMethod::MAX_LEN);
+        assert_eq!(long.parse::<Method>().unwrap().as_str(), long);
+
+        assert!("".parse::<Method>().is_err());
+        assert!("GET /".parse::<Method>().is_err());
+        assert!("M(1)".parse::<Method>().is_err());
+        assert!("ÜBER".parse::<Method>().is_err());
+        assert!("X".repeat(ExtensionMethod::MAX_LEN + 1).parse::<Method>().is_err());
+    }
+
+    #[test]
+    fn check_round_trip() {
+        for method in &[Get, Patch, MkCol, VersionControl, UpdateRedirectRef, Unbind] {
+            assert_eq!(method.to_string().parse(), Ok(*method));
+        }
+    }
+
+    #[test]
+    fn check_raw_parts() {
+        let purge: Method = "PURGE".parse().unwrap();
+        if let Extension(method) = purge {
+            let raw = ExtensionMethod::from_raw(method.raw_bytes(), 5);
+            assert_eq!(Extension(raw), purge);
+        } else {
+            panic!("PURGE is not an extension method");
+        }
+
+        let mut bytes = [0xFF; ExtensionMethod::MAX_LEN];
+        assert_eq!(ExtensionMethod::from_raw(bytes, 4).as_str(), "");
+        bytes[..5].copy_from_slice(b"PURGE");
+        assert_eq!(ExtensionMethod::from_raw(bytes, 5).as_str(), "PURGE");
+        assert_eq!(ExtensionMethod::from_raw(bytes, 255).as_str(), "");
+    }
+}
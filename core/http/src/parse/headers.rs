@@ -0,0 +1,269 @@
+use pear::{parser, switch};
+use pear::parsers::*;
+
+use crate::EntityTag;
+use crate::entity_tag::is_etagc;
+use crate::uncased::Uncased;
+use crate::headers::*;
+use crate::parse::checkers::{is_whitespace, is_valid_token};
+
+type Input<'a> = &'a str;
+type Result<'a, T> = pear::Result<T, Input<'a>>;
+
+#[parser]
+fn ascii<'a>(input: &mut Input<'a>) -> Result<'a, ()> {
+    // A `&str` input advances by characters, not bytes, so only ASCII input
+    // can be parsed safely.
+    if !input.is_ascii() {
+        return Err(pear_error!("header value contains non-ASCII characters"));
+    }
+}
+
+#[parser]
+fn token<'a>(input: &mut Input<'a>) -> Result<'a, &'a str> {
+    take_some_while(is_valid_token)?
+}
+
+fn unescape(string: &str) -> String {
+    let mut unescaped = String::with_capacity(string.len());
+    let mut chars = string.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+#[parser]
+fn quoted_string<'a>(input: &mut Input<'a>) -> Result<'a, String> {
+    eat('"')?;
+
+    let mut is_escaped = false;
+    let inner = take_while(|c| {
+        if is_escaped { is_escaped = false; return true; }
+        if c == '\\' { is_escaped = true; return true; }
+        c != '"'
+    })?;
+
+    eat('"')?;
+    unescape(inner)
+}
+
+/// A token or a quoted-string.
+#[parser]
+fn word<'a>(input: &mut Input<'a>) -> Result<'a, String> {
+    switch! {
+        peek('"') => quoted_string()?,
+        _ => token()?.to_string()
+    }
+}
+
+/// A `name=value` parameter. The name is lowercased.
+#[parser]
+fn param<'a>(input: &mut Input<'a>) -> Result<'a, (String, String)> {
+    let name = token()?.to_ascii_lowercase();
+    (skip_while(is_whitespace)?, eat('=')?, skip_while(is_whitespace)?);
+    (name, word()?)
+}
+
+#[parser]
+fn entity_tag<'a>(input: &mut Input<'a>) -> Result<'a, EntityTag> {
+    ascii()?;
+    let weak = switch! {
+        eat_slice("W/") => true,
+        _ => false
+    };
+
+    let tag = (eat('"')?, take_while(is_etagc)?, eat('"')?).1;
+    match weak {
+        true => EntityTag::weak(tag),
+        false => EntityTag::strong(tag)
+    }
+}
+
+#[parser]
+fn entity_tags<'a>(input: &mut Input<'a>) -> Result<'a, Vec<EntityTag>> {
+    series(false, ',', is_whitespace, entity_tag)?
+}
+
+#[parser]
+fn cache_directive<'a>(input: &mut Input<'a>) -> Result<'a, CacheDirective> {
+    let name = token()?.to_ascii_lowercase();
+    let value = switch! {
+        eat('=') => Some(word()?),
+        _ => None
+    };
+
+    match CacheDirective::from_parts(name, value) {
+        Ok(directive) => directive,
+        Err(e) => return Err(pear_error!("{}", e))
+    }
+}
+
+#[parser]
+fn cache_control<'a>(input: &mut Input<'a>) -> Result<'a, CacheControl> {
+    ascii()?;
+    CacheControl::new(series(false, ',', is_whitespace, cache_directive)?)
+}
+
+#[parser]
+fn vary<'a>(input: &mut Input<'a>) -> Result<'a, Vary> {
+    ascii()?;
+    switch! {
+        eat('*') => Vary::Any,
+        _ => {
+            let fields = series(false, ',', is_whitespace, token)?;
+            Vary::Fields(fields.into_iter().map(|f| Uncased::from(f.to_string())).collect())
+        }
+    }
+}
+
+#[parser]
+fn if_match<'a>(input: &mut Input<'a>) -> Result<'a, IfMatch> {
+    switch! {
+        eat('*') => IfMatch::Any,
+        _ => IfMatch::Tags(entity_tags()?)
+    }
+}
+
+#[parser]
+fn if_none_match<'a>(input: &mut Input<'a>) -> Result<'a, IfNoneMatch> {
+    switch! {
+        eat('*') => IfNoneMatch::Any,
+        _ => IfNoneMatch::Tags(entity_tags()?)
+    }
+}
+
+#[parser]
+fn link_value<'a>(input: &mut Input<'a>) -> Result<'a, LinkValue> {
+    let uri = (eat('<')?, take_while(|c| c != '>')?, eat('>')?).1;
+    let params = series(true, ';', is_whitespace, param)?;
+    LinkValue { uri: uri.to_string(), params }
+}
+
+#[parser]
+fn link<'a>(input: &mut Input<'a>) -> Result<'a, Link> {
+    ascii()?;
+    Link::new(series(false, ',', is_whitespace, link_value)?)
+}
+
+#[parser]
+fn authorization<'a>(input: &mut Input<'a>) -> Result<'a, Authorization> {
+    ascii()?;
+    let scheme = token()?;
+    let credentials = switch! {
+        eat(' ') => (skip_while(is_whitespace)?, take_while(|_| true)?).1,
+        _ => ""
+    };
+
+    match Authorization::from_parts(scheme, credentials) {
+        Ok(authorization) => authorization,
+        Err(e) => return Err(pear_error!("{}", e))
+    }
+}
+
+#[parser]
+fn content_disposition<'a>(input: &mut Input<'a>) -> Result<'a, ContentDisposition> {
+    ascii()?;
+    let kind = token()?.to_ascii_lowercase();
+    let params = series(true, ';', is_whitespace, param)?;
+    ContentDisposition { kind, params }
+}
+
+#[inline]
+fn is_reg_name_char(c: char) -> bool {
+    match c {
+        'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' | '_' | '~' | '%'
+            | '!' | '$' | '&' | '\'' | '(' | ')' | '*' | '+' | ',' | ';' | '=' => true,
+        _ => false
+    }
+}
+
+#[parser]
+fn host<'a>(input: &mut Input<'a>) -> Result<'a, Host> {
+    ascii()?;
+    let name = switch! {
+        peek('[') => enclosed('[', |c| c.is_ascii_hexdigit() || c == ':' || c == '.', ']')?,
+        _ => take_some_while(is_reg_name_char)?
+    };
+
+    let port = switch! {
+        eat(':') => {
+            let digits = take_some_while(|c| c.is_ascii_digit())?;
+            match digits.parse::<u16>() {
+                Ok(port) => Some(port),
+                Err(_) => return Err(pear_error!("invalid port: {}", digits))
+            }
+        },
+        _ => None
+    };
+
+    Host { host: name.to_ascii_lowercase(), port }
+}
+
+#[parser]
+fn user_agent<'a>(input: &mut Input<'a>) -> Result<'a, UserAgent> {
+    ascii()?;
+    let value = *input;
+
+    // The first product identifies the agent. What follows is free-form.
+    token()?;
+    switch! {
+        eat('/') => { token()?; },
+        _ => ()
+    }
+
+    let rest = take_while(|_| true)?;
+    if !rest.is_empty() && !rest.starts_with(is_whitespace) {
+        return Err(pear_error!("expected whitespace after product"));
+    }
+
+    UserAgent(value.trim_end().to_string())
+}
+
+pub fn parse_entity_tag(mut input: &str) -> Result<'_, EntityTag> {
+    parse!(entity_tag: &mut input)
+}
+
+pub fn parse_entity_tags(mut input: &str) -> Result<'_, Vec<EntityTag>> {
+    parse!(entity_tags: &mut input)
+}
+
+pub fn parse_cache_control(mut input: &str) -> Result<'_, CacheControl> {
+    parse!(cache_control: &mut input)
+}
+
+pub fn parse_vary(mut input: &str) -> Result<'_, Vary> {
+    parse!(vary: &mut input)
+}
+
+pub fn parse_if_match(mut input: &str) -> Result<'_, IfMatch> {
+    parse!(if_match: &mut input)
+}
+
+pub fn parse_if_none_match(mut input: &str) -> Result<'_, IfNoneMatch> {
+    parse!(if_none_match: &mut input)
+}
+
+pub fn parse_link(mut input: &str) -> Result<'_, Link> {
+    parse!(link: &mut input)
+}
+
+pub fn parse_authorization(mut input: &str) -> Result<'_, Authorization> {
+    parse!(authorization: &mut input)
+}
+
+pub fn parse_content_disposition(mut input: &str) -> Result<'_, ContentDisposition> {
+    parse!(content_disposition: &mut input)
+}
+
+pub fn parse_host(mut input: &str) -> Result<'_, Host> {
+    parse!(host: &mut input)
+}
+
+pub fn parse_user_agent(mut input: &str) -> Result<'_, UserAgent> {
+    parse!(user_agent: &mut input)
+}
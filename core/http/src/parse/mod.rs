@@ -1,10 +1,12 @@
 mod media_type;
 mod accept;
-mod checkers;
+mod headers;
+pub(crate) mod checkers;
 mod indexed;
 
 pub use self::media_type::*;
 pub use self::accept::*;
+pub use self::headers::*;
 
 pub mod uri;
 
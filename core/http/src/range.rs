@@ -0,0 +1,359 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Header;
+
+/// A byte range of a representation, as requested in a [`Range`] header.
+///
+/// Positions are zero-based and inclusive, as they are in HTTP. A range is
+/// resolved against the length of a representation with
+/// [`ByteRange::resolve()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteRange {
+    /// `first-last`: the bytes from `first` to `last`, inclusive.
+    Span(u64, u64),
+    /// `first-`: the bytes from `first` to the end.
+    From(u64),
+    /// `-n`: the last `n` bytes.
+    Suffix(u64),
+}
+
+impl ByteRange {
+    /// Resolves `self` against a representation of `len` bytes, returning the
+    /// first and last positions of the range, inclusive, clamped to the
+    /// representation. Returns `None` if the range is unsatisfiable: it starts
+    /// at or after `len` or, for a suffix range, is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::ByteRange;
+    ///
+    /// assert_eq!(ByteRange::Span(0, 99).resolve(50), Some((0, 49)));
+    /// assert_eq!(ByteRange::From(10).resolve(50), Some((10, 49)));
+    /// assert_eq!(ByteRange::Suffix(5).resolve(50), Some((45, 49)));
+    /// assert_eq!(ByteRange::Suffix(500).resolve(50), Some((0, 49)));
+    ///
+    /// assert_eq!(ByteRange::From(50).resolve(50), None);
+    /// assert_eq!(ByteRange::Suffix(0).resolve(50), None);
+    /// ```
+    pub fn resolve(self, len: u64) -> Option<(u64, u64)> {
+        match self {
+            ByteRange::Span(first, last) if first < len => Some((first, last.min(len - 1))),
+            ByteRange::From(first) if first < len => Some((first, len - 1)),
+            ByteRange::Suffix(n) if n > 0 && len > 0 => Some((len.saturating_sub(n), len - 1)),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for ByteRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ByteRange::Span(first, last) => write!(f, "{}-{}", first, last),
+            ByteRange::From(first) => write!(f, "{}-", first),
+            ByteRange::Suffix(n) => write!(f, "-{}", n),
+        }
+    }
+}
+
+/// The HTTP `Range` header: a request for one or more byte ranges.
+///
+/// Only the `bytes` range unit is supported. A `Range` is parsed from a string
+/// via its `FromStr` implementation; a header that doesn't parse should be
+/// ignored, as RFC 7233 requires.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{Range, ByteRange};
+///
+/// let range: Range = "bytes=0-499, -500".parse().unwrap();
+/// assert_eq!(range.ranges(), &[ByteRange::Span(0, 499), ByteRange::Suffix(500)]);
+/// assert_eq!(range.to_string(), "bytes=0-499,-500");
+/// ```
+///
+/// `Range` implements `Into<Header>`. As such, it can be used in any context
+/// where an `Into<Header>` is expected:
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::{Range, ByteRange};
+/// use rocket::local::Client;
+///
+/// let client = Client::new(rocket::ignite()).unwrap();
+/// # #[allow(unused_variables)]
+/// let request = client.get("/video").header(Range::new(vec![ByteRange::From(1024)]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Range {
+    ranges: Vec<ByteRange>,
+}
+
+impl Range {
+    /// Creates a `Range` for the byte ranges `ranges`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Range, ByteRange};
+    ///
+    /// let range = Range::new(vec![ByteRange::Span(0, 9)]);
+    /// assert_eq!(range.to_string(), "bytes=0-9");
+    /// ```
+    #[inline(always)]
+    pub fn new(ranges: Vec<ByteRange>) -> Range {
+        Range { ranges }
+    }
+
+    /// Returns the requested byte ranges, in the order they were requested.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate rocket;
+    /// use rocket::http::{Range, ByteRange};
+    ///
+    /// let range: Range = "bytes=100-".parse().unwrap();
+    /// assert_eq!(range.ranges(), &[ByteRange::From(100)]);
+    /// ```
+    #[inline(always)]
+    pub fn ranges(&self) -> &[ByteRange] {
+        &self.ranges
+    }
+}
+
+fn parse_position(string: &str) -> Result<u64, String> {
+    match string.bytes().all(|b| b.is_ascii_digit()) {
+        true => string.parse().map_err(|_| format!("invalid byte position: {:?}", string)),
+        false => Err(format!("invalid byte position: {:?}", string))
+    }
+}
+
+impl FromStr for ByteRange {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<ByteRange, String> {
+        let raw = raw.trim();
+        let dash = raw.find('-').ok_or_else(|| format!("missing '-' in range {:?}", raw))?;
+        let (first, last) = (&raw[..dash], &raw[(dash + 1)..]);
+        match (first.is_empty(), last.is_empty()) {
+            (true, true) => Err("empty byte range".into()),
+            (true, false) => Ok(ByteRange::Suffix(parse_position(last)?)),
+            (false, true) => Ok(ByteRange::From(parse_position(first)?)),
+            (false, false) => {
+                let (first, last) = (parse_position(first)?, parse_position(last)?);
+                match first <= last {
+                    true => Ok(ByteRange::Span(first, last)),
+                    false => Err(format!("byte range {:?} ends before it starts", raw))
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Range {
+    // Ideally we'd return a `ParseError`, but that requires a lifetime.
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Range, String> {
+        let raw = raw.trim();
+        let eq = raw.find('=').ok_or_else(|| "missing range unit".to_string())?;
+        if !raw[..eq].trim().eq_ignore_ascii_case("bytes") {
+            return Err(format!("unsupported range unit: {:?}", &raw[..eq]));
+        }
+
+        // Empty list elements are allowed and ignored.
+        let ranges = raw[(eq + 1)..].split(',')
+            .filter(|range| !range.trim().is_empty())
+            .map(ByteRange::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match ranges.is_empty() {
+            true => Err("no byte ranges".into()),
+            false => Ok(Range { ranges })
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bytes=")?;
+        for (i, range) in self.ranges.iter().enumerate() {
+            if i > 0 { write!(f, ",")?; }
+            write!(f, "{}", range)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates a new `Header` with name `Range` and the value set to the HTTP
+/// rendering of this `Range` header.
+impl Into<Header<'static>> for Range {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new("Range", self.to_string())
+    }
+}
+
+/// The HTTP `If-Range` header: a validator that makes a [`Range`] request
+/// conditional on the representation being unchanged.
+///
+/// The validator is either an entity-tag or an HTTP-date. Which one is
+/// determined when parsing: entity-tags are quoted, optionally prefixed with
+/// `W/`, and anything else is taken to be a date.
+///
+/// # Usage
+///
+/// ```rust
+/// # extern crate rocket;
+/// use rocket::http::IfRange;
+///
+/// let if_range: IfRange = "\"v2\"".parse().unwrap();
+/// assert!(if_range.matches(Some("\"v2\""), None));
+/// assert!(!if_range.matches(Some("W/\"v2\""), None));
+///
+/// let if_range: IfRange = "Sun, 06 Nov 1994 08:49:37 GMT".parse().unwrap();
+/// assert!(if_range.matches(None, Some("Sun, 06 Nov 1994 08:49:37 GMT")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IfRange {
+    /// An entity-tag, including its quotes and any weakness indicator.
+    ETag(String),
+    /// An HTTP-date.
+    Date(String),
+}
+
+impl IfRange {
+    /// Returns `true` if `self` matches the validators of the current
+    /// representation, `etag` and `last_modified`, which are the values of its
+    /// `ETag` and `Last-Modified` headers, if any. Only a strong comparison of
+    /// entity-tags or an exact match of dates succeeds, as RFC 7233 section
+    /// 3.2 requires.
+    pub fn matches(&self, etag: Option<&str>, last_modified: Option<&str>) -> bool {
+        match *self {
+            IfRange::ETag(ref tag) => {
+                let is_strong = |tag: &str| !tag.starts_with("W/");
+                is_strong(tag) && etag.map_or(false, |e| is_strong(e) && e.trim() == tag)
+            }
+            IfRange::Date(ref date) => last_modified.map_or(false, |d| d.trim() == date),
+        }
+    }
+}
+
+impl FromStr for IfRange {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<IfRange, String> {
+        let raw = raw.trim();
+        let tag = raw.trim_start_matches("W/");
+        if tag.len() >= 2 && tag.starts_with('"') && tag.ends_with('"') {
+            Ok(IfRange::ETag(raw.to_string()))
+        } else if raw.starts_with("W/") || raw.starts_with('"') {
+            Err(format!("invalid entity-tag: {:?}", raw))
+        } else if raw.is_empty() {
+            Err("empty If-Range validator".into())
+        } else {
+            Ok(IfRange::Date(raw.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for IfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            IfRange::ETag(ref tag) => tag.fmt(f),
+            IfRange::Date(ref date) => date.fmt(f),
+        }
+    }
+}
+
+/// Creates a new `Header` with name `If-Range` and the value set to the HTTP
+/// rendering of this `If-Range` header.
+impl Into<Header<'static>> for IfRange {
+    #[inline(always)]
+    fn into(self) -> Header<'static> {
+        Header::new("If-Range", self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Range, ByteRange, IfRange};
+
+    macro_rules! assert_range {
+        ($string:expr, [$($range:expr),*]) => ({
+            match $string.parse::<Range>() {
+                Ok(range) => assert_eq!(range.ranges(), &[$($range),*]),
+                Err(e) => panic!("{:?} failed to parse: {}", $string, e)
+            }
+        });
+    }
+
+    macro_rules! assert_no_range {
+        ($string:expr) => ({
+            if let Ok(range) = $string.parse::<Range>() {
+                panic!("{:?} parsed unexpectedly: {:?}", $string, range)
+            }
+        });
+    }
+
+    #[test]
+    fn check_range_parses() {
+        use self::ByteRange::*;
+
+        assert_range!("bytes=0-499", [Span(0, 499)]);
+        assert_range!("bytes=500-999", [Span(500, 999)]);
+        assert_range!("bytes=-500", [Suffix(500)]);
+        assert_range!("bytes=9500-", [From(9500)]);
+        assert_range!("bytes=0-0,-1", [Span(0, 0), Suffix(1)]);
+        assert_range!("BYTES = 1-2 , 4-5", [Span(1, 2), Span(4, 5)]);
+        assert_range!("bytes=1-2,,3-", [Span(1, 2), From(3)]);
+    }
+
+    #[test]
+    fn check_range_does_not_parse() {
+        assert_no_range!("");
+        assert_no_range!("0-499");
+        assert_no_range!("items=0-499");
+        assert_no_range!("bytes=");
+        assert_no_range!("bytes=-");
+        assert_no_range!("bytes=500-499");
+        assert_no_range!("bytes=a-b");
+        assert_no_range!("bytes=+1-2");
+        assert_no_range!("bytes=1-2,x");
+        assert_no_range!("bytes=99999999999999999999-");
+    }
+
+    #[test]
+    fn check_resolve() {
+        assert_eq!(ByteRange::Span(0, 0).resolve(1), Some((0, 0)));
+        assert_eq!(ByteRange::Span(5, 10).resolve(8), Some((5, 7)));
+        assert_eq!(ByteRange::Span(8, 10).resolve(8), None);
+        assert_eq!(ByteRange::From(0).resolve(0), None);
+        assert_eq!(ByteRange::Suffix(1).resolve(0), None);
+    }
+
+    #[test]
+    fn check_if_range() {
+        let etag: IfRange = "\"abc\"".parse().unwrap();
+        assert_eq!(etag, IfRange::ETag("\"abc\"".into()));
+        assert!(etag.matches(Some("\"abc\""), None));
+        assert!(!etag.matches(Some("\"abd\""), None));
+        assert!(!etag.matches(None, Some("\"abc\"")));
+
+        let weak: IfRange = "W/\"abc\"".parse().unwrap();
+        assert!(!weak.matches(Some("W/\"abc\""), None));
+
+        let date: IfRange = "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap();
+        assert!(date.matches(Some("\"abc\""), Some("Wed, 21 Oct 2015 07:28:00 GMT")));
+        assert!(!date.matches(None, Some("Wed, 21 Oct 2015 07:28:01 GMT")));
+
+        assert!("\"unterminated".parse::<IfRange>().is_err());
+        assert!("".parse::<IfRange>().is_err());
+    }
+}
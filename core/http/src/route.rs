@@ -18,6 +18,7 @@ pub enum Kind {
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Source {
+    Host,
     Path,
     Query,
     Data,
@@ -3,8 +3,10 @@
 
 #[macro_use] extern crate rocket;
 
+use rocket::{Request, Data, Route};
 use rocket::config::{Environment, Config, LoggingLevel};
-use rocket::http::RawStr;
+use rocket::handler::Outcome;
+use rocket::http::{Method, RawStr};
 
 #[get("/")]
 fn hello_world() -> &'static str { "Hello, world!" }
@@ -42,10 +44,26 @@ fn rocket() -> rocket::Rocket {
                index_b, index_c, index_dyn_a])
 }
 
+fn many_handler<'r>(req: &'r Request, _: Data) -> Outcome<'r> {
+    Outcome::from(req, "many")
+}
+
+// Mounts hundreds of routes, as a large application would.
+fn many_routes_rocket() -> rocket::Rocket {
+    let routes = (0..300).flat_map(|i| vec![
+        Route::new(Method::Get, format!("/resource{}", i), many_handler),
+        Route::new(Method::Get, format!("/resource{}/<id>", i), many_handler),
+        Route::new(Method::Post, format!("/resource{}/<id>/edit", i), many_handler),
+    ]);
+
+    let config = Config::build(Environment::Production).log_level(LoggingLevel::Off);
+    rocket::custom(config.unwrap()).mount("/", routes.collect::<Vec<_>>())
+}
+
 mod benches {
     extern crate test;
 
-    use super::{hello_world_rocket, rocket};
+    use super::{hello_world_rocket, rocket, many_routes_rocket};
     use self::test::Bencher;
     use rocket::local::Client;
 
@@ -125,4 +143,29 @@ mod benches {
             }
         });
     }
+
+    #[bench]
+    fn bench_many_routes(b: &mut Bencher) {
+        let client = Client::new(many_routes_rocket()).unwrap();
+
+        // Hold all of the requests we're going to make during the benchmark.
+        let mut requests = vec![];
+        requests.push(client.get("/resource0"));
+        requests.push(client.get("/resource150/10"));
+        requests.push(client.get("/resource299/abc"));
+        requests.push(client.post("/resource299/abc/edit"));
+        requests.push(client.get("/resource300"));
+
+        b.iter(|| {
+            for request in requests.iter_mut() {
+                request.mut_dispatch();
+            }
+        });
+    }
+
+    #[bench]
+    fn bench_many_routes_collisions(b: &mut Bencher) {
+        // Launching checks all of the mounted routes for collisions.
+        b.iter(|| Client::new(many_routes_rocket()).unwrap());
+    }
 }
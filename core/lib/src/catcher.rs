@@ -2,6 +2,8 @@ use crate::response;
 use crate::handler::ErrorHandler;
 use crate::codegen::StaticCatchInfo;
 use crate::request::Request;
+use crate::http::Status;
+use crate::http::uri::Origin;
 
 use std::fmt;
 use yansi::Color::*;
@@ -12,21 +14,56 @@ use yansi::Color::*;
 /// with the HTTP error status code they will be handling and are registered
 /// with Rocket via [`Rocket::register()`](crate::Rocket::register()). For example,
 /// to handle "404 not found" errors, a catcher for the "404" status code is
-/// registered.
+/// registered. A _default_ catcher, created with [`Catcher::default()`] or
+/// declared with `#[catch(default)]`, handles errors of any status.
 ///
 /// Because error handlers are only called when all routes are exhausted, they
 /// should not fail nor forward. If an error catcher fails, the user will
 /// receive no response. If an error catcher forwards, Rocket will respond with
 /// an internal server error.
 ///
+/// # Bases
+///
+/// Catchers registered with [`Rocket::register()`](crate::Rocket::register())
+/// handle errors for every request. Like routes, catchers can instead be
+/// registered under a base path with
+/// [`Rocket::register_at()`](crate::Rocket::register_at()), in which case they
+/// only handle errors for requests whose path is within that base. When
+/// several catchers can handle an error, the one whose base has the most
+/// segments wins. Between catchers with the same base, one for the specific
+/// status wins over a default catcher. For instance, given the catchers below,
+/// an error for a request to `/api/users` is handled by `api_default`, even if
+/// it is a `404`, while a `404` for `/index.html` is handled by `not_found`:
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Request;
+///
+/// #[catch(404)]
+/// fn not_found(req: &Request) -> String {
+///     format!("I couldn't find '{}'. Try something else?", req.uri())
+/// }
+///
+/// #[catch(default)]
+/// fn api_default(req: &Request) -> String {
+///     let status = req.error_status().expect("handling an error");
+///     format!("{{ \"error\": \"{}\", \"path\": \"{}\" }}", status, req.uri())
+/// }
+///
+/// # if false { // We don't actually want to launch the server in an example.
+/// rocket::ignite()
+///     .register(catchers![not_found])
+///     .register_at("/api", catchers![api_default])
+/// #   .launch();
+/// # }
+/// ```
+///
 /// # Built-In Catchers
 ///
-/// Rocket has many built-in, pre-registered default catchers. In particular,
-/// Rocket has catchers for all of the following status codes: 400, 401, 402,
-/// 403, 404, 405, 406, 407, 408, 409, 410, 411, 412, 413, 414, 415, 416, 417,
-/// 418, 421, 426, 428, 429, 431, 451, 500, 501, 503, and 510. As such, catchers
-/// only need to be registered if an error needs to be handled in a custom
-/// fashion.
+/// Rocket responds with a built-in HTML error page for any error that no
+/// registered catcher handles. As such, catchers only need to be registered
+/// if an error needs to be handled in a custom fashion.
 ///
 /// # Code Generation
 ///
@@ -59,11 +96,16 @@ use yansi::Color::*;
 ///
 /// A function decorated with `catch` must take exactly zero or one arguments.
 /// If the catcher takes an argument, it must be of type [`&Request`](Request).
+/// The status of the error being handled is available via
+/// [`Request::error_status()`].
 pub struct Catcher {
-    /// The HTTP status code to match against.
+    /// The HTTP status code to match against. This is `0` for a default
+    /// catcher, which matches any status code.
     pub code: u16,
     /// The catcher's associated handler.
     pub handler: ErrorHandler,
+    /// The base path under which this catcher handles errors.
+    pub base: Origin<'static>,
     pub(crate) is_default: bool,
 }
 
@@ -94,7 +136,34 @@ impl Catcher {
     /// ```
     #[inline(always)]
     pub fn new(code: u16, handler: ErrorHandler) -> Catcher {
-        Catcher { code, handler, is_default: false }
+        Catcher { code, handler, base: Origin::dummy(), is_default: false }
+    }
+
+    /// Creates a default catcher, which handles errors of any status, using
+    /// the given error handler. The handler can retrieve the status of the
+    /// error with [`Request::error_status()`]. This should only be used when
+    /// routing manually.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #![allow(unused_variables)]
+    /// use rocket::{Catcher, Request};
+    /// use rocket::response::{Result, Responder};
+    /// use rocket::response::status::Custom;
+    /// use rocket::http::Status;
+    ///
+    /// fn handle_any<'r>(req: &'r Request) -> Result<'r> {
+    ///     let status = req.error_status().unwrap_or(Status::InternalServerError);
+    ///     let res = Custom(status, format!("{}: {}", status, req.uri()));
+    ///     res.respond_to(req)
+    /// }
+    ///
+    /// let default_catcher = Catcher::default(handle_any);
+    /// ```
+    #[inline(always)]
+    pub fn default(handler: ErrorHandler) -> Catcher {
+        Catcher { code: 0, handler, base: Origin::dummy(), is_default: true }
     }
 
     #[inline(always)]
@@ -102,22 +171,46 @@ impl Catcher {
         (self.handler)(req)
     }
 
-    #[inline(always)]
-    fn new_default(code: u16, handler: ErrorHandler) -> Catcher {
-        Catcher { code, handler, is_default: true, }
+    /// Returns `true` if `self` can handle an error with status `status` for
+    /// a request to `req`.
+    pub(crate) fn matches(&self, status: Status, req: &Request<'_>) -> bool {
+        if !self.is_default && self.code != status.code {
+            return false;
+        }
+
+        let mut req_segments = req.uri().segments();
+        self.base.segments().all(|segment| req_segments.next() == Some(segment))
+    }
+
+    /// The precedence of `self` over other catchers that match the same
+    /// error. Higher is preferred.
+    pub(crate) fn precedence(&self) -> (usize, bool) {
+        (self.base.segment_count(), !self.is_default)
     }
 }
 
 #[doc(hidden)]
 impl<'a> From<&'a StaticCatchInfo> for Catcher {
     fn from(info: &'a StaticCatchInfo) -> Catcher {
-        Catcher::new(info.code, info.handler)
+        match info.code {
+            Some(code) => Catcher::new(code, info.handler),
+            None => Catcher::default(info.handler),
+        }
     }
 }
 
 impl fmt::Display for Catcher {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", Blue.paint(&self.code))
+        match self.is_default {
+            false => write!(f, "{}", Blue.paint(&self.code))?,
+            true => write!(f, "{}", Blue.paint("default"))?,
+        }
+
+        if self.base.path() != "/" {
+            write!(f, " {}", Green.paint(&self.base))?;
+        }
+
+        Ok(())
     }
 }
 
@@ -157,7 +250,7 @@ macro_rules! default_catchers {
                 ).respond_to(req)
             }
 
-            map.insert($code, Catcher::new_default($code, $fn_name));
+            map.insert($code, Catcher::new($code, $fn_name));
         )+
 
         map
@@ -169,9 +262,11 @@ pub mod defaults {
 
     use std::collections::HashMap;
 
-    use crate::request::Request;
-    use crate::response::{self, content, status, Responder};
-    use crate::http::Status;
+    use std::io::Cursor;
+
+    use crate::request::{Request, MalformedRequest};
+    use crate::response::{self, content, status, Responder, Response};
+    use crate::http::{Status, ContentType};
 
     pub fn get() -> HashMap<u16, Catcher> {
         default_catchers! {
@@ -240,5 +335,28 @@ pub mod defaults {
                 the server to fulfill it.", handle_510
         }
     }
+
+    /// The built-in handler for errors with a status that has no built-in
+    /// catcher in [`get()`]: responds with a generic error page.
+    pub fn handle_default<'r>(status: Status, req: &'r Request<'_>) -> response::Result<'r> {
+        let page = format!(
+            error_page_template!("{code}", "{reason}", "The request could not be processed."),
+            code = status.code, reason = status.reason
+        );
+
+        status::Custom(status, content::Html(page)).respond_to(req)
+    }
+
+    /// The default handler for requests that could not be parsed: responds
+    /// with the default `400` error page.
+    pub fn handle_malformed(_: &MalformedRequest) -> response::Result<'static> {
+        Response::build()
+            .status(Status::BadRequest)
+            .header(ContentType::HTML)
+            .sized_body(Cursor::new(error_page_template!(400, "Bad Request",
+                "The request could not be understood by the server due to
+                malformed syntax.")))
+            .ok()
+    }
 }
 
@@ -1,6 +1,9 @@
+use std::any::TypeId;
+
 use crate::{Request, Data};
 use crate::handler::{Outcome, ErrorHandler};
 use crate::http::{Method, MediaType};
+use crate::http::route::{Source, Kind};
 
 /// Type of a static handler, which users annotate with Rocket's attribute.
 pub type StaticHandler = for<'r> fn(&'r Request<'_>, Data) -> Outcome<'r>;
@@ -11,6 +14,8 @@ pub struct StaticRouteInfo {
     pub name: &'static str,
     /// The route's method.
     pub method: Method,
+    /// The route's host pattern, if any.
+    pub host: Option<&'static str>,
     /// The route's path, without the base mount point.
     pub path: &'static str,
     /// The route's format, if any.
@@ -19,12 +24,36 @@ pub struct StaticRouteInfo {
     pub handler: StaticHandler,
     /// The route's rank, if any.
     pub rank: Option<isize>,
+    /// The parameters of the route's handler.
+    pub params: &'static [StaticParamInfo],
+    /// The return type of the route's handler, unless it's `impl Trait`.
+    pub output: Option<StaticTypeInfo>,
+}
+
+/// Information generated by the `route` attribute about a handler parameter.
+pub struct StaticParamInfo {
+    /// The parameter's name.
+    pub name: &'static str,
+    /// Where the parameter's value comes from.
+    pub source: Source,
+    /// The kind of the segment the parameter is in, if any.
+    pub kind: Kind,
+    /// The parameter's type.
+    pub ty: StaticTypeInfo,
+}
+
+/// Information generated by the `route` attribute about a handler type.
+pub struct StaticTypeInfo {
+    /// The type as written in the source.
+    pub name: &'static str,
+    /// Returns the type's `TypeId`.
+    pub id: fn() -> TypeId,
 }
 
 /// Information generated by the `catch` attribute during codegen.
 pub struct StaticCatchInfo {
-    /// The catcher's status code.
-    pub code: u16,
+    /// The catcher's status code, or `None` for a default catcher.
+    pub code: Option<u16>,
     /// The catcher's handler, i.e, the annotated function.
     pub handler: ErrorHandler,
 }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::config::{Result, Config, Value, Environment, Limits, LoggingLevel};
+use crate::config::{Cidr, DefaultHeaders, ShutdownConfig};
 
 /// Structure following the builder pattern for building `Config` structures.
 #[derive(Clone)]
@@ -12,18 +13,44 @@ pub struct ConfigBuilder {
     pub address: String,
     /// The port to serve on.
     pub port: u16,
+    /// The permissions of the Unix domain socket file, if any.
+    pub socket_mode: Option<u32>,
     /// The number of workers to run in parallel.
     pub workers: u16,
     /// Keep-alive timeout in seconds or disabled if 0.
     pub keep_alive: u32,
+    /// Read timeout in seconds or disabled if 0.
+    pub read_timeout: u32,
+    /// Write timeout in seconds or disabled if 0.
+    pub write_timeout: u32,
+    /// Header deadline in seconds or disabled if 0.
+    pub header_timeout: u32,
+    /// Whether to answer with `405 Method Not Allowed` when appropriate.
+    pub method_not_allowed: bool,
+    /// Whether to answer `OPTIONS` requests automatically.
+    pub auto_options: bool,
     /// How much information to log.
     pub log_level: LoggingLevel,
     /// The secret key.
     pub secret_key: Option<String>,
     /// TLS configuration (path to certificates file, path to private key file).
     pub tls: Option<(String, String)>,
+    /// Whether connections to the main listener begin with a PROXY protocol
+    /// header.
+    pub proxy_protocol: bool,
+    /// Additional listeners (address, port, optional TLS certificates and
+    /// private key file paths, whether the PROXY protocol is required).
+    pub listeners: Vec<(String, u16, Option<(String, String)>, bool)>,
+    /// Address and port of a listener that redirects to HTTPS, if any.
+    pub redirect: Option<(String, u16)>,
     /// Size limits.
     pub limits: Limits,
+    /// Default response headers.
+    pub headers: DefaultHeaders,
+    /// The proxies whose forwarding headers are trusted.
+    pub trusted_proxies: Vec<Cidr>,
+    /// Graceful shutdown configuration.
+    pub shutdown: ShutdownConfig,
     /// Any extra parameters that aren't part of Rocket's config.
     pub extras: HashMap<String, Value>,
     /// The root directory of this config, if any.
@@ -55,12 +82,24 @@ impl ConfigBuilder {
             environment: config.environment,
             address: config.address,
             port: config.port,
+            socket_mode: config.socket_mode,
             workers: config.workers,
             keep_alive: config.keep_alive.unwrap_or(0),
+            read_timeout: config.read_timeout.unwrap_or(0),
+            write_timeout: config.write_timeout.unwrap_or(0),
+            header_timeout: config.header_timeout.unwrap_or(0),
+            method_not_allowed: config.method_not_allowed,
+            auto_options: config.auto_options,
             log_level: config.log_level,
             secret_key: None,
             tls: None,
+            proxy_protocol: false,
+            listeners: vec![],
+            redirect: None,
             limits: config.limits,
+            headers: config.headers,
+            trusted_proxies: config.trusted_proxies,
+            shutdown: config.shutdown,
             extras: config.extras,
             root: None,
         }
@@ -103,6 +142,27 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets the permissions of the Unix domain socket file in the
+    /// configuration being built. This only has an effect if the `address` is
+    /// a Unix domain socket address.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .socket_mode(0o660)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.socket_mode, Some(0o660));
+    /// ```
+    #[inline]
+    pub fn socket_mode(mut self, mode: u32) -> Self {
+        self.socket_mode = Some(mode);
+        self
+    }
+
     /// Sets `workers` in the configuration being built.
     ///
     /// # Example
@@ -148,6 +208,125 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets the read timeout to `timeout` seconds. If `timeout` is `0`,
+    /// reads never time out.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .read_timeout(30)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.read_timeout, Some(30));
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .read_timeout(0)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.read_timeout, None);
+    /// ```
+    #[inline]
+    pub fn read_timeout(mut self, timeout: u32) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    /// Sets the write timeout to `timeout` seconds. If `timeout` is `0`,
+    /// writes never time out.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .write_timeout(30)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.write_timeout, Some(30));
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .write_timeout(0)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.write_timeout, None);
+    /// ```
+    #[inline]
+    pub fn write_timeout(mut self, timeout: u32) -> Self {
+        self.write_timeout = timeout;
+        self
+    }
+
+    /// Sets the deadline for receiving the headers of a request to `timeout`
+    /// seconds. If `timeout` is `0`, there is no deadline.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .header_timeout(5)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.header_timeout, Some(5));
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .header_timeout(0)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.header_timeout, None);
+    /// ```
+    #[inline]
+    pub fn header_timeout(mut self, timeout: u32) -> Self {
+        self.header_timeout = timeout;
+        self
+    }
+
+    /// Sets whether requests to a path routed only under other methods are
+    /// answered with `405 Method Not Allowed` in the configuration being
+    /// built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .method_not_allowed(false)
+    ///     .unwrap();
+    ///
+    /// assert!(!config.method_not_allowed);
+    /// ```
+    #[inline]
+    pub fn method_not_allowed(mut self, enabled: bool) -> Self {
+        self.method_not_allowed = enabled;
+        self
+    }
+
+    /// Sets whether `OPTIONS` requests are answered automatically in the
+    /// configuration being built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .auto_options(false)
+    ///     .unwrap();
+    ///
+    /// assert!(!config.auto_options);
+    /// ```
+    #[inline]
+    pub fn auto_options(mut self, enabled: bool) -> Self {
+        self.auto_options = enabled;
+        self
+    }
+
     /// Sets the `log_level` in the configuration being built.
     ///
     /// # Example
@@ -200,6 +379,63 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets the default response `headers` in the configuration being built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment, DefaultHeaders};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .headers(DefaultHeaders::new().remove("Server"))
+    ///     .unwrap();
+    ///
+    /// assert!(config.headers.get("Server").is_none());
+    /// ```
+    pub fn headers(mut self, headers: DefaultHeaders) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Sets the proxies whose forwarding headers are trusted in the
+    /// configuration being built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .trusted_proxies(vec!["10.0.0.0/8".parse().unwrap()])
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.trusted_proxies.len(), 1);
+    /// ```
+    pub fn trusted_proxies(mut self, proxies: Vec<Cidr>) -> Self {
+        self.trusted_proxies = proxies;
+        self
+    }
+
+    /// Sets the graceful `shutdown` configuration in the configuration being
+    /// built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment, ShutdownConfig};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .shutdown(ShutdownConfig { ctrlc: false, grace: 5 })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.shutdown.ctrlc, false);
+    /// assert_eq!(config.shutdown.grace, 5);
+    /// ```
+    pub fn shutdown(mut self, shutdown: ShutdownConfig) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
     /// Sets the TLS configuration in the configuration being built.
     ///
     /// Certificates are read from `certs_path`. The certificate chain must be
@@ -224,6 +460,115 @@ impl ConfigBuilder {
         self
     }
 
+    /// Adds an additional plain HTTP listener on `address` and `port` to the
+    /// configuration being built.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .port(8000)
+    ///     .listener("127.0.0.1", 8001)
+    ///     .unwrap();
+    /// ```
+    pub fn listener<A: Into<String>>(mut self, address: A, port: u16) -> Self {
+        self.listeners.push((address.into(), port, None, false));
+        self
+    }
+
+    /// Adds an additional plain HTTP listener on `address` and `port` that
+    /// requires every connection to begin with a PROXY protocol header to the
+    /// configuration being built. See [`Config::set_proxy_protocol()`] for
+    /// details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .port(8000)
+    ///     .proxy_listener("10.0.0.2", 8001)
+    ///     .unwrap();
+    /// ```
+    pub fn proxy_listener<A: Into<String>>(mut self, address: A, port: u16) -> Self {
+        self.listeners.push((address.into(), port, None, true));
+        self
+    }
+
+    /// Adds an additional HTTPS listener on `address` and `port` to the
+    /// configuration being built. Certificates and the private key are read
+    /// as in [`ConfigBuilder::tls()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let mut config = Config::build(Environment::Staging)
+    ///     .tls_listener("0.0.0.0", 8443, "/path/to/certs.pem", "/path/to/key.pem")
+    /// # ; /*
+    ///     .unwrap();
+    /// # */
+    /// ```
+    pub fn tls_listener<A, C, K>(
+        mut self,
+        address: A,
+        port: u16,
+        certs_path: C,
+        key_path: K
+    ) -> Self
+        where A: Into<String>, C: Into<String>, K: Into<String>
+    {
+        let tls = Some((certs_path.into(), key_path.into()));
+        self.listeners.push((address.into(), port, tls, false));
+        self
+    }
+
+    /// Sets whether connections to the main listener must begin with a PROXY
+    /// protocol header in the configuration being built. See
+    /// [`Config::set_proxy_protocol()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .proxy_protocol(true)
+    ///     .unwrap();
+    ///
+    /// assert!(config.proxy_protocol);
+    /// ```
+    pub fn proxy_protocol(mut self, enabled: bool) -> Self {
+        self.proxy_protocol = enabled;
+        self
+    }
+
+    /// Sets the address and port of a plain HTTP listener that permanently
+    /// redirects every request to HTTPS in the configuration being built. See
+    /// [`Config::set_redirect()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let mut config = Config::build(Environment::Production)
+    ///     .port(443)
+    ///     .tls("/path/to/certs.pem", "/path/to/key.pem")
+    ///     .redirect("0.0.0.0", 80)
+    /// # ; /*
+    ///     .unwrap();
+    /// # */
+    /// ```
+    pub fn redirect<A: Into<String>>(mut self, address: A, port: u16) -> Self {
+        self.redirect = Some((address.into(), port));
+        self
+    }
+
     /// Sets the `environment` in the configuration being built.
     ///
     /// # Example
@@ -316,11 +661,20 @@ impl ConfigBuilder {
         let mut config = Config::new(self.environment);
         config.set_address(self.address)?;
         config.set_port(self.port);
+        config.socket_mode = self.socket_mode;
         config.set_workers(self.workers);
         config.set_keep_alive(self.keep_alive);
+        config.set_read_timeout(self.read_timeout);
+        config.set_write_timeout(self.write_timeout);
+        config.set_header_timeout(self.header_timeout);
+        config.set_method_not_allowed(self.method_not_allowed);
+        config.set_auto_options(self.auto_options);
         config.set_log_level(self.log_level);
         config.set_extras(self.extras);
         config.set_limits(self.limits);
+        config.set_headers(self.headers);
+        config.set_trusted_proxies(self.trusted_proxies);
+        config.set_shutdown(self.shutdown);
 
         if let Some(root) = self.root {
             config.set_root(root);
@@ -330,6 +684,22 @@ impl ConfigBuilder {
             config.set_tls(&certs_path, &key_path)?;
         }
 
+        config.set_proxy_protocol(self.proxy_protocol);
+        for (address, port, tls, proxy_protocol) in self.listeners {
+            match tls {
+                Some((certs_path, key_path)) => {
+                    config.add_tls_listener(address, port, &certs_path, &key_path)?
+                }
+                None => config.add_listener(address, port)?,
+            }
+
+            config.set_last_listener_proxy_protocol(proxy_protocol);
+        }
+
+        if let Some((address, port)) = self.redirect {
+            config.set_redirect(address, port)?;
+        }
+
         if let Some(key) = self.secret_key {
             config.set_secret_key(key)?;
         }
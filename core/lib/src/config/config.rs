@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::net::ToSocketAddrs;
+use std::net::{IpAddr, ToSocketAddrs};
 use std::path::{Path, PathBuf};
 use std::convert::AsRef;
 use std::fmt;
@@ -8,6 +8,7 @@ use crate::config::Environment::*;
 use crate::config::{Result, ConfigBuilder, Environment, ConfigError, LoggingLevel};
 use crate::config::{FullConfig, Table, Value, Array, Datetime};
 use crate::http::private::Key;
+use crate::listener::unix_socket;
 
 use super::custom_values::*;
 
@@ -37,22 +38,52 @@ use super::custom_values::*;
 pub struct Config {
     /// The environment that this configuration corresponds to.
     pub environment: Environment,
-    /// The address to serve on.
+    /// The address to serve on: a hostname, an IP, or `unix:` followed by the
+    /// path to a Unix domain socket.
     pub address: String,
     /// The port to serve on.
     pub port: u16,
+    /// The permissions to set on the Unix domain socket file, if any.
+    pub socket_mode: Option<u32>,
     /// The number of workers to run concurrently.
     pub workers: u16,
     /// Keep-alive timeout in seconds or None if disabled.
     pub keep_alive: Option<u32>,
+    /// Timeout in seconds for a single read from a connection or None if
+    /// disabled.
+    pub read_timeout: Option<u32>,
+    /// Timeout in seconds for a single write to a connection or None if
+    /// disabled.
+    pub write_timeout: Option<u32>,
+    /// Deadline in seconds for receiving the headers of a request or None if
+    /// disabled.
+    pub header_timeout: Option<u32>,
+    /// Whether requests whose path is only routed under other methods are
+    /// answered with `405 Method Not Allowed`.
+    pub method_not_allowed: bool,
+    /// Whether `OPTIONS` requests are answered automatically.
+    pub auto_options: bool,
     /// How much information to log.
     pub log_level: LoggingLevel,
     /// The secret key.
     pub(crate) secret_key: SecretKey,
     /// TLS configuration.
     pub(crate) tls: Option<TlsConfig>,
+    /// Additional listeners.
+    pub(crate) listeners: Vec<ListenerConfig>,
+    /// Plain HTTP listener that redirects to HTTPS.
+    pub(crate) redirect: Option<ListenerConfig>,
+    /// Whether connections to the main listener begin with a PROXY protocol
+    /// header.
+    pub proxy_protocol: bool,
     /// Streaming read size limits.
     pub limits: Limits,
+    /// Headers added to every response.
+    pub headers: DefaultHeaders,
+    /// The addresses of proxies whose forwarding headers are trusted.
+    pub trusted_proxies: Vec<Cidr>,
+    /// Graceful shutdown configuration.
+    pub shutdown: ShutdownConfig,
     /// Extra parameters that aren't part of Rocket's core config.
     pub extras: HashMap<String, Value>,
     /// The path to the configuration file this config was loaded from, if any.
@@ -279,12 +310,24 @@ impl Config {
                     environment: Development,
                     address: "localhost".to_string(),
                     port: 8000,
+                    socket_mode: None,
                     workers: default_workers,
                     keep_alive: Some(5),
+                    read_timeout: None,
+                    write_timeout: None,
+                    header_timeout: None,
+                    method_not_allowed: true,
+                    auto_options: true,
                     log_level: LoggingLevel::Normal,
                     secret_key: key,
                     tls: None,
+                    listeners: vec![],
+                    redirect: None,
+                    proxy_protocol: false,
                     limits: Limits::default(),
+                    headers: DefaultHeaders::default(),
+                    trusted_proxies: vec![],
+                    shutdown: ShutdownConfig::default(),
                     extras: HashMap::new(),
                     config_file_path: None,
                     root_path: None,
@@ -295,12 +338,24 @@ impl Config {
                     environment: Staging,
                     address: "0.0.0.0".to_string(),
                     port: 8000,
+                    socket_mode: None,
                     workers: default_workers,
                     keep_alive: Some(5),
+                    read_timeout: None,
+                    write_timeout: None,
+                    header_timeout: None,
+                    method_not_allowed: true,
+                    auto_options: true,
                     log_level: LoggingLevel::Normal,
                     secret_key: key,
                     tls: None,
+                    listeners: vec![],
+                    redirect: None,
+                    proxy_protocol: false,
                     limits: Limits::default(),
+                    headers: DefaultHeaders::default(),
+                    trusted_proxies: vec![],
+                    shutdown: ShutdownConfig::default(),
                     extras: HashMap::new(),
                     config_file_path: None,
                     root_path: None,
@@ -311,12 +366,24 @@ impl Config {
                     environment: Production,
                     address: "0.0.0.0".to_string(),
                     port: 8000,
+                    socket_mode: None,
                     workers: default_workers,
                     keep_alive: Some(5),
+                    read_timeout: None,
+                    write_timeout: None,
+                    header_timeout: None,
+                    method_not_allowed: true,
+                    auto_options: true,
                     log_level: LoggingLevel::Critical,
                     secret_key: key,
                     tls: None,
+                    listeners: vec![],
+                    redirect: None,
+                    proxy_protocol: false,
                     limits: Limits::default(),
+                    headers: DefaultHeaders::default(),
+                    trusted_proxies: vec![],
+                    shutdown: ShutdownConfig::default(),
                     extras: HashMap::new(),
                     config_file_path: None,
                     root_path: None,
@@ -347,22 +414,49 @@ impl Config {
     ///
     ///   * **address**: String
     ///   * **port**: Integer (16-bit unsigned)
+    ///   * **socket_mode**: Integer or String (octal digits)
     ///   * **workers**: Integer (16-bit unsigned)
     ///   * **keep_alive**: Integer
+    ///   * **read_timeout**: Integer
+    ///   * **write_timeout**: Integer
+    ///   * **header_timeout**: Integer
+    ///   * **method_not_allowed**: Boolean
+    ///   * **auto_options**: Boolean
     ///   * **log**: String
     ///   * **secret_key**: String (256-bit base64 or base16)
     ///   * **tls**: Table (`certs` (path as String), `key` (path as String))
+    ///   * **proxy_protocol**: Boolean
+    ///   * **listeners**: Array of Tables (`address` (String), `port`
+    ///     (Integer), optionally `tls` (Table))
+    ///   * **redirect**: Table (`address` (String), `port` (Integer))
+    ///   * **limits**: Table (data type (String) to limit (Integer))
+    ///   * **headers**: Table (header name (String) to value (String) or
+    ///     `false`)
+    ///   * **trusted_proxies**: Array of Strings (IP addresses or CIDR ranges)
+    ///   * **shutdown**: Table (`ctrlc` (Boolean), `grace` (Integer))
     pub(crate) fn set_raw(&mut self, name: &str, val: &Value) -> Result<()> {
         let (id, ok) = (|val| val, |_| Ok(()));
         config_from_raw!(self, name, val,
             address => (str, set_address, id),
             port => (u16, set_port, ok),
+            socket_mode => (socket_mode, set_socket_mode, ok),
             workers => (u16, set_workers, ok),
             keep_alive => (u32, set_keep_alive, ok),
+            read_timeout => (u32, set_read_timeout, ok),
+            write_timeout => (u32, set_write_timeout, ok),
+            header_timeout => (u32, set_header_timeout, ok),
+            method_not_allowed => (bool, set_method_not_allowed, ok),
+            auto_options => (bool, set_auto_options, ok),
             log => (log_level, set_log_level, ok),
             secret_key => (str, set_secret_key, id),
             tls => (tls_config, set_raw_tls, id),
+            proxy_protocol => (bool, set_proxy_protocol, ok),
+            listeners => (listeners, set_raw_listeners, id),
+            redirect => (redirect, set_raw_redirect, id),
             limits => (limits, set_limits, ok),
+            headers => (headers, set_headers, ok),
+            trusted_proxies => (trusted_proxies, set_trusted_proxies, ok),
+            shutdown => (shutdown, set_shutdown, ok),
             | _ => {
                 self.extras.insert(name.into(), val.clone());
                 Ok(())
@@ -388,12 +482,13 @@ impl Config {
         self.root_path = Some(path.as_ref().into());
     }
 
-    /// Sets the address of `self` to `address`.
+    /// Sets the address of `self` to `address`. On Unix platforms, `address`
+    /// may also be `unix:` followed by the path to a Unix domain socket.
     ///
     /// # Errors
     ///
-    /// If `address` is not a valid IP address or hostname, returns a `BadType`
-    /// error.
+    /// If `address` is not a valid IP address, hostname, or Unix domain socket
+    /// address, returns a `BadType` error.
     ///
     /// # Example
     ///
@@ -404,17 +499,56 @@ impl Config {
     /// assert!(config.set_address("localhost").is_ok());
     /// assert!(config.set_address("::").is_ok());
     /// assert!(config.set_address("?").is_err());
+    ///
+    /// # #[cfg(unix)]
+    /// assert!(config.set_address("unix:/run/app.sock").is_ok());
+    /// assert!(config.set_address("unix:").is_err());
     /// ```
     pub fn set_address<A: Into<String>>(&mut self, address: A) -> Result<()> {
         let address = address.into();
-        if (&*address, 0u16).to_socket_addrs().is_err() {
-            return Err(self.bad_type("address", "string", "a valid hostname or IP"));
+        self.check_address("address", &address)?;
+        self.address = address;
+        Ok(())
+    }
+
+    /// Returns an error if `address` is not a valid IP address, hostname, or
+    /// Unix domain socket address.
+    fn check_address(&self, name: &str, address: &str) -> Result<()> {
+        if let Some(path) = unix_socket(address) {
+            if !cfg!(unix) || path.as_os_str().is_empty() {
+                return Err(self.bad_type(name, "string", "a valid Unix socket path"));
+            }
+        } else if (address, 0u16).to_socket_addrs().is_err() {
+            return Err(self.bad_type(name, "string", "a valid hostname or IP"));
         }
 
-        self.address = address;
         Ok(())
     }
 
+    /// Returns the path to the Unix domain socket to serve on, if `address` is
+    /// a Unix domain socket address.
+    pub(crate) fn unix_socket(&self) -> Option<&Path> {
+        unix_socket(&self.address)
+    }
+
+    /// Sets the permissions of the Unix domain socket file Rocket creates when
+    /// `address` is a Unix domain socket address to `mode`. By default, the
+    /// permissions are determined by the process's umask.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let mut config = Config::new(Environment::Staging);
+    /// config.set_socket_mode(0o660);
+    /// assert_eq!(config.socket_mode, Some(0o660));
+    /// ```
+    #[inline]
+    pub fn set_socket_mode(&mut self, mode: u32) {
+        self.socket_mode = Some(mode);
+    }
+
     /// Sets the `port` of `self` to `port`.
     ///
     /// # Example
@@ -474,6 +608,134 @@ impl Config {
         }
     }
 
+    /// Sets the read timeout to `timeout` seconds. A single read from a
+    /// connection, including a read of the body of a request, fails if no data
+    /// arrives within the timeout. If `timeout` is `0`, reads never time out,
+    /// save for reads of a request body, which time out after 5 seconds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Config;
+    ///
+    /// let mut config = Config::development();
+    ///
+    /// // Set the read timeout to 30 seconds.
+    /// config.set_read_timeout(30);
+    /// assert_eq!(config.read_timeout, Some(30));
+    ///
+    /// // Disable the read timeout.
+    /// config.set_read_timeout(0);
+    /// assert_eq!(config.read_timeout, None);
+    /// ```
+    #[inline]
+    pub fn set_read_timeout(&mut self, timeout: u32) {
+        if timeout == 0 {
+            self.read_timeout = None;
+        } else {
+            self.read_timeout = Some(timeout);
+        }
+    }
+
+    /// Sets the write timeout to `timeout` seconds. A single write to a
+    /// connection fails if the client doesn't accept any data within the
+    /// timeout. If `timeout` is `0`, writes never time out.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Config;
+    ///
+    /// let mut config = Config::development();
+    ///
+    /// // Set the write timeout to 30 seconds.
+    /// config.set_write_timeout(30);
+    /// assert_eq!(config.write_timeout, Some(30));
+    ///
+    /// // Disable the write timeout.
+    /// config.set_write_timeout(0);
+    /// assert_eq!(config.write_timeout, None);
+    /// ```
+    #[inline]
+    pub fn set_write_timeout(&mut self, timeout: u32) {
+        if timeout == 0 {
+            self.write_timeout = None;
+        } else {
+            self.write_timeout = Some(timeout);
+        }
+    }
+
+    /// Sets the header timeout to `timeout` seconds. The headers of a request
+    /// must be received in full within `timeout` seconds of the request's
+    /// first byte; otherwise, the connection is closed. Unlike the read
+    /// timeout, this bounds the total time spent reading headers, no matter
+    /// how slowly they trickle in. If `timeout` is `0`, there is no deadline.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Config;
+    ///
+    /// let mut config = Config::development();
+    ///
+    /// // Require headers to arrive within 5 seconds.
+    /// config.set_header_timeout(5);
+    /// assert_eq!(config.header_timeout, Some(5));
+    ///
+    /// // Remove the deadline.
+    /// config.set_header_timeout(0);
+    /// assert_eq!(config.header_timeout, None);
+    /// ```
+    #[inline]
+    pub fn set_header_timeout(&mut self, timeout: u32) {
+        if timeout == 0 {
+            self.header_timeout = None;
+        } else {
+            self.header_timeout = Some(timeout);
+        }
+    }
+
+    /// Sets whether requests to a path that is routed only under other methods
+    /// are answered with a `405 Method Not Allowed` response, including an
+    /// `Allow` header listing the routed methods. If `enabled` is `false`,
+    /// such requests are answered with a `404 Not Found` instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Config;
+    ///
+    /// let mut config = Config::development();
+    /// assert!(config.method_not_allowed);
+    ///
+    /// config.set_method_not_allowed(false);
+    /// assert!(!config.method_not_allowed);
+    /// ```
+    #[inline]
+    pub fn set_method_not_allowed(&mut self, enabled: bool) {
+        self.method_not_allowed = enabled;
+    }
+
+    /// Sets whether `OPTIONS` requests to a routed path that aren't handled
+    /// by an `OPTIONS` route are answered automatically with a `204 No
+    /// Content` response whose `Allow` header lists the routed methods.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Config;
+    ///
+    /// let mut config = Config::development();
+    /// assert!(config.auto_options);
+    ///
+    /// config.set_auto_options(false);
+    /// assert!(!config.auto_options);
+    /// ```
+    #[inline]
+    pub fn set_auto_options(&mut self, enabled: bool) {
+        self.auto_options = enabled;
+    }
+
     /// Sets the `secret_key` in `self` to `key` which must be a 256-bit base64
     /// or base16 (hex) encoded string.
     ///
@@ -547,6 +809,63 @@ impl Config {
         self.limits = limits;
     }
 
+    /// Sets the default response headers in `self` to `headers`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, DefaultHeaders};
+    ///
+    /// let mut config = Config::development();
+    /// config.set_headers(DefaultHeaders::new().header("Server", "MyApp"));
+    /// assert_eq!(config.headers.get("Server"), Some("MyApp"));
+    /// ```
+    #[inline]
+    pub fn set_headers(&mut self, headers: DefaultHeaders) {
+        self.headers = headers;
+    }
+
+    /// Sets the proxies whose forwarding headers are trusted to `proxies`.
+    /// See [`Request::client_ip()`](crate::Request::client_ip()) for how
+    /// forwarding headers are used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Cidr};
+    ///
+    /// let mut config = Config::development();
+    /// let proxies = vec!["127.0.0.1".parse().unwrap(), "10.0.0.0/8".parse().unwrap()];
+    /// config.set_trusted_proxies(proxies);
+    /// assert!(config.trusted_proxies[1].contains("10.9.8.7".parse().unwrap()));
+    /// ```
+    #[inline]
+    pub fn set_trusted_proxies(&mut self, proxies: Vec<Cidr>) {
+        self.trusted_proxies = proxies;
+    }
+
+    /// Returns `true` if `addr` is the address of a trusted proxy.
+    #[inline]
+    pub(crate) fn is_trusted_proxy(&self, addr: IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|range| range.contains(addr))
+    }
+
+    /// Sets the graceful shutdown configuration in `self` to `shutdown`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, ShutdownConfig};
+    ///
+    /// let mut config = Config::development();
+    /// config.set_shutdown(ShutdownConfig { ctrlc: false, grace: 10 });
+    /// assert_eq!(config.shutdown.grace, 10);
+    /// ```
+    #[inline]
+    pub fn set_shutdown(&mut self, shutdown: ShutdownConfig) {
+        self.shutdown = shutdown;
+    }
+
     /// Sets the TLS configuration in `self`.
     ///
     /// Certificates are read from `certs_path`. The certificate chain must be
@@ -574,6 +893,21 @@ impl Config {
     /// ```
     #[cfg(feature = "tls")]
     pub fn set_tls(&mut self, certs_path: &str, key_path: &str) -> Result<()> {
+        self.tls = Some(self.load_tls(certs_path, key_path)?);
+        Ok(())
+    }
+
+    #[doc(hidden)]
+    #[cfg(not(feature = "tls"))]
+    pub fn set_tls(&mut self, _: &str, _: &str) -> Result<()> {
+        self.tls = Some(TlsConfig);
+        Ok(())
+    }
+
+    /// Reads the certificates at `certs_path` and the private key at
+    /// `key_path` into a `TlsConfig`.
+    #[cfg(feature = "tls")]
+    fn load_tls(&self, certs_path: &str, key_path: &str) -> Result<TlsConfig> {
         use crate::http::tls::util::{self, Error};
 
         let pem_err = "malformed PEM file";
@@ -592,15 +926,12 @@ impl Config {
                 _ => self.bad_type("tls", pem_err, "a valid private key file")
             })?;
 
-        self.tls = Some(TlsConfig { certs, key });
-        Ok(())
+        Ok(TlsConfig { certs, key })
     }
 
-    #[doc(hidden)]
     #[cfg(not(feature = "tls"))]
-    pub fn set_tls(&mut self, _: &str, _: &str) -> Result<()> {
-        self.tls = Some(TlsConfig);
-        Ok(())
+    fn load_tls(&self, _: &str, _: &str) -> Result<TlsConfig> {
+        Ok(TlsConfig)
     }
 
     #[inline(always)]
@@ -613,6 +944,152 @@ impl Config {
         { Ok(()) }
     }
 
+    /// Adds an additional plain HTTP listener on `address` and `port` to
+    /// `self`. Requests received by any listener are dispatched to the same
+    /// routes. `address` may be any value accepted by
+    /// [`Config::set_address()`].
+    ///
+    /// # Errors
+    ///
+    /// If `address` is not a valid IP address, hostname, or Unix domain socket
+    /// address, returns a `BadType` error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let mut config = Config::new(Environment::Staging);
+    /// assert!(config.add_listener("127.0.0.1", 8001).is_ok());
+    /// assert!(config.add_listener("?", 8002).is_err());
+    /// ```
+    pub fn add_listener<A: Into<String>>(&mut self, address: A, port: u16) -> Result<()> {
+        let address = address.into();
+        self.check_address("listeners.address", &address)?;
+        self.listeners.push(ListenerConfig { address, port, tls: None, proxy_protocol: false });
+        Ok(())
+    }
+
+    /// Adds an additional HTTPS listener on `address` and `port` to `self`.
+    /// Certificates and the private key are read from `certs_path` and
+    /// `key_path` as in [`Config::set_tls()`]. Requests received by any
+    /// listener are dispatched to the same routes.
+    ///
+    /// # Errors
+    ///
+    /// If `address` is not a valid IP address or hostname, returns a `BadType`
+    /// error. If reading or parsing the certificates or private key fails,
+    /// returns an error as [`Config::set_tls()`] does.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Config;
+    ///
+    /// # use rocket::config::ConfigError;
+    /// # fn config_test() -> Result<(), ConfigError> {
+    /// let mut config = Config::development();
+    /// config.add_tls_listener("0.0.0.0", 8443, "/etc/ssl/certs.pem", "/etc/ssl/key.pem")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_tls_listener<A: Into<String>>(
+        &mut self,
+        address: A,
+        port: u16,
+        certs_path: &str,
+        key_path: &str
+    ) -> Result<()> {
+        let address = address.into();
+        self.check_address("listeners.address", &address)?;
+        let tls = Some(self.load_tls(certs_path, key_path)?);
+        self.listeners.push(ListenerConfig { address, port, tls, proxy_protocol: false });
+        Ok(())
+    }
+
+    fn set_raw_listeners(&mut self, listeners: Vec<RawListener<'_>>) -> Result<()> {
+        self.listeners.clear();
+        for (address, port, tls, proxy_protocol) in listeners {
+            match tls {
+                // During unit testing, we don't want to actually read certs/keys.
+                Some((certs, key)) if !cfg!(test) => {
+                    self.add_tls_listener(address, port, certs, key)?
+                }
+                _ => self.add_listener(address, port)?,
+            }
+
+            self.set_last_listener_proxy_protocol(proxy_protocol);
+        }
+
+        Ok(())
+    }
+
+    /// Sets the address and port of a plain HTTP listener that responds to
+    /// every request with a permanent redirect to the same URI over HTTPS. The
+    /// redirect targets the port of the main listener if it uses TLS or of the
+    /// first additional HTTPS listener otherwise.
+    ///
+    /// # Errors
+    ///
+    /// If `address` is not a valid IP address, hostname, or Unix domain socket
+    /// address, returns a `BadType` error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let mut config = Config::new(Environment::Production);
+    /// assert!(config.set_redirect("0.0.0.0", 80).is_ok());
+    /// ```
+    pub fn set_redirect<A: Into<String>>(&mut self, address: A, port: u16) -> Result<()> {
+        let address = address.into();
+        self.check_address("redirect.address", &address)?;
+        self.redirect = Some(ListenerConfig { address, port, tls: None, proxy_protocol: false });
+        Ok(())
+    }
+
+    fn set_raw_redirect(&mut self, (address, port, _, proxy): RawListener<'_>) -> Result<()> {
+        self.set_redirect(address, port)?;
+        if let Some(ref mut redirect) = self.redirect {
+            redirect.proxy_protocol = proxy;
+        }
+
+        Ok(())
+    }
+
+    /// Sets whether connections to the most recently added additional
+    /// listener must begin with a PROXY protocol header.
+    pub(crate) fn set_last_listener_proxy_protocol(&mut self, enabled: bool) {
+        if let Some(listener) = self.listeners.last_mut() {
+            listener.proxy_protocol = enabled;
+        }
+    }
+
+    /// Sets whether connections to the main listener must begin with a PROXY
+    /// protocol header, version 1 or 2, as sent by load balancers such as
+    /// HAProxy. The header is read before TLS, if enabled, and HTTP. The
+    /// remote address of requests is then the client address in the header.
+    /// Connections that don't begin with a valid header are closed.
+    ///
+    /// Additional listeners are configured via their `proxy_protocol` entry.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Config;
+    ///
+    /// let mut config = Config::development();
+    /// assert!(!config.proxy_protocol);
+    ///
+    /// config.set_proxy_protocol(true);
+    /// assert!(config.proxy_protocol);
+    /// ```
+    #[inline]
+    pub fn set_proxy_protocol(&mut self, enabled: bool) {
+        self.proxy_protocol = enabled;
+    }
+
     /// Sets the extras for `self` to be the key/value pairs in `extras`.
     /// encoded string.
     ///
@@ -978,9 +1455,21 @@ impl fmt::Debug for Config {
         s.field("environment", &self.environment);
         s.field("address", &self.address);
         s.field("port", &self.port);
+        s.field("socket_mode", &self.socket_mode);
         s.field("workers", &self.workers);
         s.field("keep_alive", &self.keep_alive);
+        s.field("read_timeout", &self.read_timeout);
+        s.field("write_timeout", &self.write_timeout);
+        s.field("header_timeout", &self.header_timeout);
+        s.field("method_not_allowed", &self.method_not_allowed);
+        s.field("auto_options", &self.auto_options);
         s.field("log_level", &self.log_level);
+        s.field("listeners", &self.listeners);
+        s.field("redirect", &self.redirect);
+        s.field("proxy_protocol", &self.proxy_protocol);
+        s.field("headers", &self.headers);
+        s.field("trusted_proxies", &self.trusted_proxies);
+        s.field("shutdown", &self.shutdown);
 
         for (key, value) in self.extras() {
             s.field(key, &value);
@@ -995,9 +1484,21 @@ impl PartialEq for Config {
     fn eq(&self, other: &Config) -> bool {
         self.address == other.address
             && self.port == other.port
+            && self.socket_mode == other.socket_mode
             && self.workers == other.workers
             && self.log_level == other.log_level
             && self.keep_alive == other.keep_alive
+            && self.read_timeout == other.read_timeout
+            && self.write_timeout == other.write_timeout
+            && self.header_timeout == other.header_timeout
+            && self.method_not_allowed == other.method_not_allowed
+            && self.auto_options == other.auto_options
+            && self.listeners == other.listeners
+            && self.redirect == other.redirect
+            && self.proxy_protocol == other.proxy_protocol
+            && self.headers == other.headers
+            && self.trusted_proxies == other.trusted_proxies
+            && self.shutdown == other.shutdown
             && self.environment == other.environment
             && self.extras == other.extras
     }
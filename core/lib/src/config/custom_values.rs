@@ -1,4 +1,6 @@
 use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
 
 #[cfg(feature = "tls")] use crate::http::tls::{Certificate, PrivateKey};
 
@@ -201,10 +203,342 @@ impl fmt::Display for Limits {
     }
 }
 
+/// Graceful shutdown configuration.
+///
+/// A `ShutdownConfig` determines how Rocket reacts to a shutdown request made
+/// through a [`Shutdown`](crate::Shutdown) handle or, if enabled, through the
+/// `SIGINT` and `SIGTERM` signals.
+///
+/// # Defaults
+///
+/// As documented in [`config`](crate::config), the defaults are:
+///
+///   * **ctrlc**: `true`
+///   * **grace**: `2` seconds
+///
+/// # Usage
+///
+/// ```rust
+/// use rocket::config::ShutdownConfig;
+///
+/// // Don't react to signals; wait up to 10 seconds for in-flight requests.
+/// let shutdown = ShutdownConfig { ctrlc: false, grace: 10 };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShutdownConfig {
+    /// Whether `SIGINT` and `SIGTERM` trigger a graceful shutdown. Only
+    /// supported on Unix platforms.
+    pub ctrlc: bool,
+    /// The maximum number of seconds to wait for in-flight requests to
+    /// complete once a shutdown has been requested.
+    pub grace: u32,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> ShutdownConfig {
+        ShutdownConfig { ctrlc: true, grace: 2 }
+    }
+}
+
+impl fmt::Display for ShutdownConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ctrlc = {}, grace = {}s", self.ctrlc, self.grace)
+    }
+}
+
+/// The headers added to every response that doesn't already contain them.
+///
+/// Rocket adds each of the headers in a `DefaultHeaders` structure to every
+/// response, including error and malformed request responses, unless a header
+/// of the same name is already present. Headers are added before response
+/// fairings run. The `Date` header is always added and can't be configured.
+///
+/// # Defaults
+///
+/// As documented in [`config`](crate::config), the default headers are:
+///
+///   * **Server**: `Rocket`
+///
+/// # Usage
+///
+/// A `DefaultHeaders` structure is created following the builder pattern:
+///
+/// ```rust
+/// use rocket::config::DefaultHeaders;
+///
+/// // Identify as "MyApp" and forbid framing.
+/// let headers = DefaultHeaders::new()
+///     .header("Server", "MyApp")
+///     .header("X-Frame-Options", "DENY");
+///
+/// // Don't send a `Server` header at all.
+/// let headers = DefaultHeaders::new().remove("Server");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefaultHeaders {
+    headers: Vec<(String, String)>,
+}
+
+impl Default for DefaultHeaders {
+    fn default() -> DefaultHeaders {
+        DefaultHeaders { headers: vec![("Server".into(), "Rocket".into())] }
+    }
+}
+
+impl DefaultHeaders {
+    /// Construct a new `DefaultHeaders` structure with the default headers set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::DefaultHeaders;
+    ///
+    /// let headers = DefaultHeaders::new();
+    /// assert_eq!(headers.get("Server"), Some("Rocket"));
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        DefaultHeaders::default()
+    }
+
+    /// Adds or replaces the default header named `name`, consuming `self` and
+    /// returning a new `DefaultHeaders` structure with the header set to
+    /// `value`. Header names are case-insensitive.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::DefaultHeaders;
+    ///
+    /// let headers = DefaultHeaders::new()
+    ///     .header("server", "MyApp")
+    ///     .header("X-Frame-Options", "DENY");
+    ///
+    /// assert_eq!(headers.get("Server"), Some("MyApp"));
+    /// assert_eq!(headers.get("x-frame-options"), Some("DENY"));
+    /// ```
+    pub fn header<N, V>(mut self, name: N, value: V) -> Self
+        where N: Into<String>, V: Into<String>
+    {
+        let (name, value) = (name.into(), value.into());
+        match self.headers.iter_mut().find(|(key, _)| key.eq_ignore_ascii_case(&name)) {
+            Some(entry) => entry.1 = value,
+            None => self.headers.push((name, value)),
+        }
+
+        self
+    }
+
+    /// Removes the default header named `name`, if any, consuming `self` and
+    /// returning a new `DefaultHeaders` structure without the header.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::DefaultHeaders;
+    ///
+    /// let headers = DefaultHeaders::new().remove("Server");
+    /// assert!(headers.get("Server").is_none());
+    /// ```
+    pub fn remove(mut self, name: &str) -> Self {
+        self.headers.retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+        self
+    }
+
+    /// Retrieve the value of the default header named `name`, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::DefaultHeaders;
+    ///
+    /// let headers = DefaultHeaders::new().header("X-Frame-Options", "DENY");
+    /// assert_eq!(headers.get("Server"), Some("Rocket"));
+    /// assert_eq!(headers.get("X-Frame-Options"), Some("DENY"));
+    /// assert!(headers.get("X-Powered-By").is_none());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns an iterator over the names and values of the default headers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::DefaultHeaders;
+    ///
+    /// let headers = DefaultHeaders::new();
+    /// let all: Vec<_> = headers.iter().collect();
+    /// assert_eq!(all, vec![("Server", "Rocket")]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.headers.iter().map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+impl fmt::Display for DefaultHeaders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.headers.is_empty() {
+            return write!(f, "none");
+        }
+
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 { write!(f, ", ")?; }
+            write!(f, "{}: {}", key, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A range of IP addresses in CIDR notation, such as `10.0.0.0/8`.
+///
+/// `Cidr` values make up the `trusted_proxies` configuration parameter. A
+/// single address, such as `127.0.0.1` or `::1`, is a range containing only
+/// that address. IPv4-mapped IPv6 addresses (`::ffff:a.b.c.d`) are considered
+/// to be the IPv4 address they map.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::Cidr;
+///
+/// let private: Cidr = "10.0.0.0/8".parse().unwrap();
+/// assert!(private.contains("10.1.2.3".parse().unwrap()));
+/// assert!(!private.contains("11.0.0.1".parse().unwrap()));
+///
+/// let loopback: Cidr = "::1".parse().unwrap();
+/// assert!(loopback.contains("::1".parse().unwrap()));
+///
+/// assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+/// Returns the IPv4 address mapped by `addr` if it is an IPv4-mapped IPv6
+/// address, or `addr` otherwise.
+fn canonical(addr: IpAddr) -> IpAddr {
+    match addr {
+        IpAddr::V6(v6) => match v6.segments() {
+            [0, 0, 0, 0, 0, 0xffff, ..] => IpAddr::V4(v6.to_ipv4().expect("mapped IPv4")),
+            _ => addr,
+        }
+        IpAddr::V4(_) => addr,
+    }
+}
+
+impl Cidr {
+    /// Returns `true` if `addr` is in this range.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::Cidr;
+    ///
+    /// let range: Cidr = "192.168.0.0/16".parse().unwrap();
+    /// assert!(range.contains("192.168.10.1".parse().unwrap()));
+    /// assert!(range.contains("::ffff:192.168.10.1".parse().unwrap()));
+    /// assert!(!range.contains("192.169.0.1".parse().unwrap()));
+    /// ```
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        fn mask(bits: u32, prefix: u8) -> u128 {
+            match prefix {
+                0 => 0,
+                n => !0u128 << (bits - n as u32),
+            }
+        }
+
+        match (self.addr, canonical(addr)) {
+            (IpAddr::V4(a), IpAddr::V4(b)) => {
+                let mask = mask(32, self.prefix) as u32;
+                u32::from(a) & mask == u32::from(b) & mask
+            }
+            (IpAddr::V6(a), IpAddr::V6(b)) => {
+                let mask = mask(128, self.prefix);
+                u128::from(a) & mask == u128::from(b) & mask
+            }
+            _ => false
+        }
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        const ERROR: &str = "an IP address or CIDR range";
+
+        let (addr, prefix) = match s.find('/') {
+            Some(i) => (&s[..i], Some(&s[(i + 1)..])),
+            None => (s, None),
+        };
+
+        let addr = canonical(addr.parse().map_err(|_| ERROR)?);
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse().ok().filter(|&n| n <= max).ok_or(ERROR)?,
+            None => max,
+        };
+
+        Ok(Cidr { addr, prefix })
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+/// An additional listener: an address, a port, an optional TLS config, and
+/// whether connections must begin with a PROXY protocol header.
+#[derive(Clone)]
+pub struct ListenerConfig {
+    pub address: String,
+    pub port: u16,
+    pub tls: Option<TlsConfig>,
+    pub proxy_protocol: bool,
+}
+
+impl fmt::Debug for ListenerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ListenerConfig")
+            .field("address", &self.address)
+            .field("port", &self.port)
+            .field("tls", &self.tls.is_some())
+            .field("proxy_protocol", &self.proxy_protocol)
+            .finish()
+    }
+}
+
+/// Doesn't consider the contents of the TLS config.
+impl PartialEq for ListenerConfig {
+    fn eq(&self, other: &ListenerConfig) -> bool {
+        self.address == other.address
+            && self.port == other.port
+            && self.tls.is_some() == other.tls.is_some()
+            && self.proxy_protocol == other.proxy_protocol
+    }
+}
+
+/// An unprocessed listener: address, port, TLS certs and key paths, and
+/// whether the PROXY protocol is required.
+pub type RawListener<'v> = (&'v str, u16, Option<(&'v str, &'v str)>, bool);
+
 pub fn str<'a>(conf: &Config, name: &str, v: &'a Value) -> Result<&'a str> {
     v.as_str().ok_or_else(|| conf.bad_type(name, v.type_str(), "a string"))
 }
 
+pub fn bool(conf: &Config, name: &str, value: &Value) -> Result<bool> {
+    value.as_bool().ok_or_else(|| conf.bad_type(name, value.type_str(), "a boolean"))
+}
+
 pub fn u64(conf: &Config, name: &str, value: &Value) -> Result<u64> {
     match value.as_integer() {
         Some(x) if x >= 0 => Ok(x as u64),
@@ -226,6 +560,21 @@ pub fn u32(conf: &Config, name: &str, value: &Value) -> Result<u32> {
     }
 }
 
+/// Parses file permissions written in octal, either as a string (`"0660"`) or
+/// as an integer whose decimal digits are the octal digits (`660`).
+pub fn socket_mode(conf: &Config, name: &str, value: &Value) -> Result<u32> {
+    let digits = match *value {
+        Value::String(ref s) => s.clone(),
+        Value::Integer(i) if i >= 0 => i.to_string(),
+        _ => String::new(),
+    };
+
+    match u32::from_str_radix(&digits, 8) {
+        Ok(mode) if mode <= 0o777 => Ok(mode),
+        _ => Err(conf.bad_type(name, value.type_str(), "octal file permissions"))
+    }
+}
+
 pub fn log_level(conf: &Config,
                           name: &str,
                           value: &Value
@@ -271,3 +620,100 @@ pub fn limits(conf: &Config, name: &str, value: &Value) -> Result<Limits> {
 
     Ok(limits)
 }
+
+pub fn headers(conf: &Config, name: &str, value: &Value) -> Result<DefaultHeaders> {
+    let table = value.as_table()
+        .ok_or_else(|| conf.bad_type(name, value.type_str(), "a table"))?;
+
+    let mut headers = DefaultHeaders::default();
+    for (key, value) in table {
+        headers = match *value {
+            Value::String(ref s) => headers.header(key.as_str(), s.as_str()),
+            Value::Boolean(false) => headers.remove(key),
+            _ => {
+                let name = format!("headers.{}", key);
+                return Err(conf.bad_type(&name, value.type_str(), "a string or `false`"));
+            }
+        };
+    }
+
+    Ok(headers)
+}
+
+pub fn trusted_proxies(conf: &Config, name: &str, value: &Value) -> Result<Vec<Cidr>> {
+    const EXPECTED: &str = "an array of IP addresses or CIDR ranges";
+    let array = value.as_array()
+        .ok_or_else(|| conf.bad_type(name, value.type_str(), EXPECTED))?;
+
+    array.iter()
+        .map(|value| value.as_str()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| conf.bad_type(name, value.type_str(), EXPECTED)))
+        .collect()
+}
+
+pub fn shutdown(conf: &Config, name: &str, value: &Value) -> Result<ShutdownConfig> {
+    let table = value.as_table()
+        .ok_or_else(|| conf.bad_type(name, value.type_str(), "a table"))?;
+
+    let mut shutdown = ShutdownConfig::default();
+    for (key, value) in table {
+        match key.as_str() {
+            "ctrlc" => {
+                shutdown.ctrlc = value.as_bool().ok_or_else(|| {
+                    conf.bad_type("shutdown.ctrlc", value.type_str(), "a boolean")
+                })?;
+            }
+            "grace" => shutdown.grace = u32(conf, "shutdown.grace", value)?,
+            _ => {
+                let env = conf.environment;
+                return Err(ConfigError::UnknownKey(format!("{}.shutdown.{}", env, key)));
+            }
+        }
+    }
+
+    Ok(shutdown)
+}
+
+fn listener<'v>(conf: &Config,
+                name: &str,
+                value: &'v Value,
+                allow_tls: bool
+                ) -> Result<RawListener<'v>> {
+    let table = value.as_table()
+        .ok_or_else(|| conf.bad_type(name, value.type_str(), "a table"))?;
+
+    let (mut address, mut port, mut tls, mut proxy) = (None, None, None, false);
+    for (key, value) in table {
+        match key.as_str() {
+            "address" => address = Some(str(conf, &format!("{}.address", name), value)?),
+            "port" => port = Some(u16(conf, &format!("{}.port", name), value)?),
+            "tls" if allow_tls => tls = Some(tls_config(conf, &format!("{}.tls", name), value)?),
+            "proxy_protocol" => proxy = bool(conf, &format!("{}.proxy_protocol", name), value)?,
+            _ => {
+                let env = conf.environment;
+                return Err(ConfigError::UnknownKey(format!("{}.{}.{}", env, name, key)));
+            }
+        }
+    }
+
+    match (address, port) {
+        (Some(address), Some(port)) => Ok((address, port, tls, proxy)),
+        _ => Err(conf.bad_type(name, "a table with missing entries",
+                               "a table with `address` and `port` entries"))
+    }
+}
+
+pub fn listeners<'v>(conf: &Config,
+                     name: &str,
+                     value: &'v Value,
+                     ) -> Result<Vec<RawListener<'v>>> {
+    let array = value.as_array()
+        .ok_or_else(|| conf.bad_type(name, value.type_str(), "an array of tables"))?;
+
+    array.iter().map(|value| listener(conf, name, value, true)).collect()
+}
+
+pub fn redirect<'v>(conf: &Config, name: &str, value: &'v Value) -> Result<RawListener<'v>> {
+    listener(conf, name, value, false)
+}
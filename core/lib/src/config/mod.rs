@@ -33,16 +33,30 @@
 //!
 //! | name       | type           | description                                                 | examples                   |
 //! |------------|----------------|-------------------------------------------------------------|----------------------------|
-//! | address    | string         | ip address or host to listen on                             | `"localhost"`, `"1.2.3.4"` |
+//! | address    | string         | ip address, host, or `unix:` socket path to listen on       | `"localhost"`, `"1.2.3.4"` |
 //! | port       | integer        | port number to listen on                                    | `8000`, `80`               |
+//! | socket_mode | integer or string | octal permissions of the `unix:` socket file             | `660`, `"0600"`            |
 //! | keep_alive | integer        | keep-alive timeout in seconds                               | `0` (disable), `10`        |
+//! | read_timeout | integer      | timeout in seconds for a single read from a connection      | `0` (disable), `30`        |
+//! | write_timeout | integer     | timeout in seconds for a single write to a connection       | `0` (disable), `30`        |
+//! | header_timeout | integer    | seconds a client has to send all headers of a request       | `0` (disable), `5`         |
+//! | method_not_allowed | boolean | answer `405` for paths routed only under other methods  | `true`, `false`            |
+//! | auto_options | boolean      | answer `OPTIONS` requests automatically                     | `true`, `false`            |
 //! | workers    | integer        | number of concurrent thread workers                         | `36`, `512`                |
 //! | log        | string         | max log level: `"off"`, `"normal"`, `"debug"`, `"critical"` | `"off"`, `"normal"`        |
 //! | secret_key | 256-bit base64 | secret key for private cookies                              | `"8Xui8SI..."` (44 chars)  |
 //! | tls        | table          | tls config table with two keys (`certs`, `key`)             | _see below_                |
 //! | tls.certs  | string         | path to certificate chain in PEM format                     | `"private/cert.pem"`       |
 //! | tls.key    | string         | path to private key for `tls.certs` in PEM format           | `"private/key.pem"`        |
+//! | proxy_protocol | boolean    | require a PROXY protocol header on each connection          | `true`, `false`            |
+//! | listeners  | array of tables | additional listeners: `address`, `port`, optional `tls`    | _see below_                |
+//! | redirect   | table          | HTTP listener redirecting to HTTPS: `address`, `port`       | _see below_                |
 //! | limits     | table          | map from data type (string) to data limit (integer: bytes)  | `{ forms = 65536 }`        |
+//! | headers    | table          | map from header name to default value or `false` (omit)     | `{ Server = "MyApp" }`     |
+//! | trusted_proxies | array     | IPs or CIDR ranges of proxies whose forwarding is trusted  | `["127.0.0.1", "10.0.0.0/8"]` |
+//! | shutdown   | table          | shutdown config table with two keys (`ctrlc`, `grace`)      | _see below_                |
+//! | shutdown.ctrlc | boolean    | whether `SIGINT` and `SIGTERM` trigger a graceful shutdown  | `true`, `false`            |
+//! | shutdown.grace | integer    | seconds to wait for in-flight requests during shutdown      | `2`, `30`                  |
 //!
 //! ### Rocket.toml
 //!
@@ -64,27 +78,54 @@
 //! port = 8000
 //! workers = [number_of_cpus * 2]
 //! keep_alive = 5
+//! read_timeout = 0
+//! write_timeout = 0
+//! header_timeout = 0
+//! method_not_allowed = true
+//! auto_options = true
 //! log = "normal"
 //! secret_key = [randomly generated at launch]
 //! limits = { forms = 32768 }
+//! headers = { Server = "Rocket" }
+//! trusted_proxies = []
+//! proxy_protocol = false
+//! shutdown = { ctrlc = true, grace = 2 }
 //!
 //! [staging]
 //! address = "0.0.0.0"
 //! port = 8000
 //! workers = [number_of_cpus * 2]
 //! keep_alive = 5
+//! read_timeout = 0
+//! write_timeout = 0
+//! header_timeout = 0
+//! method_not_allowed = true
+//! auto_options = true
 //! log = "normal"
 //! secret_key = [randomly generated at launch]
 //! limits = { forms = 32768 }
+//! headers = { Server = "Rocket" }
+//! trusted_proxies = []
+//! proxy_protocol = false
+//! shutdown = { ctrlc = true, grace = 2 }
 //!
 //! [production]
 //! address = "0.0.0.0"
 //! port = 8000
 //! workers = [number_of_cpus * 2]
 //! keep_alive = 5
+//! read_timeout = 0
+//! write_timeout = 0
+//! header_timeout = 0
+//! method_not_allowed = true
+//! auto_options = true
 //! log = "critical"
 //! secret_key = [randomly generated at launch]
 //! limits = { forms = 32768 }
+//! headers = { Server = "Rocket" }
+//! trusted_proxies = []
+//! proxy_protocol = false
+//! shutdown = { ctrlc = true, grace = 2 }
 //! ```
 //!
 //! The `workers` and `secret_key` default parameters are computed by Rocket
@@ -124,6 +165,161 @@
 //! key = "/path/to/key.pem"
 //! ```
 //!
+//! ### Graceful Shutdown
+//!
+//! A launched application shuts down gracefully when a
+//! [`Shutdown`](crate::Shutdown) handle is notified or, on Unix platforms and
+//! when `shutdown.ctrlc` is `true`, when the process receives `SIGINT` or
+//! `SIGTERM`. Rocket then stops accepting connections, waits at most
+//! `shutdown.grace` seconds for in-flight requests to complete, and returns
+//! from [`Rocket::launch()`](crate::Rocket::launch()) or, if the application
+//! was started with [`Rocket::spawn()`](crate::Rocket::spawn()), from
+//! [`Running::join()`](crate::Running::join()). A second signal received
+//! during the grace period terminates the process immediately.
+//!
+//! ```toml
+//! [global.shutdown]
+//! ctrlc = false
+//! grace = 10
+//! ```
+//!
+//! ### Timeouts
+//!
+//! To keep slow or stalled clients from occupying workers indefinitely, a
+//! single read from or write to a connection fails if it makes no progress
+//! within `read_timeout` or `write_timeout` seconds, respectively. Because a
+//! client can evade a read timeout by trickling in a byte at a time, the
+//! headers of a request must additionally arrive in full within
+//! `header_timeout` seconds of the request's first byte; otherwise, the
+//! connection is closed. A read of the request body that times out fails with
+//! an I/O error of kind `TimedOut`, which is logged along with the request
+//! line. All three timeouts are disabled by default, in every environment, and
+//! setting any of these values to `0` disables the respective timeout. Reads of
+//! a request body, however, always time out: after `read_timeout` seconds or,
+//! if it is disabled, after 5 seconds. Only idle keep-alive connections are
+//! closed after `keep_alive` seconds.
+//!
+//! ```toml
+//! [production]
+//! read_timeout = 30
+//! write_timeout = 30
+//! header_timeout = 5
+//! ```
+//!
+//! ### Response Headers
+//!
+//! Every response carries a `Date` header with the current time, unless the
+//! response already contains one. Additionally, each header in `headers` is
+//! added to every response that doesn't already contain a header of the same
+//! name. By default, this is only `Server: Rocket`. Setting a header to `false`
+//! removes it from the defaults. Headers are added before response fairings
+//! run, so fairings observe and may modify them:
+//!
+//! ```toml
+//! [global.headers]
+//! Server = false
+//! X-Frame-Options = "DENY"
+//! ```
+//!
+//! ### Trusted Proxies
+//!
+//! When Rocket is deployed behind reverse proxies, the remote address of a
+//! connection is that of the nearest proxy. Proxies report the address, scheme,
+//! and host of the original client in the `Forwarded`, `X-Forwarded-For`,
+//! `X-Forwarded-Proto`, `X-Forwarded-Host`, and `X-Real-IP` headers. Because
+//! any client can send these headers, Rocket only considers them when they were
+//! added by a proxy listed in `trusted_proxies`. By default, no proxy is
+//! trusted. See [`Request::client_ip()`](crate::Request::client_ip()) for
+//! details.
+//!
+//! ```toml
+//! [production]
+//! trusted_proxies = ["127.0.0.1", "::1", "10.0.0.0/8"]
+//! ```
+//!
+//! ### PROXY Protocol
+//!
+//! Load balancers that forward TCP connections, such as HAProxy or AWS NLB,
+//! can prepend a PROXY protocol header, version 1 (text) or 2 (binary), to each
+//! connection to convey the address of the original client. When
+//! `proxy_protocol` is enabled, the main listener reads this header before
+//! TLS, if enabled, and HTTP, and the client address it contains becomes the
+//! [remote address](crate::Request::remote()) of every request received over
+//! the connection. Connections that don't begin with a valid header are
+//! closed. Headers sent by the load balancer itself, such as health checks
+//! using the `LOCAL` command, leave the remote address unchanged.
+//!
+//! Additional listeners and the `redirect` listener accept a `proxy_protocol`
+//! key of their own:
+//!
+//! ```toml
+//! [production]
+//! proxy_protocol = true
+//! listeners = [{ address = "127.0.0.1", port = 8001, proxy_protocol = false }]
+//! ```
+//!
+//! Because the header is trusted unconditionally, a listener with the PROXY
+//! protocol enabled should only be reachable through the load balancer.
+//!
+//! ### Method Not Allowed and `OPTIONS`
+//!
+//! When no route matches a request but routes for other methods match its
+//! path, Rocket responds with a `405 Method Not Allowed` and an `Allow` header
+//! listing those methods instead of a `404 Not Found`. The response body is
+//! produced by the `405` catcher. Similarly, an `OPTIONS` request to a routed
+//! path that no `OPTIONS` route handles is answered with a `204 No Content` and
+//! an `Allow` header. As with `HEAD`, `GET` routes imply `HEAD`. Either
+//! behavior can be disabled, in which case such requests are answered with a
+//! `404`:
+//!
+//! ```toml
+//! [global]
+//! method_not_allowed = false
+//! auto_options = false
+//! ```
+//!
+//! ### Multiple Listeners
+//!
+//! In addition to the main listener configured by `address`, `port`, and
+//! `tls`, any number of additional listeners can be configured via
+//! `listeners`. Each listener requires an `address` and a `port` and, if the
+//! `tls` feature is enabled, optionally accepts a `tls` table of the same form
+//! as the main `tls` parameter. Requests received by all listeners are
+//! dispatched to the same routes. Each listener is served by its own pool of
+//! `workers` threads.
+//!
+//! Additionally, a plain HTTP listener that answers every request with a
+//! `308 Permanent Redirect` to the same URI over HTTPS can be configured via
+//! `redirect`. The redirect targets the port of the main listener if it uses
+//! TLS or the port of the first additional TLS listener otherwise; launching
+//! fails if there is no such listener.
+//!
+//! ```toml
+//! [production]
+//! address = "0.0.0.0"
+//! port = 443
+//! tls = { certs = "/etc/ssl/certs.pem", key = "/etc/ssl/key.pem" }
+//! listeners = [{ address = "127.0.0.1", port = 8000 }]
+//! redirect = { address = "0.0.0.0", port = 80 }
+//! ```
+//!
+//! ### Unix Domain Sockets
+//!
+//! On Unix platforms, Rocket can listen on a Unix domain socket instead of a
+//! TCP socket by setting `address` to `unix:` followed by the path to the
+//! socket file. The `port` is then ignored. If a socket file already exists at
+//! the path and no process is listening on it, it is removed before binding.
+//! The permissions of the socket file can be set via `socket_mode`, written as
+//! octal digits; otherwise, they are determined by the process's umask.
+//! Requests received over a Unix domain socket have no remote address.
+//! Serving TLS over a Unix domain socket is not supported.
+//!
+//! ```toml
+//! [production]
+//! address = "unix:/run/app.sock"
+//! socket_mode = 660
+//! ```
+//!
 //! ### Environment Variables
 //!
 //! All configuration parameters, including extras, can be overridden through
@@ -196,7 +392,8 @@ use std::path::{Path, PathBuf};
 
 use toml;
 
-pub use self::custom_values::Limits;
+pub use self::custom_values::{Limits, DefaultHeaders, Cidr, ShutdownConfig};
+pub(crate) use self::custom_values::TlsConfig;
 pub use toml::value::{Array, Table, Value, Datetime};
 pub use self::error::ConfigError;
 pub use self::environment::Environment;
@@ -460,7 +657,7 @@ mod test {
     use std::env;
     use std::sync::Mutex;
 
-    use super::{FullConfig, ConfigError, ConfigBuilder};
+    use super::{Config, FullConfig, ConfigError, ConfigBuilder, ShutdownConfig, DefaultHeaders};
     use super::{Environment, GLOBAL_ENV_NAME};
     use super::environment::CONFIG_ENV;
     use super::Environment::*;
@@ -529,6 +726,14 @@ mod test {
             env::set_var(CONFIG_ENV, env);
             check_config!(env_default(), default_config(Production));
         }
+
+        // No environment enables connection timeouts by default.
+        for env in &[Development, Staging, Production] {
+            let config = Config::default(*env).unwrap();
+            assert_eq!(config.read_timeout, None);
+            assert_eq!(config.write_timeout, None);
+            assert_eq!(config.header_timeout, None);
+        }
     }
 
     #[test]
@@ -644,6 +849,14 @@ mod test {
                       "#.to_string(), TEST_CONFIG_FILENAME), {
                           default_config(Development).address("0.0.0.0")
                       });
+
+        #[cfg(unix)]
+        check_config!(FullConfig::parse(r#"
+                          [dev]
+                          address = "unix:/run/app.sock"
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Development).address("unix:/run/app.sock")
+                      });
     }
 
     #[test]
@@ -671,6 +884,146 @@ mod test {
             [staging]
             address = "1.2.3.4:100"
         "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [staging]
+            address = "unix:"
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+    }
+
+    #[test]
+    fn test_good_listeners_values() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::set_var(CONFIG_ENV, "prod");
+
+        check_config!(FullConfig::parse(r#"
+                          [prod]
+                          listeners = []
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Production)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [prod]
+                          listeners = [{ address = "127.0.0.1", port = 8001 }]
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Production).listener("127.0.0.1", 8001)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [prod]
+                          [[prod.listeners]]
+                          address = "::"
+                          port = 8001
+
+                          [[prod.listeners]]
+                          address = "localhost"
+                          port = 8002
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Production)
+                              .listener("::", 8001)
+                              .listener("localhost", 8002)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [prod]
+                          redirect = { address = "0.0.0.0", port = 80 }
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Production).redirect("0.0.0.0", 80)
+                      });
+    }
+
+    #[test]
+    fn test_bad_listeners_values() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::remove_var(CONFIG_ENV);
+
+        assert!(FullConfig::parse(r#"
+            [development]
+            listeners = { address = "0.0.0.0", port = 8001 }
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [development]
+            listeners = [{ port = 8001 }]
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [development]
+            listeners = [{ address = "........", port = 8001 }]
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [development]
+            listeners = [{ address = "0.0.0.0", port = 8001, backlog = 10 }]
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [development]
+            redirect = 80
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [development]
+            redirect = { address = "0.0.0.0", port = 80, tls = { certs = "a", key = "b" } }
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+    }
+
+    #[test]
+    fn test_good_socket_mode_values() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::set_var(CONFIG_ENV, "stage");
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          socket_mode = 660
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).socket_mode(0o660)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          socket_mode = "0600"
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).socket_mode(0o600)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          socket_mode = 777
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).socket_mode(0o777)
+                      });
+    }
+
+    #[test]
+    fn test_bad_socket_mode_values() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::remove_var(CONFIG_ENV);
+
+        assert!(FullConfig::parse(r#"
+            [development]
+            socket_mode = 680
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [development]
+            socket_mode = "rw-rw----"
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [development]
+            socket_mode = 1777
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [development]
+            socket_mode = -660
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
     }
 
     // Only do this test when the tls feature is disabled since the file paths
@@ -913,6 +1266,353 @@ mod test {
         "#.to_string(), TEST_CONFIG_FILENAME).is_err());
     }
 
+    #[test]
+    fn test_good_timeouts() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::set_var(CONFIG_ENV, "stage");
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          read_timeout = 30
+                          write_timeout = 60
+                          header_timeout = 2
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging)
+                              .read_timeout(30)
+                              .write_timeout(60)
+                              .header_timeout(2)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          read_timeout = 0
+                          write_timeout = 0
+                          header_timeout = 0
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging)
+                              .read_timeout(0)
+                              .write_timeout(0)
+                              .header_timeout(0)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          header_timeout = 15
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).header_timeout(15)
+                      });
+    }
+
+    #[test]
+    fn test_bad_timeouts() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::remove_var(CONFIG_ENV);
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            read_timeout = true
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            write_timeout = -10
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            header_timeout = "5s"
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            read_timeout = 4294967296
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+    }
+
+    #[test]
+    fn test_good_method_handling_values() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::set_var(CONFIG_ENV, "stage");
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          method_not_allowed = false
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).method_not_allowed(false)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          auto_options = false
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).auto_options(false)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          method_not_allowed = true
+                          auto_options = true
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging)
+                      });
+    }
+
+    #[test]
+    fn test_bad_method_handling_values() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::remove_var(CONFIG_ENV);
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            method_not_allowed = "no"
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            auto_options = 0
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+    }
+
+    #[test]
+    fn test_good_headers() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::set_var(CONFIG_ENV, "stage");
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          headers = { Server = "MyApp" }
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).headers(DefaultHeaders::new()
+                              .header("Server", "MyApp"))
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          headers = { Server = false, X-Frame-Options = "DENY" }
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).headers(DefaultHeaders::new()
+                              .remove("Server")
+                              .header("X-Frame-Options", "DENY"))
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage.headers]
+                          Permissions-Policy = "interest-cohort=()"
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).headers(DefaultHeaders::new()
+                              .header("Permissions-Policy", "interest-cohort=()"))
+                      });
+    }
+
+    #[test]
+    fn test_bad_headers() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::remove_var(CONFIG_ENV);
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            headers = "Server: Rocket"
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            headers = { Server = true }
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            headers = { X-Count = 10 }
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+    }
+
+    #[test]
+    fn test_good_trusted_proxies() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::set_var(CONFIG_ENV, "stage");
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          trusted_proxies = ["127.0.0.1", "10.0.0.0/8", "::1", "fd00::/8"]
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).trusted_proxies(vec![
+                              "127.0.0.1/32".parse().unwrap(),
+                              "10.0.0.0/8".parse().unwrap(),
+                              "::1/128".parse().unwrap(),
+                              "fd00::/8".parse().unwrap(),
+                          ])
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          trusted_proxies = []
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging)
+                      });
+    }
+
+    #[test]
+    fn test_bad_trusted_proxies() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::remove_var(CONFIG_ENV);
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            trusted_proxies = "127.0.0.1"
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            trusted_proxies = ["localhost"]
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            trusted_proxies = ["10.0.0.0/40"]
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            trusted_proxies = [127]
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+    }
+
+    #[test]
+    fn test_good_proxy_protocol_values() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::set_var(CONFIG_ENV, "prod");
+
+        check_config!(FullConfig::parse(r#"
+                          [prod]
+                          proxy_protocol = true
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Production).proxy_protocol(true)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [prod]
+                          proxy_protocol = false
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Production)
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [prod]
+                          [[prod.listeners]]
+                          address = "127.0.0.1"
+                          port = 8001
+                          proxy_protocol = true
+
+                          [[prod.listeners]]
+                          address = "127.0.0.1"
+                          port = 8002
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Production)
+                              .proxy_listener("127.0.0.1", 8001)
+                              .listener("127.0.0.1", 8002)
+                      });
+
+        let config = FullConfig::parse(r#"
+            [prod]
+            redirect = { address = "0.0.0.0", port = 80, proxy_protocol = true }
+        "#.to_string(), TEST_CONFIG_FILENAME).unwrap();
+
+        let redirect = config.active().redirect.as_ref().unwrap();
+        assert!(redirect.proxy_protocol);
+    }
+
+    #[test]
+    fn test_bad_proxy_protocol_values() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::remove_var(CONFIG_ENV);
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            proxy_protocol = "v2"
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            proxy_protocol = 1
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            listeners = [{ address = "0.0.0.0", port = 8001, proxy_protocol = "yes" }]
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+    }
+
+    #[test]
+    fn test_good_shutdown_values() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::set_var(CONFIG_ENV, "stage");
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          shutdown = { ctrlc = false }
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).shutdown(ShutdownConfig {
+                              ctrlc: false, grace: 2
+                          })
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage.shutdown]
+                          ctrlc = true
+                          grace = 30
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).shutdown(ShutdownConfig {
+                              ctrlc: true, grace: 30
+                          })
+                      });
+
+        check_config!(FullConfig::parse(r#"
+                          [stage]
+                          shutdown = { grace = 0 }
+                      "#.to_string(), TEST_CONFIG_FILENAME), {
+                          default_config(Staging).shutdown(ShutdownConfig {
+                              ctrlc: true, grace: 0
+                          })
+                      });
+    }
+
+    #[test]
+    fn test_bad_shutdown_values() {
+        // Take the lock so changing the environment doesn't cause races.
+        let _env_lock = ENV_LOCK.lock().unwrap();
+        env::remove_var(CONFIG_ENV);
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            shutdown = true
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            shutdown = { ctrlc = "yes" }
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            shutdown = { grace = -1 }
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+
+        assert!(FullConfig::parse(r#"
+            [dev]
+            shutdown = { grace = 5, mercy = 3 }
+        "#.to_string(), TEST_CONFIG_FILENAME).is_err());
+    }
+
     #[test]
     fn test_good_log_levels() {
         // Take the lock so changing the environment doesn't cause races.
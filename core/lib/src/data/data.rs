@@ -6,7 +6,10 @@ use std::time::Duration;
 #[cfg(feature = "tls")] use super::net_stream::HttpsStream;
 
 use super::data_stream::{DataStream, kill_stream};
-use super::net_stream::NetStream;
+use super::net_stream::{NetStream, BodyStream};
+#[cfg(unix)] use crate::unix::UnixStream;
+use crate::timeout::TimeoutStream;
+use crate::websocket::Connection;
 use crate::ext::ReadExt;
 
 use crate::http::hyper;
@@ -18,7 +21,7 @@ pub type HyperBodyReader<'a, 'b> =
     self::HttpReader<&'a mut hyper::buffer::BufReader<&'b mut dyn NetworkStream>>;
 
 //                              |---- from hyper ----|
-pub type BodyReader = HttpReader<Chain<Cursor<Vec<u8>>, NetStream>>;
+pub type BodyReader = HttpReader<Chain<Cursor<Vec<u8>>, BodyStream>>;
 
 /// The number of bytes to read into the "peek" buffer.
 const PEEK_BYTES: usize = 512;
@@ -80,7 +83,7 @@ impl Data {
     /// ```
     pub fn open(mut self) -> DataStream {
         let buffer = std::mem::replace(&mut self.buffer, vec![]);
-        let empty_stream = Cursor::new(vec![]).chain(NetStream::Empty);
+        let empty_stream = Cursor::new(vec![]).chain(BodyStream::empty());
 
         // FIXME: Insert a `BufReader` in front of the `NetStream` with capacity
         // 4096. We need the new `Chain` methods to get the inner reader to
@@ -91,33 +94,55 @@ impl Data {
     }
 
     // FIXME: This is absolutely terrible (downcasting!), thanks to Hyper.
-    pub(crate) fn from_hyp(mut body: HyperBodyReader<'_, '_>) -> Result<Data, &'static str> {
+    pub(crate) fn from_hyp(
+        mut body: HyperBodyReader<'_, '_>,
+        read_timeout: Option<Duration>,
+        h_method: hyper::Method,
+        h_uri: hyper::RequestUri,
+    ) -> Result<Data, &'static str> {
         #[inline(always)]
         #[cfg(feature = "tls")]
         fn concrete_stream(stream: &mut dyn NetworkStream) -> Option<NetStream> {
-            stream.downcast_ref::<HttpsStream>()
-                .map(|s| NetStream::Https(s.clone()))
+            stream.downcast_ref::<TimeoutStream<HttpsStream>>()
+                .map(|s| NetStream::Https(s.get_ref().clone()))
                 .or_else(|| {
-                    stream.downcast_ref::<HttpStream>()
-                        .map(|s| NetStream::Http(s.clone()))
+                    stream.downcast_ref::<TimeoutStream<HttpStream>>()
+                        .map(|s| NetStream::Http(s.get_ref().clone()))
                 })
+                .or_else(|| unix_stream(stream))
         }
 
         #[inline(always)]
         #[cfg(not(feature = "tls"))]
         fn concrete_stream(stream: &mut dyn NetworkStream) -> Option<NetStream> {
-            stream.downcast_ref::<HttpStream>()
-                .map(|s| NetStream::Http(s.clone()))
+            stream.downcast_ref::<TimeoutStream<HttpStream>>()
+                .map(|s| NetStream::Http(s.get_ref().clone()))
+                .or_else(|| unix_stream(stream))
+        }
+
+        #[inline(always)]
+        #[cfg(unix)]
+        fn unix_stream(stream: &dyn NetworkStream) -> Option<NetStream> {
+            stream.downcast_ref::<TimeoutStream<UnixStream>>()
+                .map(|s| NetStream::Unix(s.get_ref().clone()))
+        }
+
+        #[inline(always)]
+        #[cfg(not(unix))]
+        fn unix_stream(_: &dyn NetworkStream) -> Option<NetStream> {
+            None
         }
 
         // Retrieve the underlying Http(s)Stream from Hyper.
         let net_stream = match concrete_stream(*body.get_mut().get_mut()) {
             Some(net_stream) => net_stream,
-            None => return Err("Stream is not an HTTP(s) stream!")
+            None => return Err("Stream is not an HTTP(s) or Unix stream!")
         };
 
-        // Set the read timeout to 5 seconds.
-        let _ = net_stream.set_read_timeout(Some(Duration::from_secs(5)));
+        // Reads of the body are bounded by the configured read timeout or, if
+        // there is none, by a fixed 5 seconds.
+        let read_timeout = read_timeout.unwrap_or(Duration::from_secs(5));
+        let _ = net_stream.set_read_timeout(Some(read_timeout));
 
         // Steal the internal, undecoded data buffer from Hyper.
         let (mut hyper_buf, pos, cap) = body.get_mut().take_buf();
@@ -126,7 +151,7 @@ impl Data {
         cursor.set_position(pos as u64);
 
         // Create an HTTP reader from the buffer + stream.
-        let inner_data = cursor.chain(net_stream);
+        let inner_data = cursor.chain(BodyStream::new(net_stream, h_method, h_uri));
         let http_stream = match body {
             SizedReader(_, n) => SizedReader(inner_data, n),
             EofReader(_) => EofReader(inner_data),
@@ -258,10 +283,30 @@ impl Data {
         Data { buffer: peek_buf, stream, is_complete: eof }
     }
 
+    /// Returns the connection the request was received on so that it can be
+    /// upgraded to another protocol, or `None` if the request has a body.
+    pub(crate) fn connection(&mut self) -> Option<Connection> {
+        let (cursor, body_stream) = match self.stream {
+            EmptyReader(ref mut inner) => inner.get_mut(),
+            _ => return None
+        };
+
+        let net_stream = match body_stream.get_mut() {
+            NetStream::Empty => return None,
+            net_stream => net_stream.clone()
+        };
+
+        // Bytes already read past the request's head belong to the new
+        // protocol; an `EmptyReader` never reads them.
+        let position = cursor.position() as usize;
+        let buffered = cursor.get_ref().get(position..).unwrap_or(&[]).to_vec();
+        Some(Connection::network(buffered, net_stream))
+    }
+
     /// This creates a `data` object from a local data source `data`.
     #[inline]
     pub(crate) fn local(data: Vec<u8>) -> Data {
-        let empty_stream = Cursor::new(vec![]).chain(NetStream::Empty);
+        let empty_stream = Cursor::new(vec![]).chain(BodyStream::empty());
 
         Data {
             buffer: data,
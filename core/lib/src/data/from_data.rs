@@ -3,7 +3,7 @@ use std::borrow::Borrow;
 use crate::outcome::{self, IntoOutcome};
 use crate::outcome::Outcome::*;
 use crate::http::Status;
-use crate::request::Request;
+use crate::request::{Request, GuardError};
 use crate::data::Data;
 
 /// Type alias for the `Outcome` of a `FromData` conversion.
@@ -385,6 +385,20 @@ pub trait FromData<'a>: Sized {
     /// # }
     /// ```
     fn from_data(request: &Request<'_>, outcome: Transformed<'a, Self>) -> Outcome<Self, Self::Error>;
+
+    /// Converts the error of a failed guard into the [`GuardError`] that is
+    /// kept in the request for the error catcher.
+    ///
+    /// The default implementation keeps only the name of the error's type as
+    /// the error need not implement `Debug`. Implementations whose error type
+    /// implements `Debug` can override this method to return
+    /// [`GuardError::from_debug(&error)`](GuardError::from_debug()) or, if the
+    /// type is also `'static`, [`GuardError::new(error)`](GuardError::new()),
+    /// allowing catchers to retrieve the error's value.
+    #[inline(always)]
+    fn guard_error(_: Self::Error) -> GuardError {
+        GuardError::from_type::<Self::Error>()
+    }
 }
 
 /// The identity implementation of `FromData`. Always returns `Success`.
@@ -495,6 +509,9 @@ impl<'f> FromData<'f> for Data {
 /// ```
 pub trait FromDataSimple: Sized {
     /// The associated error to be returned when the guard fails.
+    ///
+    /// When the guard fails in a route, the error is kept in the request as a
+    /// [`GuardError`] for the error catcher.
     type Error;
 
     /// Validates, parses, and converts an instance of `Self` from the incoming
@@ -504,6 +521,14 @@ pub trait FromDataSimple: Sized {
     /// If the data is not appropriate given the type of `Self`, `Forward` is
     /// returned. If parsing fails, `Failure` is returned.
     fn from_data(request: &Request<'_>, data: Data) -> Outcome<Self, Self::Error>;
+
+    /// Converts the error of a failed guard into the [`GuardError`] that is
+    /// kept in the request for the error catcher. See
+    /// [`FromData::guard_error()`].
+    #[inline(always)]
+    fn guard_error(_: Self::Error) -> GuardError {
+        GuardError::from_type::<Self::Error>()
+    }
 }
 
 impl<'a, T: FromDataSimple> FromData<'a> for T {
@@ -520,6 +545,11 @@ impl<'a, T: FromDataSimple> FromData<'a> for T {
     fn from_data(req: &Request<'_>, o: Transformed<'a, Self>) -> Outcome<Self, Self::Error> {
         T::from_data(req, try_outcome!(o.owned()))
     }
+
+    #[inline(always)]
+    fn guard_error(error: Self::Error) -> GuardError {
+        <T as FromDataSimple>::guard_error(error)
+    }
 }
 
 impl<'a, T: FromData<'a> + 'a> FromData<'a> for Result<T, T::Error> {
@@ -576,6 +606,11 @@ impl FromDataSimple for String {
             Err(e) => Failure((Status::BadRequest, e))
         }
     }
+
+    #[inline(always)]
+    fn guard_error(error: Self::Error) -> GuardError {
+        GuardError::new(error)
+    }
 }
 
 #[cfg(debug_assertions)]
@@ -590,4 +625,9 @@ impl FromDataSimple for Vec<u8> {
             Err(e) => Failure((Status::BadRequest, e))
         }
     }
+
+    #[inline(always)]
+    fn guard_error(error: Self::Error) -> GuardError {
+        GuardError::new(error)
+    }
 }
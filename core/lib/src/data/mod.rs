@@ -7,4 +7,5 @@ mod from_data;
 
 pub use self::data::Data;
 pub use self::data_stream::DataStream;
+pub(crate) use self::net_stream::is_tls_stream;
 pub use self::from_data::{FromData, FromDataSimple, Outcome, Transform, Transformed};
@@ -3,12 +3,30 @@ use std::net::{SocketAddr, Shutdown};
 use std::time::Duration;
 
 #[cfg(feature = "tls")] use crate::http::tls::{WrappedStream, ServerSession};
+use crate::http::hyper::{Method, RequestUri};
 use crate::http::hyper::net::{HttpStream, NetworkStream};
+#[cfg(unix)] use crate::unix::UnixStream;
+use crate::timeout::is_timeout;
+#[cfg(feature = "tls")] use crate::timeout::TimeoutStream;
 
 use self::NetStream::*;
 
 #[cfg(feature = "tls")] pub type HttpsStream = WrappedStream<ServerSession>;
 
+/// Returns `true` if `stream` is a connection secured with TLS.
+#[inline(always)]
+#[cfg(feature = "tls")]
+pub(crate) fn is_tls_stream(stream: &dyn NetworkStream) -> bool {
+    stream.downcast_ref::<TimeoutStream<HttpsStream>>().is_some()
+}
+
+/// Returns `true` if `stream` is a connection secured with TLS.
+#[inline(always)]
+#[cfg(not(feature = "tls"))]
+pub(crate) fn is_tls_stream(_: &dyn NetworkStream) -> bool {
+    false
+}
+
 // This is a representation of all of the possible network streams we might get.
 // This really shouldn't be necessary, but, you know, Hyper.
 #[derive(Clone)]
@@ -16,6 +34,8 @@ pub enum NetStream {
     Http(HttpStream),
     #[cfg(feature = "tls")]
     Https(HttpsStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
     Empty,
 }
 
@@ -26,6 +46,7 @@ impl io::Read for NetStream {
         let res = match *self {
             Http(ref mut stream) => stream.read(buf),
             #[cfg(feature = "tls")] Https(ref mut stream) => stream.read(buf),
+            #[cfg(unix)] Unix(ref mut stream) => stream.read(buf),
             Empty => Ok(0),
         };
 
@@ -41,6 +62,7 @@ impl io::Write for NetStream {
         match *self {
             Http(ref mut stream) => stream.write(buf),
             #[cfg(feature = "tls")] Https(ref mut stream) => stream.write(buf),
+            #[cfg(unix)] Unix(ref mut stream) => stream.write(buf),
             Empty => Ok(0),
         }
     }
@@ -50,6 +72,7 @@ impl io::Write for NetStream {
         match *self {
             Http(ref mut stream) => stream.flush(),
             #[cfg(feature = "tls")] Https(ref mut stream) => stream.flush(),
+            #[cfg(unix)] Unix(ref mut stream) => stream.flush(),
             Empty => Ok(()),
         }
     }
@@ -61,6 +84,7 @@ impl NetworkStream for NetStream {
         match *self {
             Http(ref mut stream) => stream.peer_addr(),
             #[cfg(feature = "tls")] Https(ref mut stream) => stream.peer_addr(),
+            #[cfg(unix)] Unix(ref mut stream) => stream.peer_addr(),
             Empty => Err(io::Error::from(io::ErrorKind::AddrNotAvailable)),
         }
     }
@@ -70,6 +94,7 @@ impl NetworkStream for NetStream {
         match *self {
             Http(ref stream) => stream.set_read_timeout(dur),
             #[cfg(feature = "tls")] Https(ref stream) => stream.set_read_timeout(dur),
+            #[cfg(unix)] Unix(ref stream) => stream.set_read_timeout(dur),
             Empty => Ok(()),
         }
     }
@@ -79,6 +104,7 @@ impl NetworkStream for NetStream {
         match *self {
             Http(ref stream) => stream.set_write_timeout(dur),
             #[cfg(feature = "tls")] Https(ref stream) => stream.set_write_timeout(dur),
+            #[cfg(unix)] Unix(ref stream) => stream.set_write_timeout(dur),
             Empty => Ok(()),
         }
     }
@@ -88,7 +114,61 @@ impl NetworkStream for NetStream {
         match *self {
             Http(ref mut stream) => stream.close(how),
             #[cfg(feature = "tls")] Https(ref mut stream) => stream.close(how),
+            #[cfg(unix)] Unix(ref mut stream) => stream.close(how),
             Empty => Ok(()),
         }
     }
 }
+
+/// The network stream a request body is read from.
+///
+/// A read that times out fails with an error of kind `TimedOut`, and the abort
+/// is logged along with the request line. The connection is unusable
+/// thereafter, so all subsequent reads fail immediately.
+pub struct BodyStream {
+    stream: NetStream,
+    request: Option<(Method, RequestUri)>,
+    timed_out: bool,
+}
+
+impl BodyStream {
+    /// Creates a body stream for the request with method `method` and target
+    /// `uri`. These are only formatted if a read times out.
+    #[inline(always)]
+    pub fn new(stream: NetStream, method: Method, uri: RequestUri) -> BodyStream {
+        BodyStream { stream, request: Some((method, uri)), timed_out: false }
+    }
+
+    /// A body stream that is always at EOF.
+    #[inline(always)]
+    pub fn empty() -> BodyStream {
+        BodyStream { stream: NetStream::Empty, request: None, timed_out: false }
+    }
+
+    /// Returns the underlying network stream.
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut NetStream {
+        &mut self.stream
+    }
+}
+
+impl io::Read for BodyStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.timed_out {
+            match self.stream.read(buf) {
+                Err(ref e) if is_timeout(e) => {
+                    if let Some((ref method, ref uri)) = self.request {
+                        warn_!("Timed out reading body of '{} {}'. Aborting request.",
+                            method, uri);
+                    }
+
+                    self.timed_out = true;
+                }
+                result => return result,
+            }
+        }
+
+        let msg = "timed out reading request body";
+        Err(io::Error::new(io::ErrorKind::TimedOut, msg))
+    }
+}
@@ -0,0 +1,30 @@
+use std::cell::RefCell;
+
+use time::OffsetDateTime;
+
+use crate::http::Header;
+
+thread_local! {
+    /// The second and its formatted HTTP-date, as last computed on this thread.
+    static CACHED: RefCell<(i64, String)> = RefCell::new((i64::min_value(), String::new()));
+}
+
+/// Returns a `Date` header with the current time as an HTTP-date, the
+/// IMF-fixdate format of RFC 7231, section 7.1.1.1. For example, `Sun, 06 Nov
+/// 1994 08:49:37 GMT`.
+///
+/// HTTP-dates have a resolution of one second, so the formatted date is cached
+/// and only recomputed when the second changes. The cache is per-thread so
+/// that workers never contend for it.
+pub(crate) fn header() -> Header<'static> {
+    let now = OffsetDateTime::now_utc();
+    let timestamp = now.timestamp();
+    CACHED.with(|cached| {
+        let mut cached = cached.borrow_mut();
+        if cached.0 != timestamp {
+            *cached = (timestamp, now.format("%a, %d %b %Y %H:%M:%S GMT"));
+        }
+
+        Header::new("Date", cached.1.clone())
+    })
+}
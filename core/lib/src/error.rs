@@ -5,7 +5,8 @@ use std::sync::atomic::{Ordering, AtomicBool};
 
 use yansi::Paint;
 
-use crate::http::hyper;
+use crate::http::{hyper, Method, ContentType};
+use crate::http::uri::Origin;
 use crate::router::Route;
 
 /// The kind of launch error that occurred.
@@ -15,7 +16,8 @@ use crate::router::Route;
 /// because of ill-defined routes that lead to collisions or because a fairing
 /// encountered an error; these are represented by the `Collision` and
 /// `FailedFairing` variants, respectively. The `Unknown` variant captures all
-/// other kinds of launch errors.
+/// other kinds of launch errors. Finally, the `Shutdown` variant indicates that
+/// the application launched and was later shut down gracefully.
 #[derive(Debug)]
 pub enum LaunchErrorKind {
     /// Binding to the provided address/port failed.
@@ -23,21 +25,97 @@ pub enum LaunchErrorKind {
     /// An I/O error occurred during launch.
     Io(io::Error),
     /// Route collisions were detected.
-    Collision(Vec<(Route, Route)>),
+    Collision(Vec<RouteCollision>),
     /// A launch fairing reported an error.
     FailedFairings(Vec<&'static str>),
     /// An otherwise uncategorized error occurred during launch.
-    Unknown(Box<dyn std::error::Error + Send + Sync>)
+    Unknown(Box<dyn std::error::Error + Send + Sync>),
+    /// The application launched successfully and has since shut down
+    /// gracefully. A `LaunchError` of this kind never panics when dropped.
+    Shutdown,
+}
+
+/// A collision between two routes: a request that both routes match.
+///
+/// Rocket refuses to launch if two of its routes collide, reporting each
+/// collision in [`LaunchErrorKind::Collision`]. Along with the colliding
+/// routes, a collision contains an example request both routes match and a
+/// rank for the second route that resolves the collision. The `Display`
+/// implementation prints the example request.
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::error::LaunchErrorKind;
+///
+/// #[get("/<id>")]
+/// fn item(id: usize) { /* .. */ }
+///
+/// #[get("/<name>")]
+/// fn user(name: String) { /* .. */ }
+///
+/// let rocket = rocket::ignite().mount("/", routes![item, user]);
+/// let error = match rocket::local::Client::new(rocket) {
+///     Err(error) => error,
+///     Ok(_) => unreachable!("the routes collide"),
+/// };
+///
+/// match error.kind() {
+///     LaunchErrorKind::Collision(collisions) => {
+///         assert_eq!(collisions[0].uri.to_string(), "/1");
+///         assert_eq!(collisions[0].to_string(), "GET /1");
+///         assert_eq!(collisions[0].suggested_rank, 0);
+///     }
+///     _ => unreachable!("the error is a collision"),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RouteCollision {
+    /// The route that was added first.
+    pub first: Route,
+    /// The route that collides with `first`.
+    pub second: Route,
+    /// The method of a request that both routes match.
+    pub method: Method,
+    /// The host of a request that both routes match, if a host is needed.
+    pub host: Option<String>,
+    /// The URI of a request that both routes match.
+    pub uri: Origin<'static>,
+    /// The `Content-Type` of a request that both routes match, if one is
+    /// needed.
+    pub content_type: Option<ContentType>,
+    /// A rank for `second` at which it collides with no other route.
+    pub suggested_rank: isize,
+}
+
+impl fmt::Display for RouteCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.uri)?;
+        if let Some(ref host) = self.host {
+            write!(f, " (Host: {})", host)?;
+        }
+
+        if let Some(ref content_type) = self.content_type {
+            write!(f, " (Content-Type: {})", content_type)?;
+        }
+
+        Ok(())
+    }
 }
 
 /// An error that occurs during launch.
 ///
 /// A `LaunchError` is returned by [`launch()`](crate::Rocket::launch()) when
-/// launching an application fails.
+/// launching an application fails or, with a kind of
+/// [`LaunchErrorKind::Shutdown`], once a launched application has shut down
+/// gracefully.
 ///
 /// # Panics
 ///
-/// A value of this type panics if it is dropped without first being inspected.
+/// Unless its kind is [`LaunchErrorKind::Shutdown`], a value of this type
+/// panics if it is dropped without first being inspected.
 /// An _inspection_ occurs when any method is called. For instance, if
 /// `println!("Error: {}", e)` is called, where `e: LaunchError`, the
 /// `Display::fmt` method being called by `println!` results in `e` being marked
@@ -48,7 +126,8 @@ pub enum LaunchErrorKind {
 /// # if false {
 /// let error = rocket::ignite().launch();
 ///
-/// // This line is only reached if launching failed. This "inspects" the error.
+/// // This line is only reached if launching failed or after a shutdown. This
+/// // "inspects" the error.
 /// println!("Launch failed! Error: {}", error);
 ///
 /// // This call to drop (explicit here for demonstration) will do nothing.
@@ -88,7 +167,12 @@ pub struct LaunchError {
 impl LaunchError {
     #[inline(always)]
     pub(crate) fn new(kind: LaunchErrorKind) -> LaunchError {
-        LaunchError { handled: AtomicBool::new(false), kind }
+        let handled = match kind {
+            LaunchErrorKind::Shutdown => true,
+            _ => false
+        };
+
+        LaunchError { handled: AtomicBool::new(handled), kind }
     }
 
     #[inline(always)]
@@ -109,7 +193,7 @@ impl LaunchError {
     /// # if false {
     /// let error = rocket::ignite().launch();
     ///
-    /// // This line is only reached if launch failed.
+    /// // This line is only reached if launch failed or after a shutdown.
     /// let error_kind = error.kind();
     /// # }
     /// ```
@@ -145,7 +229,8 @@ impl fmt::Display for LaunchErrorKind {
             LaunchErrorKind::Io(ref e) => write!(f, "I/O error: {}", e),
             LaunchErrorKind::Collision(_) => write!(f, "route collisions detected"),
             LaunchErrorKind::FailedFairings(_) => write!(f, "a launch fairing failed"),
-            LaunchErrorKind::Unknown(ref e) => write!(f, "unknown error: {}", e)
+            LaunchErrorKind::Unknown(ref e) => write!(f, "unknown error: {}", e),
+            LaunchErrorKind::Shutdown => write!(f, "shut down gracefully"),
         }
     }
 }
@@ -175,7 +260,8 @@ impl std::error::Error for LaunchError {
             LaunchErrorKind::Io(_) => "an I/O error occurred during launch",
             LaunchErrorKind::Collision(_) => "route collisions were detected",
             LaunchErrorKind::FailedFairings(_) => "a launch fairing reported an error",
-            LaunchErrorKind::Unknown(_) => "an unknown error occurred during launch"
+            LaunchErrorKind::Unknown(_) => "an unknown error occurred during launch",
+            LaunchErrorKind::Shutdown => "the application shut down gracefully",
         }
     }
 }
@@ -197,8 +283,12 @@ impl Drop for LaunchError {
             }
             LaunchErrorKind::Collision(ref collisions) => {
                 error!("Rocket failed to launch due to the following routing collisions:");
-                for &(ref a, ref b) in collisions {
-                    info_!("{} {} {}", a, Paint::red("collides with").italic(), b)
+                for collision in collisions {
+                    let (a, b) = (&collision.first, &collision.second);
+                    info_!("{} {} {}", a, Paint::red("collides with").italic(), b);
+                    info_!("{} {}", Paint::default("Both match:").bold(), collision);
+                    info_!("{} rank {} as {}.", Paint::default("Suggestion:").bold(), b,
+                        Paint::default(collision.suggested_rank).bold());
                 }
 
                 info_!("Note: Collisions can usually be resolved by ranking routes.");
@@ -216,6 +306,7 @@ impl Drop for LaunchError {
                 error!("Rocket failed to launch due to an unknown error.");
                 panic!("{}", e);
             }
+            LaunchErrorKind::Shutdown => { /* not an error */ }
         }
     }
 }
@@ -224,7 +315,9 @@ use crate::http::uri;
 use crate::http::ext::IntoOwned;
 use crate::http::route::{Error as SegmentError};
 
-/// Error returned by [`set_uri()`](crate::Route::set_uri()) on invalid URIs.
+/// Error returned by [`set_uri()`](crate::Route::set_uri()) on invalid URIs
+/// and by [`HostPattern::parse()`](crate::HostPattern::parse()) on invalid
+/// host patterns.
 #[derive(Debug)]
 pub enum RouteUriError {
     /// The base (mount point) or route path contains invalid segments.
@@ -233,6 +326,8 @@ pub enum RouteUriError {
     Uri(uri::Error<'static>),
     /// The base (mount point) contains dynamic segments.
     DynamicBase,
+    /// The host pattern is malformed.
+    Host,
 }
 
 impl<'a> From<(&'a str, SegmentError<'a>)> for RouteUriError {
@@ -259,6 +354,38 @@ impl fmt::Display for RouteUriError {
             RouteUriError::Uri(error) => {
                 write!(f, "Malformed URI: {}", error)
             }
+            RouteUriError::Host => {
+                write!(f, "The host pattern is malformed.")
+            }
+        }
+    }
+}
+
+/// Error returned by [`Rocket::uri_for()`](crate::Rocket::uri_for()) and
+/// [`Route::uri_for()`](crate::Route::uri_for()) when a URI to a route can't
+/// be built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UriForError {
+    /// No mounted route has the given name.
+    UnknownRoute(String),
+    /// No value was given for the route's path parameter with the given name.
+    MissingParam(String),
+    /// A value was given for a parameter the route doesn't have.
+    UnknownParam(String),
+}
+
+impl fmt::Display for UriForError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UriForError::UnknownRoute(name) => write!(f, "No route named '{}'.", name),
+            UriForError::MissingParam(name) => {
+                write!(f, "Missing value for path parameter '{}'.", name)
+            }
+            UriForError::UnknownParam(name) => {
+                write!(f, "Route has no parameter named '{}'.", name)
+            }
         }
     }
 }
+
+impl std::error::Error for UriForError {  }
@@ -2,6 +2,7 @@ use std::sync::Mutex;
 
 use crate::{Rocket, Request, Response, Data};
 use crate::fairing::{Fairing, Kind, Info};
+use crate::request::MalformedRequest;
 
 /// A ad-hoc fairing that can be created from a function or closure.
 ///
@@ -11,8 +12,9 @@ use crate::fairing::{Fairing, Kind, Info};
 /// # Usage
 ///
 /// Use the [`on_attach`](#method.on_attach), [`on_launch`](#method.on_launch),
-/// [`on_request`](#method.on_request), or [`on_response`](#method.on_response)
-/// constructors to create an `AdHoc` structure from a function or closure.
+/// [`on_request`](#method.on_request), [`on_response`](#method.on_response),
+/// or [`on_malformed`](#method.on_malformed) constructors to create an `AdHoc`
+/// structure from a function or closure.
 /// Then, simply attach the structure to the `Rocket` instance.
 ///
 /// # Example
@@ -50,6 +52,9 @@ enum AdHocKind {
     /// An ad-hoc **response** fairing. Called when a response is ready to be
     /// sent to a client.
     Response(Box<dyn Fn(&Request<'_>, &mut Response<'_>) + Send + Sync + 'static>),
+    /// An ad-hoc **malformed** fairing. Called when a response to a request
+    /// that could not be parsed is ready to be sent to a client.
+    Malformed(Box<dyn Fn(&MalformedRequest, &mut Response<'_>) + Send + Sync + 'static>),
 }
 
 impl AdHoc {
@@ -128,6 +133,27 @@ impl AdHoc {
     {
         AdHoc { name, kind: AdHocKind::Response(Box::new(f)) }
     }
+
+    /// Constructs an `AdHoc` malformed fairing named `name`. The function `f`
+    /// will be called by Rocket when a response to a request that could not
+    /// be parsed is ready to be sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::fairing::AdHoc;
+    ///
+    /// // A fairing that records malformed requests.
+    /// let fairing = AdHoc::on_malformed("Recorder", |req, resp| {
+    ///     eprintln!("malformed request {}: {}", req, req.error());
+    /// #   let _ = resp;
+    /// });
+    /// ```
+    pub fn on_malformed<F>(name: &'static str, f: F) -> AdHoc
+        where F: Fn(&MalformedRequest, &mut Response<'_>) + Send + Sync + 'static
+    {
+        AdHoc { name, kind: AdHocKind::Malformed(Box::new(f)) }
+    }
 }
 
 impl Fairing for AdHoc {
@@ -137,6 +163,7 @@ impl Fairing for AdHoc {
             AdHocKind::Launch(_) => Kind::Launch,
             AdHocKind::Request(_) => Kind::Request,
             AdHocKind::Response(_) => Kind::Response,
+            AdHocKind::Malformed(_) => Kind::Malformed,
         };
 
         Info { name: self.name, kind }
@@ -171,4 +198,10 @@ impl Fairing for AdHoc {
             callback(request, response)
         }
     }
+
+    fn on_malformed(&self, request: &MalformedRequest, response: &mut Response<'_>) {
+        if let AdHocKind::Malformed(ref callback) = self.kind {
+            callback(request, response)
+        }
+    }
 }
@@ -1,6 +1,8 @@
 use crate::{Rocket, Request, Response, Data};
+use crate::request::MalformedRequest;
 use crate::fairing::{Fairing, Kind};
 use crate::logger::PaintExt;
+use crate::ext::catch_panic;
 
 use yansi::Paint;
 
@@ -12,6 +14,7 @@ pub struct Fairings {
     launch: Vec<usize>,
     request: Vec<usize>,
     response: Vec<usize>,
+    malformed: Vec<usize>,
 }
 
 impl Fairings {
@@ -42,6 +45,7 @@ impl Fairings {
             if kind.is(Kind::Launch) { self.launch.push(index); }
             if kind.is(Kind::Request) { self.request.push(index); }
             if kind.is(Kind::Response) { self.response.push(index); }
+            if kind.is(Kind::Malformed) { self.malformed.push(index); }
         }
     }
 
@@ -58,18 +62,58 @@ impl Fairings {
         }
     }
 
+    /// Runs the request fairings in order. If one panics, the remaining
+    /// fairings are skipped and the name of the panicking fairing is returned.
     #[inline(always)]
-    pub fn handle_request(&self, req: &mut Request<'_>, data: &Data) {
+    pub fn handle_request(&self, req: &mut Request<'_>, data: &Data) -> Result<(), &'static str> {
         for &i in &self.request {
-            self.all_fairings[i].on_request(req, data);
+            let fairing = &self.all_fairings[i];
+            catch_panic(|| fairing.on_request(req, data))
+                .map_err(|msg| Self::panicked("Request", fairing.info().name, &msg))?;
         }
+
+        Ok(())
     }
 
+    /// Runs the response fairings in order. If one panics, the remaining
+    /// fairings are skipped and the name of the panicking fairing is returned.
     #[inline(always)]
-    pub fn handle_response(&self, request: &Request<'_>, response: &mut Response<'_>) {
+    pub fn handle_response(
+        &self,
+        request: &Request<'_>,
+        response: &mut Response<'_>
+    ) -> Result<(), &'static str> {
         for &i in &self.response {
-            self.all_fairings[i].on_response(request, response);
+            let fairing = &self.all_fairings[i];
+            catch_panic(|| fairing.on_response(request, response))
+                .map_err(|msg| Self::panicked("Response", fairing.info().name, &msg))?;
         }
+
+        Ok(())
+    }
+
+    /// Runs the malformed request fairings in order. If one panics, the
+    /// remaining fairings are skipped and the name of the panicking fairing is
+    /// returned.
+    #[inline(always)]
+    pub fn handle_malformed(
+        &self,
+        request: &MalformedRequest,
+        response: &mut Response<'_>
+    ) -> Result<(), &'static str> {
+        for &i in &self.malformed {
+            let fairing = &self.all_fairings[i];
+            catch_panic(|| fairing.on_malformed(request, response))
+                .map_err(|msg| Self::panicked("Malformed", fairing.info().name, &msg))?;
+        }
+
+        Ok(())
+    }
+
+    #[cold]
+    fn panicked(kind: &str, name: &'static str, msg: &str) -> &'static str {
+        error_!("{} fairing '{}' panicked: {}", kind, Paint::default(name).bold(), msg);
+        name
     }
 
     pub fn failures(&self) -> Option<&[&'static str]> {
@@ -98,6 +142,7 @@ impl Fairings {
             self.info_for("launch", &self.launch);
             self.info_for("request", &self.request);
             self.info_for("response", &self.response);
+            self.info_for("malformed", &self.malformed);
         }
     }
 }
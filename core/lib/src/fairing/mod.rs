@@ -48,6 +48,7 @@
 //! abundantly clear, a fairing should not rewrite every request.
 
 use crate::{Rocket, Request, Response, Data};
+use crate::request::MalformedRequest;
 
 mod fairings;
 mod ad_hoc;
@@ -94,12 +95,13 @@ pub use self::info_kind::{Info, Kind};
 ///
 /// ## Fairing Callbacks
 ///
-/// There are four kinds of fairing callbacks: attach, launch, request, and
-/// response. A fairing can request any combination of these callbacks through
-/// the `kind` field of the `Info` structure returned from the `info` method.
-/// Rocket will only invoke the callbacks set in the `kind` field.
+/// There are five kinds of fairing callbacks: attach, launch, request,
+/// response, and malformed. A fairing can request any combination of these
+/// callbacks through the `kind` field of the `Info` structure returned from the
+/// `info` method. Rocket will only invoke the callbacks set in the `kind`
+/// field.
 ///
-/// The four callback kinds are as follows:
+/// The five callback kinds are as follows:
 ///
 ///   * **Attach (`on_attach`)**
 ///
@@ -152,13 +154,22 @@ pub use self::info_kind::{Info, Kind};
 ///     request. Additionally, Rocket will automatically strip the body for
 ///     `HEAD` requests _after_ response fairings have run.
 ///
+///   * **Malformed (`on_malformed`)**
+///
+///     A malformed callback, represented by the [`Fairing::on_malformed()`]
+///     method, is called instead of the request and response callbacks when
+///     Rocket receives a request it cannot parse into a [`Request`]. At this
+///     point, the application's malformed request handler has generated the
+///     would-be final response. A malformed callback can inspect the raw
+///     [`MalformedRequest`] and modify the response at will.
+///
 /// # Implementing
 ///
 /// A `Fairing` implementation has one required method: [`info`]. A `Fairing`
 /// can also implement any of the available callbacks: `on_attach`, `on_launch`,
-/// `on_request`, and `on_response`. A `Fairing` _must_ set the appropriate
-/// callback kind in the `kind` field of the returned `Info` structure from
-/// [`info`] for a callback to actually be called by Rocket.
+/// `on_request`, `on_response`, and `on_malformed`. A `Fairing` _must_ set the
+/// appropriate callback kind in the `kind` field of the returned `Info`
+/// structure from [`info`] for a callback to actually be called by Rocket.
 ///
 /// ## Fairing `Info`
 ///
@@ -409,6 +420,20 @@ pub trait Fairing: Send + Sync + 'static {
     /// The default implementation of this method does nothing.
     #[allow(unused_variables)]
     fn on_response(&self, request: &Request<'_>, response: &mut Response<'_>) {}
+
+    /// The malformed request callback.
+    ///
+    /// This method is called when a request that could not be parsed has been
+    /// answered by the application's malformed request handler if
+    /// `Kind::Malformed` is in the `kind` field of the `Info` structure for
+    /// this fairing. The `&MalformedRequest` parameter is the raw request, and
+    /// the `&mut Response` parameter is the resulting response.
+    ///
+    /// ## Default Implementation
+    ///
+    /// The default implementation of this method does nothing.
+    #[allow(unused_variables)]
+    fn on_malformed(&self, request: &MalformedRequest, response: &mut Response<'_>) {}
 }
 
 impl<T: Fairing> Fairing for std::sync::Arc<T> {
@@ -436,4 +461,9 @@ impl<T: Fairing> Fairing for std::sync::Arc<T> {
     fn on_response(&self, request: &Request<'_>, response: &mut Response<'_>) {
         (self as &T).on_response(request, response)
     }
+
+    #[inline]
+    fn on_malformed(&self, request: &MalformedRequest, response: &mut Response<'_>) {
+        (self as &T).on_malformed(request, response)
+    }
 }
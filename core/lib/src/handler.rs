@@ -1,7 +1,7 @@
 //! Types and traits for request and error handlers and their return values.
 
 use crate::data::Data;
-use crate::request::Request;
+use crate::request::{Request, MalformedRequest};
 use crate::response::{self, Response, Responder};
 use crate::http::Status;
 use crate::outcome;
@@ -181,6 +181,10 @@ impl<F: Clone + Sync + Send + 'static> Handler for F
 /// The type of an error handler.
 pub type ErrorHandler = for<'r> fn(&'r Request<'_>) -> response::Result<'r>;
 
+/// The type of a handler for requests that could not be parsed. See
+/// [`Rocket::on_malformed()`](crate::Rocket::on_malformed()).
+pub type MalformedHandler = fn(&MalformedRequest) -> response::Result<'static>;
+
 impl<'r> Outcome<'r> {
     /// Return the `Outcome` of response to `req` from `responder`.
     ///
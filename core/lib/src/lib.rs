@@ -108,6 +108,7 @@ pub mod data;
 pub mod handler;
 pub mod fairing;
 pub mod error;
+pub mod websocket;
 
 // Reexport of HTTP everything.
 pub mod http {
@@ -125,17 +126,27 @@ mod rocket;
 mod codegen;
 mod catcher;
 mod ext;
+mod shutdown;
+mod listener;
+mod server;
+mod timeout;
+mod proxy;
+mod date;
+#[cfg(unix)] mod unix;
 
 #[doc(inline)] pub use crate::response::Response;
-#[doc(inline)] pub use crate::handler::{Handler, ErrorHandler};
+#[doc(inline)] pub use crate::handler::{Handler, ErrorHandler, MalformedHandler};
 #[doc(hidden)] pub use crate::codegen::{StaticRouteInfo, StaticCatchInfo};
+#[doc(hidden)] pub use crate::codegen::{StaticParamInfo, StaticTypeInfo};
 #[doc(inline)] pub use crate::outcome::Outcome;
 #[doc(inline)] pub use crate::data::Data;
 #[doc(inline)] pub use crate::config::Config;
-pub use crate::router::Route;
+pub use crate::router::{Route, HostPattern, UriParams, Signature, ParamInfo, TypeInfo};
 pub use crate::request::{Request, State};
 pub use crate::catcher::Catcher;
 pub use crate::rocket::Rocket;
+pub use crate::shutdown::{Shutdown, Running};
+pub use crate::listener::Listener;
 
 /// Alias to [`Rocket::ignite()`] Creates a new instance of `Rocket`.
 pub fn ignite() -> Rocket {
@@ -0,0 +1,303 @@
+use std::io;
+use std::net::{SocketAddr, TcpListener};
+use std::path::{Path, PathBuf};
+
+#[cfg(unix)] use std::os::unix::net::UnixListener;
+
+use crate::config::Config;
+
+/// A bound socket that Rocket can listen on.
+///
+/// By default, Rocket binds its own socket using the `address` and `port` in
+/// its configuration. An already bound socket can be used instead by passing a
+/// `Listener` to [`Rocket::listen_on()`](crate::Rocket::listen_on()). A
+/// `Listener` can be created from a [`std::net::TcpListener`] or, on Unix
+/// platforms, from a [`std::os::unix::net::UnixListener`] via `From`, or
+/// retrieved from systemd via [`Listener::systemd()`].
+///
+/// When Rocket launches on a `Listener`, the configured `address` and `port`
+/// are ignored. Instead, they are set to those of the listener's socket. TLS,
+/// if configured, is used on TCP listeners as usual.
+///
+/// # Example
+///
+/// Launch on a socket that was bound beforehand:
+///
+/// ```rust
+/// use std::net::TcpListener;
+///
+/// # if false {
+/// let listener = TcpListener::bind("127.0.0.1:8000").expect("bound socket");
+/// rocket::ignite().listen_on(listener).launch();
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum Listener {
+    /// A TCP socket.
+    Tcp(TcpListener),
+    /// A Unix domain socket.
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+/// The prefix of an address that names a Unix domain socket.
+const UNIX_PREFIX: &str = "unix:";
+
+/// Returns the path to the Unix domain socket named by `address`, if `address`
+/// is of the form `unix:{path}`.
+pub(crate) fn unix_socket(address: &str) -> Option<&Path> {
+    if address.starts_with(UNIX_PREFIX) {
+        Some(Path::new(&address[UNIX_PREFIX.len()..]))
+    } else {
+        None
+    }
+}
+
+/// The first file descriptor passed via systemd socket activation.
+#[cfg(unix)]
+const SD_LISTEN_FDS_START: i32 = 3;
+
+impl Listener {
+    /// Binds a new listener to `address` and `port`. If `address` names a Unix
+    /// domain socket, `port` is ignored and the permissions of the socket file
+    /// are set to `mode`, if any.
+    pub(crate) fn bind(address: &str, port: u16, mode: Option<u32>) -> io::Result<Listener> {
+        #[cfg(unix)] {
+            if let Some(path) = unix_socket(address) {
+                return crate::unix::bind(path, mode).map(Listener::Unix);
+            }
+        }
+
+        let _ = mode;
+        TcpListener::bind((address, port)).map(Listener::Tcp)
+    }
+
+    /// Returns the address this listener is bound to. Unix domain sockets
+    /// report an unspecified address.
+    pub(crate) fn local_addr(&self) -> io::Result<SocketAddr> {
+        match *self {
+            Listener::Tcp(ref listener) => listener.local_addr(),
+            #[cfg(unix)]
+            Listener::Unix(_) => Ok(crate::unix::unspecified_addr()),
+        }
+    }
+
+    /// Returns `true` if this is a Unix domain socket listener.
+    #[inline]
+    pub(crate) fn is_unix(&self) -> bool {
+        match *self {
+            Listener::Tcp(_) => false,
+            #[cfg(unix)]
+            Listener::Unix(_) => true,
+        }
+    }
+
+    /// Returns the path to the socket file of a Unix domain socket listener.
+    pub(crate) fn socket_path(&self) -> Option<PathBuf> {
+        match *self {
+            Listener::Tcp(_) => None,
+            #[cfg(unix)]
+            Listener::Unix(ref listener) => listener.local_addr().ok()
+                .and_then(|addr| addr.as_pathname().map(|path| path.to_path_buf())),
+        }
+    }
+
+    /// Sets the `address` and `port` in `config` to those of this listener.
+    pub(crate) fn configure(&self, config: &mut Config) -> io::Result<()> {
+        match *self {
+            Listener::Tcp(ref listener) => {
+                let addr = listener.local_addr()?;
+                config.address = addr.ip().to_string();
+                config.port = addr.port();
+            }
+            #[cfg(unix)]
+            Listener::Unix(ref listener) => {
+                let addr = listener.local_addr()?;
+                let path = addr.as_pathname().map(|p| p.display().to_string());
+                config.address = format!("unix:{}", path.unwrap_or_default());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the first socket passed to this process via systemd socket
+    /// activation, if any.
+    ///
+    /// Sockets are passed via socket activation when the `LISTEN_PID`
+    /// environment variable is set to the ID of this process and the
+    /// `LISTEN_FDS` environment variable is set to the number of sockets
+    /// passed. If either variable is missing or doesn't refer to this process,
+    /// `Ok(None)` is returned. Otherwise, the variables, along with
+    /// `LISTEN_FDNAMES`, are removed from the environment so that they aren't
+    /// inherited by child processes. Any sockets beyond the first are ignored.
+    ///
+    /// On platforms other than Unix, this method always returns `Ok(None)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the environment variables are malformed or if the
+    /// passed file descriptor is not a listening stream socket, such as a
+    /// datagram socket or a file, or is neither a TCP nor a Unix domain socket.
+    ///
+    /// # Example
+    ///
+    /// Launch on the socket passed by systemd, if any, or bind to the
+    /// configured address otherwise:
+    ///
+    /// ```rust
+    /// use rocket::Listener;
+    ///
+    /// # if false {
+    /// let mut rocket = rocket::ignite();
+    /// if let Some(listener) = Listener::systemd().expect("systemd socket") {
+    ///     rocket = rocket.listen_on(listener);
+    /// }
+    ///
+    /// rocket.launch();
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn systemd() -> io::Result<Option<Listener>> {
+        use std::env;
+        use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+        fn malformed(var: &str) -> io::Error {
+            let msg = format!("malformed systemd socket activation variable {}", var);
+            io::Error::new(io::ErrorKind::InvalidInput, msg)
+        }
+
+        let (pid, fds) = match (env::var("LISTEN_PID"), env::var("LISTEN_FDS")) {
+            (Ok(pid), Ok(fds)) => (pid, fds),
+            _ => return Ok(None),
+        };
+
+        let pid: u32 = pid.trim().parse().map_err(|_| malformed("LISTEN_PID"))?;
+        if pid != std::process::id() {
+            return Ok(None);
+        }
+
+        let fds: u32 = fds.trim().parse().map_err(|_| malformed("LISTEN_FDS"))?;
+        for var in &["LISTEN_PID", "LISTEN_FDS", "LISTEN_FDNAMES"] {
+            env::remove_var(var);
+        }
+
+        match fds {
+            0 => return Ok(None),
+            1 => { /* exactly what we expect */ },
+            n => warn_!("Ignoring {} additional systemd socket(s).", n - 1),
+        }
+
+        // Only wrap listening stream sockets: accepting on anything else fails
+        // on every call or, for a file, on the first read.
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidInput, msg.to_string());
+        if sockopt(SD_LISTEN_FDS_START, libc::SO_TYPE)? != libc::SOCK_STREAM {
+            return Err(invalid("systemd passed a socket that is not a stream socket"));
+        }
+
+        if sockopt(SD_LISTEN_FDS_START, libc::SO_ACCEPTCONN)? == 0 {
+            return Err(invalid("systemd passed a socket that is not listening"));
+        }
+
+        // `getsockname()` only succeeds as a TCP listener for TCP sockets.
+        let tcp = unsafe { TcpListener::from_raw_fd(SD_LISTEN_FDS_START) };
+        if tcp.local_addr().is_ok() {
+            return Ok(Some(Listener::Tcp(tcp)));
+        }
+
+        let unix = unsafe { UnixListener::from_raw_fd(tcp.into_raw_fd()) };
+        if unix.local_addr().is_ok() {
+            return Ok(Some(Listener::Unix(unix)));
+        }
+
+        // Don't close a file descriptor we don't understand.
+        let _ = unix.into_raw_fd();
+        Err(invalid("systemd passed a socket that is neither TCP nor Unix"))
+    }
+
+    /// Always returns `Ok(None)`: socket activation is only supported on Unix
+    /// platforms.
+    #[cfg(not(unix))]
+    pub fn systemd() -> io::Result<Option<Listener>> {
+        Ok(None)
+    }
+}
+
+/// Returns the value of the integer `SOL_SOCKET` option `option` of the socket
+/// `fd`. Fails if `fd` is not a socket.
+#[cfg(unix)]
+fn sockopt(fd: std::os::unix::io::RawFd, option: libc::c_int) -> io::Result<libc::c_int> {
+    let mut value: libc::c_int = 0;
+    let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    let ptr = &mut value as *mut libc::c_int as *mut libc::c_void;
+    match unsafe { libc::getsockopt(fd, libc::SOL_SOCKET, option, ptr, &mut len) } {
+        0 => Ok(value),
+        _ => Err(io::Error::last_os_error()),
+    }
+}
+
+/// The socket files of the Unix domain socket listeners bound during launch.
+///
+/// The files are removed when this value is dropped, so that a launch that
+/// fails after binding some of its listeners leaves no socket files behind,
+/// unless they have been handed off via [`SocketFiles::keep()`].
+#[derive(Default)]
+pub(crate) struct SocketFiles(Vec<PathBuf>);
+
+impl SocketFiles {
+    /// Records the socket file of `listener`, if it has one.
+    pub(crate) fn track(&mut self, listener: &Listener) {
+        self.0.extend(listener.socket_path());
+    }
+
+    /// Returns the recorded socket files without removing them.
+    pub(crate) fn keep(mut self) -> Vec<PathBuf> {
+        std::mem::replace(&mut self.0, vec![])
+    }
+}
+
+impl Drop for SocketFiles {
+    fn drop(&mut self) {
+        for path in &self.0 {
+            if let Err(e) = std::fs::remove_file(path) {
+                warn_!("Failed to remove socket file '{}': {}", path.display(), e);
+            }
+        }
+    }
+}
+
+impl From<TcpListener> for Listener {
+    #[inline(always)]
+    fn from(listener: TcpListener) -> Listener {
+        Listener::Tcp(listener)
+    }
+}
+
+#[cfg(unix)]
+impl From<UnixListener> for Listener {
+    #[inline(always)]
+    fn from(listener: UnixListener) -> Listener {
+        Listener::Unix(listener)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::net::{TcpListener, UdpSocket};
+    use std::os::unix::io::AsRawFd;
+
+    use super::sockopt;
+
+    #[test]
+    fn sockopt_identifies_listening_stream_sockets() {
+        let tcp = TcpListener::bind("127.0.0.1:0").unwrap();
+        assert_eq!(sockopt(tcp.as_raw_fd(), libc::SO_TYPE).unwrap(), libc::SOCK_STREAM);
+        assert_ne!(sockopt(tcp.as_raw_fd(), libc::SO_ACCEPTCONN).unwrap(), 0);
+
+        let udp = UdpSocket::bind("127.0.0.1:0").unwrap();
+        assert_eq!(sockopt(udp.as_raw_fd(), libc::SO_TYPE).unwrap(), libc::SOCK_DGRAM);
+
+        let file = std::fs::File::open("Cargo.toml").unwrap();
+        assert!(sockopt(file.as_raw_fd(), libc::SO_TYPE).is_err());
+    }
+}
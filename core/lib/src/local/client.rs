@@ -329,6 +329,10 @@ impl Client {
     ///
     /// let client = Client::new(rocket::ignite()).expect("valid rocket");
     /// let req = client.req(Method::Get, "/hello");
+    ///
+    /// // WebDAV and extension methods are sent the same way.
+    /// let req = client.req(Method::PropFind, "/collection");
+    /// let req = client.req("PURGE".parse().unwrap(), "/cache");
     /// ```
     #[inline(always)]
     pub fn req<'c, 'u: 'c, U>(&'c self, method: Method, uri: U) -> LocalRequest<'c>
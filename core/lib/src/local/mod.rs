@@ -97,6 +97,8 @@
 
 mod request;
 mod client;
+mod websocket;
 
 pub use self::request::{LocalResponse, LocalRequest};
 pub use self::client::Client;
+pub use self::websocket::LocalWebSocket;
@@ -3,10 +3,13 @@ use std::rc::Rc;
 use std::net::SocketAddr;
 use std::ops::{Deref, DerefMut};
 use std::borrow::Cow;
+use std::thread;
 
 use crate::{Request, Response, Data};
-use crate::http::{Status, Method, Header, Cookie, uri::Origin, ext::IntoOwned};
-use crate::local::Client;
+use crate::request::MalformedRequest;
+use crate::http::{Method, Status, Header, HeaderMap, Cookie, uri::Origin, ext::IntoOwned};
+use crate::local::{Client, LocalWebSocket};
+use crate::websocket::{Socket, Connection, Role};
 
 /// A structure representing a local request as created by [`Client`].
 ///
@@ -389,6 +392,51 @@ impl<'c> LocalRequest<'c> {
         LocalRequest::_dispatch(self.client, req, rc_req, &self.uri, data)
     }
 
+    /// Dispatches the request as a WebSocket handshake and, if the handshake
+    /// is accepted, returns the client's end of the conversation. Otherwise,
+    /// returns the response.
+    ///
+    /// The `Upgrade`, `Connection`, `Sec-WebSocket-Version`, and
+    /// `Sec-WebSocket-Key` headers of the handshake are added unless the
+    /// request already has them. The server's end of the conversation runs on
+    /// a separate thread.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::local::Client;
+    /// use rocket::http::Status;
+    ///
+    /// let client = Client::new(rocket::ignite()).unwrap();
+    /// let response = client.get("/chat").websocket().unwrap_err();
+    /// assert_eq!(response.status(), Status::NotFound);
+    /// ```
+    pub fn websocket(mut self) -> Result<LocalWebSocket<'c>, LocalResponse<'c>> {
+        let handshake = [
+            ("Upgrade", "websocket"),
+            ("Connection", "Upgrade"),
+            ("Sec-WebSocket-Version", "13"),
+            ("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="),
+        ];
+
+        for &(name, value) in &handshake {
+            if !self.inner().headers().contains(name) {
+                self.request_mut().add_header(Header::new(name, value));
+            }
+        }
+
+        let mut response = self.dispatch();
+        let upgrade = match response.take_upgrade() {
+            Some(upgrade) if response.status() == Status::SwitchingProtocols => upgrade,
+            _ => return Err(response)
+        };
+
+        let (client_end, server_end) = Connection::pair();
+        let server = thread::spawn(move || upgrade(server_end));
+        let socket = Socket::new(client_end, Role::Client, u64::max_value());
+        Ok(LocalWebSocket::new(response, socket, server))
+    }
+
     // Performs the actual dispatch.
     fn _dispatch(
         client: &'c Client,
@@ -397,14 +445,24 @@ impl<'c> LocalRequest<'c> {
         uri: &str,
         data: Vec<u8>
     ) -> LocalResponse<'c> {
-        // First, validate the URI, returning an error response (generated from
-        // an error catcher) immediately if it's invalid.
-        if let Ok(uri) = Origin::parse(uri) {
-            request.set_uri(uri.into_owned());
-        } else {
-            error!("Malformed request URI: {}", uri);
-            let res = client.rocket().handle_error(Status::BadRequest, request);
-            return LocalResponse { _request: owned_request, response: res };
+        // First, validate the URI, returning an error response (generated by
+        // the malformed request handler) immediately if it's invalid.
+        match Origin::parse(uri) {
+            Ok(uri) => request.set_uri(uri.into_owned()),
+            Err(e) => {
+                let mut headers = HeaderMap::new();
+                for header in request.headers().iter() {
+                    let (name, value) = (header.name().to_string(), header.value().to_string());
+                    headers.add(Header::new(name, value));
+                }
+
+                let method = request.method().to_string();
+                let malformed = MalformedRequest::new(method, uri.to_string(), headers,
+                                                      e.to_string(), request.remote());
+
+                let res = client.rocket().handle_malformed(&malformed);
+                return LocalResponse { _request: owned_request, response: res };
+            }
         }
 
         // Actually dispatch the request.
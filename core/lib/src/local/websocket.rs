@@ -0,0 +1,101 @@
+use std::{fmt, io, panic, thread};
+use std::ops::{Deref, DerefMut};
+
+use crate::local::LocalResponse;
+use crate::websocket::Socket;
+
+/// A WebSocket conversation with a local request, as started by
+/// [`LocalRequest::websocket()`].
+///
+/// A `LocalWebSocket` is the client's end of the conversation. It dereferences
+/// to a [`Socket`] to send and receive messages; the handshake response is
+/// available via [`response()`](LocalWebSocket::response()). The server's end
+/// runs on a separate thread.
+///
+/// [`LocalRequest::websocket()`]: crate::local::LocalRequest::websocket()
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::local::Client;
+/// use rocket::websocket::{WebSocket, Channel, Message};
+///
+/// #[get("/echo")]
+/// fn echo(ws: WebSocket) -> Channel {
+///     ws.channel(|mut socket| {
+///         while let Some(message) = socket.recv()? {
+///             if let Message::Text(text) = message {
+///                 socket.send(Message::text(text))?;
+///             }
+///         }
+///
+///         Ok(())
+///     })
+/// }
+///
+/// let client = Client::new(rocket::ignite().mount("/", routes![echo])).unwrap();
+/// let mut ws = client.get("/echo").websocket().expect("handshake");
+/// ws.send(Message::text("hi")).unwrap();
+/// assert_eq!(ws.recv().unwrap(), Some(Message::text("hi")));
+/// ws.join().unwrap();
+/// ```
+pub struct LocalWebSocket<'c> {
+    response: LocalResponse<'c>,
+    socket: Socket,
+    server: thread::JoinHandle<()>,
+}
+
+impl<'c> LocalWebSocket<'c> {
+    pub(crate) fn new(
+        response: LocalResponse<'c>,
+        socket: Socket,
+        server: thread::JoinHandle<()>
+    ) -> LocalWebSocket<'c> {
+        LocalWebSocket { response, socket, server }
+    }
+
+    /// Returns the `101 Switching Protocols` response to the handshake.
+    #[inline(always)]
+    pub fn response(&self) -> &LocalResponse<'c> {
+        &self.response
+    }
+
+    /// Closes the conversation, if it isn't already, and waits for the
+    /// server's handler to return. If the handler panicked, the panic is
+    /// propagated to the caller.
+    pub fn join(mut self) -> io::Result<()> {
+        let result = self.socket.close(None);
+        if let Err(payload) = self.server.join() {
+            panic::resume_unwind(payload);
+        }
+
+        result
+    }
+}
+
+impl Deref for LocalWebSocket<'_> {
+    type Target = Socket;
+
+    #[inline(always)]
+    fn deref(&self) -> &Socket {
+        &self.socket
+    }
+}
+
+impl DerefMut for LocalWebSocket<'_> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Socket {
+        &mut self.socket
+    }
+}
+
+impl fmt::Debug for LocalWebSocket<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalWebSocket")
+            .field("response", &self.response)
+            .field("socket", &self.socket)
+            .finish()
+    }
+}
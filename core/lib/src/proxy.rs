@@ -0,0 +1,204 @@
+use std::io::{self, Read};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, Shutdown};
+use std::time::Duration;
+
+use crate::http::hyper;
+use crate::http::hyper::net::{NetworkListener, NetworkStream, SslServer};
+use crate::timeout::TimeoutStream;
+
+/// The signature that begins every version 2 PROXY protocol header.
+const V2_SIGNATURE: &[u8; 12] = b"\r\n\r\n\0\r\nQUIT\n";
+
+/// The maximum length of a version 1 header, including the trailing `CRLF`.
+const V1_MAX_LEN: usize = 107;
+
+/// The default deadline for receiving the header when neither a header nor a
+/// read timeout is configured.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A listener whose connections begin with a PROXY protocol header, as sent by
+/// load balancers such as HAProxy, which is read and removed before the
+/// connection is secured with `ssl` and handed to hyper. The client address in
+/// the header becomes the peer address of the connection.
+///
+/// The header must be read before TLS, so the listener and TLS server are
+/// wrapped together; plain listeners use the `Plain` server. Like
+/// `TimeoutListener`, the streams this listener returns enforce the header
+/// deadline.
+#[derive(Clone)]
+pub(crate) struct ProxyListener<L, S> {
+    listener: L,
+    ssl: S,
+    header_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+}
+
+impl<L: NetworkListener, S: SslServer<L::Stream>> ProxyListener<L, S> {
+    pub(crate) fn new(listener: L, ssl: S, header_timeout: Option<Duration>) -> Self {
+        ProxyListener { listener, ssl, header_timeout, read_timeout: None }
+    }
+
+    /// Reads the PROXY header from `stream`, allowing at most the header
+    /// timeout, then restores the configured read timeout.
+    fn read_header(&self, stream: &mut L::Stream) -> io::Result<Option<SocketAddr>> {
+        let timeout = self.header_timeout.or(self.read_timeout).unwrap_or(DEFAULT_TIMEOUT);
+        stream.set_read_timeout(Some(timeout))?;
+        let header = read_header(stream);
+        stream.set_read_timeout(self.read_timeout)?;
+        header
+    }
+}
+
+impl<L, S> NetworkListener for ProxyListener<L, S>
+    where L: NetworkListener + Clone, S: SslServer<L::Stream> + Clone
+{
+    type Stream = TimeoutStream<S::Stream>;
+
+    fn accept(&mut self) -> Result<Self::Stream, hyper::Error> {
+        let mut stream = self.listener.accept()?;
+        let source = match self.read_header(&mut stream) {
+            Ok(source) => source,
+            Err(e) => {
+                let peer = stream.peer_addr()
+                    .map(|addr| addr.to_string())
+                    .unwrap_or_else(|_| "unknown peer".into());
+
+                warn_!("Invalid PROXY protocol header from {}: {}. Closing connection.", peer, e);
+                let _ = stream.close(Shutdown::Both);
+                return Err(e.into());
+            }
+        };
+
+        let stream = self.ssl.wrap_server(stream)?;
+        let stream = TimeoutStream::new(stream, self.header_timeout, self.read_timeout);
+        Ok(stream.with_peer(source))
+    }
+
+    fn local_addr(&mut self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    fn set_read_timeout(&mut self, duration: Option<Duration>) {
+        self.read_timeout = duration;
+        self.listener.set_read_timeout(duration);
+    }
+
+    fn set_write_timeout(&mut self, duration: Option<Duration>) {
+        self.listener.set_write_timeout(duration);
+    }
+}
+
+/// An `SslServer` that leaves connections unsecured, for use with a
+/// `ProxyListener` on plain listeners.
+#[derive(Clone)]
+pub(crate) struct Plain;
+
+impl<T: NetworkStream + Send + Clone> SslServer<T> for Plain {
+    type Stream = T;
+
+    #[inline(always)]
+    fn wrap_server(&self, stream: T) -> Result<T, hyper::Error> {
+        Ok(stream)
+    }
+}
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Reads a version 1 or version 2 PROXY protocol header from `reader`,
+/// consuming exactly the header's bytes. Returns the source address in the
+/// header or `None` if the header doesn't carry one, as with the `UNKNOWN`
+/// protocol in version 1 and the `LOCAL` command and unsupported address
+/// families in version 2.
+pub(crate) fn read_header<R: Read>(reader: &mut R) -> io::Result<Option<SocketAddr>> {
+    let mut prefix = [0u8; 5];
+    reader.read_exact(&mut prefix)?;
+    if &prefix == b"PROXY" {
+        read_v1(reader)
+    } else if prefix[..] == V2_SIGNATURE[..5] {
+        read_v2(reader)
+    } else {
+        Err(invalid("missing PROXY protocol header"))
+    }
+}
+
+/// Reads the remainder of a version 1 header, following the `PROXY` prefix:
+///
+/// `PROXY TCP4 192.168.0.1 192.168.0.11 56324 443\r\n`
+fn read_v1<R: Read>(reader: &mut R) -> io::Result<Option<SocketAddr>> {
+    // The header's length is unknown, so it's read a byte at a time to avoid
+    // consuming the bytes that follow it.
+    let mut line = Vec::with_capacity(V1_MAX_LEN);
+    line.extend_from_slice(b"PROXY");
+    while !line.ends_with(b"\r\n") {
+        if line.len() >= V1_MAX_LEN {
+            return Err(invalid("PROXY v1 header is too long"));
+        }
+
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        line.push(byte[0]);
+    }
+
+    let line = std::str::from_utf8(&line[..(line.len() - 2)])
+        .map_err(|_| invalid("PROXY v1 header is not valid UTF-8"))?;
+
+    let parts: Vec<_> = line.split(' ').collect();
+    match parts.get(1).cloned() {
+        Some("UNKNOWN") => return Ok(None),
+        Some("TCP4") | Some("TCP6") if parts.len() == 6 => {},
+        _ => return Err(invalid("malformed PROXY v1 header")),
+    }
+
+    let ip: IpAddr = parts[2].parse().map_err(|_| invalid("invalid PROXY v1 source address"))?;
+    let port: u16 = parts[4].parse().map_err(|_| invalid("invalid PROXY v1 source port"))?;
+    match (parts[1], ip) {
+        ("TCP4", IpAddr::V4(_)) | ("TCP6", IpAddr::V6(_)) => Ok(Some(SocketAddr::new(ip, port))),
+        _ => Err(invalid("PROXY v1 address doesn't match protocol")),
+    }
+}
+
+/// Reads the remainder of a version 2 header, following the first five bytes
+/// of the signature. The header is binary: the signature, a version and
+/// command byte, an address family and protocol byte, the big-endian length
+/// of the rest of the header, and the addresses, possibly followed by TLVs.
+fn read_v2<R: Read>(reader: &mut R) -> io::Result<Option<SocketAddr>> {
+    let mut header = [0u8; 11];
+    reader.read_exact(&mut header)?;
+    if header[..7] != V2_SIGNATURE[5..] {
+        return Err(invalid("invalid PROXY v2 signature"));
+    }
+
+    let (version, command, family) = (header[7] >> 4, header[7] & 0x0F, header[8]);
+    let len = u16::from_be_bytes([header[9], header[10]]) as usize;
+    if version != 2 {
+        return Err(invalid("unsupported PROXY protocol version"));
+    }
+
+    let mut body = vec![0u8; len];
+    reader.read_exact(&mut body)?;
+
+    let port = |i: usize| u16::from_be_bytes([body[i], body[i + 1]]);
+    match command {
+        // LOCAL: the connection was made by the proxy itself.
+        0x0 => Ok(None),
+        // PROXY: the connection was relayed on behalf of a client. Any TLVs
+        // following the addresses are ignored.
+        0x1 => match family >> 4 {
+            0x1 if len >= 12 => {
+                let ip = Ipv4Addr::new(body[0], body[1], body[2], body[3]);
+                Ok(Some(SocketAddr::new(ip.into(), port(8))))
+            }
+            0x2 if len >= 36 => {
+                let mut octets = [0u8; 16];
+                octets.copy_from_slice(&body[..16]);
+                Ok(Some(SocketAddr::new(Ipv6Addr::from(octets).into(), port(32))))
+            }
+            0x1 | 0x2 => Err(invalid("truncated PROXY v2 addresses")),
+            // AF_UNSPEC and AF_UNIX carry no usable remote address.
+            _ => Ok(None),
+        },
+        _ => Err(invalid("unsupported PROXY v2 command")),
+    }
+}
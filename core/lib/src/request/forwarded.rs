@@ -0,0 +1,218 @@
+use std::net::{IpAddr, SocketAddr};
+
+use crate::request::{Request, FromRequest, Outcome};
+use crate::outcome::Outcome::*;
+
+/// The address, scheme, and host of the client that originated a request, as
+/// resolved through trusted proxies.
+///
+/// A `ClientInfo` is retrieved via [`Request::client_info()`] or used directly
+/// as a request guard, which always succeeds. The individual values are also
+/// available via [`Request::client_ip()`], [`Request::client_scheme()`], and
+/// [`Request::client_host()`]; see [`Request::client_ip()`] for how they are
+/// resolved.
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::ClientInfo;
+///
+/// #[get("/")]
+/// fn index(client: ClientInfo) -> String {
+///     format!("{} requested {}://{}", client.ip().unwrap(),
+///         client.scheme(), client.host().unwrap_or("unknown"))
+/// }
+/// # fn main() {  }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInfo {
+    ip: Option<IpAddr>,
+    scheme: String,
+    host: Option<String>,
+}
+
+impl ClientInfo {
+    /// The IP address of the client, if it is known.
+    #[inline(always)]
+    pub fn ip(&self) -> Option<IpAddr> {
+        self.ip
+    }
+
+    /// The scheme of the client's request, in lowercase. Usually `http` or
+    /// `https`.
+    #[inline(always)]
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// The host the client requested, if it is known.
+    #[inline(always)]
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_ref().map(|s| s.as_str())
+    }
+}
+
+impl FromRequest<'_, '_> for ClientInfo {
+    type Error = std::convert::Infallible;
+
+    fn from_request(request: &Request<'_>) -> Outcome<Self, Self::Error> {
+        Success(request.client_info())
+    }
+}
+
+impl FromRequest<'_, '_> for IpAddr {
+    type Error = std::convert::Infallible;
+
+    fn from_request(request: &Request<'_>) -> Outcome<Self, Self::Error> {
+        match request.client_ip() {
+            Some(ip) => Success(ip),
+            None => Forward(())
+        }
+    }
+}
+
+/// A single hop as reported by a proxy: the address of the client the proxy
+/// received the request from, and the scheme and host of that request.
+#[derive(Debug, Default)]
+struct Hop {
+    addr: Option<IpAddr>,
+    proto: Option<String>,
+    host: Option<String>,
+}
+
+/// Resolves the client of `request`. Starting at the remote address, hops
+/// reported by forwarding headers are followed, nearest first, for as long as
+/// the address being considered is that of a trusted proxy.
+pub(crate) fn resolve(request: &Request<'_>) -> ClientInfo {
+    let config = request.state.config;
+    let scheme = if request.state.secure { "https" } else { "http" };
+    let mut client = ClientInfo {
+        ip: request.remote().map(|addr| addr.ip()),
+        scheme: scheme.into(),
+        host: request.headers().get_one("Host").map(|host| host.into()),
+    };
+
+    let trusted = |ip: Option<IpAddr>| ip.map_or(false, |ip| config.is_trusted_proxy(ip));
+    if !trusted(client.ip) {
+        return client;
+    }
+
+    for hop in hops(request).into_iter().rev() {
+        // The proxy that reported this hop is trusted, so the hop is too.
+        let addr = hop.addr;
+        client.ip = addr.or(client.ip);
+        if let Some(proto) = hop.proto {
+            client.scheme = proto.to_ascii_lowercase();
+        }
+
+        if let Some(host) = hop.host {
+            client.host = Some(host);
+        }
+
+        // An unknown or obfuscated address ends the chain: there's no way to
+        // know whether the hop before it is trustworthy.
+        if addr.is_none() || !trusted(addr) {
+            break;
+        }
+    }
+
+    client
+}
+
+/// Returns the hops reported in the forwarding headers of `request`, farthest
+/// first. The standard `Forwarded` header takes precedence over the
+/// `X-Forwarded-*` headers, which take precedence over `X-Real-IP`.
+fn hops(request: &Request<'_>) -> Vec<Hop> {
+    let headers = request.headers();
+    if headers.contains("Forwarded") {
+        return headers.get("Forwarded")
+            .flat_map(split_list)
+            .map(parse_forwarded_element)
+            .collect();
+    }
+
+    if headers.contains("X-Forwarded-For") {
+        let list = |name: &str| headers.get(name).flat_map(split_list).collect::<Vec<_>>();
+        let for_ = list("X-Forwarded-For");
+        let proto = list("X-Forwarded-Proto");
+        let host = list("X-Forwarded-Host");
+
+        // Proxies append to each list in turn, so the lists align at the end.
+        let nth_from_end = |list: &[&str], n: usize| -> Option<String> {
+            list.len().checked_sub(for_.len() - n).and_then(|i| list.get(i))
+                .map(|value| value.to_string())
+        };
+
+        return for_.iter().enumerate()
+            .map(|(i, addr)| Hop {
+                addr: parse_node(addr),
+                proto: nth_from_end(&proto, i),
+                host: nth_from_end(&host, i),
+            })
+            .collect();
+    }
+
+    match headers.get_one("X-Real-IP") {
+        Some(ip) => vec![Hop { addr: parse_node(ip), ..Hop::default() }],
+        None => vec![]
+    }
+}
+
+/// Splits a comma-separated header value into its trimmed, non-empty items.
+/// Commas in quoted strings don't separate items.
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    let mut quoted = false;
+    value.split(move |c| {
+            if c == '"' { quoted = !quoted; }
+            c == ',' && !quoted
+        })
+        .map(|item| item.trim())
+        .filter(|item| !item.is_empty())
+}
+
+/// Parses a `forwarded-element` of an RFC 7239 `Forwarded` header: a list of
+/// `;` separated `key=value` pairs. Unknown and malformed pairs are ignored.
+fn parse_forwarded_element(element: &str) -> Hop {
+    let mut hop = Hop::default();
+    for pair in element.split(';') {
+        let mut kv = pair.splitn(2, '=');
+        let (key, value) = match (kv.next(), kv.next()) {
+            (Some(key), Some(value)) => (key.trim(), unquote(value.trim())),
+            _ => continue,
+        };
+
+        if key.eq_ignore_ascii_case("for") {
+            hop.addr = parse_node(value);
+        } else if key.eq_ignore_ascii_case("proto") {
+            hop.proto = Some(value.into());
+        } else if key.eq_ignore_ascii_case("host") {
+            hop.host = Some(value.into());
+        }
+    }
+
+    hop
+}
+
+/// Removes the quotes around `value` if it is a quoted string.
+fn unquote(value: &str) -> &str {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        &value[1..(value.len() - 1)]
+    } else {
+        value
+    }
+}
+
+/// Parses a node: an IP address, optionally with a port. IPv6 addresses may be
+/// enclosed in brackets, as required by `Forwarded` when a port is present.
+/// Returns `None` for `unknown` and obfuscated identifiers.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim();
+    node.parse::<IpAddr>().ok()
+        .or_else(|| node.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+        .or_else(|| {
+            let end = node.find(']')?;
+            node.get(1..end).filter(|_| node.starts_with('['))?.parse().ok()
+        })
+}
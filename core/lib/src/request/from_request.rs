@@ -2,11 +2,13 @@ use std::fmt::Debug;
 use std::net::SocketAddr;
 
 use crate::router::Route;
-use crate::request::Request;
+use crate::request::{Request, GuardError};
 use crate::outcome::{self, IntoOutcome};
 use crate::outcome::Outcome::*;
 
 use crate::http::{Status, ContentType, Accept, Method, Cookies, uri::Origin};
+use crate::http::headers::{TypedHeader, Authorization, CacheControl, Vary, IfMatch, IfNoneMatch};
+use crate::http::headers::{Link, RetryAfter, ContentDisposition, Host, UserAgent};
 
 /// Type alias for the `Outcome` of a `FromRequest` conversion.
 pub type Outcome<S, E> = outcome::Outcome<S, (Status, E), ()>;
@@ -138,6 +140,48 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 ///
 ///     _This implementation always returns successfully._
 ///
+///   * **IpAddr**
+///
+///     Extracts the IP address of the client that originated the request, as
+///     resolved through trusted proxies by [`Request::client_ip()`]. If the
+///     address is not known, the request is forwarded.
+///
+///   * **ClientInfo**
+///
+///     Extracts the IP address, scheme, and host of the client that originated
+///     the request as a [`ClientInfo`](crate::request::ClientInfo).
+///
+///     _This implementation always returns successfully._
+///
+///   * **LastEventId**
+///
+///     Extracts the value of the `Last-Event-ID` header, sent by clients
+///     reconnecting to an [`EventStream`](crate::response::EventStream), as a
+///     [`LastEventId`](crate::request::LastEventId). If the header is not
+///     present, the request is forwarded.
+///
+///   * **WebSocket**
+///
+///     Validates a request to upgrade the connection to a WebSocket as a
+///     [`WebSocket`](crate::websocket::WebSocket). If the request doesn't ask
+///     for an upgrade, it is forwarded. If the handshake is invalid, the guard
+///     fails with status `400` and a
+///     [`HandshakeError`](crate::websocket::HandshakeError).
+///
+///   * **RouteUris**
+///
+///     Builds URIs to mounted routes by name, including their mount points, as
+///     a [`RouteUris`](crate::request::RouteUris).
+///
+///     _This implementation always returns successfully._
+///
+///   * **Authorization**, **CacheControl**, **Host**, **UserAgent**, ...
+///
+///     Each of the typed headers in [`http::headers`](crate::http::headers) is
+///     parsed from the request's headers of the same name. If the header is
+///     not present, the request is forwarded. If it is malformed, the guard
+///     fails with status `400` and the parse error.
+///
 ///   * **Option&lt;T>** _where_ **T: FromRequest**
 ///
 ///     The type `T` is derived from the incoming request using `T`'s
@@ -345,6 +389,9 @@ impl<S, E> IntoOutcome<S, (Status, E), ()> for Result<S, E> {
 
 pub trait FromRequest<'a, 'r>: Sized {
     /// The associated error to be returned if derivation fails.
+    ///
+    /// When the guard fails in a route, the error is kept in the request as a
+    /// [`GuardError`](crate::request::GuardError) for the error catcher.
     type Error: Debug;
 
     /// Derives an instance of `Self` from the incoming request metadata.
@@ -354,6 +401,19 @@ pub trait FromRequest<'a, 'r>: Sized {
     /// `Forward` is returned to indicate that the request should be forwarded
     /// to other matching routes, if any.
     fn from_request(request: &'a Request<'r>) -> Outcome<Self, Self::Error>;
+
+    /// Converts the error of a failed guard into the [`GuardError`] that is
+    /// kept in the request for the error catcher.
+    ///
+    /// The default implementation keeps only the `Debug` form of `error` as
+    /// its type need not be `'static`. Implementations whose error type is
+    /// `'static` can override this method to return
+    /// [`GuardError::new(error)`](GuardError::new()), allowing catchers to
+    /// retrieve the error's value.
+    #[inline(always)]
+    fn guard_error(error: Self::Error) -> GuardError {
+        GuardError::from_debug(&error)
+    }
 }
 
 impl FromRequest<'_, '_> for Method {
@@ -424,6 +484,32 @@ impl FromRequest<'_, '_> for SocketAddr {
     }
 }
 
+macro_rules! impl_with_typed_header {
+    ($($T:ident),+) => ($(
+        impl FromRequest<'_, '_> for $T {
+            type Error = String;
+
+            fn from_request(request: &Request<'_>) -> Outcome<Self, Self::Error> {
+                match $T::from_headers(request.headers()) {
+                    Some(Ok(value)) => Success(value),
+                    Some(Err(e)) => Failure((Status::BadRequest, e)),
+                    None => Forward(())
+                }
+            }
+
+            #[inline(always)]
+            fn guard_error(error: Self::Error) -> GuardError {
+                GuardError::new(error)
+            }
+        }
+    )+)
+}
+
+impl_with_typed_header!(
+    Authorization, CacheControl, Vary, IfMatch, IfNoneMatch, Link, RetryAfter,
+    ContentDisposition, Host, UserAgent
+);
+
 impl<'a, 'r, T: FromRequest<'a, 'r>> FromRequest<'a, 'r> for Result<T, T::Error> {
     type Error = std::convert::Infallible;
 
@@ -0,0 +1,244 @@
+use std::any::{Any, type_name};
+use std::fmt;
+use std::rc::Rc;
+
+/// The error value of a request or data guard that failed.
+///
+/// When a request guard ([`FromRequest`](crate::request::FromRequest)) or data
+/// guard ([`FromData`](crate::data::FromData)) in a route's signature returns a
+/// `Failure((status, error))`, Rocket keeps `error` in the request as a
+/// `GuardError` before invoking the catcher for `status`. The catcher
+/// retrieves it with [`Request::guard_error()`](crate::Request::guard_error()).
+///
+/// A `GuardError` provides the `Debug` form of the error or, if the error has
+/// none, the name of its type, both through its own `Debug` and `Display`
+/// implementations. A guard whose error type is `'static` can also keep the
+/// value itself, which can then be retrieved with
+/// [`GuardError::downcast_ref()`], by overriding its `guard_error()` method to
+/// return [`GuardError::new()`]. See
+/// [`FromRequest::guard_error()`](crate::request::FromRequest::guard_error())
+/// and [`FromData::guard_error()`](crate::data::FromData::guard_error()).
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// #
+/// use rocket::Outcome;
+/// use rocket::http::Status;
+/// use rocket::request::{self, Request, FromRequest, GuardError};
+///
+/// struct ApiKey(String);
+///
+/// #[derive(Debug)]
+/// enum ApiKeyError {
+///     Missing,
+///     Invalid,
+/// }
+///
+/// impl FromRequest<'_, '_> for ApiKey {
+///     type Error = ApiKeyError;
+///
+///     fn from_request(request: &Request<'_>) -> request::Outcome<Self, Self::Error> {
+///         match request.headers().get_one("x-api-key") {
+///             None => Outcome::Failure((Status::BadRequest, ApiKeyError::Missing)),
+///             Some(key) if key == "valid" => Outcome::Success(ApiKey(key.into())),
+///             Some(_) => Outcome::Failure((Status::BadRequest, ApiKeyError::Invalid)),
+///         }
+///     }
+///
+///     fn guard_error(error: ApiKeyError) -> GuardError {
+///         GuardError::new(error)
+///     }
+/// }
+///
+/// #[get("/sensitive")]
+/// fn sensitive(key: ApiKey) -> &'static str {
+/// #   let _key = key;
+///     "Sensitive data."
+/// }
+///
+/// #[catch(400)]
+/// fn bad_request(req: &Request) -> String {
+///     let error = match req.guard_error() {
+///         Some(error) => error,
+///         None => return "Bad request.".into(),
+///     };
+///
+///     match error.downcast_ref::<ApiKeyError>() {
+///         Some(ApiKeyError::Missing) => "Missing API key.".into(),
+///         Some(ApiKeyError::Invalid) => "Invalid API key.".into(),
+///         None => format!("Bad request: {}", error),
+///     }
+/// }
+///
+/// # fn main() { }
+/// ```
+#[derive(Clone)]
+pub struct GuardError {
+    param: Option<&'static str>,
+    type_name: &'static str,
+    debug: Option<Rc<str>>,
+    value: Option<Rc<dyn Any>>,
+}
+
+impl GuardError {
+    /// Creates a `GuardError` that keeps `error` so that it can be retrieved
+    /// with [`GuardError::downcast_ref()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::GuardError;
+    ///
+    /// let error = GuardError::new(String::from("bad header"));
+    /// assert_eq!(error.downcast_ref::<String>().unwrap(), "bad header");
+    /// assert_eq!(error.to_string(), "\"bad header\"");
+    /// ```
+    pub fn new<E: fmt::Debug + 'static>(error: E) -> GuardError {
+        GuardError {
+            param: None,
+            type_name: type_name::<E>(),
+            debug: Some(format!("{:?}", error).into()),
+            value: Some(Rc::new(error)),
+        }
+    }
+
+    /// Creates a `GuardError` that keeps only the `Debug` form of `error`.
+    /// This is used when `error` may borrow from the request's data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::GuardError;
+    ///
+    /// let string = String::from("bad header");
+    /// let error = GuardError::from_debug(&string.as_str());
+    /// assert!(error.downcast_ref::<&str>().is_none());
+    /// assert_eq!(error.to_string(), "\"bad header\"");
+    /// ```
+    pub fn from_debug<E: fmt::Debug + ?Sized>(error: &E) -> GuardError {
+        GuardError {
+            param: None,
+            type_name: type_name::<E>(),
+            debug: Some(format!("{:?}", error).into()),
+            value: None,
+        }
+    }
+
+    /// Creates a `GuardError` that keeps only the name of the error's type,
+    /// `E`. This is used when the error doesn't implement `Debug`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::GuardError;
+    ///
+    /// struct Opaque;
+    ///
+    /// let error = GuardError::from_type::<Opaque>();
+    /// assert!(error.downcast_ref::<Opaque>().is_none());
+    /// assert!(error.to_string().ends_with("Opaque"));
+    /// ```
+    pub fn from_type<E: ?Sized>() -> GuardError {
+        GuardError {
+            param: None,
+            type_name: type_name::<E>(),
+            debug: None,
+            value: None,
+        }
+    }
+
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn with_param(mut self, name: &'static str) -> GuardError {
+        self.param = Some(name);
+        self
+    }
+
+    /// Returns the name of the route parameter whose guard failed, if the
+    /// error was recorded by a route generated by Rocket's codegen.
+    #[inline(always)]
+    pub fn param(&self) -> Option<&'static str> {
+        self.param
+    }
+
+    /// Returns the name of the error's type, as reported by
+    /// [`std::any::type_name()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::GuardError;
+    ///
+    /// let error = GuardError::new(10usize);
+    /// assert_eq!(error.type_name(), "usize");
+    /// ```
+    #[inline(always)]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns `true` if the error's value was kept and is of type `E`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::GuardError;
+    ///
+    /// let error = GuardError::new(10usize);
+    /// assert!(error.is::<usize>());
+    /// assert!(!error.is::<u8>());
+    /// ```
+    #[inline]
+    pub fn is<E: 'static>(&self) -> bool {
+        self.downcast_ref::<E>().is_some()
+    }
+
+    /// Returns a reference to the error's value if it was kept and is of type
+    /// `E`. Otherwise returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::GuardError;
+    ///
+    /// let error = GuardError::new(10usize);
+    /// assert_eq!(error.downcast_ref::<usize>(), Some(&10));
+    /// assert_eq!(error.downcast_ref::<String>(), None);
+    /// ```
+    #[inline]
+    pub fn downcast_ref<E: 'static>(&self) -> Option<&E> {
+        self.value.as_ref().and_then(|value| value.downcast_ref())
+    }
+
+    /// The `Debug` form of the error or, failing that, the name of its type.
+    #[inline]
+    fn error(&self) -> &str {
+        self.debug.as_ref().map_or(self.type_name, |debug| debug)
+    }
+}
+
+impl fmt::Debug for GuardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GuardError")
+            .field("param", &self.param)
+            .field("type_name", &self.type_name)
+            .field("error", &format_args!("{}", self.error()))
+            .finish()
+    }
+}
+
+/// Writes the `Debug` form of the error or, if it has none, the name of its
+/// type, prefixed by the name of the parameter whose guard failed if it is
+/// known.
+impl fmt::Display for GuardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(param) = self.param {
+            write!(f, "{}: ", param)?;
+        }
+
+        write!(f, "{}", self.error())
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+use std::net::SocketAddr;
+
+use yansi::Paint;
+
+use crate::http::HeaderMap;
+
+/// A request that Rocket received but could not parse into a
+/// [`Request`](crate::Request).
+///
+/// A request is malformed when its method isn't one that Rocket understands or
+/// when its target isn't a valid origin URI. Such a request can't be routed.
+/// Instead, it is passed to the application's malformed request handler,
+/// registered via [`Rocket::on_malformed()`](crate::Rocket::on_malformed()),
+/// which produces the response, and to the `on_malformed` callback of any
+/// attached [fairings](crate::fairing::Fairing::on_malformed()).
+///
+/// A `MalformedRequest` exposes the request exactly as it was received: the raw
+/// method, the raw request target, the headers, and a description of why the
+/// request couldn't be parsed.
+///
+/// # Example
+///
+/// A malformed request handler that reports the parse error to the client:
+///
+/// ```rust
+/// use std::io::Cursor;
+///
+/// use rocket::request::MalformedRequest;
+/// use rocket::response::{self, Response};
+/// use rocket::http::Status;
+///
+/// fn malformed(request: &MalformedRequest) -> response::Result<'static> {
+///     let body = format!("malformed request: {}", request.error());
+///     Response::build()
+///         .status(Status::BadRequest)
+///         .sized_body(Cursor::new(body))
+///         .ok()
+/// }
+///
+/// let rocket = rocket::ignite().on_malformed(malformed);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MalformedRequest {
+    method: String,
+    target: String,
+    headers: HeaderMap<'static>,
+    error: String,
+    remote: Option<SocketAddr>,
+}
+
+impl MalformedRequest {
+    #[inline]
+    pub(crate) fn new(
+        method: String,
+        target: String,
+        headers: HeaderMap<'static>,
+        error: String,
+        remote: Option<SocketAddr>,
+    ) -> MalformedRequest {
+        MalformedRequest { method, target, headers, error, remote }
+    }
+
+    /// Returns the method of the request as it was received.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::MalformedRequest;
+    ///
+    /// fn is_get(request: &MalformedRequest) -> bool {
+    ///     request.method() == "GET"
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Returns the target of the request, the part of the request line between
+    /// the method and the HTTP version, as it was received.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::MalformedRequest;
+    ///
+    /// fn is_asterisk(request: &MalformedRequest) -> bool {
+    ///     request.target() == "*"
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Returns the headers of the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::MalformedRequest;
+    ///
+    /// fn user_agent(request: &MalformedRequest) -> Option<&str> {
+    ///     request.headers().get_one("User-Agent")
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn headers(&self) -> &HeaderMap<'static> {
+        &self.headers
+    }
+
+    /// Returns a description of why the request could not be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::MalformedRequest;
+    ///
+    /// fn log(request: &MalformedRequest) {
+    ///     eprintln!("{}: {}", request, request.error());
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn error(&self) -> &str {
+        &self.error
+    }
+
+    /// Returns the address of the remote client, if it is known. As with
+    /// [`Request::remote()`](crate::Request::remote()), the address is unknown
+    /// for requests received over a Unix domain socket.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::request::MalformedRequest;
+    ///
+    /// fn is_local(request: &MalformedRequest) -> bool {
+    ///     request.remote().map_or(false, |addr| addr.ip().is_loopback())
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn remote(&self) -> Option<SocketAddr> {
+        self.remote
+    }
+}
+
+impl fmt::Display for MalformedRequest {
+    /// Pretty prints the raw method and target of the request. This is
+    /// primarily used by Rocket's logging infrastructure.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", Paint::red(&self.method), Paint::red(&self.target))
+    }
+}
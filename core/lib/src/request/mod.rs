@@ -6,6 +6,10 @@ mod form;
 mod from_request;
 mod state;
 mod query;
+mod malformed;
+mod forwarded;
+mod guard_error;
+mod route_uris;
 
 #[cfg(test)]
 mod tests;
@@ -20,6 +24,14 @@ pub use self::form::{Form, LenientForm, FormItems, FormItem};
 pub use self::form::{FormError, FormParseError, FormDataError};
 pub use self::state::State;
 pub use self::query::{Query, FromQuery};
+pub use self::malformed::MalformedRequest;
+pub use self::forwarded::ClientInfo;
+pub use self::guard_error::GuardError;
+pub use self::route_uris::RouteUris;
 
 #[doc(inline)]
 pub use crate::response::flash::FlashMessage;
+#[doc(inline)]
+pub use crate::response::event_stream::LastEventId;
+#[doc(inline)]
+pub use crate::response::conditional::Preconditions;
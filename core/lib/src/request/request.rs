@@ -8,14 +8,18 @@ use yansi::Paint;
 use state::{Container, Storage};
 
 use crate::request::{FromParam, FromSegments, FromRequest, Outcome};
-use crate::request::{FromFormValue, FormItems, FormItem};
+use crate::request::{FromFormValue, FormItems, FormItem, MalformedRequest, ClientInfo};
+use crate::request::GuardError;
+use crate::request::forwarded;
 
 use crate::rocket::Rocket;
-use crate::router::Route;
+use crate::shutdown::Shutdown;
+use crate::router::{Route, Router};
 use crate::config::{Config, Limits};
 use crate::http::{hyper, uri::{Origin, Segments}};
-use crate::http::{Method, Header, HeaderMap, Cookies};
+use crate::http::{Method, Header, HeaderMap, Cookies, Status};
 use crate::http::{RawStr, ContentType, Accept, MediaType};
+use crate::http::headers::Host;
 use crate::http::private::{Indexed, SmallVec, CookieJar};
 
 type Indices = (usize, usize);
@@ -39,13 +43,19 @@ pub struct Request<'r> {
 pub(crate) struct RequestState<'r> {
     pub config: &'r Config,
     pub managed: &'r Container,
+    pub shutdown: &'r Shutdown,
+    pub router: &'r Router,
     pub path_segments: SmallVec<[Indices; 12]>,
     pub query_items: Option<SmallVec<[IndexedFormItem; 6]>>,
     pub route: Cell<Option<&'r Route>>,
     pub cookies: RefCell<CookieJar>,
+    pub secure: bool,
     pub accept: Storage<Option<Accept>>,
     pub content_type: Storage<Option<ContentType>>,
+    pub host: Storage<Option<Host>>,
     pub cache: Rc<Container>,
+    pub guard_error: RefCell<Option<GuardError>>,
+    pub error_status: Cell<Option<Status>>,
 }
 
 #[derive(Clone)]
@@ -73,11 +83,17 @@ impl<'r> Request<'r> {
                 query_items: None,
                 config: &rocket.config,
                 managed: &rocket.state,
+                shutdown: &rocket.shutdown,
+                router: &rocket.router,
                 route: Cell::new(None),
                 cookies: RefCell::new(CookieJar::new()),
+                secure: false,
                 accept: Storage::new(),
                 content_type: Storage::new(),
+                host: Storage::new(),
                 cache: Rc::new(Container::new()),
+                guard_error: RefCell::new(None),
+                error_status: Cell::new(None),
             }
         };
 
@@ -162,15 +178,19 @@ impl<'r> Request<'r> {
 
     /// Returns the address of the remote connection that initiated this
     /// request if the address is known. If the address is not known, `None` is
-    /// returned.
+    /// returned. This is always the case for requests received over a Unix
+    /// domain socket, unless a client address is received via the PROXY
+    /// protocol.
+    ///
+    /// When the PROXY protocol is enabled for the listener that received the
+    /// request, this is the client address reported by the load balancer in
+    /// the connection's PROXY header.
     ///
     /// Because it is common for proxies to forward connections for clients, the
-    /// remote address may contain information about the proxy instead of the
-    /// client. For this reason, proxies typically set the "X-Real-IP" header
-    /// with the client's true IP. To extract this IP from the request, use the
-    /// [`real_ip()`] or [`client_ip()`] methods.
+    /// remote address may be that of a proxy instead of the client. Proxies
+    /// report the client's address in forwarding headers. To retrieve the
+    /// client's address through trusted proxies, use [`client_ip()`].
     ///
-    /// [`real_ip()`]: #method.real_ip
     /// [`client_ip()`]: #method.client_ip
     ///
     /// # Example
@@ -212,21 +232,28 @@ impl<'r> Request<'r> {
     }
 
     /// Returns the IP address in the "X-Real-IP" header of the request if such
-    /// a header exists and contains a valid IP address.
+    /// a header exists, contains a valid IP address, and the request was
+    /// received from a trusted proxy, as configured via `trusted_proxies`.
+    /// Because any client can set the header, it is ignored otherwise.
     ///
     /// # Example
     ///
     /// ```rust
     /// # use rocket::Request;
     /// # use rocket::http::{Header, Method};
-    /// # use std::net::{SocketAddr, IpAddr, Ipv4Addr};
-    ///
     /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// // By default, no proxy is trusted, so the header is ignored.
+    /// request.set_remote("127.0.0.1:8000".parse().unwrap());
     /// request.add_header(Header::new("X-Real-IP", "8.8.8.8"));
-    /// assert_eq!(request.real_ip(), Some("8.8.8.8".parse().unwrap()));
+    /// assert!(request.real_ip().is_none());
     /// # });
     /// ```
     pub fn real_ip(&self) -> Option<IpAddr> {
+        let remote = self.remote()?.ip();
+        if !self.state.config.is_trusted_proxy(remote) {
+            return None;
+        }
+
         self.headers()
             .get_one("X-Real-IP")
             .and_then(|ip| {
@@ -236,36 +263,150 @@ impl<'r> Request<'r> {
             })
     }
 
-    /// Attempts to return the client's IP address by first inspecting the
-    /// "X-Real-IP" header and then using the remote connection's IP address.
+    /// Returns the IP address of the client that originated this request, if
+    /// it is known.
+    ///
+    /// Unless the remote address is that of a trusted proxy, as configured via
+    /// `trusted_proxies`, the client is the remote connection and its IP
+    /// address is returned. Otherwise, the client is found by walking the chain
+    /// of forwarding headers from the nearest proxy outwards for as long as
+    /// each hop is a trusted proxy. The chain is read from the `Forwarded`
+    /// header (RFC 7239) if present, otherwise from the `X-Forwarded-For`
+    /// header, and otherwise from the `X-Real-IP` header. The walk stops at the
+    /// first untrusted address, which is returned, or at an unknown or
+    /// obfuscated address, in which case the last known address is returned.
+    ///
+    /// The scheme and host requested by the client are resolved the same way
+    /// and are available via [`client_scheme()`] and [`client_host()`].
     ///
-    /// If the "X-Real-IP" header exists and contains a valid IP address, that
-    /// address is returned. Otherwise, if the address of the remote connection
-    /// is known, that address is returned. Otherwise, `None` is returned.
+    /// [`client_scheme()`]: #method.client_scheme
+    /// [`client_host()`]: #method.client_host
     ///
     /// # Example
     ///
     /// ```rust
     /// # use rocket::Request;
     /// # use rocket::http::{Header, Method};
-    /// # use std::net::{SocketAddr, IpAddr, Ipv4Addr};
-    ///
     /// # Request::example(Method::Get, "/uri", |mut request| {
-    /// // starting without an "X-Real-IP" header or remote addresss
+    /// // starting without a remote address
     /// assert!(request.client_ip().is_none());
     ///
     /// // add a remote address; this is done by Rocket automatically
     /// request.set_remote("127.0.0.1:8000".parse().unwrap());
     /// assert_eq!(request.client_ip(), Some("127.0.0.1".parse().unwrap()));
     ///
-    /// // now with an X-Real-IP header
-    /// request.add_header(Header::new("X-Real-IP", "8.8.8.8"));
-    /// assert_eq!(request.client_ip(), Some("8.8.8.8".parse().unwrap()));
+    /// // the remote isn't a trusted proxy, so forwarding headers are ignored
+    /// request.add_header(Header::new("X-Forwarded-For", "8.8.8.8"));
+    /// assert_eq!(request.client_ip(), Some("127.0.0.1".parse().unwrap()));
     /// # });
     /// ```
+    ///
+    /// With a trusted proxy:
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    /// use rocket::http::Header;
+    /// use rocket::local::Client;
+    ///
+    /// let config = Config::build(Environment::Development)
+    ///     .trusted_proxies(vec!["10.0.0.0/8".parse().unwrap()])
+    ///     .unwrap();
+    ///
+    /// let client = Client::new(rocket::custom(config)).unwrap();
+    /// let request = client.get("/")
+    ///     .remote("10.0.0.1:8000".parse().unwrap())
+    ///     .header(Header::new("X-Forwarded-For", "1.2.3.4, 8.8.8.8, 10.0.0.2"));
+    ///
+    /// // `10.0.0.2` is trusted, `8.8.8.8` isn't: it's the client.
+    /// assert_eq!(request.inner().client_ip(), Some("8.8.8.8".parse().unwrap()));
+    /// ```
     #[inline]
     pub fn client_ip(&self) -> Option<IpAddr> {
-        self.real_ip().or_else(|| self.remote().map(|r| r.ip()))
+        self.client_info().ip()
+    }
+
+    /// Returns the scheme, in lowercase, of the request as made by the client
+    /// that originated it. This is the scheme of the remote connection, either
+    /// `http` or `https`, unless it is overridden by forwarding headers of
+    /// trusted proxies. See [`client_ip()`](#method.client_ip) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    /// use rocket::http::Header;
+    /// use rocket::local::Client;
+    ///
+    /// let config = Config::build(Environment::Development)
+    ///     .trusted_proxies(vec!["127.0.0.1".parse().unwrap()])
+    ///     .unwrap();
+    ///
+    /// let client = Client::new(rocket::custom(config)).unwrap();
+    /// let request = client.get("/");
+    /// assert_eq!(request.inner().client_scheme(), "http");
+    ///
+    /// let request = client.get("/")
+    ///     .remote("127.0.0.1:8000".parse().unwrap())
+    ///     .header(Header::new("Forwarded", "for=8.8.8.8;proto=https"));
+    ///
+    /// assert_eq!(request.inner().client_scheme(), "https");
+    /// ```
+    #[inline]
+    pub fn client_scheme(&self) -> String {
+        self.client_info().scheme().to_string()
+    }
+
+    /// Returns the host requested by the client that originated this request,
+    /// if it is known. This is the value of the `Host` header unless it is
+    /// overridden by forwarding headers of trusted proxies. See
+    /// [`client_ip()`](#method.client_ip) for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    /// use rocket::http::Header;
+    /// use rocket::local::Client;
+    ///
+    /// let config = Config::build(Environment::Development)
+    ///     .trusted_proxies(vec!["127.0.0.1".parse().unwrap()])
+    ///     .unwrap();
+    ///
+    /// let client = Client::new(rocket::custom(config)).unwrap();
+    /// let request = client.get("/")
+    ///     .remote("127.0.0.1:8000".parse().unwrap())
+    ///     .header(Header::new("Host", "internal:8000"))
+    ///     .header(Header::new("X-Forwarded-For", "8.8.8.8"))
+    ///     .header(Header::new("X-Forwarded-Host", "example.com"));
+    ///
+    /// assert_eq!(request.inner().client_host(), Some("example.com".into()));
+    /// ```
+    #[inline]
+    pub fn client_host(&self) -> Option<String> {
+        self.client_info().host().map(|host| host.to_string())
+    }
+
+    /// Returns the IP address, scheme, and host of the client that originated
+    /// this request. See [`client_ip()`](#method.client_ip) for how these are
+    /// resolved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::{Header, Method};
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// request.set_remote("127.0.0.1:8000".parse().unwrap());
+    /// request.add_header(Header::new("Host", "rocket.rs"));
+    ///
+    /// let client = request.client_info();
+    /// assert_eq!(client.ip(), Some("127.0.0.1".parse().unwrap()));
+    /// assert_eq!(client.scheme(), "http");
+    /// assert_eq!(client.host(), Some("rocket.rs"));
+    /// # });
+    /// ```
+    pub fn client_info(&self) -> ClientInfo {
+        forwarded::resolve(self)
     }
 
     /// Returns a wrapped borrow to the cookies in `self`.
@@ -424,6 +565,32 @@ impl<'r> Request<'r> {
         }).as_ref()
     }
 
+    /// Returns the Host header of `self`. If the header is not present or is
+    /// malformed, returns `None`. The Host header is cached after the first
+    /// call to this function. As a result, subsequent calls will always return
+    /// the same value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use rocket::Request;
+    /// # use rocket::http::Method;
+    /// use rocket::http::headers::Host;
+    ///
+    /// # Request::example(Method::Get, "/uri", |mut request| {
+    /// request.add_header(Host::new("api.rocket.rs", Some(8000)));
+    /// let host = request.host().unwrap();
+    /// assert_eq!(host.host(), "api.rocket.rs");
+    /// assert_eq!(host.port(), Some(8000));
+    /// # });
+    /// ```
+    #[inline(always)]
+    pub fn host(&self) -> Option<&Host> {
+        self.state.host.get_or_set(|| {
+            self.headers().get_one("Host").and_then(|v| v.parse().ok())
+        }).as_ref()
+    }
+
     /// Returns the media type "format" of the request.
     ///
     /// The "format" of a request is either the Content-Type, if the request
@@ -561,6 +728,55 @@ impl<'r> Request<'r> {
             })
     }
 
+    /// Returns the error of the request or data guard that failed while
+    /// routing `self`, if any. The error is recorded by routes generated with
+    /// Rocket's code generation, so this method is typically used by error
+    /// catchers. See [`GuardError`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    ///
+    /// #[catch(400)]
+    /// fn bad_request(req: &Request) -> String {
+    ///     match req.guard_error() {
+    ///         Some(error) => format!("Bad request: {}", error),
+    ///         None => "Bad request.".into(),
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn guard_error(&self) -> Option<GuardError> {
+        self.state.guard_error.borrow().clone()
+    }
+
+    /// Returns the status of the error that an error catcher is handling for
+    /// `self`, or `None` if no error catcher has been invoked. This is
+    /// typically used by default catchers, which handle errors of any status.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    ///
+    /// #[catch(default)]
+    /// fn default(req: &Request) -> String {
+    ///     match req.error_status() {
+    ///         Some(status) => format!("Error: {}", status),
+    ///         None => "Error.".into(),
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    pub fn error_status(&self) -> Option<Status> {
+        self.state.error_status.get()
+    }
+
     /// Retrieves and parses into `T` the 0-indexed `n`th segment from the
     /// request. Returns `None` if `n` is greater than the number of segments.
     /// Returns `Some(Err(T::Error))` if the parameter type `T` failed to be
@@ -695,6 +911,12 @@ impl<'r> Request<'r> {
 // They _are not_ part of the stable API.
 #[doc(hidden)]
 impl<'r> Request<'r> {
+    // Records the error of a failing guard for the error catcher.
+    #[inline(always)]
+    pub fn set_guard_error(&self, error: GuardError) {
+        self.state.guard_error.replace(Some(error));
+    }
+
     // Only used by doc-tests! Needs to be `pub` because doc-test are external.
     pub fn example<F: Fn(&mut Request<'_>)>(method: Method, uri: &str, f: F) {
         let rocket = Rocket::custom(Config::development());
@@ -737,6 +959,15 @@ impl<'r> Request<'r> {
             .map(|(i, _)| Segments(&self.uri.path()[i..]) )
     }
 
+    /// Get the `n`th label, 0-indexed, of the request's host, as a string, if
+    /// it exists. Used by codegen for parameters in a route's host.
+    #[inline]
+    pub fn raw_host_label(&self, n: usize) -> Option<&RawStr> {
+        self.host()
+            .and_then(|host| host.host().split('.').nth(n))
+            .map(|label| label.into())
+    }
+
     // Returns an iterator over the raw segments of the path URI. Does not take
     // into account the current route. This is used during routing.
     #[inline]
@@ -780,32 +1011,33 @@ impl<'r> Request<'r> {
         self.method.set(method);
     }
 
-    /// Convert from Hyper types into a Rocket Request.
+    /// Convert from Hyper types into a Rocket Request. If the method or the
+    /// target of the request can't be parsed, the request is returned as a
+    /// `MalformedRequest` instead.
     pub(crate) fn from_hyp(
         rocket: &'r Rocket,
-        h_method: hyper::Method,
+        h_method: &hyper::Method,
         h_headers: hyper::header::Headers,
-        h_uri: hyper::RequestUri,
-        h_addr: SocketAddr,
-    ) -> Result<Request<'r>, String> {
-        // Get a copy of the URI for later use.
-        let uri = match h_uri {
-            hyper::RequestUri::AbsolutePath(s) => s,
-            _ => return Err(format!("Bad URI: {}", h_uri)),
-        };
-
-        // Ensure that the method is known. TODO: Allow made-up methods?
-        let method = match Method::from_hyp(&h_method) {
-            Some(method) => method,
-            None => return Err(format!("Invalid method: {}", h_method))
+        h_uri: &hyper::RequestUri,
+        h_addr: Option<SocketAddr>,
+        secure: bool,
+    ) -> Result<Request<'r>, MalformedRequest> {
+        let (method, uri) = match Request::parse_hyp_line(h_method, h_uri) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                let headers = Request::headers_from_hyp(&h_headers);
+                let (method, target) = (h_method.to_string(), h_uri.to_string());
+                return Err(MalformedRequest::new(method, target, headers, error, h_addr));
+            }
         };
 
-        // We need to re-parse the URI since we don't trust Hyper... :(
-        let uri = Origin::parse_owned(uri).map_err(|e| e.to_string())?;
-
         // Construct the request object.
         let mut request = Request::new(rocket, method, uri);
-        request.set_remote(h_addr);
+        if let Some(addr) = h_addr {
+            request.set_remote(addr);
+        }
+
+        request.state.secure = secure;
 
         // Set the request cookies, if they exist.
         if let Some(cookie_headers) = h_headers.get_raw("Cookie") {
@@ -827,18 +1059,46 @@ impl<'r> Request<'r> {
         }
 
         // Set the rest of the headers.
+        request.headers = Request::headers_from_hyp(&h_headers);
+        Ok(request)
+    }
+
+    /// Parses the method and target of a Hyper request.
+    fn parse_hyp_line(
+        h_method: &hyper::Method,
+        h_uri: &hyper::RequestUri,
+    ) -> Result<(Method, Origin<'static>), String> {
+        // Get a copy of the URI for later use.
+        let uri = match *h_uri {
+            hyper::RequestUri::AbsolutePath(ref s) => s.clone(),
+            _ => return Err(format!("Bad URI: {}", h_uri)),
+        };
+
+        // Ensure that the method is known.
+        let method = match Method::from_hyp(h_method) {
+            Some(method) => method,
+            None => return Err(format!("Invalid method: {}", h_method))
+        };
+
+        // We need to re-parse the URI since we don't trust Hyper... :(
+        let uri = Origin::parse_owned(uri).map_err(|e| e.to_string())?;
+        Ok((method, uri))
+    }
+
+    /// Converts Hyper's headers into Rocket's.
+    fn headers_from_hyp(h_headers: &hyper::header::Headers) -> HeaderMap<'static> {
+        let mut headers = HeaderMap::new();
         for hyp in h_headers.iter() {
             if let Some(header_values) = h_headers.get_raw(hyp.name()) {
                 for value in header_values {
                     // This is not totally correct since values needn't be UTF8.
                     let value_str = String::from_utf8_lossy(value).into_owned();
-                    let header = Header::new(hyp.name().to_string(), value_str);
-                    request.add_header(header);
+                    headers.add(Header::new(hyp.name().to_string(), value_str));
                 }
             }
         }
 
-        Ok(request)
+        headers
     }
 }
 
@@ -0,0 +1,67 @@
+use crate::request::{self, FromRequest, Request};
+use crate::router::{Router, UriParams};
+use crate::error::UriForError;
+use crate::outcome::Outcome;
+use crate::http::uri::Origin;
+
+/// Request guard to build URIs to mounted routes by name.
+///
+/// `RouteUris` provides [`Rocket::uri_for()`](crate::Rocket::uri_for()) to
+/// request handlers. Unlike a URI built with `uri!`, which only includes a
+/// mount point if it is repeated in the macro invocation, a URI built with
+/// `RouteUris` always includes the mount point the route was actually mounted
+/// at.
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::UriParams;
+/// use rocket::request::RouteUris;
+/// use rocket::response::Redirect;
+///
+/// #[get("/<id>")]
+/// fn user(id: usize) -> String {
+///     format!("user {}", id)
+/// }
+///
+/// #[get("/me")]
+/// fn me(uris: RouteUris) -> Option<Redirect> {
+///     let uri = uris.uri_for("user", &UriParams::new().param("id", &1)).ok()?;
+///     Some(Redirect::to(uri))
+/// }
+///
+/// fn main() {
+/// # if false { // We don't actually want to launch the server in an example.
+///     rocket::ignite().mount("/users", routes![user, me])
+/// #       .launch();
+/// # }
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct RouteUris<'r> {
+    router: &'r Router,
+}
+
+impl RouteUris<'_> {
+    /// Returns the URI to the mounted route named `name`. See
+    /// [`Rocket::uri_for()`](crate::Rocket::uri_for()) for details.
+    #[inline]
+    pub fn uri_for(
+        &self,
+        name: &str,
+        params: &UriParams<'_>
+    ) -> Result<Origin<'static>, UriForError> {
+        self.router.uri_for(name, params)
+    }
+}
+
+impl<'r> FromRequest<'_, 'r> for RouteUris<'r> {
+    type Error = std::convert::Infallible;
+
+    #[inline(always)]
+    fn from_request(request: &Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(RouteUris { router: request.state.router })
+    }
+}
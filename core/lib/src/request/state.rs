@@ -1,7 +1,7 @@
 use std::ops::Deref;
 
 use crate::Rocket;
-use crate::request::{self, FromRequest, Request};
+use crate::request::{self, FromRequest, Request, GuardError};
 use crate::outcome::Outcome;
 use crate::http::Status;
 
@@ -178,6 +178,11 @@ impl<'r, T: Send + Sync + 'static> FromRequest<'_, 'r> for State<'r, T> {
             }
         }
     }
+
+    #[inline(always)]
+    fn guard_error(error: Self::Error) -> GuardError {
+        GuardError::new(error)
+    }
 }
 
 impl<T: Send + Sync + 'static> Deref for State<'_, T> {
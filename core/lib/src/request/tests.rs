@@ -9,7 +9,7 @@ macro_rules! assert_headers {
         // Set up the parameters to the hyper request object.
         let h_method = hyper::Method::Get;
         let h_uri = hyper::RequestUri::AbsolutePath("/test".to_string());
-        let h_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8000);
+        let h_addr = Some(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8000));
         let mut h_headers = hyper::header::Headers::new();
 
         // Add all of the passed in headers to the request.
@@ -22,7 +22,7 @@ macro_rules! assert_headers {
         // Dispatch the request and check that the headers are what we expect.
         let config = Config::development();
         let r = Rocket::custom(config);
-        let req = Request::from_hyp(&r, h_method, h_headers, h_uri, h_addr).unwrap();
+        let req = Request::from_hyp(&r, &h_method, h_headers, &h_uri, h_addr, false).unwrap();
         let actual_headers = req.headers();
         for (key, values) in expected.iter() {
             let actual: Vec<_> = actual_headers.get(key).collect();
@@ -0,0 +1,287 @@
+use crate::request::{self, Request, FromRequest};
+use crate::response::Response;
+use crate::outcome::Outcome::*;
+use crate::http::{Method, Status, HeaderMap, EntityTag, HttpDate};
+
+/// The validators of the current representation of a resource.
+#[derive(Clone, Copy)]
+struct Validators<'v> {
+    etag: Option<&'v EntityTag>,
+    last_modified: Option<HttpDate>,
+    /// Whether the resource has a current representation at all.
+    exists: bool,
+}
+
+/// Returns whether any of the entity-tags in the `name` headers matches
+/// `current`, or `None` if there are no such headers. Tags are compared with
+/// the strong comparison function if `strong`, else with the weak one.
+fn any_matches(
+    headers: &HeaderMap<'_>,
+    name: &str,
+    current: Validators<'_>,
+    strong: bool
+) -> Option<bool> {
+    let mut values = headers.get(name).peekable();
+    values.peek()?;
+
+    for value in values {
+        if value.trim() == "*" {
+            return Some(current.exists);
+        }
+
+        // A malformed list matches nothing: `If-Match` fails, conservatively,
+        // while `If-None-Match` lets the request proceed.
+        let tags = EntityTag::parse_list(value).unwrap_or_default();
+        let matched = current.etag.map_or(false, |etag| tags.iter().any(|tag| match strong {
+            true => tag.strong_eq(etag),
+            false => tag.weak_eq(etag),
+        }));
+
+        if matched {
+            return Some(true);
+        }
+    }
+
+    Some(false)
+}
+
+/// Returns the valid date in the `name` header, if any.
+fn date(headers: &HeaderMap<'_>, name: &str) -> Option<HttpDate> {
+    headers.get_one(name).and_then(|date| date.parse().ok())
+}
+
+/// Evaluates the preconditions in `headers` against `current` in the order
+/// prescribed by RFC 7232 section 6. Returns the status the request should be
+/// answered with instead of the response, if any: `304 Not Modified` for safe
+/// requests whose representation is unchanged, or `412 Precondition Failed`.
+fn evaluate(headers: &HeaderMap<'_>, safe: bool, current: Validators<'_>) -> Option<Status> {
+    match any_matches(headers, "If-Match", current, true) {
+        Some(false) => return Some(Status::PreconditionFailed),
+        Some(true) => { /* the next precondition applies */ },
+        None => if let Some(date) = date(headers, "If-Unmodified-Since") {
+            if current.last_modified.map_or(false, |modified| modified > date) {
+                return Some(Status::PreconditionFailed);
+            }
+        }
+    }
+
+    match any_matches(headers, "If-None-Match", current, false) {
+        Some(true) if safe => Some(Status::NotModified),
+        Some(true) => Some(Status::PreconditionFailed),
+        Some(false) => None,
+        None if safe => {
+            let since = date(headers, "If-Modified-Since")?;
+            match current.last_modified? <= since {
+                true => Some(Status::NotModified),
+                false => None
+            }
+        }
+        None => None,
+    }
+}
+
+#[inline]
+fn is_safe(method: Method) -> bool {
+    method == Method::Get || method == Method::Head
+}
+
+/// Evaluates the preconditions of a `GET` or `HEAD` `request` against the
+/// validators of `response`: its `ETag` and `Last-Modified` headers. Returns
+/// `Some(Status::NotModified)` if the client's copy is current, and
+/// `Some(Status::PreconditionFailed)` if an `If-Match` or
+/// `If-Unmodified-Since` precondition fails.
+///
+/// As RFC 7232 requires, preconditions are ignored unless the response is
+/// successful.
+pub(crate) fn evaluate_response(
+    request: &Request<'_>,
+    response: &Response<'_>
+) -> Option<Status> {
+    if !is_safe(request.method()) || !response.status().class().is_success() {
+        return None;
+    }
+
+    let etag = response.etag();
+    let last_modified = response.last_modified();
+    let current = Validators { etag: etag.as_ref(), last_modified, exists: true };
+    evaluate(request.headers(), true, current)
+}
+
+/// Turns `response` into a `304 Not Modified` response. Its headers, including
+/// its validators and any caching directives, are kept, save for those that
+/// describe the omitted body.
+pub(crate) fn not_modified(response: &mut Response<'_>) {
+    response.set_status(Status::NotModified);
+    response.remove_header("Content-Type");
+    response.remove_header("Content-Length");
+    response.take_body();
+}
+
+/// A request guard for evaluating a request's preconditions before a handler
+/// acts on it.
+///
+/// Rocket answers conditional `GET` and `HEAD` requests on its own, by
+/// comparing the `If-None-Match`, `If-Modified-Since`, `If-Match`, and
+/// `If-Unmodified-Since` headers against the validators of the response: see
+/// [`Response::set_etag()`] and [`Response::set_last_modified()`]. A handler
+/// for an unsafe method like `PUT` or `DELETE`, however, must evaluate the
+/// `If-Match` and `If-Unmodified-Since` preconditions _before_ it changes any
+/// state. `Preconditions` does just that with [`Preconditions::check()`],
+/// given the validators of the resource's current representation.
+///
+/// This guard always succeeds.
+///
+/// # Example
+///
+/// A handler that only replaces a document if the client has seen its latest
+/// version, answering with `412 Precondition Failed` otherwise:
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::{Status, EntityTag};
+/// use rocket::request::Preconditions;
+///
+/// # fn current_version() -> u64 { 1 }
+/// # fn store(_: String) -> u64 { 2 }
+/// #[put("/document", data = "<document>")]
+/// fn update(pre: Preconditions, document: String) -> Result<String, Status> {
+///     let etag = EntityTag::strong(current_version().to_string());
+///     pre.check(Some(&etag), None)?;
+///
+///     Ok(format!("stored version {}", store(document)))
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Preconditions<'a, 'r: 'a> {
+    request: &'a Request<'r>,
+}
+
+impl Preconditions<'_, '_> {
+    /// Evaluates the request's preconditions against the validators of the
+    /// current representation of the resource: its entity-tag `etag` and its
+    /// modification date `last_modified`. Pass `None` for both if the resource
+    /// doesn't currently exist.
+    ///
+    /// Returns `Err(Status::PreconditionFailed)` if the request must not be
+    /// carried out: if an `If-Match` or `If-Unmodified-Since` precondition
+    /// fails or, for methods other than `GET` and `HEAD`, if an
+    /// `If-None-Match` precondition holds. Otherwise, returns `Ok(())`.
+    ///
+    /// # Example
+    ///
+    /// A handler that only creates a resource if it doesn't exist yet when the
+    /// client asks for that with `If-None-Match: *`:
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::http::{Status, EntityTag};
+    /// use rocket::request::Preconditions;
+    ///
+    /// # fn version(_: &str) -> Option<u64> { None }
+    /// #[put("/<name>", data = "<contents>")]
+    /// fn put(pre: Preconditions, name: String, contents: String) -> Result<(), Status> {
+    ///     let etag = version(&name).map(|v| EntityTag::strong(v.to_string()));
+    ///     pre.check(etag.as_ref(), None)?;
+    ///     # let _ = contents;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn check(
+        &self,
+        etag: Option<&EntityTag>,
+        last_modified: Option<HttpDate>
+    ) -> Result<(), Status> {
+        let exists = etag.is_some() || last_modified.is_some();
+        let current = Validators { etag, last_modified, exists };
+
+        // `304 Not Modified` responses to safe requests are left to Rocket.
+        match evaluate(self.request.headers(), is_safe(self.request.method()), current) {
+            Some(Status::PreconditionFailed) => Err(Status::PreconditionFailed),
+            _ => Ok(())
+        }
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for Preconditions<'a, 'r> {
+    type Error = std::convert::Infallible;
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        Success(Preconditions { request })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evaluate, Validators};
+    use crate::http::{Status, Header, HeaderMap, EntityTag, HttpDate};
+
+    const NOT_MODIFIED: Option<Status> = Some(Status::NotModified);
+    const FAILED: Option<Status> = Some(Status::PreconditionFailed);
+
+    const EARLIER: &str = "Sun, 06 Nov 1994 08:49:36 GMT";
+    const MODIFIED: &str = "Sun, 06 Nov 1994 08:49:37 GMT";
+    const LATER: &str = "Sun, 06 Nov 1994 08:49:38 GMT";
+
+    fn eval(
+        headers: &[(&'static str, &'static str)],
+        safe: bool,
+        etag: Option<&EntityTag>
+    ) -> Option<Status> {
+        let mut map = HeaderMap::new();
+        for &(name, value) in headers {
+            map.add(Header::new(name, value));
+        }
+
+        let last_modified = Some(MODIFIED.parse::<HttpDate>().unwrap());
+        evaluate(&map, safe, Validators { etag, last_modified, exists: true })
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let etag = EntityTag::strong("v1");
+        let etag = Some(&etag);
+        assert_eq!(eval(&[("If-None-Match", "\"v1\"")], true, etag), NOT_MODIFIED);
+        assert_eq!(eval(&[("If-None-Match", "W/\"v1\"")], true, etag), NOT_MODIFIED);
+        assert_eq!(eval(&[("If-None-Match", "\"v0\", \"v1\"")], true, etag), NOT_MODIFIED);
+        assert_eq!(eval(&[("If-None-Match", "*")], true, etag), NOT_MODIFIED);
+        assert_eq!(eval(&[("If-None-Match", "\"v0\"")], true, etag), None);
+        assert_eq!(eval(&[("If-None-Match", "\"v1\"")], false, etag), FAILED);
+
+        // `If-None-Match` takes precedence over `If-Modified-Since`.
+        let headers = [("If-None-Match", "\"v0\""), ("If-Modified-Since", LATER)];
+        assert_eq!(eval(&headers, true, etag), None);
+    }
+
+    #[test]
+    fn test_if_modified_since() {
+        assert_eq!(eval(&[("If-Modified-Since", MODIFIED)], true, None), NOT_MODIFIED);
+        assert_eq!(eval(&[("If-Modified-Since", LATER)], true, None), NOT_MODIFIED);
+        assert_eq!(eval(&[("If-Modified-Since", EARLIER)], true, None), None);
+        assert_eq!(eval(&[("If-Modified-Since", "yesterday")], true, None), None);
+        assert_eq!(eval(&[("If-Modified-Since", LATER)], false, None), None);
+    }
+
+    #[test]
+    fn test_if_match() {
+        let etag = EntityTag::strong("v1");
+        let etag = Some(&etag);
+        assert_eq!(eval(&[("If-Match", "\"v1\"")], false, etag), None);
+        assert_eq!(eval(&[("If-Match", "*")], false, etag), None);
+        assert_eq!(eval(&[("If-Match", "W/\"v1\"")], false, etag), FAILED);
+        assert_eq!(eval(&[("If-Match", "\"v0\"")], false, etag), FAILED);
+        assert_eq!(eval(&[("If-Match", "\"v1\"")], false, None), FAILED);
+
+        // `If-Match` takes precedence over `If-Unmodified-Since`.
+        let headers = [("If-Match", "\"v1\""), ("If-Unmodified-Since", EARLIER)];
+        assert_eq!(eval(&headers, false, etag), None);
+    }
+
+    #[test]
+    fn test_if_unmodified_since() {
+        assert_eq!(eval(&[("If-Unmodified-Since", MODIFIED)], false, None), None);
+        assert_eq!(eval(&[("If-Unmodified-Since", EARLIER)], false, None), FAILED);
+        assert_eq!(eval(&[("If-Unmodified-Since", EARLIER)], true, None), FAILED);
+    }
+}
@@ -0,0 +1,407 @@
+use std::io::{self, Read};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::request::{self, Request, FromRequest};
+use crate::response::{Response, Responder};
+use crate::outcome::Outcome::*;
+use crate::http::{Status, ContentType};
+
+/// A single Server-Sent Event, as sent by an [`EventStream`].
+///
+/// Each event consists of optional `data`, an optional `event` type, an
+/// optional `id`, and an optional `retry` interval. Events are usually created
+/// with [`Event::data()`] and then adorned with the `with_` methods, but the
+/// fields can also be set directly.
+///
+/// Since each field is sent on a line of its own, line breaks in `id` and
+/// `event` are removed when an event is written. Line breaks in `data` are
+/// preserved: each line of `data` is sent as a separate `data` field, which
+/// clients join back together.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rocket::response::Event;
+///
+/// let event = Event::data("Rocket has launched!")
+///     .with_event("launch")
+///     .with_id("1")
+///     .with_retry(Duration::from_secs(5));
+///
+/// assert_eq!(event.to_string(),
+///     "retry: 5000\nid: 1\nevent: launch\ndata: Rocket has launched!\n\n");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Event {
+    /// The event's ID. Clients send the ID of the last event they received in
+    /// the `Last-Event-ID` header when reconnecting.
+    pub id: Option<String>,
+    /// The event's type. Clients dispatch events without a type as `message`.
+    pub event: Option<String>,
+    /// The event's data.
+    pub data: Option<String>,
+    /// How long clients should wait before reconnecting, if the connection is
+    /// lost.
+    pub retry: Option<Duration>,
+}
+
+impl Event {
+    /// Creates a new event with `data` and no other fields.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Event;
+    ///
+    /// let event = Event::data("hello");
+    /// assert_eq!(event.data, Some("hello".to_string()));
+    /// assert_eq!(event.event, None);
+    /// ```
+    #[inline]
+    pub fn data<S: Into<String>>(data: S) -> Event {
+        Event { data: Some(data.into()), ..Event::default() }
+    }
+
+    /// Sets the ID of `self` to `id`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Event;
+    ///
+    /// let event = Event::data("hello").with_id("42");
+    /// assert_eq!(event.id, Some("42".to_string()));
+    /// ```
+    #[inline]
+    pub fn with_id<S: Into<String>>(mut self, id: S) -> Event {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the event type of `self` to `event`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::Event;
+    ///
+    /// let event = Event::data("hello").with_event("greeting");
+    /// assert_eq!(event.event, Some("greeting".to_string()));
+    /// ```
+    #[inline]
+    pub fn with_event<S: Into<String>>(mut self, event: S) -> Event {
+        self.event = Some(event.into());
+        self
+    }
+
+    /// Sets the reconnection interval of `self` to `retry`. Clients only
+    /// honor whole milliseconds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use rocket::response::Event;
+    ///
+    /// let event = Event::default().with_retry(Duration::from_secs(10));
+    /// assert_eq!(event.retry, Some(Duration::from_secs(10)));
+    /// ```
+    #[inline]
+    pub fn with_retry(mut self, retry: Duration) -> Event {
+        self.retry = Some(retry);
+        self
+    }
+}
+
+/// Writes `value` without line breaks.
+struct OneLine<'a>(&'a str);
+
+impl fmt::Display for OneLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in self.0.split(|c| c == '\r' || c == '\n') {
+            f.write_str(part)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats `self` in the `text/event-stream` format, including the blank line
+/// that terminates the event.
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(retry) = self.retry {
+            writeln!(f, "retry: {}", retry.as_millis())?;
+        }
+
+        if let Some(ref id) = self.id {
+            writeln!(f, "id: {}", OneLine(id))?;
+        }
+
+        if let Some(ref event) = self.event {
+            writeln!(f, "event: {}", OneLine(event))?;
+        }
+
+        if let Some(ref data) = self.data {
+            // `\r\n`, `\r`, and `\n` all end a line.
+            for line in data.split('\n') {
+                let line = match line.ends_with('\r') {
+                    true => &line[..(line.len() - 1)],
+                    false => line,
+                };
+
+                for line in line.split('\r') {
+                    writeln!(f, "data: {}", line)?;
+                }
+            }
+        }
+
+        writeln!(f)
+    }
+}
+
+/// Where the events of an `EventStream` come from.
+enum Source<'r> {
+    Iter(Box<dyn Iterator<Item = Event> + 'r>),
+    Channel(Receiver<Event>),
+}
+
+/// A Server-Sent Events stream: a streamed `text/event-stream` response that
+/// sends each [`Event`] to the client as soon as it is produced.
+///
+/// Events come from an iterator, via [`EventStream::new()`], or from the
+/// receiving end of a channel, via [`EventStream::from()`]. The stream ends
+/// when the iterator is exhausted or when every sender of the channel has been
+/// dropped.
+///
+/// # Keep-Alive
+///
+/// Proxies and clients may close connections that are idle for too long. To
+/// prevent this, an `EventStream` can send a comment, which clients ignore,
+/// whenever no event has been sent for a given interval; see
+/// [`EventStream::heartbeat()`]. Only channel-backed streams can send
+/// comments: an iterator can't be interrupted while it waits for its next
+/// event.
+///
+/// # Last-Event-ID
+///
+/// When a client reconnects, it sends the `id` of the last event it received
+/// in the `Last-Event-ID` header, which is available to handlers via the
+/// [`LastEventId`](crate::request::LastEventId) request guard.
+///
+/// # Example
+///
+/// Send an event to every client each second from a background thread:
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use std::sync::mpsc;
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// use rocket::request::LastEventId;
+/// use rocket::response::{Event, EventStream};
+///
+/// #[get("/ticks")]
+/// fn ticks(last: Option<LastEventId<'_>>) -> EventStream<'static> {
+///     let mut n: usize = last.and_then(|id| id.parse().ok()).unwrap_or(0);
+///     let (tx, rx) = mpsc::channel();
+///     thread::spawn(move || loop {
+///         n += 1;
+///         let event = Event::data(format!("tick {}", n)).with_id(n.to_string());
+///         if tx.send(event).is_err() {
+///             break;
+///         }
+///
+///         thread::sleep(Duration::from_secs(1));
+///     });
+///
+///     EventStream::from(rx).heartbeat(Duration::from_secs(15))
+/// }
+/// # fn main() {  }
+/// ```
+pub struct EventStream<'r> {
+    source: Source<'r>,
+    heartbeat: Option<Duration>,
+}
+
+impl<'r> EventStream<'r> {
+    /// Creates a new stream that sends the events yielded by `events`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::response::{Event, EventStream};
+    ///
+    /// # #[allow(unused_variables)]
+    /// let stream = EventStream::new((1..=3).map(|n| Event::data(n.to_string())));
+    /// ```
+    pub fn new<I>(events: I) -> EventStream<'r>
+        where I: IntoIterator<Item = Event>, I::IntoIter: 'r
+    {
+        EventStream { source: Source::Iter(Box::new(events.into_iter())), heartbeat: None }
+    }
+
+    /// Sends a keep-alive comment whenever no event has been sent for
+    /// `interval`. Has no effect on streams created from an iterator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::mpsc;
+    /// use std::time::Duration;
+    /// use rocket::response::{Event, EventStream};
+    ///
+    /// let (tx, rx) = mpsc::channel::<Event>();
+    /// # #[allow(unused_variables)]
+    /// let stream = EventStream::from(rx).heartbeat(Duration::from_secs(30));
+    /// ```
+    #[inline]
+    pub fn heartbeat(mut self, interval: Duration) -> EventStream<'r> {
+        self.heartbeat = Some(interval);
+        self
+    }
+}
+
+/// Creates a new stream that sends the events received on `receiver`.
+impl From<Receiver<Event>> for EventStream<'_> {
+    fn from(receiver: Receiver<Event>) -> Self {
+        EventStream { source: Source::Channel(receiver), heartbeat: None }
+    }
+}
+
+impl fmt::Debug for EventStream<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self.source {
+            Source::Iter(_) => "iterator",
+            Source::Channel(_) => "channel",
+        };
+
+        f.debug_struct("EventStream")
+            .field("source", &source)
+            .field("heartbeat", &self.heartbeat)
+            .finish()
+    }
+}
+
+/// Reads an `EventStream` in the `text/event-stream` format. Each call to
+/// `read` returns bytes of at most one event, so that every event is flushed
+/// to the client as soon as it's available.
+struct EventReader<'r> {
+    stream: EventStream<'r>,
+    pending: Vec<u8>,
+    position: usize,
+}
+
+impl EventReader<'_> {
+    /// Waits for the next event or keep-alive comment and returns it,
+    /// formatted, or `None` if the stream has ended.
+    fn next_chunk(&mut self) -> Option<String> {
+        let heartbeat = self.stream.heartbeat;
+        match self.stream.source {
+            Source::Iter(ref mut events) => events.next().map(|e| e.to_string()),
+            Source::Channel(ref receiver) => match heartbeat {
+                Some(interval) => match receiver.recv_timeout(interval) {
+                    Ok(event) => Some(event.to_string()),
+                    Err(RecvTimeoutError::Timeout) => Some(":\n\n".into()),
+                    Err(RecvTimeoutError::Disconnected) => None,
+                },
+                None => receiver.recv().ok().map(|e| e.to_string()),
+            }
+        }
+    }
+}
+
+impl Read for EventReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position >= self.pending.len() {
+            match self.next_chunk() {
+                Some(chunk) => self.pending = chunk.into_bytes(),
+                None => return Ok(0),
+            }
+
+            self.position = 0;
+        }
+
+        let remaining = &self.pending[self.position..];
+        let n = remaining.len().min(buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Streams the events to the client with a `Content-Type` of
+/// `text/event-stream` and a `Cache-Control` of `no-cache`.
+impl<'r> Responder<'r> for EventStream<'r> {
+    fn respond_to(self, _: &Request<'_>) -> Result<Response<'r>, Status> {
+        let reader = EventReader { stream: self, pending: vec![], position: 0 };
+        let mut response = Response::build()
+            .header(ContentType::EventStream)
+            .raw_header("Cache-Control", "no-cache")
+            .finalize();
+
+        response.set_live_body(reader);
+        Ok(response)
+    }
+}
+
+/// The value of the `Last-Event-ID` header, sent by Server-Sent Events clients
+/// when reconnecting to an [`EventStream`].
+///
+/// As a request guard, `LastEventId` forwards if the header is not present.
+/// Use `Option<LastEventId>` to handle both initial connections and
+/// reconnections in a single route. `LastEventId` dereferences to the ID as a
+/// `str`.
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::request::LastEventId;
+///
+/// #[get("/events")]
+/// fn events(last: Option<LastEventId<'_>>) -> String {
+///     match last {
+///         Some(id) => format!("Resuming after {}.", &*id),
+///         None => "Starting from the beginning.".into(),
+///     }
+/// }
+/// # fn main() {  }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LastEventId<'r>(&'r str);
+
+impl<'r> LastEventId<'r> {
+    /// Returns the ID as a string slice.
+    #[inline(always)]
+    pub fn as_str(&self) -> &'r str {
+        self.0
+    }
+}
+
+impl Deref for LastEventId<'_> {
+    type Target = str;
+
+    #[inline(always)]
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl<'a> FromRequest<'a, '_> for LastEventId<'a> {
+    type Error = std::convert::Infallible;
+
+    fn from_request(request: &'a Request<'_>) -> request::Outcome<Self, Self::Error> {
+        match request.headers().get_one("Last-Event-ID") {
+            Some(id) => Success(LastEventId(id)),
+            None => Forward(())
+        }
+    }
+}
@@ -4,7 +4,7 @@ use time::Duration;
 
 use crate::outcome::IntoOutcome;
 use crate::response::{Response, Responder};
-use crate::request::{self, Request, FromRequest};
+use crate::request::{self, Request, FromRequest, GuardError};
 use crate::http::{Status, Cookie};
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -266,4 +266,9 @@ impl<'a, 'r> FromRequest<'a, 'r> for Flash<&'a Request<'r>> {
             }
         }).into_outcome(Status::BadRequest)
     }
+
+    #[inline(always)]
+    fn guard_error(error: Self::Error) -> GuardError {
+        GuardError::new(error)
+    }
 }
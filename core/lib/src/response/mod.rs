@@ -31,6 +31,9 @@ mod response;
 mod debug;
 
 pub(crate) mod flash;
+pub(crate) mod event_stream;
+pub(crate) mod range;
+pub(crate) mod conditional;
 
 pub mod content;
 pub mod status;
@@ -38,11 +41,13 @@ pub mod status;
 #[doc(hidden)] pub use rocket_codegen::Responder;
 
 pub use self::response::{Response, ResponseBuilder, Body, DEFAULT_CHUNK_SIZE};
+pub(crate) use self::response::Upgrade;
 pub use self::responder::Responder;
 pub use self::redirect::Redirect;
 pub use self::flash::Flash;
 pub use self::named_file::NamedFile;
 pub use self::stream::Stream;
+pub use self::event_stream::{EventStream, Event};
 pub use self::debug::Debug;
 #[doc(inline)] pub use self::content::Content;
 
@@ -2,10 +2,11 @@ use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::io;
 use std::ops::{Deref, DerefMut};
+use std::time::UNIX_EPOCH;
 
 use crate::request::Request;
 use crate::response::{self, Responder};
-use crate::http::ContentType;
+use crate::http::{ContentType, EntityTag};
 
 /// A file with an associated name; responds with the Content-Type based on the
 /// file extension.
@@ -78,15 +79,41 @@ impl NamedFile {
 /// recognized. See [`ContentType::from_extension()`] for more information. If
 /// you would like to stream a file with a different Content-Type than that
 /// implied by its extension, use a [`File`] directly.
+///
+/// The response advertises support for byte ranges with an `Accept-Ranges:
+/// bytes` header. Requests for ranges of the file are answered with `206
+/// Partial Content` or `416 Range Not Satisfiable`, as appropriate.
+///
+/// The response's `ETag` and `Last-Modified` headers are derived from the
+/// file's size and modification time, if the platform reports one, so that
+/// conditional requests for an unchanged file are answered with `304 Not
+/// Modified`.
 impl Responder<'_> for NamedFile {
     fn respond_to(self, req: &Request<'_>) -> response::Result<'static> {
+        let modified = self.1.metadata()
+            .and_then(|metadata| Ok((metadata.modified()?, metadata.len())))
+            .ok();
+
         let mut response = self.1.respond_to(req)?;
+        if let Some((modified, len)) = modified {
+            if let Ok(since) = modified.duration_since(UNIX_EPOCH) {
+                let tag = format!("{:x}.{:x}-{:x}", since.as_secs(), since.subsec_nanos(), len);
+                response.set_etag(EntityTag::strong(tag));
+            }
+
+            response.set_last_modified(modified);
+        }
+
         if let Some(ext) = self.0.extension() {
             if let Some(ct) = ContentType::from_extension(&ext.to_string_lossy()) {
                 response.set_header(ct);
             }
         }
 
+        if response.body().map_or(false, |body| body.is_sized()) {
+            response.set_raw_header("Accept-Ranges", "bytes");
+        }
+
         Ok(response)
     }
 }
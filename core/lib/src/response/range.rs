@@ -0,0 +1,180 @@
+use std::io::{self, Read, Seek, SeekFrom, Cursor};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::request::Request;
+use crate::response::{Response, Body};
+use super::response::ReadSeek;
+use crate::http::{Method, Status, Range, IfRange};
+
+/// The most ranges served in one response. Requests for more are served the
+/// full representation: they're more likely an attempt to make the server do
+/// excess work than a legitimate request.
+const MAX_RANGES: usize = 16;
+
+/// Serves the ranges requested by `request` of `response`'s body.
+///
+/// Ranges are only served for `GET` requests answered with `200 OK` and a
+/// seekable, sized body, as set by [`Response::set_sized_body()`]. A valid
+/// `Range` header whose `If-Range` validator, if any, matches the response's
+/// `ETag` or `Last-Modified` header results in one of:
+///
+///   * `206 Partial Content` with a `Content-Range` header for a single
+///     satisfiable range.
+///   * `206 Partial Content` with a `multipart/byteranges` body for several.
+///   * `416 Range Not Satisfiable` if none of the ranges are satisfiable.
+///
+/// Otherwise, `response` is left untouched.
+pub(crate) fn serve_ranges(request: &Request<'_>, response: &mut Response<'_>) {
+    if request.method() != Method::Get || response.status() != Status::Ok {
+        return;
+    }
+
+    let range = match request.headers().get_one("Range").map(str::parse::<Range>) {
+        Some(Ok(range)) if range.ranges().len() <= MAX_RANGES => range,
+        _ => return
+    };
+
+    if let Some(if_range) = request.headers().get_one("If-Range") {
+        let etag = response.headers().get_one("ETag");
+        let last_modified = response.headers().get_one("Last-Modified");
+        match if_range.parse::<IfRange>() {
+            Ok(ref validator) if validator.matches(etag, last_modified) => { /* serve */ },
+            _ => return
+        }
+    }
+
+    let (mut body, size) = match response.take_seekable_body() {
+        Some(body) => body,
+        None => return
+    };
+
+    let ranges: Vec<_> = range.ranges().iter().filter_map(|r| r.resolve(size)).collect();
+    match ranges.len() {
+        0 => {
+            response.set_status(Status::RangeNotSatisfiable);
+            response.remove_header("Content-Type");
+            response.set_raw_header("Content-Range", format!("bytes */{}", size));
+        }
+        1 => {
+            let (first, last) = ranges[0];
+            if let Err(e) = body.seek(SeekFrom::Start(first)) {
+                error_!("Failed to seek to requested range: {:?}", e);
+                response.set_status(Status::InternalServerError);
+                return;
+            }
+
+            response.set_status(Status::PartialContent);
+            response.set_raw_header("Content-Range", format!("bytes {}-{}/{}", first, last, size));
+            response.set_raw_body(Body::Sized(body, last - first + 1));
+        }
+        _ => {
+            let boundary = boundary(size);
+            let content_type = response.headers().get_one("Content-Type").map(String::from);
+            let multipart = Multipart::new(body, &ranges, size, &boundary, content_type);
+            let length = multipart.len();
+
+            response.set_status(Status::PartialContent);
+            response.set_raw_header("Content-Type",
+                format!("multipart/byteranges; boundary={}", boundary));
+            response.set_raw_body(Body::Sized(multipart, length));
+        }
+    }
+}
+
+/// Returns a boundary for a `multipart/byteranges` body. It only needs to be
+/// unlikely to occur in the body, not unpredictable.
+fn boundary(size: u64) -> String {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+
+    format!("rocket-{:016x}{:016x}", nanos, size)
+}
+
+/// A part of a `multipart/byteranges` body.
+enum Segment {
+    /// Literal bytes: part headers and boundaries.
+    Bytes(Cursor<Vec<u8>>),
+    /// A range of the representation: its first position and length.
+    Range(u64, u64),
+}
+
+/// A `multipart/byteranges` body that reads each range from the underlying
+/// representation as it's written out.
+struct Multipart<'r> {
+    body: Box<dyn ReadSeek + 'r>,
+    segments: Vec<Segment>,
+    current: usize,
+    positioned: bool,
+}
+
+impl<'r> Multipart<'r> {
+    fn new(
+        body: Box<dyn ReadSeek + 'r>,
+        ranges: &[(u64, u64)],
+        size: u64,
+        boundary: &str,
+        content_type: Option<String>,
+    ) -> Multipart<'r> {
+        let mut segments = vec![];
+        for &(first, last) in ranges {
+            let mut head = format!("\r\n--{}\r\n", boundary);
+            if let Some(ref content_type) = content_type {
+                head.push_str(&format!("Content-Type: {}\r\n", content_type));
+            }
+
+            head.push_str(&format!("Content-Range: bytes {}-{}/{}\r\n\r\n", first, last, size));
+            segments.push(Segment::Bytes(Cursor::new(head.into_bytes())));
+            segments.push(Segment::Range(first, last - first + 1));
+        }
+
+        let tail = format!("\r\n--{}--\r\n", boundary);
+        segments.push(Segment::Bytes(Cursor::new(tail.into_bytes())));
+        Multipart { body, segments, current: 0, positioned: false }
+    }
+
+    /// The total length of the body, in bytes.
+    fn len(&self) -> u64 {
+        self.segments.iter()
+            .map(|segment| match *segment {
+                Segment::Bytes(ref bytes) => bytes.get_ref().len() as u64,
+                Segment::Range(_, len) => len,
+            })
+            .sum()
+    }
+}
+
+impl Read for Multipart<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while let Some(segment) = self.segments.get_mut(self.current) {
+            let n = match *segment {
+                Segment::Bytes(ref mut bytes) => bytes.read(buf)?,
+                Segment::Range(first, ref mut remaining) => {
+                    if !self.positioned {
+                        self.body.seek(SeekFrom::Start(first))?;
+                        self.positioned = true;
+                    }
+
+                    let max = std::cmp::min(buf.len() as u64, *remaining) as usize;
+                    let n = self.body.read(&mut buf[..max])?;
+                    if n == 0 && max > 0 {
+                        let msg = "representation ended before the requested range";
+                        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, msg));
+                    }
+
+                    *remaining -= n as u64;
+                    n
+                }
+            };
+
+            if n > 0 || buf.is_empty() {
+                return Ok(n);
+            }
+
+            self.current += 1;
+            self.positioned = false;
+        }
+
+        Ok(0)
+    }
+}
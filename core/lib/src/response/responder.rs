@@ -2,7 +2,7 @@ use std::fs::File;
 use std::io::{Cursor, BufReader};
 
 use crate::http::{Status, ContentType, StatusClass};
-use crate::response::{self, Response, Body};
+use crate::response::{self, Response};
 use crate::request::Request;
 
 /// Trait implemented by types that generate responses for clients.
@@ -243,7 +243,11 @@ impl Responder<'_> for File {
     fn respond_to(self, _: &Request<'_>) -> response::Result<'static> {
         let (metadata, file) = (self.metadata(), BufReader::new(self));
         match metadata {
-            Ok(md) => Response::build().raw_body(Body::Sized(file, md.len())).ok(),
+            Ok(md) => {
+                let mut response = Response::new();
+                response.set_seekable_body(file, md.len());
+                Ok(response)
+            }
             Err(_) => Response::build().streamed_body(file).ok()
         }
     }
@@ -2,7 +2,8 @@ use std::{io, fmt, str};
 use std::borrow::Cow;
 
 use crate::response::Responder;
-use crate::http::{Header, HeaderMap, Status, ContentType, Cookie};
+use crate::http::{Header, HeaderMap, Status, ContentType, Cookie, EntityTag, HttpDate};
+use crate::websocket::Connection;
 
 /// The default size, in bytes, of a chunk for streamed responses.
 pub const DEFAULT_CHUNK_SIZE: u64 = 4096;
@@ -96,6 +97,39 @@ impl<T> fmt::Debug for Body<T> {
     }
 }
 
+/// A reader that can also seek.
+pub(crate) trait ReadSeek: io::Read + io::Seek {}
+
+impl<T: io::Read + io::Seek> ReadSeek for T {}
+
+/// The reader of a response's body. Bodies set with `set_sized_body()` remain
+/// seekable so that ranges of them can be served. The data of `Live` bodies,
+/// like event streams, is sent as soon as it is read.
+enum Reader<'r> {
+    Stream(Box<dyn io::Read + 'r>),
+    Seekable(io::Take<Box<dyn ReadSeek + 'r>>),
+    Live(Box<dyn io::Read + 'r>),
+}
+
+impl<'r> Reader<'r> {
+    fn into_boxed(self) -> Box<dyn io::Read + 'r> {
+        match self {
+            Reader::Stream(reader) | Reader::Live(reader) => reader,
+            Reader::Seekable(reader) => Box::new(reader),
+        }
+    }
+}
+
+impl io::Read for Reader<'_> {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match *self {
+            Reader::Stream(ref mut reader) | Reader::Live(ref mut reader) => reader.read(buf),
+            Reader::Seekable(ref mut reader) => reader.read(buf),
+        }
+    }
+}
+
 /// Type for easily building `Response`s.
 ///
 /// Building a [`Response`] can be a low-level ordeal; this structure presents a
@@ -330,6 +364,48 @@ impl<'r> ResponseBuilder<'r> {
         self
     }
 
+    /// Sets the `ETag` header of the `Response` to `etag`, replacing any
+    /// existing one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let response = Response::build()
+    ///     .etag(EntityTag::strong("v1"))
+    ///     .finalize();
+    ///
+    /// assert_eq!(response.etag(), Some(EntityTag::strong("v1")));
+    /// ```
+    #[inline(always)]
+    pub fn etag(&mut self, etag: EntityTag) -> &mut ResponseBuilder<'r> {
+        self.response.set_etag(etag);
+        self
+    }
+
+    /// Sets the `Last-Modified` header of the `Response` to `date`, replacing
+    /// any existing one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::SystemTime;
+    /// use rocket::Response;
+    ///
+    /// let response = Response::build()
+    ///     .last_modified(SystemTime::now())
+    ///     .finalize();
+    ///
+    /// assert!(response.last_modified().is_some());
+    /// ```
+    #[inline(always)]
+    pub fn last_modified<D: Into<HttpDate>>(&mut self, date: D) -> &mut ResponseBuilder<'r> {
+        self.response.set_last_modified(date);
+        self
+    }
+
     /// Sets the body of the `Response` to be the fixed-sized `body`.
     ///
     /// # Example
@@ -560,9 +636,13 @@ impl<'r> ResponseBuilder<'r> {
 pub struct Response<'r> {
     status: Option<Status>,
     headers: HeaderMap<'r>,
-    body: Option<Body<Box<dyn io::Read + 'r>>>,
+    body: Option<Body<Reader<'r>>>,
+    upgrade: Option<Upgrade>,
 }
 
+/// Takes over the connection after a `101 Switching Protocols` response.
+pub(crate) type Upgrade = Box<dyn FnOnce(Connection) + Send + 'static>;
+
 impl<'r> Response<'r> {
     /// Creates a new, empty `Response` without a status, body, or headers.
     /// Because all HTTP responses must have a status, if a default `Response`
@@ -587,6 +667,7 @@ impl<'r> Response<'r> {
             status: None,
             headers: HeaderMap::new(),
             body: None,
+            upgrade: None,
         }
     }
 
@@ -675,6 +756,87 @@ impl<'r> Response<'r> {
         self.headers().get_one("Content-Type").and_then(|v| v.parse().ok())
     }
 
+    /// Returns the entity-tag in the `ETag` header of `self`. If the header is
+    /// not present or is malformed, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let mut response = Response::new();
+    /// response.set_etag(EntityTag::strong("v1"));
+    /// assert_eq!(response.etag(), Some(EntityTag::strong("v1")));
+    /// ```
+    #[inline(always)]
+    pub fn etag(&self) -> Option<EntityTag> {
+        self.headers().get_one("ETag").and_then(|v| v.parse().ok())
+    }
+
+    /// Sets the `ETag` header of `self` to `etag`, replacing any existing one.
+    ///
+    /// An entity-tag allows Rocket to answer conditional requests for the
+    /// response: a `GET` or `HEAD` request whose `If-None-Match` header matches
+    /// `etag` is answered with `304 Not Modified` and no body.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    /// use rocket::http::EntityTag;
+    ///
+    /// let mut response = Response::new();
+    /// response.set_etag(EntityTag::weak("v1"));
+    /// assert_eq!(response.headers().get_one("ETag"), Some("W/\"v1\""));
+    /// ```
+    #[inline(always)]
+    pub fn set_etag(&mut self, etag: EntityTag) {
+        self.set_header(etag);
+    }
+
+    /// Returns the date in the `Last-Modified` header of `self`. If the header
+    /// is not present or is malformed, returns `None`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::Response;
+    ///
+    /// let mut response = Response::new();
+    /// response.set_raw_header("Last-Modified", "Sun, 06 Nov 1994 08:49:37 GMT");
+    /// assert_eq!(response.last_modified().unwrap().unix_timestamp(), 784111777);
+    /// ```
+    #[inline(always)]
+    pub fn last_modified(&self) -> Option<HttpDate> {
+        self.headers().get_one("Last-Modified").and_then(|v| v.parse().ok())
+    }
+
+    /// Sets the `Last-Modified` header of `self` to `date`, replacing any
+    /// existing one. `date` can be an [`HttpDate`] or a
+    /// [`SystemTime`](std::time::SystemTime).
+    ///
+    /// A modification date allows Rocket to answer conditional requests for
+    /// the response: a `GET` or `HEAD` request whose `If-Modified-Since` header
+    /// is no earlier than `date` is answered with `304 Not Modified` and no
+    /// body.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::time::{Duration, UNIX_EPOCH};
+    /// use rocket::Response;
+    ///
+    /// let mut response = Response::new();
+    /// response.set_last_modified(UNIX_EPOCH + Duration::from_secs(784111777));
+    /// let last_modified = response.headers().get_one("Last-Modified");
+    /// assert_eq!(last_modified, Some("Sun, 06 Nov 1994 08:49:37 GMT"));
+    /// ```
+    #[inline(always)]
+    pub fn set_last_modified<D: Into<HttpDate>>(&mut self, date: D) {
+        self.set_raw_header("Last-Modified", date.into().to_string());
+    }
+
     /// Sets the status of `self` to a custom `status` with status code `code`
     /// and reason phrase `reason`. This method should be used sparingly; prefer
     /// to use [set_status](#method.set_status) instead.
@@ -893,8 +1055,8 @@ impl<'r> Response<'r> {
         // Looks crazy, right? Needed so Rust infers lifetime correctly. Weird.
         match self.body.as_mut() {
             Some(body) => Some(match body.as_mut() {
-                Body::Sized(b, size) => Body::Sized(b, size),
-                Body::Chunked(b, chunk_size) => Body::Chunked(b, chunk_size),
+                Body::Sized(b, size) => Body::Sized(b as &mut dyn io::Read, size),
+                Body::Chunked(b, chunk_size) => Body::Chunked(b as &mut dyn io::Read, chunk_size),
             }),
             None => None
         }
@@ -967,7 +1129,27 @@ impl<'r> Response<'r> {
     /// ```
     #[inline(always)]
     pub fn take_body(&mut self) -> Option<Body<Box<dyn io::Read + 'r>>> {
-        self.body.take()
+        self.body.take().map(|body| body.map(Reader::into_boxed))
+    }
+
+    /// Moves the body of `self` out and returns it along with its size if it
+    /// is a sized body that was set with `set_sized_body()`. Otherwise, leaves
+    /// the body in place and returns `None`.
+    pub(crate) fn take_seekable_body(&mut self) -> Option<(Box<dyn ReadSeek + 'r>, u64)> {
+        match self.body.take() {
+            Some(Body::Sized(Reader::Seekable(reader), size)) => Some((reader.into_inner(), size)),
+            body => {
+                self.body = body;
+                None
+            }
+        }
+    }
+
+    /// Sets the body of `self` to the seekable `body` of `size` bytes without
+    /// seeking to determine its size.
+    pub(crate) fn set_seekable_body<B: ReadSeek + 'r>(&mut self, body: B, size: u64) {
+        let body: Box<dyn ReadSeek + 'r> = Box::new(body);
+        self.body = Some(Body::Sized(Reader::Seekable(body.take(size)), size));
     }
 
     // Makes the `Read`er in the body empty but leaves the size of the body if
@@ -976,7 +1158,7 @@ impl<'r> Response<'r> {
     pub(crate) fn strip_body(&mut self) {
         if let Some(body) = self.take_body() {
             self.body = match body {
-                Body::Sized(_, n) => Some(Body::Sized(Box::new(io::empty()), n)),
+                Body::Sized(_, n) => Some(Body::Sized(Reader::Stream(Box::new(io::empty())), n)),
                 Body::Chunked(..) => None
             };
         }
@@ -986,6 +1168,10 @@ impl<'r> Response<'r> {
     /// body is obtained by `seek`ing to the end and then `seek`ing back to the
     /// start.
     ///
+    /// Because the body is seekable, a `GET` request for ranges of it, via the
+    /// `Range` header, is answered with `206 Partial Content` and only the
+    /// requested ranges.
+    ///
     /// # Panics
     ///
     /// If either seek fails, this method panics. If you believe it is possible
@@ -1010,7 +1196,7 @@ impl<'r> Response<'r> {
             .expect("Attempted to retrieve size by seeking, but failed.");
         body.seek(io::SeekFrom::Start(0))
             .expect("Attempted to reset body by seeking after getting size.");
-        self.body = Some(Body::Sized(Box::new(body.take(size)), size));
+        self.set_seekable_body(body, size);
     }
 
     /// Sets the body of `self` to be `body`, which will be streamed. The chunk
@@ -1049,7 +1235,21 @@ impl<'r> Response<'r> {
     #[inline(always)]
     pub fn set_chunked_body<B>(&mut self, body: B, chunk_size: u64)
             where B: io::Read + 'r {
-        self.body = Some(Body::Chunked(Box::new(body), chunk_size));
+        self.body = Some(Body::Chunked(Reader::Stream(Box::new(body)), chunk_size));
+    }
+
+    /// Sets the body of `self` to be `body`, which will be streamed with each
+    /// chunk sent as soon as it is read.
+    pub(crate) fn set_live_body<B: io::Read + 'r>(&mut self, body: B) {
+        self.body = Some(Body::Chunked(Reader::Live(Box::new(body)), DEFAULT_CHUNK_SIZE));
+    }
+
+    /// Returns `true` if the body of `self` was set with `set_live_body()`.
+    pub(crate) fn has_live_body(&self) -> bool {
+        match self.body {
+            Some(Body::Chunked(Reader::Live(_), _)) => true,
+            _ => false
+        }
     }
 
     /// Sets the body of `self` to be `body`. This method should typically not
@@ -1072,8 +1272,8 @@ impl<'r> Response<'r> {
     #[inline(always)]
     pub fn set_raw_body<T: io::Read + 'r>(&mut self, body: Body<T>) {
         self.body = Some(match body {
-            Body::Sized(b, n) => Body::Sized(Box::new(b.take(n)), n),
-            Body::Chunked(b, n) => Body::Chunked(Box::new(b), n),
+            Body::Sized(b, n) => Body::Sized(Reader::Stream(Box::new(b.take(n))), n),
+            Body::Chunked(b, n) => Body::Chunked(Reader::Stream(Box::new(b)), n),
         });
     }
 
@@ -1121,6 +1321,10 @@ impl<'r> Response<'r> {
             self.body = Some(body);
         }
 
+        if let Some(upgrade) = other.upgrade {
+            self.upgrade = Some(upgrade);
+        }
+
         for (name, values) in other.headers.into_iter_raw() {
             self.headers.replace_all(name.into_cow(), values);
         }
@@ -1170,10 +1374,27 @@ impl<'r> Response<'r> {
             self.body = other.body;
         }
 
+        if self.upgrade.is_none() {
+            self.upgrade = other.upgrade;
+        }
+
         for (name, mut values) in other.headers.into_iter_raw() {
             self.headers.add_all(name.into_cow(), &mut values);
         }
     }
+
+    /// Sets the function that takes over the connection once `self`, a `101
+    /// Switching Protocols` response, has been written.
+    #[inline(always)]
+    pub(crate) fn set_upgrade(&mut self, upgrade: Upgrade) {
+        self.upgrade = Some(upgrade);
+    }
+
+    /// Removes and returns the connection upgrade of `self`, if any.
+    #[inline(always)]
+    pub(crate) fn take_upgrade(&mut self) -> Option<Upgrade> {
+        self.upgrade.take()
+    }
 }
 
 impl fmt::Debug for Response<'_> {
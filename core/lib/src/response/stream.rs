@@ -10,7 +10,8 @@ use crate::http::Status;
 /// The client is sent a "chunked" response, where the chunk size is at most
 /// 4KiB. This means that at most 4KiB are stored in memory while the response
 /// is being sent. This type should be used when sending responses that are
-/// arbitrarily large in size, such as when streaming from a local socket.
+/// arbitrarily large in size, such as when streaming from a local socket. To
+/// send Server-Sent Events, use [`EventStream`](crate::response::EventStream).
 pub struct Stream<T: Read>(T, u64);
 
 impl<T: Read> Stream<T> {
@@ -2,40 +2,53 @@ use std::collections::HashMap;
 use std::str::from_utf8;
 use std::cmp::min;
 use std::io::{self, Write};
+use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 use std::mem;
 
 use yansi::Paint;
 use state::Container;
 
-#[cfg(feature = "tls")] use crate::http::tls::TlsServer;
-
 use crate::{logger, handler};
-use crate::ext::ReadExt;
+use crate::handler::MalformedHandler;
+use crate::ext::{ReadExt, catch_panic};
 use crate::config::{Config, FullConfig, ConfigError, LoggedValue};
-use crate::request::{Request, FormItems};
-use crate::data::Data;
+use crate::request::{Request, MalformedRequest};
+use crate::data::{Data, is_tls_stream};
 use crate::response::{Body, Response};
-use crate::router::{Router, Route};
+use crate::response::range::serve_ranges;
+use crate::response::conditional;
+use crate::router::{Router, Route, HostPattern, UriParams};
 use crate::catcher::{self, Catcher};
 use crate::outcome::Outcome;
-use crate::error::{LaunchError, LaunchErrorKind};
+use crate::error::{LaunchError, LaunchErrorKind, UriForError};
 use crate::fairing::{Fairing, Fairings};
 use crate::logger::PaintExt;
+use crate::shutdown::{Shutdown, Running};
+use crate::listener::{Listener, SocketFiles};
+use crate::server::{self, Shared, Redirect};
+use crate::date;
+use crate::websocket::Connection;
+#[cfg(unix)] use crate::unix;
 
 use crate::http::{Method, Status, Header};
 use crate::http::hyper::{self, header};
 use crate::http::uri::Origin;
+use crate::http::ext::IntoOwned;
 
 /// The main `Rocket` type: used to mount routes and catchers and launch the
 /// application.
 pub struct Rocket {
     pub(crate) config: Config,
-    router: Router,
+    pub(crate) router: Router,
     default_catchers: HashMap<u16, Catcher>,
-    catchers: HashMap<u16, Catcher>,
+    catchers: Vec<Catcher>,
     pub(crate) state: Container,
+    malformed: MalformedHandler,
     fairings: Fairings,
+    pub(crate) shutdown: Shutdown,
+    listener: Option<Listener>,
 }
 
 #[doc(hidden)]
@@ -50,74 +63,66 @@ impl hyper::Handler for Rocket {
         hyp_req: hyper::Request<'h, 'k>,
         res: hyper::FreshResponse<'h>,
     ) {
+        // Keep the request counted until a response has been written out.
+        let _in_flight = self.shutdown.in_flight();
+
         // Get all of the information from Hyper.
-        let (h_addr, h_method, h_headers, h_uri, _, h_body) = hyp_req.deconstruct();
+        let (h_addr, h_method, h_headers, h_uri, _, mut h_body) = hyp_req.deconstruct();
+
+        // Connections over Unix domain sockets don't have a remote address
+        // unless one is received via the PROXY protocol.
+        #[cfg(unix)]
+        let h_addr = {
+            let stream = &**h_body.get_mut().get_mut();
+            Some(h_addr).filter(|_| !unix::is_anonymous_unix_stream(stream))
+        };
+        #[cfg(not(unix))]
+        let h_addr = Some(h_addr);
 
         // Convert the Hyper request into a Rocket request.
-        let req_res = Request::from_hyp(self, h_method, h_headers, h_uri, h_addr);
+        let secure = is_tls_stream(&**h_body.get_mut().get_mut());
+        let req_res = Request::from_hyp(self, &h_method, h_headers, &h_uri, h_addr, secure);
         let mut req = match req_res {
             Ok(req) => req,
-            Err(e) => {
-                error!("Bad incoming request: {}", e);
-                // TODO: We don't have a request to pass in, so we just
-                // fabricate one. This is weird. We should let the user know
-                // that we failed to parse a request (by invoking some special
-                // handler) instead of doing this.
-                let dummy = Request::new(self, Method::Get, Origin::dummy());
-                let r = self.handle_error(Status::BadRequest, &dummy);
-                return self.issue_response(r, res);
+            Err(malformed) => {
+                let r = self.handle_malformed(&malformed);
+                return self.issue_response(r, res, None);
             }
         };
 
         // Retrieve the data from the hyper body.
-        let data = match Data::from_hyp(h_body) {
+        let read_timeout = self.config.read_timeout.map(|s| Duration::from_secs(s as u64));
+        let mut data = match Data::from_hyp(h_body, read_timeout, h_method, h_uri) {
             Ok(data) => data,
             Err(reason) => {
                 error_!("Bad data in request: {}", reason);
-                let r = self.handle_error(Status::InternalServerError, &req);
-                return self.issue_response(r, res);
+                let mut r = self.handle_error(Status::InternalServerError, &req);
+                self.add_default_headers(&mut r);
+                return self.issue_response(r, res, None);
             }
         };
 
+        // A request to upgrade the connection hands it over, if accepted.
+        let connection = match req.headers().contains("Upgrade") {
+            true => data.connection(),
+            false => None
+        };
+
         // Dispatch the request to get a response, then write that response out.
         let response = self.dispatch(&mut req, data);
-        self.issue_response(response, res)
+        self.issue_response(response, res, connection)
     }
 }
 
-// This macro is a terrible hack to get around Hyper's Server<L> type. What we
-// want is to use almost exactly the same launch code when we're serving over
-// HTTPS as over HTTP. But Hyper forces two different types, so we can't use the
-// same code, at least not trivially. These macros get around that by passing in
-// the same code as a continuation in `$continue`. This wouldn't work as a
-// regular function taking in a closure because the types of the inputs to the
-// closure would be different depending on whether TLS was enabled or not.
-#[cfg(not(feature = "tls"))]
-macro_rules! serve {
-    ($rocket:expr, $addr:expr, |$server:ident, $proto:ident| $continue:expr) => ({
-        let ($proto, $server) = ("http://", hyper::Server::http($addr));
-        $continue
-    })
-}
-
-#[cfg(feature = "tls")]
-macro_rules! serve {
-    ($rocket:expr, $addr:expr, |$server:ident, $proto:ident| $continue:expr) => ({
-        if let Some(tls) = $rocket.config.tls.clone() {
-            let tls = TlsServer::new(tls.certs, tls.key);
-            let ($proto, $server) = ("https://", hyper::Server::https($addr, tls));
-            $continue
-        } else {
-            let ($proto, $server) = ("http://", hyper::Server::http($addr));
-            $continue
-        }
-    })
-}
-
 impl Rocket {
     #[inline]
-    fn issue_response(&self, response: Response<'_>, hyp_res: hyper::FreshResponse<'_>) {
-        match self.write_response(response, hyp_res) {
+    fn issue_response(
+        &self,
+        response: Response<'_>,
+        hyp_res: hyper::FreshResponse<'_>,
+        connection: Option<Connection>,
+    ) {
+        match self.write_response(response, hyp_res, connection) {
             Ok(_) => info_!("{}", Paint::green("Response succeeded.")),
             Err(e) => error_!("Failed to write response: {:?}.", e),
         }
@@ -128,9 +133,15 @@ impl Rocket {
         &self,
         mut response: Response<'_>,
         mut hyp_res: hyper::FreshResponse<'_>,
+        connection: Option<Connection>,
     ) -> io::Result<()> {
         *hyp_res.status_mut() = hyper::StatusCode::from_u16(response.status().code);
 
+        // Don't keep connections alive while shutting down.
+        if self.shutdown.is_notified() {
+            hyp_res.headers_mut().set(header::Connection::close());
+        }
+
         for header in response.headers().iter() {
             // FIXME: Using hyper here requires two allocations.
             let name = header.name.into_string();
@@ -138,9 +149,30 @@ impl Rocket {
             hyp_res.headers_mut().append_raw(name, value);
         }
 
+        // Hand the connection over once the switch has been announced. When
+        // the upgrade returns, the connection is closed.
+        if let Some(upgrade) = response.take_upgrade() {
+            match connection {
+                Some(connection) if response.status() == Status::SwitchingProtocols => {
+                    let mut stream = hyp_res.start()?;
+                    stream.flush()?;
+                    connection.run(upgrade);
+                    return stream.end();
+                }
+                _ => error_!("Only a `101 Switching Protocols` response to an upgrade \
+                    request without a body can take over the connection."),
+            }
+        }
+
+        let is_live = response.has_live_body();
         match response.body() {
             None => {
-                hyp_res.headers_mut().set(header::ContentLength(0));
+                // A `304` response's length would be that of the representation
+                // it stands in for, so none is declared.
+                if response.status() != Status::NotModified {
+                    hyp_res.headers_mut().set(header::ContentLength(0));
+                }
+
                 hyp_res.start()?.end()
             }
             Some(Body::Sized(body, size)) => {
@@ -156,13 +188,29 @@ impl Rocket {
                     return Err(io::Error::new(io::ErrorKind::Other, msg));
                 }
 
-                // The buffer stores the current chunk being written out.
+                // The buffer stores the current chunk being written out. The
+                // data of live bodies, like event streams, is flushed as soon
+                // as it's read so that it reaches the client without delay.
                 let mut buffer = vec![0; chunk_size as usize];
                 let mut stream = hyp_res.start()?;
-                loop {
-                    match body.read_max(&mut buffer)? {
-                        0 => break,
-                        n => stream.write_all(&buffer[..n])?,
+                if is_live {
+                    loop {
+                        match body.read(&mut buffer) {
+                            Ok(0) => break,
+                            Ok(n) => {
+                                stream.write_all(&buffer[..n])?;
+                                stream.flush()?;
+                            }
+                            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                            Err(e) => return Err(e),
+                        }
+                    }
+                } else {
+                    loop {
+                        match body.read_max(&mut buffer)? {
+                            0 => break,
+                            n => stream.write_all(&buffer[..n])?,
+                        }
                     }
                 }
 
@@ -178,20 +226,30 @@ impl Rocket {
     /// Keep this in-sync with derive_form when preprocessing form fields.
     fn preprocess_request(&self, req: &mut Request<'_>, data: &Data) {
         // Check if this is a form and if the form contains the special _method
-        // field which we use to reinterpret the request's method.
+        // field which we use to reinterpret the request's method. Only built-in
+        // methods are accepted, and only if the field's value is complete: it
+        // must be followed by a `&` or the end of the body.
         let data_len = data.peek().len();
-        let (min_len, max_len) = ("_method=get".len(), "_method=delete".len());
+        let (min_len, max_len) = ("_method=get".len(), "_method=updateredirectref&".len());
         let is_form = req.content_type().map_or(false, |ct| ct.is_form());
 
         if is_form && req.method() == Method::Post && data_len >= min_len {
+            let is_whole_body = data_len <= max_len && data.peek_complete();
             if let Ok(form) = from_utf8(&data.peek()[..min(data_len, max_len)]) {
-                let method: Option<Result<Method, _>> = FormItems::from(form)
-                    .filter(|item| item.key.as_str() == "_method")
-                    .map(|item| item.value.parse())
-                    .next();
+                let mut fields = form.split('&').peekable();
+                while let Some(field) = fields.next() {
+                    if fields.peek().is_none() && !is_whole_body {
+                        break;
+                    }
 
-                if let Some(Ok(method)) = method {
-                    req.set_method(method);
+                    if field.starts_with("_method=") {
+                        match field["_method=".len()..].parse::<Method>() {
+                            Ok(method) if !method.is_extension() => req.set_method(method),
+                            _ => { /* not a method we can reinterpret as */ }
+                        }
+
+                        break;
+                    }
                 }
             }
         }
@@ -209,22 +267,42 @@ impl Rocket {
         self.preprocess_request(request, &data);
 
         // Run the request fairings.
-        self.fairings.handle_request(request, &data);
+        let fairings_ok = self.fairings.handle_request(request, &data).is_ok();
 
         // Remember if the request is a `HEAD` request for later body stripping.
         let was_head_request = request.method() == Method::Head;
 
-        // Route the request and run the user's handlers.
-        let mut response = self.route_and_process(request, data);
+        // Route the request and run the user's handlers, unless a request
+        // fairing panicked, in which case the request can't be trusted.
+        let mut response = match fairings_ok {
+            true => self.route_and_process(request, data),
+            false => self.handle_error(Status::InternalServerError, request),
+        };
+
+        // Add the `Date` and configured default headers so that fairings see
+        // them. Headers already set by the handler or catcher are kept.
+        self.add_default_headers(&mut response);
 
-        // Add a default 'Server' header if it isn't already there.
-        // TODO: If removing Hyper, write out `Date` header too.
-        if !response.headers().contains("Server") {
-            response.set_header(Header::new("Server", "Rocket"));
+        // Run the response fairings. A panicking fairing may have left the
+        // response in any state, so it's replaced with a 500 response.
+        if self.fairings.handle_response(request, &mut response).is_err() {
+            response = self.handle_error(Status::InternalServerError, request);
+            self.add_default_headers(&mut response);
         }
 
-        // Run the response fairings.
-        self.fairings.handle_response(request, &mut response);
+        // Answer conditional requests using the response's validators. If the
+        // full representation is still to be sent, serve only the requested
+        // ranges of it, if any. Ranges don't apply to `HEAD` requests, which
+        // are dispatched as `GET` requests.
+        match conditional::evaluate_response(request, &response) {
+            Some(Status::NotModified) => conditional::not_modified(&mut response),
+            Some(status) => {
+                response = self.handle_error(status, request);
+                self.add_default_headers(&mut response);
+            }
+            None if !was_head_request => serve_ranges(request, &mut response),
+            None => { /* the body is stripped below */ }
+        }
 
         // Strip the body if this is a `HEAD` request.
         if was_head_request {
@@ -234,6 +312,49 @@ impl Rocket {
         response
     }
 
+    /// Responds to a request that could not be parsed using the malformed
+    /// request handler, falling back to the default handler if it fails or
+    /// panics, then runs the malformed request fairings.
+    pub(crate) fn handle_malformed(&self, request: &MalformedRequest) -> Response<'static> {
+        warn!("Malformed request {}:", request);
+        warn_!("{}", request.error());
+
+        let handler = self.malformed;
+        let result = catch_panic(|| handler(request)).unwrap_or_else(|msg| {
+            error_!("Malformed request handler panicked: {}", msg);
+            Err(Status::InternalServerError)
+        });
+
+        let mut response = result.unwrap_or_else(|status| {
+            error_!("Malformed request handler failed with status: {}!", status);
+            warn_!("Using default malformed request handler.");
+            catcher::defaults::handle_malformed(request).expect("default 400 response")
+        });
+
+        self.add_default_headers(&mut response);
+        if self.fairings.handle_malformed(request, &mut response).is_err() {
+            response = catcher::defaults::handle_malformed(request)
+                .expect("default 400 response");
+            self.add_default_headers(&mut response);
+        }
+
+        response
+    }
+
+    /// Adds a `Date` header and the configured default headers to `response`,
+    /// skipping any header that `response` already contains.
+    fn add_default_headers(&self, response: &mut Response<'_>) {
+        if !response.headers().contains("Date") {
+            response.set_header(date::header());
+        }
+
+        for (name, value) in self.config.headers.iter() {
+            if !response.headers().contains(name) {
+                response.set_header(Header::new(name.to_string(), value.to_string()));
+            }
+        }
+    }
+
     /// Route the request and process the outcome to eventually get a response.
     fn route_and_process<'s, 'r>(
         &'s self,
@@ -252,10 +373,11 @@ impl Rocket {
 
                     // Return early so we don't set cookies twice.
                     return self.route_and_process(request, data);
-                } else {
-                    // No match was found and it can't be autohandled. 404.
-                    self.handle_error(Status::NotFound, request)
                 }
+
+                // No match was found. See if the path is routed under some
+                // other method, in which case we may be able to respond.
+                self.handle_unrouted(request)
             }
             Outcome::Failure(status) => self.handle_error(status, request),
         };
@@ -269,6 +391,45 @@ impl Rocket {
         response
     }
 
+    /// Responds to a `request` that no route handled. If routes of other
+    /// methods match the request's path, `OPTIONS` requests are autohandled
+    /// and other requests fail with a `405`, each with an `Allow` header
+    /// listing the methods the path can be requested with, as configured.
+    /// Otherwise, the request fails with a `404`.
+    fn handle_unrouted<'r>(&self, request: &'r Request<'_>) -> Response<'r> {
+        let mut methods = self.router.methods_for(request);
+        if methods.is_empty() || methods.contains(&request.method()) {
+            // Nothing to autohandle: the path is unknown or routes forwarded.
+            return self.handle_error(Status::NotFound, request);
+        }
+
+        // `HEAD` is autohandled by `GET` routes.
+        if methods.contains(&Method::Get) && !methods.contains(&Method::Head) {
+            methods.push(Method::Head);
+        }
+
+        if self.config.auto_options && !methods.contains(&Method::Options) {
+            methods.push(Method::Options);
+        }
+
+        let mut methods: Vec<_> = methods.iter().map(|m| m.as_str()).collect();
+        methods.sort();
+        let allow = Header::new("Allow", methods.join(", "));
+        if request.method() == Method::Options && self.config.auto_options {
+            info_!("Autohandling {} request.", Paint::default("OPTIONS").bold());
+            let mut response = Response::new();
+            response.set_status(Status::NoContent);
+            response.set_header(allow);
+            response
+        } else if self.config.method_not_allowed {
+            let mut response = self.handle_error(Status::MethodNotAllowed, request);
+            response.set_header(allow);
+            response
+        } else {
+            self.handle_error(Status::NotFound, request)
+        }
+    }
+
     /// Tries to find a `Responder` for a given `request`. It does this by
     /// routing the request and calling the handler for each matching route
     /// until one of the handlers returns success or failure, or there are no
@@ -293,8 +454,18 @@ impl Rocket {
             info_!("Matched: {}", route);
             request.set_route(route);
 
-            // Dispatch the request to the handler.
-            let outcome = route.handler.handle(request, data);
+            // Dispatch the request to the handler. A panic in the handler or
+            // in one of its guards fails the request.
+            let outcome = match catch_panic(|| route.handler.handle(request, data)) {
+                Ok(outcome) => outcome,
+                Err(msg) => {
+                    let name = route.name.map(|name| name.to_string())
+                        .unwrap_or_else(|| route.to_string());
+
+                    error_!("Route {} panicked: {}", Paint::default(name).bold(), msg);
+                    Outcome::Failure(Status::InternalServerError)
+                }
+            };
 
             // Check if the request processing completed or if the request needs
             // to be forwarded. If it does, continue the loop to try again.
@@ -311,10 +482,12 @@ impl Rocket {
 
     // Finds the error catcher for the status `status` and executes it for the
     // given request `req`; the cookies in `req` are reset to their original
-    // state before invoking the error handler. If a user has registered a
-    // catcher for `status`, the catcher is called. If the catcher fails to
-    // return a good response, the 500 catcher is executed. If there is no
-    // registered catcher for `status`, the default catcher is used.
+    // state before invoking the error handler. Of the registered catchers for
+    // `status` or of the default ones, whose base contains `req`'s path, the
+    // one with the most specific base is called, preferring catchers for
+    // `status`. If the catcher fails to return a good response, the built-in
+    // 500 catcher is executed. If no registered catcher matches, the built-in
+    // catcher for `status` is used.
     pub(crate) fn handle_error<'r>(
         &self,
         status: Status,
@@ -327,16 +500,32 @@ impl Rocket {
         // We may wish to relax this in the future.
         req.cookies().reset_delta();
 
-        // Try to get the active catcher but fallback to user's 500 catcher.
-        let catcher = self.catchers.get(&status.code).unwrap_or_else(|| {
-            error_!("No catcher found for {}. Using 500 catcher.", status);
-            self.catchers.get(&500).expect("500 catcher.")
+        if let Some(error) = req.guard_error() {
+            info_!("Guard error: {}", error);
+        }
+
+        // Try to get the most specific user catcher but fallback to the
+        // built-in catcher for `status`, if there is one.
+        let user_catcher = self.catchers.iter()
+            .filter(|catcher| catcher.matches(status, req))
+            .max_by_key(|catcher| catcher.precedence());
+
+        let catcher = user_catcher.or_else(|| self.default_catchers.get(&status.code));
+
+        // Dispatch to the catcher. If it fails or panics, use the default 500.
+        req.state.error_status.set(Some(status));
+        let result = catch_panic(|| match catcher {
+            Some(catcher) => catcher.handle(req),
+            None => catcher::defaults::handle_default(status, req),
+        }).unwrap_or_else(|msg| {
+            error_!("Catcher for {} panicked: {}", status, msg);
+            Err(Status::InternalServerError)
         });
 
-        // Dispatch to the user's catcher. If it fails, use the default 500.
-        catcher.handle(req).unwrap_or_else(|err_status| {
+        result.unwrap_or_else(|err_status| {
             error_!("Catcher failed with status: {}!", err_status);
             warn_!("Using default 500 error catcher.");
+            req.state.error_status.set(Some(Status::InternalServerError));
             let default = self.default_catchers.get(&500).expect("Default 500");
             default.handle(req).expect("Default 500 response.")
         })
@@ -424,12 +613,30 @@ impl Rocket {
         launch_info_!("workers: {}", Paint::default(config.workers).bold());
         launch_info_!("secret key: {}", Paint::default(&config.secret_key).bold());
         launch_info_!("limits: {}", Paint::default(&config.limits).bold());
+        launch_info_!("default headers: {}", Paint::default(&config.headers).bold());
 
         match config.keep_alive {
             Some(v) => launch_info_!("keep-alive: {}", Paint::default(format!("{}s", v)).bold()),
             None => launch_info_!("keep-alive: {}", Paint::default("disabled").bold()),
         }
 
+        let timeouts = [
+            ("read", config.read_timeout),
+            ("write", config.write_timeout),
+            ("header", config.header_timeout)
+        ];
+
+        for &(name, timeout) in &timeouts {
+            match timeout {
+                Some(v) => {
+                    launch_info_!("{} timeout: {}", name, Paint::default(format!("{}s", v)).bold())
+                }
+                None => launch_info_!("{} timeout: {}", name, Paint::default("disabled").bold()),
+            }
+        }
+
+        launch_info_!("shutdown: {}", Paint::default(&config.shutdown).bold());
+
         let tls_configured = config.tls.is_some();
         if tls_configured && cfg!(feature = "tls") {
             launch_info_!("tls: {}", Paint::default("enabled").bold());
@@ -440,6 +647,11 @@ impl Rocket {
             launch_info_!("tls: {}", Paint::default("disabled").bold());
         }
 
+        match config.proxy_protocol {
+            true => launch_info_!("proxy protocol: {}", Paint::default("required").bold()),
+            false => launch_info_!("proxy protocol: {}", Paint::default("disabled").bold()),
+        }
+
         if config.secret_key.is_generated() && config.environment.is_prod() {
             warn!("environment is 'production', but no `secret_key` is configured");
         }
@@ -454,9 +666,12 @@ impl Rocket {
             config,
             router: Router::new(),
             default_catchers: catcher::defaults::get(),
-            catchers: catcher::defaults::get(),
+            catchers: vec![],
+            malformed: catcher::defaults::handle_malformed,
             state: Container::new(),
             fairings: Fairings::new(),
+            shutdown: Shutdown::new(),
+            listener: None,
         }
     }
 
@@ -515,10 +730,70 @@ impl Rocket {
     /// # }
     /// ```
     #[inline]
-    pub fn mount<R: Into<Vec<Route>>>(mut self, base: &str, routes: R) -> Self {
-        info!("{}{} {}{}",
+    pub fn mount<R: Into<Vec<Route>>>(self, base: &str, routes: R) -> Self {
+        self._mount(None, base, routes.into())
+    }
+
+    /// Mounts all of the routes in the supplied vector at the given `base`
+    /// path for requests to hosts matching the [`HostPattern`] `host`. Routes
+    /// that already have a host pattern keep it. Otherwise, this is identical
+    /// to [`Rocket::mount()`].
+    ///
+    /// A route with a host only matches requests whose `Host` header matches
+    /// the pattern. Among matching routes of the same rank, those with more
+    /// specific hosts are tried first, so routes mounted at a host don't
+    /// collide with the same routes mounted for all hosts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `host` is not a valid host pattern. Otherwise panics for the
+    /// same reasons as [`Rocket::mount()`].
+    ///
+    /// # Example
+    ///
+    /// Requests to `/` on `api.rocket.rs` are dispatched to `api_index`, while
+    /// requests to `/` on any other host are dispatched to `index`.
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// #
+    /// #[get("/")]
+    /// fn index() -> &'static str {
+    ///     "Hello!"
+    /// }
+    ///
+    /// #[get("/")]
+    /// fn api_index() -> &'static str {
+    ///     "Hello, API!"
+    /// }
+    ///
+    /// fn main() {
+    /// # if false { // We don't actually want to launch the server in an example.
+    ///     rocket::ignite()
+    ///         .mount("/", routes![index])
+    ///         .mount_host("api.rocket.rs", "/", routes![api_index])
+    /// #       .launch();
+    /// # }
+    /// }
+    /// ```
+    #[inline]
+    pub fn mount_host<R: Into<Vec<Route>>>(self, host: &str, base: &str, routes: R) -> Self {
+        let pattern = HostPattern::parse(host)
+            .unwrap_or_else(|e| {
+                error_!("Invalid host pattern '{}' used as mount point.", host);
+                panic!("Error: {}", e);
+            });
+
+        self._mount(Some(pattern), base, routes.into())
+    }
+
+    fn _mount(mut self, host: Option<HostPattern>, base: &str, routes: Vec<Route>) -> Self {
+        let host_str = host.as_ref().map(|h| h.to_string()).unwrap_or_default();
+        info!("{}{} {}{}{}",
               Paint::emoji("🛰  "),
               Paint::magenta("Mounting"),
+              Paint::blue(host_str),
               Paint::blue(base),
               Paint::magenta(":"));
 
@@ -533,21 +808,32 @@ impl Rocket {
             panic!("Invalid mount point.");
         }
 
-        for mut route in routes.into() {
+        for mut route in routes {
             let path = route.uri.clone();
             if let Err(e) = route.set_uri(base_uri.clone(), path) {
                 error_!("{}", e);
                 panic!("Invalid route URI.");
             }
 
+            if route.host.is_none() {
+                route.host = host.clone();
+            }
+
             info_!("{}", route);
-            self.router.add(route);
+            if let Some(first) = self.router.add(route) {
+                let name = first.name.unwrap_or_default();
+                warn_!("The route name '{}' is already used by {}.", name, first);
+                warn_!("URIs built for '{}' will refer to that route. \
+                    Use `name = \"...\"` to rename one of the routes.", name);
+            }
         }
 
         self
     }
 
-    /// Registers all of the catchers in the supplied vector.
+    /// Registers all of the catchers in the supplied vector. The catchers
+    /// handle errors for every request, unless a catcher registered under a
+    /// more specific base with [`Rocket::register_at()`] applies.
     ///
     /// # Examples
     ///
@@ -575,21 +861,128 @@ impl Rocket {
     /// }
     /// ```
     #[inline]
-    pub fn register(mut self, catchers: Vec<Catcher>) -> Self {
-        info!("{}{}", Paint::emoji("👾 "), Paint::magenta("Catchers:"));
-        for c in catchers {
-            if self.catchers.get(&c.code).map_or(false, |e| !e.is_default) {
+    pub fn register(self, catchers: Vec<Catcher>) -> Self {
+        self.register_at("/", catchers)
+    }
+
+    /// Registers all of the catchers in the supplied vector under the base
+    /// path `base`. A catcher only handles errors for requests whose path is
+    /// within its base. See [`Catcher`] for how a catcher is chosen when
+    /// several match.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `base` is not a valid static path: a valid origin URI
+    /// without dynamic parameters or a query.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::Request;
+    ///
+    /// #[catch(404)]
+    /// fn not_found(req: &Request) -> String {
+    ///     format!("I couldn't find '{}'. Try something else?", req.uri())
+    /// }
+    ///
+    /// #[catch(default)]
+    /// fn api_error(req: &Request) -> String {
+    ///     let status = req.error_status().expect("handling an error");
+    ///     format!("{{ \"status\": {} }}", status.code)
+    /// }
+    ///
+    /// fn main() {
+    /// # if false { // We don't actually want to launch the server in an example.
+    ///     rocket::ignite()
+    ///         .register(catchers![not_found])
+    ///         .register_at("/api", catchers![api_error])
+    /// #       .launch();
+    /// # }
+    /// }
+    /// ```
+    pub fn register_at(mut self, base: &str, catchers: Vec<Catcher>) -> Self {
+        info!("{}{} {}{}",
+              Paint::emoji("👾 "),
+              Paint::magenta("Catchers"),
+              Paint::blue(base),
+              Paint::magenta(":"));
+
+        let base_uri = Origin::parse(base)
+            .unwrap_or_else(|e| {
+                error_!("Invalid origin URI '{}' used as catcher base.", base);
+                panic!("Error: {}", e);
+            });
+
+        if base_uri.query().is_some() || base_uri.segments().any(|s| s.starts_with('<')) {
+            error_!("Catcher base '{}' contains a query string or parameters.", base);
+            panic!("Invalid catcher base.");
+        }
+
+        let base_uri = base_uri.to_normalized().into_owned();
+        for mut c in catchers {
+            c.base = base_uri.clone();
+            let existing = self.catchers.iter()
+                .position(|e| e.code == c.code && e.is_default == c.is_default && e.base == c.base);
+
+            if let Some(i) = existing {
                 info_!("{} {}", c, Paint::yellow("(warning: duplicate catcher!)"));
+                self.catchers[i] = c;
             } else {
                 info_!("{}", c);
+                self.catchers.push(c);
             }
-
-            self.catchers.insert(c.code, c);
         }
 
         self
     }
 
+    /// Sets the handler for requests that Rocket could not parse to `handler`.
+    ///
+    /// A request is malformed when its method isn't one that Rocket
+    /// understands or when its target isn't a valid origin URI. Such requests
+    /// can't be routed and never reach a route or catcher. Instead, `handler`
+    /// is called with the raw request as a [`MalformedRequest`] and produces
+    /// the response. If `handler` fails or panics, Rocket responds with its
+    /// default `400 Bad Request` page. By default, Rocket always responds with
+    /// that page.
+    ///
+    /// Responses produced by `handler` are passed to the `on_malformed`
+    /// callbacks of attached [fairings](crate::fairing::Fairing), not to their
+    /// response callbacks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io::Cursor;
+    ///
+    /// use rocket::request::MalformedRequest;
+    /// use rocket::response::{self, Response};
+    /// use rocket::http::Status;
+    ///
+    /// fn malformed(request: &MalformedRequest) -> response::Result<'static> {
+    ///     let body = format!("Couldn't understand {}: {}", request.target(), request.error());
+    ///     Response::build()
+    ///         .status(Status::BadRequest)
+    ///         .sized_body(Cursor::new(body))
+    ///         .ok()
+    /// }
+    ///
+    /// fn main() {
+    /// # if false { // We don't actually want to launch the server in an example.
+    ///     rocket::ignite()
+    ///         .on_malformed(malformed)
+    /// #       .launch();
+    /// # }
+    /// }
+    /// ```
+    #[inline]
+    pub fn on_malformed(mut self, handler: MalformedHandler) -> Self {
+        self.malformed = handler;
+        self
+    }
+
     /// Add `state` to the state managed by this instance of Rocket.
     ///
     /// This method can be called any number of times as long as each call
@@ -671,6 +1064,28 @@ impl Rocket {
         self
     }
 
+    /// Sets the socket this instance of Rocket listens on when launched to
+    /// `listener` instead of binding a new one according to the configured
+    /// `address` and `port`. See [`Listener`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0").expect("bound socket");
+    /// let addr = listener.local_addr().unwrap();
+    ///
+    /// let running = rocket::ignite().listen_on(listener).spawn().expect("launched");
+    /// assert_eq!(running.addr(), addr);
+    /// running.stop();
+    /// ```
+    #[inline]
+    pub fn listen_on<L: Into<Listener>>(mut self, listener: L) -> Self {
+        self.listener = Some(listener.into());
+        self
+    }
+
     pub(crate) fn prelaunch_check(mut self) -> Result<Rocket, LaunchError> {
         self.router = match self.router.collisions() {
             Ok(router) => router,
@@ -686,13 +1101,20 @@ impl Rocket {
 
     /// Starts the application server and begins listening for and dispatching
     /// requests to mounted routes and catchers. Unless there is an error, this
-    /// function does not return and blocks until program termination.
+    /// function blocks until a graceful shutdown, requested via a [`Shutdown`]
+    /// handle or, if enabled in the configuration, by a `SIGINT` or `SIGTERM`
+    /// signal, has completed: the server stops accepting connections, waits for
+    /// in-flight requests to complete for at most the configured grace period,
+    /// and then returns a [`LaunchError`] of kind
+    /// [`LaunchErrorKind::Shutdown`]. To launch without blocking, use
+    /// [`Rocket::spawn()`].
     ///
     /// # Error
     ///
     /// If there is a problem starting the application, a [`LaunchError`] is
     /// returned. Note that a value of type `LaunchError` panics if dropped
-    /// without first being inspected. See the [`LaunchError`] documentation for
+    /// without first being inspected, unless its kind is
+    /// [`LaunchErrorKind::Shutdown`]. See the [`LaunchError`] documentation for
     /// more information.
     ///
     /// # Example
@@ -702,54 +1124,220 @@ impl Rocket {
     /// rocket::ignite().launch();
     /// # }
     /// ```
-    pub fn launch(mut self) -> LaunchError {
-        self = match self.prelaunch_check() {
-            Ok(rocket) => rocket,
-            Err(launch_error) => return launch_error
-        };
+    ///
+    /// To distinguish a graceful shutdown from a failure to launch:
+    ///
+    /// ```rust
+    /// use rocket::error::LaunchErrorKind;
+    ///
+    /// # if false {
+    /// let error = rocket::ignite().launch();
+    /// if let LaunchErrorKind::Shutdown = error.kind() {
+    ///     println!("Rocket has shut down.");
+    /// }
+    /// # }
+    /// ```
+    pub fn launch(self) -> LaunchError {
+        match self.spawn() {
+            Ok(running) => {
+                running.join();
+                LaunchError::new(LaunchErrorKind::Shutdown)
+            }
+            Err(launch_error) => launch_error
+        }
+    }
 
+    /// Starts the application server in the background and returns a
+    /// [`Running`] handle to it. This function returns as soon as the server
+    /// is bound and all launch fairings have run. The handle reports the
+    /// address the server is bound to and can be used to wait for or to
+    /// request a graceful shutdown.
+    ///
+    /// # Error
+    ///
+    /// If there is a problem starting the application, a [`LaunchError`] is
+    /// returned. See [`Rocket::launch()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::config::{Config, Environment};
+    ///
+    /// let config = Config::build(Environment::Staging)
+    ///     .address("127.0.0.1")
+    ///     .port(0)
+    ///     .unwrap();
+    ///
+    /// let running = rocket::custom(config).spawn().expect("launch failed");
+    /// println!("Rocket is listening on {}", running.addr());
+    /// running.stop();
+    /// ```
+    pub fn spawn(mut self) -> Result<Running, LaunchError> {
+        self = self.prelaunch_check()?;
         self.fairings.pretty_print_counts();
 
-        let full_addr = format!("{}:{}", self.config.address, self.config.port);
-        serve!(self, &full_addr, |server, proto| {
-            let mut server = match server {
-                Ok(server) => server,
-                Err(e) => return LaunchError::new(LaunchErrorKind::Bind(e)),
-            };
+        // Use the listener we were given, if any, or bind one as configured.
+        // Only socket files we created ourselves are removed during shutdown
+        // or, if launching fails, when `sockets` is dropped.
+        let mut sockets = SocketFiles::default();
+        let mode = self.config.socket_mode;
+        let mut bind = |address: &str, port: u16| {
+            let listener = Listener::bind(address, port, mode)
+                .map_err(|e| LaunchError::new(LaunchErrorKind::Bind(e.into())))?;
+
+            sockets.track(&listener);
+            Ok::<_, LaunchError>(listener)
+        };
 
-            // Determine the address and port we actually binded to.
-            match server.local_addr() {
-                Ok(server_addr) => self.config.port = server_addr.port(),
-                Err(e) => return LaunchError::from(e),
+        let listener = match self.listener.take() {
+            Some(listener) => {
+                listener.configure(&mut self.config)?;
+                listener
             }
+            None => bind(&self.config.address, self.config.port)?,
+        };
 
-            // Set the keep-alive.
-            let timeout = self.config.keep_alive.map(|s| Duration::from_secs(s as u64));
-            server.keep_alive(timeout);
+        // Determine the address and port we actually binded to.
+        let addr = listener.local_addr()?;
+        if !listener.is_unix() {
+            self.config.port = addr.port();
+        }
 
-            // Freeze managed state for synchronization-free accesses later.
-            self.state.freeze();
+        // Bind all of the additional listeners.
+        let mut extras = vec![];
+        for config in self.config.listeners.clone() {
+            let listener = bind(&config.address, config.port)?;
+            extras.push((listener.local_addr()?, listener, config));
+        }
 
-            // Run the launch fairings.
-            self.fairings.handle_launch(&self);
+        // Bind the redirecting listener, which targets the first TLS listener.
+        let redirect = match self.config.redirect.clone() {
+            Some(config) => {
+                let target = match self.config.tls {
+                    Some(_) if !listener.is_unix() => Some(addr.port()),
+                    _ => extras.iter()
+                        .find(|(_, listener, config)| config.tls.is_some() && !listener.is_unix())
+                        .map(|(addr, _, _)| addr.port())
+                };
+
+                let port = target.ok_or_else(|| {
+                    let msg = "`redirect` requires a TCP listener with TLS enabled";
+                    LaunchError::new(LaunchErrorKind::Unknown(msg.into()))
+                })?;
+
+                let listener = bind(&config.address, config.port)?;
+                Some((listener.local_addr()?, listener, config, port))
+            }
+            None => None
+        };
 
-            let full_addr = format!("{}:{}", self.config.address, self.config.port);
-            launch_info!("{}{} {}{}",
-                         Paint::emoji("🚀 "),
-                         Paint::default("Rocket has launched from").bold(),
-                         Paint::default(proto).bold().underline(),
-                         Paint::default(&full_addr).bold().underline());
+        // Freeze managed state for synchronization-free accesses later.
+        self.state.freeze();
 
-            // Restore the log level back to what it originally was.
-            logger::pop_max_level();
+        // Run the launch fairings.
+        self.fairings.handle_launch(&self);
 
-            let threads = self.config.workers as usize;
-            if let Err(e) = server.handle_threads(self, threads) {
-                return LaunchError::from(e);
+        let display = |address: &str, addr: SocketAddr, listener: &Listener| {
+            match listener.is_unix() {
+                true => address.to_string(),
+                false => format!("{}:{}", address, addr.port()),
             }
+        };
 
-            unreachable!("the call to `handle_threads` should block on success")
-        })
+        let proto = server::proto(&listener, self.config.tls.is_some());
+        let full_addr = display(&self.config.address, addr, &listener);
+        launch_info!("{}{} {}{}",
+                     Paint::emoji("🚀 "),
+                     Paint::default("Rocket has launched from").bold(),
+                     Paint::default(proto).bold().underline(),
+                     Paint::default(&full_addr).bold().underline());
+
+        let proxied = |enabled: bool| match enabled {
+            true => " (proxy protocol)",
+            false => "",
+        };
+
+        for (addr, listener, config) in &extras {
+            let proto = server::proto(listener, config.tls.is_some());
+            let full_addr = display(&config.address, *addr, listener);
+            launch_info_!("also listening on {}{}{}",
+                          Paint::default(proto).bold().underline(),
+                          Paint::default(&full_addr).bold().underline(),
+                          proxied(config.proxy_protocol));
+        }
+
+        if let Some((addr, ref listener, ref config, port)) = redirect {
+            let full_addr = display(&config.address, addr, listener);
+            launch_info_!("redirecting {}{}{} to HTTPS on port {}",
+                          Paint::default(server::proto(listener, false)).bold().underline(),
+                          Paint::default(&full_addr).bold().underline(),
+                          proxied(config.proxy_protocol),
+                          Paint::default(port).bold());
+        }
+
+        // Restore the log level back to what it originally was.
+        logger::pop_max_level();
+
+        // Listen for shutdown signals, if requested, until the server stops.
+        let signals = match self.config.shutdown.ctrlc {
+            true => self.shutdown.listen_for_signals()
+                .map_err(|e| warn!("Failed to listen for shutdown signals: {}", e))
+                .ok(),
+            false => None,
+        };
+
+        let shutdown = self.shutdown.clone();
+        let grace = Duration::from_secs(self.config.shutdown.grace as u64);
+        let tls = self.config.tls.clone();
+
+        // All listeners, except the redirecting one, dispatch to this Rocket.
+        let rocket = Arc::new(self);
+        let config = &rocket.config;
+        let mut addrs = vec![addr];
+        let mut servers = vec![];
+        let handler = Shared(rocket.clone());
+        let proxy = config.proxy_protocol;
+        servers.push(server::serve(listener, tls, proxy, handler, &shutdown, config)?);
+
+        for (addr, listener, listener_config) in extras {
+            let handler = Shared(rocket.clone());
+            addrs.push(addr);
+            let proxy = listener_config.proxy_protocol;
+            servers.push(server::serve(listener, listener_config.tls, proxy, handler,
+                                       &shutdown, config)?);
+        }
+
+        if let Some((addr, listener, redirect_config, port)) = redirect {
+            let handler = Redirect { port, shutdown: shutdown.clone() };
+            let proxy = redirect_config.proxy_protocol;
+            addrs.push(addr);
+            servers.push(server::serve(listener, None, proxy, handler, &shutdown, config)?);
+        }
+
+        Ok(Running::new(addrs, sockets.keep(), shutdown, signals, grace, servers)?)
+    }
+
+    /// Returns a handle that can be used to gracefully shut down this instance
+    /// of Rocket once it has been launched. See [`Shutdown`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # if false {
+    /// let rocket = rocket::ignite();
+    /// let shutdown = rocket.shutdown();
+    /// std::thread::spawn(move || {
+    ///     std::thread::sleep(std::time::Duration::from_secs(10));
+    ///     shutdown.notify();
+    /// });
+    ///
+    /// // Shuts down after approximately ten seconds.
+    /// rocket.spawn().expect("launch failed").join();
+    /// # }
+    /// ```
+    #[inline(always)]
+    pub fn shutdown(&self) -> Shutdown {
+        self.shutdown.clone()
     }
 
     /// Returns an iterator over all of the routes mounted on this instance of
@@ -789,6 +1377,62 @@ impl Rocket {
         self.router.routes()
     }
 
+    /// Returns the URI to the mounted route named `name`, including the mount
+    /// point, with the values in `params` for the route's dynamic parameters.
+    /// A route's name is the name of its function unless the route attribute
+    /// sets one with `name = "..."`. If several mounted routes have the name
+    /// `name`, the one mounted first is used, and Rocket warns about the
+    /// duplicate name when the later route is mounted.
+    ///
+    /// Within a request handler, the [`RouteUris`](crate::request::RouteUris)
+    /// request guard provides the same functionality.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no mounted route is named `name` or if `params`
+    /// doesn't fit the route. See [`Route::uri_for()`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::UriParams;
+    ///
+    /// #[get("/<id>?<verbose>")]
+    /// fn user(id: usize, verbose: Option<bool>) { /* .. */ }
+    ///
+    /// #[get("/", name = "home")]
+    /// fn index() { /* .. */ }
+    ///
+    /// fn main() {
+    ///     let rocket = rocket::ignite()
+    ///         .mount("/", routes![index])
+    ///         .mount("/users", routes![user]);
+    ///
+    ///     let uri = rocket.uri_for("user", &UriParams::new().param("id", &7)).unwrap();
+    ///     assert_eq!(uri.to_string(), "/users/7");
+    ///
+    ///     let params = UriParams::new().param("id", &7).param("verbose", &true);
+    ///     let uri = rocket.uri_for("user", &params).unwrap();
+    ///     assert_eq!(uri.to_string(), "/users/7?verbose=true");
+    ///
+    ///     let uri = rocket.uri_for("home", &UriParams::new()).unwrap();
+    ///     assert_eq!(uri.to_string(), "/");
+    ///
+    ///     assert!(rocket.uri_for("index", &UriParams::new()).is_err());
+    ///     assert!(rocket.uri_for("user", &UriParams::new()).is_err());
+    /// }
+    /// ```
+    #[inline]
+    pub fn uri_for(
+        &self,
+        name: &str,
+        params: &UriParams<'_>
+    ) -> Result<Origin<'static>, UriForError> {
+        self.router.uri_for(name, params)
+    }
+
     /// Returns `Some` of the managed state value for the type `T` if it is
     /// being managed by `self`. Otherwise, returns `None`.
     ///
@@ -1,9 +1,14 @@
 use super::Route;
 
-use crate::http::MediaType;
-use crate::http::route::Kind;
+use crate::http::{MediaType, ContentType};
+use crate::http::route::{RouteSegment, Kind};
+use crate::http::uri::{Origin, Path};
 use crate::request::Request;
 
+/// The value of dynamic parameters in example requests. Most parameter types
+/// can be parsed from it.
+const EXAMPLE_VALUE: &str = "1";
+
 impl Route {
     /// Determines if two routes can match against some request. That is, if two
     /// routes `collide`, there exists a request that can match against both
@@ -17,10 +22,19 @@ impl Route {
     ///
     /// Because query parsing is lenient, and dynamic query parameters can be
     /// missing, queries do not impact whether two routes collide.
+    ///
+    /// Host collisions work like this:
+    ///
+    ///   * Routes without a host collide with each other.
+    ///   * Routes with a host pattern collide if some host matches both
+    ///     patterns and both patterns have as many static labels.
+    ///   * Otherwise, routes don't collide: the route with the more specific
+    ///     host, if any, is always tried first.
     #[doc(hidden)]
     pub fn collides_with(&self, other: &Route) -> bool {
         self.method == other.method
             && self.rank == other.rank
+            && hosts_collide(self, other)
             && paths_collide(self, other)
             && formats_collide(self, other)
     }
@@ -29,6 +43,9 @@ impl Route {
     /// that:
     ///
     ///   * The route's method matches that of the incoming request.
+    ///   * The route's host pattern (if any) matches the incoming request's
+    ///     `Host` header, ignoring its port.
+    ///     - If route has no host, it gets requests for any host.
     ///   * The route's format (if any) matches that of the incoming request.
     ///     - If route specifies format, it only gets requests for that format.
     ///     - If route doesn't specify format, it gets requests for any format.
@@ -40,10 +57,48 @@ impl Route {
     #[doc(hidden)]
     pub fn matches(&self, req: &Request<'_>) -> bool {
         self.method == req.method()
+            && hosts_match(self, req)
             && paths_match(self, req)
             && queries_match(self, req)
             && formats_match(self, req)
     }
+
+    /// Determines if this route matches the host, path, and query of `req`,
+    /// regardless of the request's method and format.
+    pub(crate) fn matches_uri(&self, req: &Request<'_>) -> bool {
+        hosts_match(self, req) && paths_match(self, req) && queries_match(self, req)
+    }
+
+    /// Returns an example of a request that both `self` and `other`, which
+    /// must collide, match: its host and `Content-Type`, if either is needed
+    /// for the request to match, and its URI. Dynamic segments and query
+    /// parameters are given a value of `1`.
+    pub(crate) fn collision_example(
+        &self,
+        other: &Route
+    ) -> (Option<String>, Origin<'static>, Option<ContentType>) {
+        let host = match (self.host.as_ref(), other.host.as_ref()) {
+            (Some(a), Some(b)) => Some(a.example(b)),
+            _ => None
+        };
+
+        (host, example_uri(self, other), example_content_type(self, other))
+    }
+}
+
+fn hosts_collide(route: &Route, other: &Route) -> bool {
+    match (route.host.as_ref(), other.host.as_ref()) {
+        (Some(a), Some(b)) => a.specificity() == b.specificity() && a.collides_with(b),
+        (None, None) => true,
+        _ => false
+    }
+}
+
+fn hosts_match(route: &Route, request: &Request<'_>) -> bool {
+    match route.host {
+        Some(ref pattern) => request.host().map_or(false, |h| pattern.matches(h.host())),
+        None => true
+    }
 }
 
 fn paths_collide(route: &Route, other: &Route) -> bool {
@@ -109,6 +164,77 @@ fn queries_match(route: &Route, request: &Request<'_>) -> bool {
     true
 }
 
+fn example_uri(route: &Route, other: &Route) -> Origin<'static> {
+    let value = |segment: &RouteSegment<'_, Path>| match segment.kind {
+        Kind::Static => segment.string.to_string(),
+        Kind::Single | Kind::Multi => EXAMPLE_VALUE.to_string(),
+    };
+
+    let a_segments = &route.metadata.path_segments;
+    let b_segments = &other.metadata.path_segments;
+    let mut segments = vec![];
+    for (i, (seg_a, seg_b)) in a_segments.iter().zip(b_segments.iter()).enumerate() {
+        // A multi-segment parameter matches the rest of the other path.
+        let rest = match (seg_a.kind, seg_b.kind) {
+            (Kind::Multi, Kind::Multi) => &a_segments[i..=i],
+            (Kind::Multi, _) => &b_segments[i..],
+            (_, Kind::Multi) => &a_segments[i..],
+            (Kind::Static, _) => { segments.push(value(seg_a)); continue }
+            _ => { segments.push(value(seg_b)); continue }
+        };
+
+        segments.extend(rest.iter().map(value));
+        break;
+    }
+
+    // Static query segments must be present for a route to match; dynamic
+    // parameters are given a value so that they can be parsed.
+    let mut query: Vec<String> = vec![];
+    let query_segments = route.metadata.query_segments.iter()
+        .chain(other.metadata.query_segments.iter())
+        .flatten();
+
+    for segment in query_segments {
+        let item = match segment.kind {
+            Kind::Static => segment.string.to_string(),
+            Kind::Single => format!("{}={}", segment.name, EXAMPLE_VALUE),
+            Kind::Multi => continue,
+        };
+
+        if !query.contains(&item) {
+            query.push(item);
+        }
+    }
+
+    let path = format!("/{}", segments.join("/"));
+    let query = Some(query.join("&")).filter(|query| !query.is_empty());
+    Origin::new(path, query)
+}
+
+fn example_content_type(route: &Route, other: &Route) -> Option<ContentType> {
+    // Only the `Content-Type` of requests with payloads must match a format.
+    if !route.method.supports_payload() {
+        return None;
+    }
+
+    let (a, b) = match (route.format.as_ref(), other.format.as_ref()) {
+        (Some(a), Some(b)) => (a, b),
+        (Some(a), None) | (None, Some(a)) => (a, a),
+        (None, None) => return None
+    };
+
+    // A request's `Content-Type` must be fully specified to match a format.
+    let pick = |a: &str, b: &str, default: &str| match (a, b) {
+        ("*", "*") => default.to_string(),
+        ("*", b) => b.to_string(),
+        (a, _) => a.to_string(),
+    };
+
+    let top = pick(a.top().as_str(), b.top().as_str(), "application");
+    let sub = pick(a.sub().as_str(), b.sub().as_str(), "octet-stream");
+    Some(ContentType(MediaType::new(top, sub)))
+}
+
 fn formats_collide(route: &Route, other: &Route) -> bool {
     // When matching against the `Accept` header, the client can always provide
     // a media type that will cause a collision through non-specificity.
@@ -156,8 +282,8 @@ mod tests {
     use crate::rocket::Rocket;
     use crate::config::Config;
     use crate::request::Request;
-    use crate::router::{dummy_handler, route::Route};
-    use crate::http::{Method, MediaType, ContentType, Accept};
+    use crate::router::{dummy_handler, route::Route, HostPattern};
+    use crate::http::{Method, MediaType, ContentType, Accept, Header};
     use crate::http::uri::Origin;
     use crate::http::Method::*;
 
@@ -509,4 +635,118 @@ mod tests {
         assert!(!req_route_path_match("/a/b", "/a/b?foo&<rest..>"));
         assert!(!req_route_path_match("/a/b", "/a/b?<a>&b&<rest..>"));
     }
+
+    fn host_route(host: Option<&'static str>) -> Route {
+        let mut route = Route::ranked(0, Get, "/", dummy_handler);
+        route.host = host.map(|h| HostPattern::parse(h).expect("valid host"));
+        route
+    }
+
+    fn hosts_collide(a: Option<&'static str>, b: Option<&'static str>) -> bool {
+        host_route(a).collides_with(&host_route(b))
+    }
+
+    #[test]
+    fn test_host_collisions() {
+        assert!(hosts_collide(None, None));
+        assert!(hosts_collide(Some("rocket.rs"), Some("rocket.rs")));
+        assert!(hosts_collide(Some("Rocket.RS"), Some("rocket.rs")));
+        assert!(hosts_collide(Some("<a>.rocket.rs"), Some("<b>.rocket.rs")));
+        assert!(hosts_collide(Some("api.<a>.rs"), Some("<a>.rocket.rs")));
+        assert!(hosts_collide(Some("<a>.<b>"), Some("<c>.<d>")));
+
+        assert!(!hosts_collide(Some("rocket.rs"), None));
+        assert!(!hosts_collide(None, Some("<a>.rocket.rs")));
+        assert!(!hosts_collide(Some("api.rocket.rs"), Some("www.rocket.rs")));
+        assert!(!hosts_collide(Some("api.rocket.rs"), Some("<a>.rocket.rs")));
+        assert!(!hosts_collide(Some("<a>.rocket.rs"), Some("rocket.rs")));
+        assert!(!hosts_collide(Some("<a>.rocket.rs"), Some("<a>.<b>.rocket.rs")));
+    }
+
+    fn req_route_host_match(host: Option<&'static str>, pattern: Option<&'static str>) -> bool {
+        let rocket = Rocket::custom(Config::development());
+        let mut req = Request::new(&rocket, Get, Origin::parse("/").expect("valid URI"));
+        if let Some(host) = host {
+            req.add_header(Header::new("Host", host));
+        }
+
+        host_route(pattern).matches(&req)
+    }
+
+    #[test]
+    fn test_req_route_host_match() {
+        assert!(req_route_host_match(None, None));
+        assert!(req_route_host_match(Some("rocket.rs"), None));
+        assert!(req_route_host_match(Some("rocket.rs"), Some("rocket.rs")));
+        assert!(req_route_host_match(Some("ROCKET.rs:8000"), Some("rocket.rs")));
+        assert!(req_route_host_match(Some("api.rocket.rs"), Some("<sub>.rocket.rs")));
+        assert!(req_route_host_match(Some("api.rocket.rs"), Some("<sub>.<domain>.rs")));
+
+        assert!(!req_route_host_match(None, Some("rocket.rs")));
+        assert!(!req_route_host_match(Some("rocket.rs"), Some("<sub>.rocket.rs")));
+        assert!(!req_route_host_match(Some("a.b.rocket.rs"), Some("<sub>.rocket.rs")));
+        assert!(!req_route_host_match(Some("api.rocket.rs"), Some("www.rocket.rs")));
+        assert!(!req_route_host_match(Some("127.0.0.1:8000"), Some("rocket.rs")));
+    }
+
+    fn collision_example(
+        a: (Method, Option<&'static str>, &'static str, Option<&'static str>),
+        b: (Method, Option<&'static str>, &'static str, Option<&'static str>),
+    ) -> (Option<String>, String, Option<String>) {
+        let route = |(method, host, path, format): (_, Option<&str>, &str, Option<&str>)| {
+            let mut route = Route::ranked(0, method, path, dummy_handler);
+            route.host = host.map(|h| HostPattern::parse(h).expect("valid host"));
+            route.format = format.map(|f| MediaType::from_str(f).expect("valid format"));
+            route
+        };
+
+        let (a, b) = (route(a), route(b));
+        assert!(a.collides_with(&b), "{} does not collide with {}", a, b);
+
+        let (host, uri, content_type) = a.collision_example(&b);
+        let rocket = Rocket::custom(Config::development());
+        let mut req = Request::new(&rocket, a.method, uri.clone());
+        if let Some(ref host) = host {
+            req.add_header(Header::new("Host", host.clone()));
+        }
+
+        if let Some(ref content_type) = content_type {
+            req.add_header(content_type.clone());
+        }
+
+        assert!(a.matches(&req) && b.matches(&req), "{} doesn't match {} and {}", uri, a, b);
+        (host, uri.to_string(), content_type.map(|c| c.to_string()))
+    }
+
+    #[test]
+    fn test_collision_examples() {
+        let example = |a, b| collision_example((Get, None, a, None), (Get, None, b, None)).1;
+        assert_eq!(example("/", "/"), "/");
+        assert_eq!(example("/a/<b>", "/<a>/c"), "/a/c");
+        assert_eq!(example("/<a>/<b>", "/<c>/<d>"), "/1/1");
+        assert_eq!(example("/a/<b..>", "/<a>/b/c/<d>"), "/a/b/c/1");
+        assert_eq!(example("/<a..>", "/<b..>"), "/1");
+        assert_eq!(example("/a?b&<c>", "/<a>?d&<e..>"), "/a?b&c=1&d");
+        assert_eq!(example("/a?<c>", "/a?c=1"), "/a?c=1");
+
+        let (host, _, _) = collision_example(
+            (Get, Some("<a>.rocket.rs"), "/", None),
+            (Get, Some("api.<b>.rs"), "/", None));
+        assert_eq!(host, Some("api.rocket.rs".into()));
+
+        let (_, _, content_type) = collision_example(
+            (Post, None, "/", Some("application/*")),
+            (Post, None, "/", Some("*/json")));
+        assert_eq!(content_type, Some("application/json".into()));
+
+        let (_, _, content_type) = collision_example(
+            (Post, None, "/", Some("text/*")),
+            (Post, None, "/", None));
+        assert_eq!(content_type, Some("text/octet-stream".into()));
+
+        let (_, _, content_type) = collision_example(
+            (Get, None, "/", Some("application/json")),
+            (Get, None, "/", Some("text/html")));
+        assert_eq!(content_type, None);
+    }
 }
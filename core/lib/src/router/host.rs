@@ -0,0 +1,181 @@
+use std::fmt;
+
+use crate::http::route::{RouteSegment, Kind};
+use crate::http::uri::Path;
+use crate::error::RouteUriError;
+
+/// A pattern matched against the host of a request: the virtual host of a
+/// route or mount point.
+///
+/// A host pattern is a sequence of `.`-separated labels. Each label is either
+/// static, like `api` or `example`, or a dynamic `<param>` that matches any one
+/// label of the request's host. Static labels are compared case-insensitively.
+/// A pattern cannot contain a port; the port of a request's host is ignored.
+///
+/// A pattern matches a host with the same number of labels whose static labels
+/// are equal to those of the pattern in the same position. A route with a host
+/// pattern only matches requests whose `Host` header matches the pattern, while
+/// a route without one matches requests to any host.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::HostPattern;
+///
+/// let pattern = HostPattern::parse("<tenant>.example.com").unwrap();
+/// assert!(pattern.matches("acme.example.com"));
+/// assert!(pattern.matches("ACME.Example.com"));
+/// assert!(!pattern.matches("example.com"));
+/// assert!(!pattern.matches("a.b.example.com"));
+///
+/// assert!(HostPattern::parse("example.com:8000").is_err());
+/// assert!(HostPattern::parse("<sub..>.example.com").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HostPattern {
+    labels: Vec<Label>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Label {
+    Static(String),
+    Dynamic(String),
+}
+
+impl HostPattern {
+    /// Parses `pattern` as a host pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `pattern` is empty, contains an empty label or a
+    /// port, if a static label contains characters other than ASCII letters,
+    /// digits, or `-`, or if a dynamic label is not of the form `<param>` with
+    /// a unique `param`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::HostPattern;
+    ///
+    /// let pattern = HostPattern::parse("API.example.com").unwrap();
+    /// assert_eq!(pattern.to_string(), "api.example.com");
+    ///
+    /// assert!(HostPattern::parse("").is_err());
+    /// assert!(HostPattern::parse("api..com").is_err());
+    /// assert!(HostPattern::parse("<a>.<a>.com").is_err());
+    /// ```
+    pub fn parse(pattern: &str) -> Result<HostPattern, RouteUriError> {
+        let mut labels = vec![];
+        for label in pattern.split('.') {
+            if label.starts_with('<') || label.ends_with('>') {
+                let segment = <RouteSegment<'_, Path>>::parse_one(label)
+                    .map_err(|_| RouteUriError::Host)?;
+                let name = segment.name.into_owned();
+                let duplicate = labels.iter().any(|l| l == &Label::Dynamic(name.clone()));
+                if segment.kind != Kind::Single || duplicate {
+                    return Err(RouteUriError::Host);
+                }
+
+                labels.push(Label::Dynamic(name));
+            } else if is_valid_label(label) {
+                labels.push(Label::Static(label.to_ascii_lowercase()));
+            } else {
+                return Err(RouteUriError::Host);
+            }
+        }
+
+        Ok(HostPattern { labels })
+    }
+
+    /// Returns `true` if `host`, a host name without a port, matches `self`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::HostPattern;
+    ///
+    /// let pattern = HostPattern::parse("api.<domain>.com").unwrap();
+    /// assert!(pattern.matches("api.rocket.com"));
+    /// assert!(!pattern.matches("www.rocket.com"));
+    /// ```
+    pub fn matches(&self, host: &str) -> bool {
+        let mut host_labels = host.split('.');
+        for label in &self.labels {
+            match (label, host_labels.next()) {
+                (Label::Static(s), Some(h)) if s.eq_ignore_ascii_case(h) => continue,
+                (Label::Dynamic(_), Some(h)) if !h.is_empty() => continue,
+                _ => return false,
+            }
+        }
+
+        host_labels.next().is_none()
+    }
+
+    /// Returns `true` if `self` contains at least one dynamic label.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::HostPattern;
+    ///
+    /// assert!(HostPattern::parse("<sub>.rocket.rs").unwrap().is_dynamic());
+    /// assert!(!HostPattern::parse("api.rocket.rs").unwrap().is_dynamic());
+    /// ```
+    pub fn is_dynamic(&self) -> bool {
+        self.labels.iter().any(|l| match l {
+            Label::Dynamic(_) => true,
+            Label::Static(_) => false,
+        })
+    }
+
+    /// The number of static labels in `self`. When two routes of the same rank
+    /// match a request, the route whose host is more specific is tried first.
+    pub(crate) fn specificity(&self) -> usize {
+        self.labels.iter().filter(|l| match l {
+            Label::Static(_) => true,
+            Label::Dynamic(_) => false,
+        }).count()
+    }
+
+    /// Returns `true` if there is a host that both `self` and `other` match.
+    pub(crate) fn collides_with(&self, other: &HostPattern) -> bool {
+        self.labels.len() == other.labels.len()
+            && self.labels.iter().zip(other.labels.iter()).all(|pair| match pair {
+                (Label::Static(a), Label::Static(b)) => a == b,
+                _ => true,
+            })
+    }
+
+    /// Returns a host that both `self` and `other`, which must collide, match.
+    pub(crate) fn example(&self, other: &HostPattern) -> String {
+        let labels: Vec<&str> = self.labels.iter().zip(other.labels.iter())
+            .map(|pair| match pair {
+                (Label::Static(s), _) | (_, Label::Static(s)) => s.as_str(),
+                _ => "x",
+            })
+            .collect();
+
+        labels.join(".")
+    }
+}
+
+fn is_valid_label(label: &str) -> bool {
+    !label.is_empty() && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+impl fmt::Display for HostPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, label) in self.labels.iter().enumerate() {
+            if i != 0 {
+                f.write_str(".")?;
+            }
+
+            match label {
+                Label::Static(s) => f.write_str(s)?,
+                Label::Dynamic(name) => write!(f, "<{}>", name)?,
+            }
+        }
+
+        Ok(())
+    }
+}
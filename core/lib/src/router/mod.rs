@@ -1,68 +1,132 @@
 mod collider;
+mod host;
 mod route;
+mod signature;
+mod trie;
+mod uri_for;
 
+use std::cmp::Reverse;
 use std::collections::hash_map::HashMap;
 
 pub use self::route::Route;
+pub use self::host::HostPattern;
+pub use self::signature::{Signature, ParamInfo, TypeInfo};
+pub use self::uri_for::UriParams;
 
 use crate::request::Request;
+use crate::error::{UriForError, RouteCollision};
 use crate::http::Method;
+use crate::http::uri::Origin;
+use crate::http::private::SmallVec;
+
+use self::trie::Node;
 
 // type Selector = (Method, usize);
 type Selector = Method;
 
 // A handler to use when one is needed temporarily.
+#[cfg(test)]
 pub(crate) fn dummy_handler<'r>(r: &'r crate::Request<'_>, _: crate::Data) -> crate::handler::Outcome<'r> {
     crate::Outcome::from(r, ())
 }
 
+/// Routes requests using a trie of path segments per method. Routes are kept
+/// in the order they were added; the tries and the index of route names refer
+/// to them by index.
 #[derive(Default)]
 pub struct Router {
-    routes: HashMap<Selector, Vec<Route>>,
+    routes: Vec<Route>,
+    tries: HashMap<Selector, Node>,
+    names: HashMap<&'static str, usize>,
 }
 
 impl Router {
     pub fn new() -> Router {
-        Router { routes: HashMap::new() }
+        Router { routes: vec![], tries: HashMap::new(), names: HashMap::new() }
+    }
+
+    /// Adds `route` to the router. Returns the route that was previously
+    /// added with the same name, if any, which `uri_for()` continues to use.
+    pub fn add(&mut self, route: Route) -> Option<&Route> {
+        let index = self.routes.len();
+        self.tries.entry(route.method).or_default().insert(&route, index);
+        let first = route.name.map(|name| *self.names.entry(name).or_insert(index));
+        self.routes.push(route);
+        match first {
+            Some(i) if i != index => Some(&self.routes[i]),
+            _ => None
+        }
     }
 
-    pub fn add(&mut self, route: Route) {
-        let selector = route.method;
-        let entries = self.routes.entry(selector).or_insert_with(|| vec![]);
-        let i = entries.binary_search_by_key(&route.rank, |r| r.rank)
-            .unwrap_or_else(|i| i);
+    /// Returns the URI to the first route added with the name `name`. See
+    /// [`Route::uri_for()`].
+    pub(crate) fn uri_for(
+        &self,
+        name: &str,
+        params: &UriParams<'_>
+    ) -> Result<Origin<'static>, UriForError> {
+        match self.names.get(name) {
+            Some(&i) => self.routes[i].uri_for(params),
+            None => Err(UriForError::UnknownRoute(name.into())),
+        }
+    }
+
+    // Returns the indices of the routes for `method` whose paths match the
+    // path of `req`, in the order they were added.
+    fn candidates(&self, method: Method, req: &Request<'_>) -> Vec<usize> {
+        let mut candidates = vec![];
+        if let Some(trie) = self.tries.get(&method) {
+            let segments: SmallVec<[&str; 12]> = req.raw_path_segments()
+                .map(|s| s.as_str())
+                .collect();
+
+            trie.find(&segments, &mut candidates);
+            candidates.sort_unstable();
+        }
 
-        entries.insert(i, route);
+        candidates
     }
 
     pub fn route<'b>(&'b self, req: &Request<'_>) -> Vec<&'b Route> {
-        // Note that routes are presorted by rank on each `add`.
-        let matches = self.routes.get(&req.method()).map_or(vec![], |routes| {
-            routes.iter()
-                .filter(|r| r.matches(req))
-                .collect()
-        });
+        // The trie only considers paths; hosts, queries, and formats are checked
+        // here. Among routes with equal ranks, those with more specific hosts
+        // come first; otherwise, routes stay in the order they were added.
+        let mut matches: Vec<_> = self.candidates(req.method(), req).into_iter()
+            .map(|i| &self.routes[i])
+            .filter(|r| r.matches(req))
+            .collect();
+
+        matches.sort_by_key(|r| (r.rank, Reverse(r.host.as_ref().map(|h| h.specificity()))));
 
         trace_!("Routing the request: {}", req);
         trace_!("All matches: {:?}", matches);
         matches
     }
 
-    pub(crate) fn collisions(mut self) -> Result<Router, Vec<(Route, Route)>> {
+    /// Returns the methods of all routes, of any method, that match the host,
+    /// path, and query of `req`, in no particular order.
+    pub(crate) fn methods_for(&self, req: &Request<'_>) -> Vec<Method> {
+        self.tries.keys()
+            .filter(|&&method| {
+                self.candidates(method, req).into_iter()
+                    .any(|i| self.routes[i].matches_uri(req))
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn collisions(self) -> Result<Router, Vec<RouteCollision>> {
+        // Only routes found in the trie along a route's path can collide with
+        // it. Each pair is checked once, from the route added first.
         let mut collisions = vec![];
-        for routes in self.routes.values_mut() {
-            for i in 0..routes.len() {
-                let (left, right) = routes.split_at_mut(i);
-                for a_route in left.iter_mut() {
-                    for b_route in right.iter_mut() {
-                        if a_route.collides_with(b_route) {
-                            let dummy_a = Route::new(Method::Get, "/", dummy_handler);
-                            let a = std::mem::replace(a_route, dummy_a);
-                            let dummy_b = Route::new(Method::Get, "/", dummy_handler);
-                            let b = std::mem::replace(b_route, dummy_b);
-                            collisions.push((a, b));
-                        }
-                    }
+        for (i, route) in self.routes.iter().enumerate() {
+            let mut candidates = vec![];
+            self.tries[&route.method].colliding(&route.metadata.path_segments, &mut candidates);
+            candidates.sort_unstable();
+
+            for j in candidates.into_iter().filter(|&j| j > i) {
+                if route.collides_with(&self.routes[j]) {
+                    collisions.push(self.collision(route, &self.routes[j]));
                 }
             }
         }
@@ -74,20 +138,38 @@ impl Router {
         }
     }
 
+    /// Explains the collision of `first` with `second`, which was added later.
+    fn collision(&self, first: &Route, second: &Route) -> RouteCollision {
+        // Suggest the smallest rank greater than `first`'s at which `second`
+        // doesn't collide with any route.
+        let mut reranked = second.clone();
+        reranked.rank = first.rank + 1;
+        while self.routes.iter().any(|route| reranked.collides_with(route)) {
+            reranked.rank += 1;
+        }
+
+        let (host, uri, content_type) = first.collision_example(second);
+        RouteCollision {
+            first: first.clone(),
+            second: second.clone(),
+            method: first.method,
+            suggested_rank: reranked.rank,
+            host, uri, content_type,
+        }
+    }
+
     #[inline]
     pub fn routes<'a>(&'a self) -> impl Iterator<Item=&'a Route> + 'a {
-        self.routes.values().flat_map(|v| v.iter())
+        self.routes.iter()
     }
 
     // This is slow. Don't expose this publicly; only for tests.
     #[cfg(test)]
     fn has_collisions(&self) -> bool {
-        for routes in self.routes.values() {
-            for (i, a_route) in routes.iter().enumerate() {
-                for b_route in routes.iter().skip(i + 1) {
-                    if a_route.collides_with(b_route) {
-                        return true;
-                    }
+        for (i, a_route) in self.routes.iter().enumerate() {
+            for b_route in self.routes.iter().skip(i + 1) {
+                if a_route.collides_with(b_route) {
+                    return true;
                 }
             }
         }
@@ -98,11 +180,11 @@ impl Router {
 
 #[cfg(test)]
 mod test {
-    use super::{Router, Route, dummy_handler};
+    use super::{Router, Route, HostPattern, UriParams, dummy_handler};
 
     use crate::rocket::Rocket;
     use crate::config::Config;
-    use crate::http::Method;
+    use crate::http::{Method, Header};
     use crate::http::Method::*;
     use crate::http::uri::Origin;
     use crate::request::Request;
@@ -473,4 +555,134 @@ mod test {
             expect: "/a/b?c", "/a/b?<c>", "/a/b", "/a/<b>?c", "/a/<b>?<c>", "/<a>/<b>"
         );
     }
+
+    const TRIE_ROUTES: &[&str] = &[
+        "/", "/a", "/a/b", "/a/<b>", "/<a>", "/<a>/b", "/<a>/<b>", "/a/<b..>",
+        "/<a..>", "/a/b/c", "/a/b/<c..>", "/b/<c>/d", "/a?<q>", "/a?q", "/<a>?q=1",
+    ];
+
+    #[test]
+    fn test_trie_routing_matches_linear_scan() {
+        let mut router = Router::new();
+        for (i, path) in TRIE_ROUTES.iter().enumerate() {
+            router.add(Route::ranked(i as isize % 3, Get, path.to_string(), dummy_handler));
+        }
+
+        let requests = &[
+            "/", "/a", "/b", "/a/b", "/a/c", "/c/b", "/a/b/c", "/a/b/c/d", "/b/x/d",
+            "/a?q", "/a?q=1", "/x?q=1", "/a/b?q", "/x/y/z",
+        ];
+
+        let rocket = Rocket::custom(Config::development());
+        for uri in requests {
+            let request = Request::new(&rocket, Get, Origin::parse(uri).unwrap());
+            let mut expected: Vec<_> = router.routes().filter(|r| r.matches(&request)).collect();
+            expected.sort_by_key(|r| r.rank);
+
+            let uris = |routes: Vec<&Route>| {
+                routes.iter().map(|r| r.uri.to_string()).collect::<Vec<_>>()
+            };
+
+            assert_eq!(uris(router.route(&request)), uris(expected), "routing {}", uri);
+        }
+    }
+
+    #[test]
+    fn test_trie_collisions_match_pairwise_check() {
+        for &a in TRIE_ROUTES {
+            for &b in TRIE_ROUTES {
+                let router = router_with_unranked_routes(&[a, b]);
+                let pairwise = router.has_collisions();
+                assert_eq!(router.collisions().is_err(), pairwise, "{} and {}", a, b);
+            }
+        }
+
+        let router = router_with_unranked_routes(TRIE_ROUTES);
+        let pairwise = router.routes().enumerate()
+            .flat_map(|(i, a)| router.routes().skip(i + 1).filter(move |b| a.collides_with(b)))
+            .count();
+
+        assert_eq!(router.collisions().err().map_or(0, |c| c.len()), pairwise);
+    }
+
+    #[test]
+    fn test_collision_examples_match_both_routes() {
+        let rocket = Rocket::custom(Config::development());
+        for &a in TRIE_ROUTES {
+            for &b in TRIE_ROUTES {
+                let router = router_with_unranked_routes(&[a, b]);
+                for collision in router.collisions().err().unwrap_or_default() {
+                    let request = Request::new(&rocket, collision.method, collision.uri.clone());
+                    assert!(collision.first.matches(&request), "{} for {}", collision, a);
+                    assert!(collision.second.matches(&request), "{} for {}", collision, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_collision_suggested_rank() {
+        let suggested_ranks = |routes: &[(isize, &'static str)]| {
+            router_with_ranked_routes(routes).collisions().err().unwrap_or_default()
+                .iter()
+                .map(|c| (c.second.uri.to_string(), c.suggested_rank))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(suggested_ranks(&[(0, "/<a>"), (0, "/<b>")]), vec![("/<b>".into(), 1)]);
+        assert_eq!(suggested_ranks(&[(0, "/<a>"), (0, "/<b>"), (1, "/hello")]),
+            vec![("/<b>".into(), 2)]);
+        assert_eq!(suggested_ranks(&[(-2, "/a/<b>"), (-2, "/<a>/b"), (-1, "/a/b/<c..>")]),
+            vec![("/<a>/b".into(), -1)]);
+    }
+
+    #[test]
+    fn test_host_routing() {
+        let mut router = Router::new();
+        for host in &[None, Some("<sub>.rocket.rs"), Some("api.rocket.rs")] {
+            let mut route = Route::new(Get, "/", dummy_handler);
+            route.host = host.map(|h| HostPattern::parse(h).unwrap());
+            router.add(route);
+        }
+
+        assert!(!router.has_collisions());
+
+        let rocket = Rocket::custom(Config::development());
+        let routed_host = |host: Option<&str>| {
+            let mut request = Request::new(&rocket, Get, Origin::parse("/").unwrap());
+            if let Some(host) = host {
+                request.add_header(Header::new("Host", host.to_string()));
+            }
+
+            router.route(&request).iter()
+                .map(|r| r.host.as_ref().map(|h| h.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        let (api, sub) = (Some("api.rocket.rs".into()), Some("<sub>.rocket.rs".into()));
+        assert_eq!(routed_host(Some("api.rocket.rs")), vec![api, sub.clone(), None]);
+        assert_eq!(routed_host(Some("www.rocket.rs:8000")), vec![sub, None]);
+        assert_eq!(routed_host(Some("rocket.rs")), vec![None]);
+        assert_eq!(routed_host(None), vec![None]);
+    }
+
+    #[test]
+    fn test_duplicate_names() {
+        let named = |name, path: &str| {
+            let mut route = Route::new(Get, path.to_string(), dummy_handler);
+            route.name = name;
+            route
+        };
+
+        let mut router = Router::new();
+        assert!(router.add(named(Some("index"), "/a")).is_none());
+        assert!(router.add(named(None, "/b")).is_none());
+        assert!(router.add(named(Some("other"), "/c")).is_none());
+
+        let first = router.add(named(Some("index"), "/d")).map(|r| r.uri.to_string());
+        assert_eq!(first, Some("/a".to_string()));
+
+        let uri = router.uri_for("index", &UriParams::new()).unwrap();
+        assert_eq!(uri.to_string(), "/a");
+    }
 }
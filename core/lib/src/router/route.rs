@@ -8,18 +8,26 @@ use crate::handler::Handler;
 use crate::http::{Method, MediaType};
 use crate::http::route::{RouteSegment, Kind};
 use crate::error::RouteUriError;
+use crate::router::{HostPattern, Signature};
 use crate::http::ext::IntoOwned;
 use crate::http::uri::{Origin, Path, Query};
 
-/// A route: a method, its handler, path, rank, and format/media type.
+/// A route: a method, its handler, host, path, rank, and format/media type.
 #[derive(Clone)]
 pub struct Route {
-    /// The name of this route, if one was given.
+    /// The name of this route, if one was given. Routes generated by codegen
+    /// are named after their function unless their attribute sets a `name`.
+    /// Names are used to build URIs with [`Rocket::uri_for()`].
+    ///
+    /// [`Rocket::uri_for()`]: crate::Rocket::uri_for()
     pub name: Option<&'static str>,
     /// The method this route matches against.
     pub method: Method,
     /// The function that should be called when the route matches.
     pub handler: Box<dyn Handler>,
+    /// The host pattern this route matches against, if any. A route without
+    /// a host matches requests to any host.
+    pub host: Option<HostPattern>,
     /// The base mount point of this `Route`.
     pub base: Origin<'static>,
     /// The uri (in Rocket's route format) that should be matched against. This
@@ -29,6 +37,9 @@ pub struct Route {
     pub rank: isize,
     /// The media type this route matches against, if any.
     pub format: Option<MediaType>,
+    /// The signature of this route's handler, if it is known. Routes generated
+    /// by codegen always have a signature.
+    pub signature: Option<Signature>,
     /// Cached metadata that aids in routing later.
     pub(crate) metadata: Metadata
 }
@@ -182,6 +193,8 @@ impl Route {
         let mut route = Route {
             name: None,
             format: None,
+            signature: None,
+            host: None,
             base: Origin::dummy(),
             handler: Box::new(handler),
             metadata: Metadata::default(),
@@ -282,7 +295,12 @@ impl Route {
 
 impl fmt::Display for Route {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {}", Paint::green(&self.method), Paint::blue(&self.uri))?;
+        write!(f, "{} ", Paint::green(&self.method))?;
+        if let Some(ref host) = self.host {
+            write!(f, "{}", Paint::blue(host))?;
+        }
+
+        write!(f, "{}", Paint::blue(&self.uri))?;
 
         if self.rank > 1 {
             write!(f, " [{}]", Paint::default(&self.rank).bold())?;
@@ -306,10 +324,12 @@ impl fmt::Debug for Route {
         f.debug_struct("Route")
             .field("name", &self.name)
             .field("method", &self.method)
+            .field("host", &self.host)
             .field("base", &self.base)
             .field("uri", &self.uri)
             .field("rank", &self.rank)
             .field("format", &self.format)
+            .field("signature", &self.signature)
             .field("metadata", &self.metadata)
             .finish()
     }
@@ -318,10 +338,15 @@ impl fmt::Debug for Route {
 #[doc(hidden)]
 impl From<&StaticRouteInfo> for Route {
     fn from(info: &StaticRouteInfo) -> Route {
-        // This should never panic since `info.path` is statically checked.
+        // This should never panic since `info.path` and `info.host` are
+        // statically checked.
         let mut route = Route::new(info.method, info.path, info.handler);
         route.format = info.format.clone();
+        route.host = info.host.map(|host| {
+            HostPattern::parse(host).unwrap_or_else(|e| panic!("invalid host '{}': {}", host, e))
+        });
         route.name = Some(info.name);
+        route.signature = Some(Signature::from(info));
         if let Some(rank) = info.rank {
             route.rank = rank;
         }
@@ -0,0 +1,110 @@
+use std::any::TypeId;
+
+use crate::codegen::{StaticRouteInfo, StaticParamInfo, StaticTypeInfo};
+use crate::http::route::{Source, Kind};
+
+/// The signature of a route's handler: the parameters it takes and the type it
+/// returns.
+///
+/// Routes generated by codegen carry a signature in
+/// [`Route::signature`](crate::Route::signature) describing the arguments of
+/// the decorated function and where their values come from. Tools, such as
+/// API document generators, can use it to describe a route without access to
+/// its source code.
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::http::route::Source;
+///
+/// #[get("/<id>?<verbose>")]
+/// fn item(id: usize, verbose: Option<bool>) -> String {
+///     format!("{} {:?}", id, verbose)
+/// }
+///
+/// let rocket = rocket::ignite().mount("/", routes![item]);
+/// let route = rocket.routes().next().unwrap();
+/// let signature = route.signature.as_ref().unwrap();
+///
+/// assert_eq!(signature.params[0].name, "id");
+/// assert_eq!(signature.params[0].source, Source::Path);
+/// assert!(signature.params[0].ty.is::<usize>());
+/// assert_eq!(signature.params[1].source, Source::Query);
+/// assert!(signature.output.unwrap().is::<String>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Signature {
+    /// The parameters of the handler, in the order they are declared.
+    pub params: Vec<ParamInfo>,
+    /// The type returned by the handler, unless it is an `impl Trait` type.
+    pub output: Option<TypeInfo>,
+}
+
+/// A parameter of a route's handler. See [`Signature`].
+#[derive(Debug, Clone, Copy)]
+pub struct ParamInfo {
+    /// The name of the parameter.
+    pub name: &'static str,
+    /// Where the value of the parameter comes from. Request guards have a
+    /// source of [`Source::Unknown`].
+    pub source: Source,
+    /// Whether the parameter is a single (`<param>`) or multiple
+    /// (`<param..>`) segment parameter. Data and request guards are `Single`.
+    pub kind: Kind,
+    /// The type of the parameter.
+    pub ty: TypeInfo,
+}
+
+/// The name and [`TypeId`] of a type in a [`Signature`].
+///
+/// Lifetimes in the type are `'static` as far as the `TypeId` is concerned: a
+/// parameter of type `&'r str` has the `TypeId` of `&'static str`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeInfo {
+    /// The name of the type as it was written in the source.
+    pub name: &'static str,
+    /// The `TypeId` of the type.
+    pub id: TypeId,
+}
+
+impl TypeInfo {
+    /// Returns `true` if `self` is the type `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::any::TypeId;
+    /// use rocket::TypeInfo;
+    ///
+    /// let info = TypeInfo { name: "String", id: TypeId::of::<String>() };
+    /// assert!(info.is::<String>());
+    /// assert!(!info.is::<&str>());
+    /// ```
+    #[inline]
+    pub fn is<T: ?Sized + 'static>(&self) -> bool {
+        self.id == TypeId::of::<T>()
+    }
+}
+
+impl From<&StaticTypeInfo> for TypeInfo {
+    fn from(info: &StaticTypeInfo) -> TypeInfo {
+        TypeInfo { name: info.name, id: (info.id)() }
+    }
+}
+
+impl From<&StaticParamInfo> for ParamInfo {
+    fn from(info: &StaticParamInfo) -> ParamInfo {
+        ParamInfo { name: info.name, source: info.source, kind: info.kind, ty: (&info.ty).into() }
+    }
+}
+
+impl From<&StaticRouteInfo> for Signature {
+    fn from(info: &StaticRouteInfo) -> Signature {
+        Signature {
+            params: info.params.iter().map(ParamInfo::from).collect(),
+            output: info.output.as_ref().map(TypeInfo::from),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+
+use crate::http::route::{RouteSegment, Kind};
+use crate::http::uri::Path;
+
+use super::Route;
+
+/// A node in a trie of route path segments. Routes are identified by their
+/// index in the `Router`.
+///
+/// Static segments are keyed by their string, all single dynamic segments
+/// (`<param>`) share one child, and routes with a multi-segment parameter
+/// (`<param..>`) are kept in the node at the parameter's position, ending the
+/// route's path in the trie.
+#[derive(Debug, Default)]
+pub(crate) struct Node {
+    statics: HashMap<String, Node>,
+    dynamic: Option<Box<Node>>,
+    terminal: Vec<usize>,
+    multi: Vec<usize>,
+}
+
+impl Node {
+    /// Inserts the route `route` with index `index` into the trie.
+    pub fn insert(&mut self, route: &Route, index: usize) {
+        let mut node = self;
+        for segment in &route.metadata.path_segments {
+            node = match segment.kind {
+                Kind::Static => node.statics.entry(segment.string.to_string()).or_default(),
+                Kind::Single => &mut **node.dynamic.get_or_insert_with(Default::default),
+                Kind::Multi => return node.multi.push(index),
+            };
+        }
+
+        node.terminal.push(index);
+    }
+
+    /// Pushes to `matches` the index of every route whose path matches the
+    /// path with segments `segments`.
+    pub fn find(&self, segments: &[&str], matches: &mut Vec<usize>) {
+        match segments.split_first() {
+            None => matches.extend(&self.terminal),
+            Some((first, rest)) => {
+                matches.extend(&self.multi);
+                if let Some(child) = self.statics.get(*first) {
+                    child.find(rest, matches);
+                }
+
+                if let Some(ref child) = self.dynamic {
+                    child.find(rest, matches);
+                }
+            }
+        }
+    }
+
+    /// Pushes to `collisions` the index of every route whose path collides
+    /// with the route path with segments `segments`. This visits only the
+    /// parts of the trie that such routes can be in.
+    pub fn colliding(&self, segments: &[RouteSegment<'_, Path>], collisions: &mut Vec<usize>) {
+        let (first, rest) = match segments.split_first() {
+            Some(split) => split,
+            None => return collisions.extend(&self.terminal),
+        };
+
+        if first.kind == Kind::Multi {
+            return self.descendants(collisions);
+        }
+
+        collisions.extend(&self.multi);
+        if first.kind == Kind::Static {
+            if let Some(child) = self.statics.get(&*first.string) {
+                child.colliding(rest, collisions);
+            }
+        } else {
+            for child in self.statics.values() {
+                child.colliding(rest, collisions);
+            }
+        }
+
+        if let Some(ref child) = self.dynamic {
+            child.colliding(rest, collisions);
+        }
+    }
+
+    /// Pushes to `routes` the index of every route with more path segments
+    /// than there are up to this node.
+    fn descendants(&self, routes: &mut Vec<usize>) {
+        routes.extend(&self.multi);
+        for child in self.statics.values().chain(self.dynamic.as_ref().map(|c| &**c)) {
+            routes.extend(&child.terminal);
+            child.descendants(routes);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::http::route::Kind;
+use crate::http::uri::{Origin, Path, Query, UriDisplay};
+use crate::http::uri::{UriArguments, UriArgumentsKind, UriQueryArgument};
+use crate::error::UriForError;
+
+use super::Route;
+
+/// Values for the dynamic parameters of a route, used to build a URI to the
+/// route at runtime with [`Rocket::uri_for()`](crate::Rocket::uri_for()).
+///
+/// Each value is given along with the name of the route parameter it is for.
+/// Values are percent-encoded using their [`UriDisplay`] implementation for
+/// the part of the URI the parameter is in. Values given with
+/// [`UriParams::param()`] can be used for parameters in either the path or the
+/// query; those given with [`UriParams::path()`] or [`UriParams::query()`] can
+/// only be used for parameters in that part.
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use std::path::PathBuf;
+/// use rocket::UriParams;
+///
+/// #[get("/<user>/files/<path..>?<sort>")]
+/// fn files(user: String, path: PathBuf, sort: Option<bool>) { /* .. */ }
+///
+/// let rocket = rocket::ignite().mount("/api", routes![files]);
+///
+/// let path = PathBuf::from("a b/c");
+/// let params = UriParams::new()
+///     .param("user", &"Bob Smith")
+///     .path("path", &path)
+///     .query("sort", &true);
+///
+/// let uri = rocket.uri_for("files", &params).unwrap();
+/// assert_eq!(uri.to_string(), "/api/Bob%20Smith/files/a%20b/c?sort=true");
+/// ```
+#[derive(Default)]
+pub struct UriParams<'a> {
+    params: Vec<UriParam<'a>>,
+}
+
+struct UriParam<'a> {
+    name: &'a str,
+    path: Option<&'a dyn UriDisplay<Path>>,
+    query: Option<&'a dyn UriDisplay<Query>>,
+}
+
+impl<'a> UriParams<'a> {
+    /// Returns an empty set of parameter values.
+    #[inline(always)]
+    pub fn new() -> UriParams<'a> {
+        UriParams::default()
+    }
+
+    /// Adds `value` as the value of the parameter named `name`, which can be
+    /// in the path or the query.
+    #[inline]
+    pub fn param<T>(mut self, name: &'a str, value: &'a T) -> Self
+        where T: UriDisplay<Path> + UriDisplay<Query>
+    {
+        self.params.push(UriParam { name, path: Some(value), query: Some(value) });
+        self
+    }
+
+    /// Adds `value` as the value of the path parameter named `name`.
+    #[inline]
+    pub fn path<T: UriDisplay<Path>>(mut self, name: &'a str, value: &'a T) -> Self {
+        self.params.push(UriParam { name, path: Some(value), query: None });
+        self
+    }
+
+    /// Adds `value` as the value of the query parameter named `name`.
+    #[inline]
+    pub fn query<T: UriDisplay<Query>>(mut self, name: &'a str, value: &'a T) -> Self {
+        self.params.push(UriParam { name, path: None, query: Some(value) });
+        self
+    }
+
+    fn find(&self, name: &str) -> Option<&UriParam<'a>> {
+        self.params.iter().rev().find(|p| p.name == name)
+    }
+}
+
+impl Route {
+    /// Returns the URI to this route, including its mount point, with the
+    /// values in `params` for its dynamic parameters.
+    ///
+    /// Every dynamic path parameter must be given a value. Query parameters
+    /// without a value are left out of the URI. The route's host, if any, is
+    /// not part of the returned URI.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a path parameter has no value or if a value is
+    /// given for a parameter the route doesn't have.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::{Route, UriParams};
+    /// use rocket::http::Method;
+    /// # use rocket::{Request, Data};
+    /// # use rocket::handler::Outcome;
+    /// # fn handler<'r>(request: &'r Request, _data: Data) -> Outcome<'r> {
+    /// #     Outcome::from(request, "Hello, world!")
+    /// # }
+    ///
+    /// let route = Route::new(Method::Get, "/hello/<name>?<age>", handler);
+    ///
+    /// let uri = route.uri_for(&UriParams::new().param("name", &"Jo")).unwrap();
+    /// assert_eq!(uri.to_string(), "/hello/Jo");
+    ///
+    /// let params = UriParams::new().param("name", &"Jo").param("age", &30);
+    /// let uri = route.uri_for(&params).unwrap();
+    /// assert_eq!(uri.to_string(), "/hello/Jo?age=30");
+    ///
+    /// assert!(route.uri_for(&UriParams::new()).is_err());
+    /// assert!(route.uri_for(&params.param("nmae", &"Jo")).is_err());
+    /// ```
+    pub fn uri_for(&self, params: &UriParams<'_>) -> Result<Origin<'static>, UriForError> {
+        let is_param = |name: &str| {
+            let dynamic = |kind: Kind, param: &str| kind != Kind::Static && param == name;
+            self.metadata.path_segments.iter().any(|s| dynamic(s.kind, &s.name))
+                || self.metadata.query_segments.iter().flatten().any(|s| dynamic(s.kind, &s.name))
+        };
+
+        if let Some(unknown) = params.params.iter().find(|p| !is_param(p.name)) {
+            return Err(UriForError::UnknownParam(unknown.name.into()));
+        }
+
+        let mut path: Vec<&dyn UriDisplay<Path>> = vec![];
+        for segment in &self.metadata.path_segments {
+            match segment.kind {
+                Kind::Static => path.push(&segment.string),
+                Kind::Single | Kind::Multi => {
+                    let value = params.find(&segment.name).and_then(|p| p.path);
+                    let missing = || UriForError::MissingParam(segment.name.to_string());
+                    path.push(value.ok_or_else(missing)?);
+                }
+            }
+        }
+
+        let mut query = vec![];
+        for segment in self.metadata.query_segments.iter().flatten() {
+            if segment.kind == Kind::Static {
+                query.push(UriQueryArgument::Raw(&segment.string));
+                continue;
+            }
+
+            if let Some(value) = params.find(&segment.name).and_then(|p| p.query) {
+                query.push(match segment.kind {
+                    Kind::Multi => UriQueryArgument::Value(value),
+                    _ => UriQueryArgument::NameValue(&segment.name, value),
+                });
+            }
+        }
+
+        let query = self.metadata.query_segments.as_ref()
+            .map(|_| UriArgumentsKind::Dynamic(&query[..]));
+
+        Ok(UriArguments { path: UriArgumentsKind::Dynamic(&path[..]), query }.into_origin())
+    }
+}
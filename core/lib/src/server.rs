@@ -0,0 +1,154 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "tls")] use crate::http::tls::TlsServer;
+
+use crate::rocket::Rocket;
+use crate::listener::Listener;
+use crate::shutdown::{Shutdown, ShutdownListener, Serving, Wakeup};
+use crate::timeout::TimeoutListener;
+use crate::proxy::{ProxyListener, Plain};
+use crate::config::{Config, TlsConfig};
+use crate::http::hyper::{self, header};
+#[cfg(unix)] use crate::unix::UnixListener;
+
+/// Starts serving requests received by `listener` with `handler` using the
+/// workers and timeouts in `config`. If `tls` is `Some` and `listener` is a TCP
+/// listener, connections are secured with TLS. If `proxy` is `true`, every
+/// connection must begin with a PROXY protocol header.
+///
+/// Hyper's `Server<L>` is generic over the type of listener, and the types of
+/// TLS, plain, and Unix domain socket listeners differ. As such, each case is
+/// handled separately, with the common setup performed by `serve_on!`. In all
+/// cases, the listener is wrapped in a `TimeoutListener`, or a `ProxyListener`
+/// when `proxy` is set, which enforces the header deadline, and in a
+/// `ShutdownListener` so that no new connections are accepted once a shutdown
+/// has been requested and the listener can be closed.
+pub(crate) fn serve<H: hyper::Handler + 'static>(
+    listener: Listener,
+    tls: Option<TlsConfig>,
+    proxy: bool,
+    handler: H,
+    shutdown: &Shutdown,
+    config: &Config,
+) -> Result<Serving, hyper::Error> {
+    let secs = |timeout: Option<u32>| timeout.map(|s| Duration::from_secs(s as u64));
+    let header_timeout = secs(config.header_timeout);
+    let acceptors = Arc::new(());
+
+    macro_rules! serve_on {
+        (@wrapped $listener:expr) => ({
+            let listener = ShutdownListener::new($listener, shutdown.clone(), acceptors.clone());
+            let mut server = hyper::Server::new(listener);
+            server.keep_alive(secs(config.keep_alive));
+            server.set_read_timeout(secs(config.read_timeout));
+            server.set_write_timeout(secs(config.write_timeout));
+            server.handle_threads(handler, config.workers as usize)
+        });
+        ($listener:expr) => (
+            match proxy {
+                true => serve_on!(@wrapped ProxyListener::new($listener, Plain, header_timeout)),
+                false => serve_on!(@wrapped TimeoutListener::new($listener, header_timeout)),
+            }
+        );
+        ($listener:expr, $tls:expr) => (
+            match proxy {
+                true => serve_on!(@wrapped ProxyListener::new($listener, $tls, header_timeout)),
+                false => {
+                    let listener = hyper::net::HttpsListener::with_listener($listener, $tls);
+                    serve_on!(@wrapped TimeoutListener::new(listener, header_timeout))
+                }
+            }
+        );
+    }
+
+    #[cfg(not(feature = "tls"))]
+    let _ = tls;
+
+    let wakeup = match listener.socket_path() {
+        #[cfg(unix)]
+        Some(path) => Some(Wakeup::Unix(path)),
+        _ if listener.is_unix() => None,
+        _ => listener.local_addr().ok().map(Wakeup::Tcp),
+    };
+
+    let listening = match listener {
+        #[cfg(feature = "tls")]
+        Listener::Tcp(l) => match tls {
+            Some(tls) => {
+                let tls = TlsServer::new(tls.certs, tls.key);
+                serve_on!(hyper::net::HttpListener::from(l), tls)
+            }
+            None => serve_on!(hyper::net::HttpListener::from(l)),
+        }
+        #[cfg(not(feature = "tls"))]
+        Listener::Tcp(l) => serve_on!(hyper::net::HttpListener::from(l)),
+        #[cfg(unix)]
+        Listener::Unix(l) => serve_on!(UnixListener::from(l)),
+    }?;
+
+    Ok(Serving::new(listening, acceptors, wakeup))
+}
+
+/// Returns the scheme prefix used when logging the address of `listener`.
+pub(crate) fn proto(listener: &Listener, tls: bool) -> &'static str {
+    match (listener.is_unix(), tls && cfg!(feature = "tls")) {
+        (true, _) => "http+",
+        (false, true) => "https://",
+        (false, false) => "http://",
+    }
+}
+
+/// Dispatches requests from any number of servers to one shared `Rocket`.
+pub(crate) struct Shared(pub Arc<Rocket>);
+
+impl hyper::Handler for Shared {
+    #[inline(always)]
+    fn handle<'h, 'k>(&self, req: hyper::Request<'h, 'k>, res: hyper::FreshResponse<'h>) {
+        hyper::Handler::handle(&*self.0, req, res)
+    }
+}
+
+/// Responds to every request with a permanent redirect to the same URI over
+/// HTTPS on `port`.
+pub(crate) struct Redirect {
+    pub port: u16,
+    pub shutdown: Shutdown,
+}
+
+impl hyper::Handler for Redirect {
+    fn handle<'h, 'k>(&self, req: hyper::Request<'h, 'k>, mut res: hyper::FreshResponse<'h>) {
+        let _in_flight = self.shutdown.in_flight();
+
+        let host = req.headers.get::<header::Host>().map(|host| host.hostname.clone());
+        let path = match req.uri {
+            hyper::RequestUri::AbsolutePath(ref path) => Some(path.clone()),
+            _ => None,
+        };
+
+        match (host, path) {
+            (Some(host), Some(path)) => {
+                let location = match self.port {
+                    443 => format!("https://{}{}", host, path),
+                    port => format!("https://{}:{}{}", host, port, path),
+                };
+
+                info_!("Redirecting {} {} to {}.", req.method, req.uri, location);
+                *res.status_mut() = hyper::StatusCode::PermanentRedirect;
+                res.headers_mut().set(header::Location(location));
+            }
+            _ => {
+                warn_!("Unable to redirect {} {} to HTTPS.", req.method, req.uri);
+                *res.status_mut() = hyper::StatusCode::BadRequest;
+            }
+        }
+
+        if self.shutdown.is_notified() {
+            res.headers_mut().set(header::Connection::close());
+        }
+
+        if let Err(e) = res.send(&[]) {
+            error_!("Failed to write response: {:?}.", e);
+        }
+    }
+}
@@ -0,0 +1,520 @@
+use std::io;
+use std::net::{SocketAddr, TcpStream};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, Condvar};
+use std::time::{Duration, Instant};
+use std::thread::{self, JoinHandle};
+
+use crate::request::{self, FromRequest, Request};
+use crate::outcome::Outcome;
+use crate::http::hyper::{self, net::NetworkListener};
+use crate::logger::PaintExt;
+
+use yansi::Paint;
+
+/// A handle to request a graceful shutdown of a launched Rocket application.
+///
+/// A `Shutdown` handle can be retrieved before launch via
+/// [`Rocket::shutdown()`](crate::Rocket::shutdown()) or, while requests are
+/// being handled, as a request guard. Calling [`Shutdown::notify()`] on any
+/// handle instructs the server to stop accepting new connections, wait for
+/// in-flight requests to complete for at most the configured grace period,
+/// and then return from [`Rocket::launch()`](crate::Rocket::launch()) or, if
+/// the server was started with [`Rocket::spawn()`](crate::Rocket::spawn()),
+/// from [`Running::join()`].
+///
+/// Handles are cheap to clone: all clones refer to the same server.
+///
+/// # Example
+///
+/// ```rust
+/// # #![feature(proc_macro_hygiene)]
+/// # #[macro_use] extern crate rocket;
+/// use rocket::Shutdown;
+///
+/// #[get("/shutdown")]
+/// fn shutdown(handle: Shutdown) -> &'static str {
+///     handle.notify();
+///     "Shutting down..."
+/// }
+///
+/// fn main() {
+/// # if false {
+///     let running = rocket::ignite()
+///         .mount("/", routes![shutdown])
+///         .spawn()
+///         .expect("launch failed");
+///
+///     // This returns only after `/shutdown` has been requested.
+///     running.join();
+/// # }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Shutdown(Arc<ShutdownState>);
+
+struct ShutdownState {
+    status: Mutex<Status>,
+    changed: Condvar,
+}
+
+#[derive(Default)]
+struct Status {
+    notified: bool,
+    in_flight: usize,
+}
+
+/// Keeps a request counted as in-flight until it is dropped.
+pub(crate) struct InFlight<'a>(&'a Shutdown);
+
+impl Shutdown {
+    pub(crate) fn new() -> Shutdown {
+        Shutdown(Arc::new(ShutdownState {
+            status: Mutex::new(Status::default()),
+            changed: Condvar::new(),
+        }))
+    }
+
+    #[inline]
+    fn status(&self) -> MutexGuard<'_, Status> {
+        // Nothing run while holding the lock can panic; recover regardless.
+        self.0.status.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Notifies the server to shut down gracefully. Calling this method more
+    /// than once, or before the server has launched, is allowed. In the latter
+    /// case, the server shuts down as soon as it has started.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let rocket = rocket::ignite();
+    /// let shutdown = rocket.shutdown();
+    /// assert!(!shutdown.is_notified());
+    ///
+    /// shutdown.notify();
+    /// assert!(shutdown.is_notified());
+    /// ```
+    pub fn notify(&self) {
+        let mut status = self.status();
+        if !status.notified {
+            status.notified = true;
+            self.0.changed.notify_all();
+        }
+    }
+
+    /// Returns `true` if a shutdown has been requested via
+    /// [`Shutdown::notify()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let shutdown = rocket::ignite().shutdown();
+    /// assert!(!shutdown.is_notified());
+    /// ```
+    #[inline]
+    pub fn is_notified(&self) -> bool {
+        self.status().notified
+    }
+
+    /// Marks a request as in-flight for as long as the returned value lives.
+    pub(crate) fn in_flight(&self) -> InFlight<'_> {
+        self.status().in_flight += 1;
+        InFlight(self)
+    }
+
+    /// Blocks the calling thread until a shutdown has been requested.
+    pub(crate) fn wait(&self) {
+        let mut status = self.status();
+        while !status.notified {
+            status = self.0.changed.wait(status).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Blocks until all in-flight requests have completed or `grace` has
+    /// elapsed, whichever comes first. Returns the number of requests that
+    /// were still in-flight when this method returned.
+    pub(crate) fn drain(&self, grace: Duration) -> usize {
+        let deadline = Instant::now() + grace;
+        let mut status = self.status();
+        while status.in_flight > 0 {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+
+            status = self.0.changed.wait_timeout(status, deadline - now)
+                .map(|(status, _)| status)
+                .unwrap_or_else(|e| e.into_inner().0);
+        }
+
+        status.in_flight
+    }
+
+    /// Spawns a thread that notifies `self` when the process receives `SIGINT`
+    /// or `SIGTERM`. A second signal terminates the process immediately. The
+    /// signals are unregistered and the thread exits when the returned
+    /// `Signals` is closed.
+    #[cfg(unix)]
+    pub(crate) fn listen_for_signals(&self) -> io::Result<Signals> {
+        use signal_hook::{SIGINT, SIGTERM};
+
+        let signals = Arc::new(signal_hook::iterator::Signals::new(&[SIGINT, SIGTERM])?);
+        let (iter, shutdown) = (signals.clone(), self.clone());
+        let thread = thread::Builder::new()
+            .name("rocket-signals".into())
+            .spawn(move || {
+                let mut received = false;
+                for signal in iter.forever() {
+                    if received {
+                        warn!("Received a second signal ({}). Exiting now.", signal);
+                        std::process::exit(1);
+                    }
+
+                    received = true;
+                    match shutdown.is_notified() {
+                        true => warn!("Received signal {} during shutdown.", signal),
+                        false => warn!("Received signal {}. Shutting down gracefully.", signal),
+                    }
+
+                    shutdown.notify();
+                }
+            })?;
+
+        Ok(Signals { signals, thread })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn listen_for_signals(&self) -> io::Result<Signals> {
+        let msg = "shutdown signals are only supported on Unix platforms";
+        Err(io::Error::new(io::ErrorKind::Other, msg))
+    }
+}
+
+/// The signals listened for by [`Shutdown::listen_for_signals()`].
+pub(crate) struct Signals {
+    #[cfg(unix)]
+    signals: Arc<signal_hook::iterator::Signals>,
+    #[cfg(unix)]
+    thread: JoinHandle<()>,
+}
+
+impl Signals {
+    /// Unregisters the signals and waits for the listening thread to exit.
+    pub(crate) fn close(self) {
+        #[cfg(unix)] {
+            self.signals.close();
+            let _ = self.thread.join();
+        }
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        let mut status = (self.0).status();
+        status.in_flight -= 1;
+        if status.in_flight == 0 {
+            (self.0).0.changed.notify_all();
+        }
+    }
+}
+
+impl FromRequest<'_, '_> for Shutdown {
+    type Error = std::convert::Infallible;
+
+    #[inline]
+    fn from_request(request: &Request<'_>) -> request::Outcome<Self, Self::Error> {
+        Outcome::Success(request.state.shutdown.clone())
+    }
+}
+
+/// A handle to a Rocket application running in the background.
+///
+/// A `Running` handle is returned by [`Rocket::spawn()`](crate::Rocket::spawn()),
+/// which returns as soon as the server is bound and the launch fairings have
+/// run. The handle exposes the address the server is listening on, allows
+/// stopping the server gracefully via [`Running::stop()`], and allows waiting
+/// for it to shut down via [`Running::join()`].
+///
+/// Dropping a `Running` handle does _not_ stop the server. Once a server has
+/// shut down, its listeners are closed and their ports can be bound again.
+///
+/// # Example
+///
+/// ```rust
+/// use rocket::config::{Config, Environment};
+///
+/// let config = Config::build(Environment::Staging)
+///     .address("127.0.0.1")
+///     .port(0)
+///     .unwrap();
+///
+/// let running = rocket::custom(config).spawn().expect("launch failed");
+/// assert_ne!(running.addr().port(), 0);
+///
+/// // Make requests against `running.addr()`, then stop the server.
+/// running.stop();
+/// ```
+pub struct Running {
+    addrs: Vec<SocketAddr>,
+    shutdown: Shutdown,
+    thread: JoinHandle<()>,
+}
+
+impl Running {
+    /// Spawns a thread that waits for `shutdown` to be notified, drains
+    /// in-flight requests for at most `grace`, and then closes every server in
+    /// `listening`, removing the Unix domain socket files in `sockets` and
+    /// closing `signals`, if any.
+    pub(crate) fn new(
+        addrs: Vec<SocketAddr>,
+        sockets: Vec<PathBuf>,
+        shutdown: Shutdown,
+        signals: Option<Signals>,
+        grace: Duration,
+        servers: Vec<Serving>,
+    ) -> io::Result<Running> {
+        let handle = shutdown.clone();
+        let thread = thread::Builder::new()
+            .name("rocket-shutdown".into())
+            .spawn(move || {
+                handle.wait();
+                launch_info!("{}{}", Paint::emoji("🛑 "),
+                    Paint::default("Shutdown requested. Draining requests...").bold());
+
+                match handle.drain(grace) {
+                    0 => launch_info_!("All requests completed."),
+                    n => warn_!("Grace period expired with {} request(s) in-flight.", n),
+                }
+
+                // Connections that remain open keep their acceptor thread, and
+                // thus the listener, alive. Give them another grace period.
+                let deadline = Instant::now() + grace;
+                let open = servers.into_iter()
+                    .map(|server| server.close(deadline))
+                    .filter(|closed| !closed)
+                    .count();
+
+                if open > 0 {
+                    warn_!("{} listener(s) have open connections and will close \
+                        once the connections do.", open);
+                }
+
+                for path in sockets {
+                    if let Err(e) = std::fs::remove_file(&path) {
+                        warn_!("Failed to remove socket file '{}': {}", path.display(), e);
+                    }
+                }
+
+                if let Some(signals) = signals {
+                    signals.close();
+                }
+
+                launch_info!("{}{}", Paint::emoji("🚀 "),
+                    Paint::default("Rocket has shut down.").bold());
+            })?;
+
+        Ok(Running { addrs, shutdown, thread })
+    }
+
+    /// Returns the socket address the server is bound to. If the server was
+    /// configured with port `0`, the returned address contains the port that
+    /// was actually assigned by the operating system. If the server is bound
+    /// to a Unix domain socket, the returned address is unspecified.
+    ///
+    /// When additional listeners are configured, this is the address of the
+    /// primary listener. Use [`Running::addrs()`] to retrieve all of them.
+    #[inline(always)]
+    pub fn addr(&self) -> SocketAddr {
+        self.addrs[0]
+    }
+
+    /// Returns the socket addresses of all of the server's listeners: the
+    /// primary listener first, followed by any additional listeners in the
+    /// order they were configured and, finally, the redirecting listener, if
+    /// any.
+    #[inline(always)]
+    pub fn addrs(&self) -> &[SocketAddr] {
+        &self.addrs
+    }
+
+    /// Returns a [`Shutdown`] handle for the running server.
+    #[inline(always)]
+    pub fn shutdown(&self) -> Shutdown {
+        self.shutdown.clone()
+    }
+
+    /// Blocks the calling thread until the server has shut down. The server
+    /// shuts down once any [`Shutdown`] handle for it is notified. When this
+    /// method returns, the server's listeners have been closed unless
+    /// connections remained open beyond the grace period, in which case each
+    /// affected listener is closed once its connections are.
+    pub fn join(self) {
+        if let Err(panic) = self.thread.join() {
+            std::panic::resume_unwind(panic);
+        }
+    }
+
+    /// Requests a graceful shutdown of the server and blocks until it has
+    /// completed. This is equivalent to calling [`Shutdown::notify()`] followed
+    /// by [`Running::join()`].
+    pub fn stop(self) {
+        self.shutdown.notify();
+        self.join();
+    }
+}
+
+/// A server started by `server::serve()` on a [`ShutdownListener`].
+pub(crate) struct Serving {
+    listening: hyper::Listening,
+    acceptors: Arc<()>,
+    wakeup: Option<Wakeup>,
+}
+
+/// The address to connect to in order to wake a thread blocked in `accept()`.
+pub(crate) enum Wakeup {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl Wakeup {
+    /// Connects to the listener and immediately disconnects.
+    fn wake(&self) {
+        let timeout = Duration::from_millis(100);
+        let _ = match *self {
+            Wakeup::Tcp(addr) if addr.ip().is_unspecified() => {
+                let ip: std::net::IpAddr = match addr {
+                    SocketAddr::V4(_) => std::net::Ipv4Addr::LOCALHOST.into(),
+                    SocketAddr::V6(_) => std::net::Ipv6Addr::LOCALHOST.into(),
+                };
+
+                TcpStream::connect_timeout(&SocketAddr::new(ip, addr.port()), timeout)
+                    .map(drop)
+            }
+            Wakeup::Tcp(addr) => TcpStream::connect_timeout(&addr, timeout).map(drop),
+            #[cfg(unix)]
+            Wakeup::Unix(ref path) => std::os::unix::net::UnixStream::connect(path).map(drop),
+        };
+    }
+}
+
+impl Serving {
+    pub(crate) fn new(
+        listening: hyper::Listening,
+        acceptors: Arc<()>,
+        wakeup: Option<Wakeup>
+    ) -> Serving {
+        Serving { listening, acceptors, wakeup }
+    }
+
+    /// Waits until `deadline` for every acceptor thread to exit, waking those
+    /// blocked in `accept()`, and then drops the listener. Returns `false` if
+    /// threads were still serving connections at `deadline`; the listener is
+    /// dropped when the last of them exits.
+    fn close(self, deadline: Instant) -> bool {
+        let Serving { mut listening, acceptors, wakeup } = self;
+        while cfg!(panic = "unwind") && Arc::strong_count(&acceptors) > 1 {
+            if Instant::now() >= deadline {
+                break;
+            }
+
+            if let Some(ref wakeup) = wakeup {
+                (1..Arc::strong_count(&acceptors)).for_each(|_| wakeup.wake());
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        if Arc::strong_count(&acceptors) > 1 {
+            // Dropping `Listening` joins hyper's supervisor thread, which only
+            // exits once an acceptor thread has. Detach it instead.
+            let _ = listening.close();
+            return false;
+        }
+
+        true
+    }
+}
+
+/// A listener that stops accepting connections once a shutdown is notified.
+///
+/// Hyper's acceptor threads loop forever and are replaced by a fresh clone of
+/// the listener when they exit. Once a shutdown has been requested, both
+/// accepting and cloning unwind the calling thread instead, so that every
+/// acceptor thread, and eventually hyper's supervisor thread, exits, dropping
+/// its copy of the listener. A connection accepted concurrently with the
+/// notification is closed immediately. Threads blocked in `accept()` are woken
+/// by connecting to the listener; see [`Serving::close()`].
+///
+/// When panics abort instead of unwinding, the threads park forever instead
+/// and the listener is never closed.
+pub(crate) struct ShutdownListener<L> {
+    inner: L,
+    shutdown: Shutdown,
+    /// Dropped after `inner`: one reference per live copy of the listener.
+    acceptors: Arc<()>,
+}
+
+/// The payload of the unwind that exits an acceptor thread.
+#[cfg(panic = "unwind")]
+struct AcceptorExit;
+
+impl<L: NetworkListener> ShutdownListener<L> {
+    pub(crate) fn new(inner: L, shutdown: Shutdown, acceptors: Arc<()>) -> Self {
+        ShutdownListener { inner, shutdown, acceptors }
+    }
+}
+
+/// Exits the calling acceptor thread without invoking the panic hook.
+fn exit_acceptor() -> ! {
+    #[cfg(panic = "unwind")]
+    std::panic::resume_unwind(Box::new(AcceptorExit));
+
+    #[cfg(not(panic = "unwind"))]
+    loop { thread::park(); }
+}
+
+impl<L: Clone> Clone for ShutdownListener<L> {
+    fn clone(&self) -> Self {
+        if self.shutdown.is_notified() {
+            exit_acceptor();
+        }
+
+        ShutdownListener {
+            inner: self.inner.clone(),
+            shutdown: self.shutdown.clone(),
+            acceptors: self.acceptors.clone(),
+        }
+    }
+}
+
+impl<L: NetworkListener + Clone> NetworkListener for ShutdownListener<L> {
+    type Stream = L::Stream;
+
+    fn accept(&mut self) -> Result<Self::Stream, hyper::Error> {
+        if self.shutdown.is_notified() {
+            exit_acceptor();
+        }
+
+        let result = self.inner.accept();
+        if self.shutdown.is_notified() {
+            drop(result);
+            exit_acceptor();
+        }
+
+        result
+    }
+
+    fn local_addr(&mut self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    fn set_read_timeout(&mut self, duration: Option<Duration>) {
+        self.inner.set_read_timeout(duration)
+    }
+
+    fn set_write_timeout(&mut self, duration: Option<Duration>) {
+        self.inner.set_write_timeout(duration)
+    }
+}
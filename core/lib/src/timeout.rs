@@ -0,0 +1,235 @@
+use std::io;
+use std::net::{SocketAddr, Shutdown};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::http::hyper::{self, net::{NetworkListener, NetworkStream}};
+
+/// A listener whose connections must deliver the headers of every request
+/// within `header_timeout` of the request's first byte.
+///
+/// Socket timeouts only bound the time a _single_ read may block, so a client
+/// that trickles in a byte at a time can hold on to a worker indefinitely. The
+/// streams returned by this listener additionally enforce a deadline on the
+/// headers as a whole.
+#[derive(Clone)]
+pub(crate) struct TimeoutListener<L> {
+    inner: L,
+    header_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+}
+
+impl<L: NetworkListener> TimeoutListener<L> {
+    pub(crate) fn new(inner: L, header_timeout: Option<Duration>) -> Self {
+        TimeoutListener { inner, header_timeout, read_timeout: None }
+    }
+}
+
+impl<L: NetworkListener + Clone> NetworkListener for TimeoutListener<L> {
+    type Stream = TimeoutStream<L::Stream>;
+
+    fn accept(&mut self) -> Result<Self::Stream, hyper::Error> {
+        let stream = self.inner.accept()?;
+        Ok(TimeoutStream::new(stream, self.header_timeout, self.read_timeout))
+    }
+
+    fn local_addr(&mut self) -> io::Result<SocketAddr> {
+        self.inner.local_addr()
+    }
+
+    fn set_read_timeout(&mut self, duration: Option<Duration>) {
+        self.read_timeout = duration;
+        self.inner.set_read_timeout(duration);
+    }
+
+    fn set_write_timeout(&mut self, duration: Option<Duration>) {
+        self.inner.set_write_timeout(duration);
+    }
+}
+
+/// Where a connection is in the request/response cycle.
+#[derive(Debug, Clone, Copy)]
+enum Phase {
+    /// Waiting for the first byte of the next request.
+    Idle,
+    /// Reading the headers of a request, which must be complete by the
+    /// contained deadline.
+    Headers(Instant),
+    /// The headers have been read; the request is being handled.
+    Handling,
+}
+
+#[derive(Debug)]
+struct State {
+    phase: Phase,
+    read_timeout: Option<Duration>,
+}
+
+/// A connection accepted by a `TimeoutListener`.
+///
+/// Hyper doesn't announce when it has finished reading the headers of a
+/// request, but it does reset the read timeout of a connection at two fixed
+/// points: once the headers of a request have been read, to the read timeout,
+/// and once the response has been written, to the keep-alive timeout. These
+/// calls alternate, and we use them to track the phase of the connection.
+///
+/// Hyper reads from a clone of the connection, so the state is shared.
+pub(crate) struct TimeoutStream<S> {
+    inner: S,
+    header_timeout: Option<Duration>,
+    state: Arc<Mutex<State>>,
+    peer: Option<SocketAddr>,
+}
+
+impl<S> TimeoutStream<S> {
+    pub(crate) fn new(
+        inner: S,
+        header_timeout: Option<Duration>,
+        read_timeout: Option<Duration>
+    ) -> Self {
+        let state = State { phase: Phase::Idle, read_timeout };
+        let state = Arc::new(Mutex::new(state));
+        TimeoutStream { inner, header_timeout, state, peer: None }
+    }
+
+    /// Overrides the peer address of the connection with `peer`, if it is
+    /// `Some`, as when the address is received via the PROXY protocol.
+    #[inline(always)]
+    pub(crate) fn with_peer(mut self, peer: Option<SocketAddr>) -> Self {
+        self.peer = peer;
+        self
+    }
+
+    /// Returns the peer address received via the PROXY protocol, if any.
+    #[inline(always)]
+    pub(crate) fn peer(&self) -> Option<SocketAddr> {
+        self.peer
+    }
+
+    /// Returns the underlying stream.
+    #[inline(always)]
+    pub(crate) fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    #[inline]
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("connection state lock")
+    }
+}
+
+/// Returns `true` if `error` is the result of a socket timeout. Depending on
+/// the platform, a timed out read or write fails with either error kind.
+#[inline]
+pub(crate) fn is_timeout(error: &io::Error) -> bool {
+    match error.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => true,
+        _ => false
+    }
+}
+
+impl<S: NetworkStream> io::Read for TimeoutStream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let header_timeout = match self.header_timeout {
+            Some(timeout) => timeout,
+            None => return self.inner.read(buf),
+        };
+
+        let (phase, read_timeout) = {
+            let state = self.state();
+            (state.phase, state.read_timeout)
+        };
+
+        match phase {
+            Phase::Handling => self.inner.read(buf),
+            Phase::Idle => {
+                let n = self.inner.read(buf)?;
+                if n > 0 {
+                    self.state().phase = Phase::Headers(Instant::now() + header_timeout);
+                }
+
+                Ok(n)
+            }
+            Phase::Headers(deadline) => {
+                let now = Instant::now();
+                if now < deadline {
+                    // Never block past the deadline, even if the read timeout
+                    // would allow it.
+                    let remaining = deadline - now;
+                    let timeout = read_timeout.map_or(remaining, |t| t.min(remaining));
+                    self.inner.set_read_timeout(Some(timeout))?;
+                    match self.inner.read(buf) {
+                        Err(ref e) if is_timeout(e) && Instant::now() >= deadline => {},
+                        result => return result,
+                    }
+                }
+
+                warn_!("Request headers not received within {}s. Closing connection.",
+                       header_timeout.as_secs());
+
+                let msg = "deadline for receiving request headers expired";
+                Err(io::Error::new(io::ErrorKind::TimedOut, msg))
+            }
+        }
+    }
+}
+
+impl<S: NetworkStream> io::Write for TimeoutStream<S> {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<S: NetworkStream + Clone> Clone for TimeoutStream<S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        TimeoutStream {
+            inner: self.inner.clone(),
+            header_timeout: self.header_timeout,
+            state: self.state.clone(),
+            peer: self.peer,
+        }
+    }
+}
+
+impl<S: NetworkStream> NetworkStream for TimeoutStream<S> {
+    #[inline(always)]
+    fn peer_addr(&mut self) -> io::Result<SocketAddr> {
+        match self.peer {
+            Some(peer) => Ok(peer),
+            None => self.inner.peer_addr(),
+        }
+    }
+
+    fn set_read_timeout(&self, dur: Option<Duration>) -> io::Result<()> {
+        {
+            let mut state = self.state();
+            let phase = state.phase;
+            state.phase = match phase {
+                Phase::Idle | Phase::Headers(_) => {
+                    state.read_timeout = dur;
+                    Phase::Handling
+                }
+                Phase::Handling => Phase::Idle,
+            };
+        }
+
+        self.inner.set_read_timeout(dur)
+    }
+
+    #[inline(always)]
+    fn set_write_timeout(&self, dur: Option<Duration>) -> io::Result<()> {
+        self.inner.set_write_timeout(dur)
+    }
+
+    #[inline(always)]
+    fn close(&mut self, how: Shutdown) -> io::Result<()> {
+        self.inner.close(how)
+    }
+}
@@ -0,0 +1,160 @@
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, Shutdown};
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::http::hyper::{self, net::{NetworkListener, NetworkStream}};
+use crate::timeout::TimeoutStream;
+
+/// Unix domain sockets don't have a `SocketAddr`, but Hyper insists on one for
+/// every listener and stream. This is the address reported in its place.
+#[inline(always)]
+pub fn unspecified_addr() -> SocketAddr {
+    SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0))
+}
+
+/// Returns `true` if `stream` is a connection over a Unix domain socket whose
+/// peer address wasn't received via the PROXY protocol.
+#[inline(always)]
+pub fn is_anonymous_unix_stream(stream: &dyn NetworkStream) -> bool {
+    stream.downcast_ref::<TimeoutStream<UnixStream>>().map_or(false, |s| s.peer().is_none())
+}
+
+/// A Hyper listener on a Unix domain socket.
+#[derive(Clone)]
+pub struct UnixListener {
+    listener: Arc<net::UnixListener>,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+}
+
+impl From<net::UnixListener> for UnixListener {
+    fn from(listener: net::UnixListener) -> UnixListener {
+        UnixListener {
+            listener: Arc::new(listener),
+            read_timeout: None,
+            write_timeout: None
+        }
+    }
+}
+
+/// Binds a new listener to the socket file at `path`. A stale socket file, one
+/// that no process is listening on, is removed first. If `mode` is `Some`, the
+/// permissions of the socket file are set to `mode`.
+pub fn bind(path: &Path, mode: Option<u32>) -> io::Result<net::UnixListener> {
+    remove_stale_socket(path)?;
+    let listener = net::UnixListener::bind(path)?;
+    if let Some(mode) = mode {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    }
+
+    Ok(listener)
+}
+
+/// Removes the socket file at `path` if it is not in use. Returns an error if
+/// `path` exists but isn't a socket or if some process is listening on it.
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+
+    if !metadata.file_type().is_socket() {
+        let msg = format!("'{}' exists and is not a socket", path.display());
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, msg));
+    }
+
+    match net::UnixStream::connect(path) {
+        Ok(_) => {
+            let msg = format!("socket '{}' is already in use", path.display());
+            Err(io::Error::new(io::ErrorKind::AddrInUse, msg))
+        }
+        Err(ref e) if e.kind() == io::ErrorKind::ConnectionRefused => {
+            warn_!("Removing stale socket file '{}'.", path.display());
+            fs::remove_file(path)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+impl NetworkListener for UnixListener {
+    type Stream = UnixStream;
+
+    fn accept(&mut self) -> Result<UnixStream, hyper::Error> {
+        let (stream, _) = self.listener.accept()?;
+        stream.set_read_timeout(self.read_timeout)?;
+        stream.set_write_timeout(self.write_timeout)?;
+        Ok(UnixStream(stream))
+    }
+
+    fn local_addr(&mut self) -> io::Result<SocketAddr> {
+        Ok(unspecified_addr())
+    }
+
+    fn set_read_timeout(&mut self, duration: Option<Duration>) {
+        self.read_timeout = duration;
+    }
+
+    fn set_write_timeout(&mut self, duration: Option<Duration>) {
+        self.write_timeout = duration;
+    }
+}
+
+/// A Hyper stream over a Unix domain socket connection.
+pub struct UnixStream(net::UnixStream);
+
+impl Clone for UnixStream {
+    #[inline]
+    fn clone(&self) -> UnixStream {
+        UnixStream(self.0.try_clone().expect("cloned Unix socket"))
+    }
+}
+
+impl io::Read for UnixStream {
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl io::Write for UnixStream {
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl NetworkStream for UnixStream {
+    #[inline(always)]
+    fn peer_addr(&mut self) -> io::Result<SocketAddr> {
+        Ok(unspecified_addr())
+    }
+
+    #[inline(always)]
+    fn set_read_timeout(&self, dur: Option<Duration>) -> io::Result<()> {
+        self.0.set_read_timeout(dur)
+    }
+
+    #[inline(always)]
+    fn set_write_timeout(&self, dur: Option<Duration>) -> io::Result<()> {
+        self.0.set_write_timeout(dur)
+    }
+
+    #[inline]
+    fn close(&mut self, how: Shutdown) -> io::Result<()> {
+        match self.0.shutdown(how) {
+            Err(ref e) if e.kind() == io::ErrorKind::NotConnected => Ok(()),
+            result => result
+        }
+    }
+}
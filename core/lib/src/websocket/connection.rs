@@ -0,0 +1,107 @@
+use std::io::{self, Read, Write, Cursor};
+use std::net::Shutdown;
+use std::sync::mpsc::{channel, Sender, Receiver};
+
+use crate::ext::catch_panic;
+use crate::http::hyper::net::NetworkStream;
+use crate::response::Upgrade;
+
+/// The raw connection of an upgraded request: a network connection, or one
+/// end of an in-memory pipe for local requests.
+pub(crate) struct Connection {
+    pub(crate) reader: Box<dyn Read + Send>,
+    pub(crate) writer: Box<dyn Write + Send>,
+    network: Option<Box<dyn NetworkStream + Send>>,
+}
+
+impl Connection {
+    /// Creates a connection over the network stream `stream`. `buffered`
+    /// contains bytes that were already read from the stream but not
+    /// consumed.
+    pub(crate) fn network<S>(buffered: Vec<u8>, stream: S) -> Connection
+        where S: NetworkStream + Send + Clone + 'static
+    {
+        Connection {
+            reader: Box::new(Cursor::new(buffered).chain(stream.clone())),
+            writer: Box::new(stream.clone()),
+            network: Some(Box::new(stream)),
+        }
+    }
+
+    /// Creates a pair of connections, connected to one another in memory.
+    pub(crate) fn pair() -> (Connection, Connection) {
+        let (a_tx, a_rx) = channel();
+        let (b_tx, b_rx) = channel();
+        let a = Connection {
+            reader: Box::new(PipeReader { rx: b_rx, buffer: Cursor::new(vec![]) }),
+            writer: Box::new(PipeWriter { tx: a_tx }),
+            network: None,
+        };
+
+        let b = Connection {
+            reader: Box::new(PipeReader { rx: a_rx, buffer: Cursor::new(vec![]) }),
+            writer: Box::new(PipeWriter { tx: b_tx }),
+            network: None,
+        };
+
+        (a, b)
+    }
+
+    /// Runs `upgrade` with `self`. A network connection is no longer subject
+    /// to the server's read timeouts and is closed once `upgrade` returns, even
+    /// though clones of it, like the one held by hyper, remain.
+    pub(crate) fn run(mut self, upgrade: Upgrade) {
+        let network = self.network.take();
+        if let Some(ref stream) = network {
+            let _ = stream.set_read_timeout(None);
+        }
+
+        if let Err(e) = catch_panic(move || upgrade(self)) {
+            error_!("Connection upgrade panicked: {}", e);
+        }
+
+        if let Some(mut stream) = network {
+            if let Err(e) = stream.close(Shutdown::Both) {
+                debug_!("Failed to close upgraded connection: {:?}", e);
+            }
+        }
+    }
+}
+
+/// The reading end of an in-memory pipe. Reads return EOF once the writing
+/// end has been dropped.
+struct PipeReader {
+    rx: Receiver<Vec<u8>>,
+    buffer: Cursor<Vec<u8>>,
+}
+
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.buffer.position() as usize >= self.buffer.get_ref().len() {
+            match self.rx.recv() {
+                Ok(bytes) => self.buffer = Cursor::new(bytes),
+                Err(_) => return Ok(0),
+            }
+        }
+
+        self.buffer.read(buf)
+    }
+}
+
+/// The writing end of an in-memory pipe.
+struct PipeWriter {
+    tx: Sender<Vec<u8>>,
+}
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.tx.send(buf.to_vec())
+            .map(|_| buf.len())
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use std::io::{self, Read, Write};
+
+/// The opcode of a frame, RFC 6455 section 5.2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OpCode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+}
+
+impl OpCode {
+    fn from_u8(byte: u8) -> Option<OpCode> {
+        match byte {
+            0x0 => Some(OpCode::Continuation),
+            0x1 => Some(OpCode::Text),
+            0x2 => Some(OpCode::Binary),
+            0x8 => Some(OpCode::Close),
+            0x9 => Some(OpCode::Ping),
+            0xA => Some(OpCode::Pong),
+            _ => None
+        }
+    }
+
+    fn as_u8(self) -> u8 {
+        match self {
+            OpCode::Continuation => 0x0,
+            OpCode::Text => 0x1,
+            OpCode::Binary => 0x2,
+            OpCode::Close => 0x8,
+            OpCode::Ping => 0x9,
+            OpCode::Pong => 0xA,
+        }
+    }
+
+    /// Returns `true` if `self` is the opcode of a control frame.
+    pub(crate) fn is_control(self) -> bool {
+        match self {
+            OpCode::Close | OpCode::Ping | OpCode::Pong => true,
+            _ => false
+        }
+    }
+}
+
+/// A single frame, with its payload unmasked.
+#[derive(Debug)]
+pub(crate) struct Frame {
+    pub fin: bool,
+    pub opcode: OpCode,
+    pub payload: Vec<u8>,
+}
+
+/// Why a frame couldn't be read. Each variant corresponds to the status code
+/// the connection is closed with.
+#[derive(Debug)]
+pub(crate) enum FrameError {
+    /// The connection failed or was closed.
+    Io(io::Error),
+    /// The peer violated the protocol: status code `1002`.
+    Protocol(&'static str),
+    /// The frame exceeds the configured size limit: status code `1009`.
+    TooLarge,
+}
+
+impl From<io::Error> for FrameError {
+    fn from(error: io::Error) -> FrameError {
+        FrameError::Io(error)
+    }
+}
+
+/// Reads a frame from `reader`. If `masked` is `true`, the frame must be
+/// masked, as frames sent by clients are; otherwise, it must not be. Frames
+/// with a payload larger than `limit` bytes are rejected before the payload is
+/// read.
+pub(crate) fn read_frame<R: Read>(
+    reader: &mut R,
+    masked: bool,
+    limit: u64
+) -> Result<Frame, FrameError> {
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head)?;
+
+    let fin = head[0] & 0x80 != 0;
+    if head[0] & 0x70 != 0 {
+        return Err(FrameError::Protocol("reserved bits set without an extension"));
+    }
+
+    let opcode = OpCode::from_u8(head[0] & 0x0F)
+        .ok_or(FrameError::Protocol("unknown opcode"))?;
+
+    if head[1] & 0x80 != 0 && !masked {
+        return Err(FrameError::Protocol("frames from servers must not be masked"));
+    } else if head[1] & 0x80 == 0 && masked {
+        return Err(FrameError::Protocol("frames from clients must be masked"));
+    }
+
+    let len = match head[1] & 0x7F {
+        126 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_be_bytes(len) as u64
+        }
+        127 => {
+            let mut len = [0u8; 8];
+            reader.read_exact(&mut len)?;
+            let len = u64::from_be_bytes(len);
+            if len & (1 << 63) != 0 {
+                return Err(FrameError::Protocol("payload length is out of range"));
+            }
+
+            len
+        }
+        len => len as u64
+    };
+
+    if opcode.is_control() && (!fin || len > 125) {
+        return Err(FrameError::Protocol("control frames must be small and unfragmented"));
+    }
+
+    if len > limit {
+        return Err(FrameError::TooLarge);
+    }
+
+    let mut mask = [0u8; 4];
+    if masked {
+        reader.read_exact(&mut mask)?;
+    }
+
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload)?;
+    if masked {
+        apply_mask(&mut payload, mask);
+    }
+
+    Ok(Frame { fin, opcode, payload })
+}
+
+/// Writes a single, final frame with `opcode` and `payload` to `writer`,
+/// masking it with `mask` if it is `Some`, and flushes `writer`.
+pub(crate) fn write_frame<W: Write>(
+    writer: &mut W,
+    opcode: OpCode,
+    payload: &[u8],
+    mask: Option<[u8; 4]>
+) -> io::Result<()> {
+    let mut head = Vec::with_capacity(14);
+    head.push(0x80 | opcode.as_u8());
+
+    let mask_bit = if mask.is_some() { 0x80 } else { 0 };
+    match payload.len() {
+        len if len < 126 => head.push(mask_bit | len as u8),
+        len if len <= u16::max_value() as usize => {
+            head.push(mask_bit | 126);
+            head.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            head.push(mask_bit | 127);
+            head.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+
+    match mask {
+        Some(mask) => {
+            head.extend_from_slice(&mask);
+            let mut payload = payload.to_vec();
+            apply_mask(&mut payload, mask);
+            writer.write_all(&head)?;
+            writer.write_all(&payload)?;
+        }
+        None => {
+            writer.write_all(&head)?;
+            writer.write_all(payload)?;
+        }
+    }
+
+    writer.flush()
+}
+
+/// Masks or unmasks `payload` with `mask`.
+fn apply_mask(payload: &mut [u8], mask: [u8; 4]) {
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+}
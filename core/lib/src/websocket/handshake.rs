@@ -0,0 +1,264 @@
+use std::{fmt, io};
+
+use sha1::{Sha1, Digest};
+
+use crate::request::{self, FromRequest, Request, GuardError};
+use crate::response::{self, Responder, Response};
+use crate::outcome::Outcome;
+use crate::http::{Method, Status};
+
+use super::socket::{Socket, Role};
+
+/// The GUID appended to a client's key to compute the accept key, from RFC
+/// 6455 section 1.3.
+const GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// The default `websocket` limit: 1MiB.
+const DEFAULT_LIMIT: u64 = 1 << 20;
+
+/// Request guard for a WebSocket handshake.
+///
+/// The guard forwards if the request doesn't ask to be upgraded to a
+/// WebSocket, so a route serving regular requests can share the path at a
+/// higher rank. Otherwise, it validates the handshake: the request must be a
+/// `GET` with a `Connection: Upgrade` header, version `13`, and a well-formed
+/// `Sec-WebSocket-Key`. If it isn't, the guard fails with `400 Bad Request` and
+/// a [`HandshakeError`].
+///
+/// Complete the handshake by returning the [`Channel`] created by
+/// [`WebSocket::channel()`]. See the [module documentation](crate::websocket)
+/// for an example.
+#[derive(Debug)]
+pub struct WebSocket {
+    key: String,
+    protocols: Vec<String>,
+    limit: u64,
+}
+
+/// The reason a WebSocket handshake was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandshakeError {
+    /// The request method isn't `GET`.
+    Method,
+    /// The `Connection` header doesn't contain the `upgrade` option.
+    Connection,
+    /// The `Sec-WebSocket-Version` header is missing or isn't `13`.
+    Version,
+    /// The `Sec-WebSocket-Key` header is missing or isn't a base64 encoded,
+    /// 16-byte value.
+    Key,
+}
+
+impl fmt::Display for HandshakeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            HandshakeError::Method => "WebSocket handshake must use GET".fmt(f),
+            HandshakeError::Connection => "missing `Connection: Upgrade` header".fmt(f),
+            HandshakeError::Version => "unsupported WebSocket version".fmt(f),
+            HandshakeError::Key => "missing or invalid `Sec-WebSocket-Key`".fmt(f),
+        }
+    }
+}
+
+/// Returns the comma-separated values of all headers named `name` in
+/// `request`.
+fn values(request: &Request<'_>, name: &str) -> Vec<String> {
+    request.headers().get(name)
+        .flat_map(|value| value.split(','))
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for WebSocket {
+    type Error = HandshakeError;
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        if !values(request, "Upgrade").iter().any(|v| v.eq_ignore_ascii_case("websocket")) {
+            return Outcome::Forward(());
+        }
+
+        let error = |e| Outcome::Failure((Status::BadRequest, e));
+        if request.method() != Method::Get {
+            return error(HandshakeError::Method);
+        }
+
+        if !values(request, "Connection").iter().any(|v| v.eq_ignore_ascii_case("upgrade")) {
+            return error(HandshakeError::Connection);
+        }
+
+        if request.headers().get_one("Sec-WebSocket-Version").map(|v| v.trim()) != Some("13") {
+            return error(HandshakeError::Version);
+        }
+
+        // `binascii` requires a bit more space than actual output for padding.
+        let key = request.headers().get_one("Sec-WebSocket-Key").unwrap_or("").trim();
+        let mut bytes = [0u8; 24];
+        match binascii::b64decode(key.as_bytes(), &mut bytes) {
+            Ok(decoded) if decoded.len() == 16 => { /* valid */ }
+            _ => return error(HandshakeError::Key)
+        }
+
+        Outcome::Success(WebSocket {
+            key: key.to_string(),
+            protocols: values(request, "Sec-WebSocket-Protocol"),
+            limit: request.limits().get("websocket").unwrap_or(DEFAULT_LIMIT),
+        })
+    }
+
+    #[inline(always)]
+    fn guard_error(error: Self::Error) -> GuardError {
+        GuardError::new(error)
+    }
+}
+
+impl WebSocket {
+    /// Returns the subprotocols the client requested, in order of preference.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// use rocket::websocket::{WebSocket, Channel};
+    ///
+    /// #[get("/chat")]
+    /// fn chat(ws: WebSocket) -> Option<Channel> {
+    ///     if !ws.protocols().iter().any(|p| p == "chat.v1") {
+    ///         return None;
+    ///     }
+    ///
+    ///     Some(ws.channel(|socket| Ok(())).protocol("chat.v1"))
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn protocols(&self) -> &[String] {
+        &self.protocols
+    }
+
+    /// Accepts the handshake. Once the response is sent, `handler` is called
+    /// with the [`Socket`] for the conversation on the connection's thread; the
+    /// connection is closed when `handler` returns. An error returned by
+    /// `handler` is logged.
+    ///
+    /// `handler` must be `'static`, so it can't borrow from the request or its
+    /// guards. To share managed state with it, manage an `Arc` and move a clone
+    /// into the closure.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #![feature(proc_macro_hygiene)]
+    /// # #[macro_use] extern crate rocket;
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// use rocket::State;
+    /// use rocket::websocket::{WebSocket, Channel, Message};
+    ///
+    /// #[get("/count")]
+    /// fn count(ws: WebSocket, counter: State<Arc<AtomicUsize>>) -> Channel {
+    ///     let counter = counter.inner().clone();
+    ///     ws.channel(move |mut socket| {
+    ///         while let Some(_) = socket.recv()? {
+    ///             let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+    ///             socket.send(Message::text(n.to_string()))?;
+    ///         }
+    ///
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    pub fn channel<F>(self, handler: F) -> Channel
+        where F: FnOnce(Socket) -> io::Result<()> + Send + 'static
+    {
+        Channel {
+            accept: accept_key(&self.key),
+            protocol: None,
+            limit: self.limit,
+            handler: Box::new(handler),
+        }
+    }
+}
+
+/// Computes the `Sec-WebSocket-Accept` value for the client's key `key`.
+fn accept_key(key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(GUID.as_bytes());
+
+    let mut encoded = [0u8; 28];
+    let encoded = binascii::b64encode(&hasher.finalize(), &mut encoded)
+        .expect("SHA-1 digest fits in 28 base64 characters");
+
+    String::from_utf8_lossy(encoded).into_owned()
+}
+
+/// Responder that completes a WebSocket handshake.
+///
+/// A `Channel` is created by [`WebSocket::channel()`]. It responds with `101
+/// Switching Protocols` and then hands the connection to its handler. When a
+/// `Channel` is returned to a client that isn't connected over the network or
+/// through [`LocalRequest::websocket()`], its handler isn't called.
+///
+/// [`LocalRequest::websocket()`]: crate::local::LocalRequest::websocket()
+pub struct Channel {
+    accept: String,
+    protocol: Option<String>,
+    limit: u64,
+    handler: Box<dyn FnOnce(Socket) -> io::Result<()> + Send>,
+}
+
+impl Channel {
+    /// Sets the subprotocol of the conversation. It should be one of those
+    /// returned by [`WebSocket::protocols()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::websocket::{WebSocket, Channel};
+    ///
+    /// fn accept(ws: WebSocket) -> Channel {
+    ///     ws.channel(|socket| Ok(())).protocol("chat.v1")
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn protocol<S: Into<String>>(mut self, protocol: S) -> Channel {
+        self.protocol = Some(protocol.into());
+        self
+    }
+}
+
+impl fmt::Debug for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Channel")
+            .field("protocol", &self.protocol)
+            .finish()
+    }
+}
+
+impl<'r> Responder<'r> for Channel {
+    fn respond_to(self, _: &Request<'_>) -> response::Result<'r> {
+        let mut response = Response::build()
+            .status(Status::SwitchingProtocols)
+            .raw_header("Upgrade", "websocket")
+            .raw_header("Connection", "Upgrade")
+            .raw_header("Sec-WebSocket-Accept", self.accept)
+            .finalize();
+
+        if let Some(protocol) = self.protocol {
+            response.set_raw_header("Sec-WebSocket-Protocol", protocol);
+        }
+
+        let (handler, limit) = (self.handler, self.limit);
+        response.set_upgrade(Box::new(move |connection| {
+            let socket = Socket::new(connection, Role::Server, limit);
+            if let Err(e) = handler(socket) {
+                warn_!("WebSocket handler failed: {}", e);
+            }
+        }));
+
+        Ok(response)
+    }
+}
@@ -0,0 +1,63 @@
+//! Types for upgrading requests to WebSocket conversations.
+//!
+//! A route accepts WebSocket connections with the [`WebSocket`] request guard
+//! and responds with the [`Channel`] returned by [`WebSocket::channel()`].
+//! Once the `101 Switching Protocols` response is sent, the channel's handler
+//! is called with a [`Socket`] to exchange [`Message`]s over:
+//!
+//! ```rust
+//! # #![feature(proc_macro_hygiene)]
+//! # #[macro_use] extern crate rocket;
+//! use rocket::websocket::{WebSocket, Channel, Message};
+//!
+//! #[get("/echo")]
+//! fn echo(ws: WebSocket) -> Channel {
+//!     ws.channel(|mut socket| {
+//!         while let Some(message) = socket.recv()? {
+//!             match message {
+//!                 Message::Text(_) | Message::Binary(_) => socket.send(message)?,
+//!                 _ => { /* pings are answered automatically */ }
+//!             }
+//!         }
+//!
+//!         Ok(())
+//!     })
+//! }
+//! ```
+//!
+//! Routes with a `WebSocket` guard are otherwise like any other: other request
+//! guards, including [`State`](crate::State), run before the handshake is
+//! completed, and a failing guard prevents it. Because the `WebSocket` guard
+//! forwards requests that don't ask for an upgrade, a route at a higher rank
+//! can serve regular requests to the same path.
+//!
+//! The handler runs on the thread that accepted the connection for as long as
+//! the conversation lasts. Each open WebSocket thus occupies one of the
+//! server's `workers`.
+//!
+//! # Limits
+//!
+//! The size of a received message is bounded by the `websocket` limit, which
+//! defaults to 1MiB. A larger message closes the connection with status code
+//! `1009`. To change it, set the limit in `Rocket.toml`:
+//!
+//! ```toml
+//! [global.limits]
+//! websocket = 4194304
+//! ```
+//!
+//! # Testing
+//!
+//! A WebSocket conversation can be driven in tests with
+//! [`LocalRequest::websocket()`](crate::local::LocalRequest::websocket()).
+
+mod frame;
+mod socket;
+mod handshake;
+mod connection;
+
+pub use self::handshake::{WebSocket, Channel, HandshakeError};
+pub use self::socket::{Socket, Message, CloseFrame};
+
+pub(crate) use self::connection::Connection;
+pub(crate) use self::socket::Role;
@@ -0,0 +1,388 @@
+use std::{fmt, io};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::Connection;
+use super::frame::{self, OpCode, Frame, FrameError};
+
+/// A message sent or received over a WebSocket.
+///
+/// Text and binary messages carry application data; fragmented messages are
+/// reassembled before they are returned from [`Socket::recv()`]. Pings, pongs,
+/// and closes are control messages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// A UTF-8 text message.
+    Text(String),
+    /// A binary message.
+    Binary(Vec<u8>),
+    /// A ping. Received pings are answered with a pong automatically.
+    Ping(Vec<u8>),
+    /// A pong, usually in response to a ping.
+    Pong(Vec<u8>),
+    /// A close, optionally with a status code and reason.
+    Close(Option<CloseFrame>),
+}
+
+impl Message {
+    /// Creates a text message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::websocket::Message;
+    ///
+    /// assert_eq!(Message::text("hi"), Message::Text("hi".into()));
+    /// ```
+    #[inline(always)]
+    pub fn text<S: Into<String>>(text: S) -> Message {
+        Message::Text(text.into())
+    }
+
+    /// Creates a binary message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::websocket::Message;
+    ///
+    /// assert_eq!(Message::binary(&[1, 2][..]), Message::Binary(vec![1, 2]));
+    /// ```
+    #[inline(always)]
+    pub fn binary<B: Into<Vec<u8>>>(bytes: B) -> Message {
+        Message::Binary(bytes.into())
+    }
+}
+
+/// The status code and reason of a close message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloseFrame {
+    /// The status code, as defined in RFC 6455 section 7.4. `1000` indicates a
+    /// normal closure.
+    pub code: u16,
+    /// The reason for closing. It is truncated to fit in a control frame.
+    pub reason: String,
+}
+
+impl CloseFrame {
+    /// Creates a close frame with status code `code` and reason `reason`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rocket::websocket::CloseFrame;
+    ///
+    /// let frame = CloseFrame::new(1001, "going away");
+    /// assert_eq!(frame.code, 1001);
+    /// ```
+    #[inline(always)]
+    pub fn new<S: Into<String>>(code: u16, reason: S) -> CloseFrame {
+        CloseFrame { code, reason: reason.into() }
+    }
+
+    fn parse(payload: &[u8]) -> Result<Option<CloseFrame>, u16> {
+        match payload.len() {
+            0 => Ok(None),
+            1 => Err(1002),
+            _ => {
+                let code = u16::from_be_bytes([payload[0], payload[1]]);
+                let reason = String::from_utf8(payload[2..].to_vec()).map_err(|_| 1007)?;
+                Ok(Some(CloseFrame { code, reason }))
+            }
+        }
+    }
+
+    fn to_payload(&self) -> Vec<u8> {
+        // A control frame's payload is at most 125 bytes, 2 of them the code.
+        let mut end = std::cmp::min(self.reason.len(), 123);
+        while !self.reason.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        let mut payload = self.code.to_be_bytes().to_vec();
+        payload.extend_from_slice(self.reason[..end].as_bytes());
+        payload
+    }
+}
+
+impl fmt::Display for CloseFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code)?;
+        if !self.reason.is_empty() {
+            write!(f, " ({})", self.reason)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Which end of the conversation a `Socket` is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Role {
+    Server,
+    Client,
+}
+
+/// A WebSocket connection that sends and receives [`Message`]s.
+///
+/// A `Socket` is handed to the function passed to [`WebSocket::channel()`];
+/// see the [module documentation](crate::websocket) for an example. Protocol
+/// details are handled internally:
+///
+///   * Fragmented messages are reassembled and text is validated as UTF-8.
+///   * Pings are answered with a pong before they are returned.
+///   * A close from the peer is echoed before it is returned. Thereafter,
+///     [`recv()`](Socket::recv()) returns `None`.
+///   * If the peer violates the protocol or sends a message larger than the
+///     `websocket` limit, the socket is closed with the appropriate status
+///     code and `recv()` returns an error.
+///
+/// If a socket is dropped before a close was sent, it sends a normal closure.
+///
+/// [`WebSocket::channel()`]: crate::websocket::WebSocket::channel()
+pub struct Socket {
+    connection: Connection,
+    role: Role,
+    limit: u64,
+    partial: Option<(OpCode, Vec<u8>)>,
+    close_sent: bool,
+    close_received: bool,
+    mask_state: u32,
+}
+
+impl Socket {
+    pub(crate) fn new(connection: Connection, role: Role, limit: u64) -> Socket {
+        // Clients mask their frames. The masks only need to be unpredictable
+        // to intermediaries, which the test client doesn't contend with.
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+
+        Socket {
+            connection, role, limit,
+            partial: None,
+            close_sent: false,
+            close_received: false,
+            mask_state: nanos | 1,
+        }
+    }
+
+    /// Receives the next message, blocking until one arrives. Returns
+    /// `Ok(None)` once the conversation has been closed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use rocket::websocket::{Socket, Message};
+    ///
+    /// fn echo(mut socket: Socket) -> io::Result<()> {
+    ///     while let Some(message) = socket.recv()? {
+    ///         if let Message::Text(text) = message {
+    ///             socket.send(Message::text(text))?;
+    ///         }
+    ///     }
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn recv(&mut self) -> io::Result<Option<Message>> {
+        loop {
+            if self.close_received {
+                return Ok(None);
+            }
+
+            let masked = self.role == Role::Server;
+            let frame = match frame::read_frame(&mut self.connection.reader, masked, self.limit) {
+                Ok(frame) => frame,
+                Err(FrameError::Io(ref e)) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                    self.close_received = true;
+                    self.close_sent = true;
+                    return Ok(None);
+                }
+                Err(FrameError::Io(e)) => return Err(e),
+                Err(FrameError::Protocol(reason)) => return Err(self.fail(1002, reason)),
+                Err(FrameError::TooLarge) => return Err(self.fail(1009, "message too large")),
+            };
+
+            if let Some(message) = self.process(frame)? {
+                return Ok(Some(message));
+            }
+        }
+    }
+
+    fn process(&mut self, frame: Frame) -> io::Result<Option<Message>> {
+        let Frame { fin, opcode, payload } = frame;
+        let (opcode, payload) = match opcode {
+            OpCode::Ping => {
+                if !self.close_sent {
+                    self.write(OpCode::Pong, &payload)?;
+                }
+
+                return Ok(Some(Message::Ping(payload)));
+            }
+            OpCode::Pong => return Ok(Some(Message::Pong(payload))),
+            OpCode::Close => {
+                let frame = match CloseFrame::parse(&payload) {
+                    Ok(frame) => frame,
+                    Err(code) => return Err(self.fail(code, "invalid close frame")),
+                };
+
+                // The peer may hang up as soon as it sees our close, so the
+                // echo is sent on a best-effort basis.
+                self.close_received = true;
+                if !self.close_sent {
+                    self.close_sent = true;
+                    let echo = frame.as_ref().map(|f| f.code.to_be_bytes().to_vec());
+                    let _ = self.write(OpCode::Close, &echo.unwrap_or_default());
+                }
+
+                return Ok(Some(Message::Close(frame)));
+            }
+            OpCode::Text | OpCode::Binary if self.partial.is_some() => {
+                return Err(self.fail(1002, "expected a continuation frame"));
+            }
+            OpCode::Text | OpCode::Binary if !fin => {
+                self.partial = Some((opcode, payload));
+                return Ok(None);
+            }
+            OpCode::Text | OpCode::Binary => (opcode, payload),
+            OpCode::Continuation => {
+                let (opcode, mut buffer) = match self.partial.take() {
+                    Some(partial) => partial,
+                    None => return Err(self.fail(1002, "unexpected continuation frame")),
+                };
+
+                if (buffer.len() + payload.len()) as u64 > self.limit {
+                    return Err(self.fail(1009, "message too large"));
+                }
+
+                buffer.extend_from_slice(&payload);
+                if !fin {
+                    self.partial = Some((opcode, buffer));
+                    return Ok(None);
+                }
+
+                (opcode, buffer)
+            }
+        };
+
+        match opcode {
+            OpCode::Text => match String::from_utf8(payload) {
+                Ok(text) => Ok(Some(Message::Text(text))),
+                Err(_) => Err(self.fail(1007, "text message is not valid UTF-8")),
+            },
+            _ => Ok(Some(Message::Binary(payload)))
+        }
+    }
+
+    /// Sends `message`. Fails if a close has already been sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use rocket::websocket::{Socket, Message};
+    ///
+    /// fn greet(mut socket: Socket) -> io::Result<()> {
+    ///     socket.send(Message::text("hello"))?;
+    ///     socket.send(Message::binary(vec![0xCA, 0xFE]))
+    /// }
+    /// ```
+    pub fn send(&mut self, message: Message) -> io::Result<()> {
+        if self.close_sent {
+            let msg = "the WebSocket connection is closed";
+            return Err(io::Error::new(io::ErrorKind::NotConnected, msg));
+        }
+
+        match message {
+            Message::Text(text) => self.write(OpCode::Text, text.as_bytes()),
+            Message::Binary(bytes) => self.write(OpCode::Binary, &bytes),
+            Message::Ping(bytes) => self.write(OpCode::Ping, &bytes),
+            Message::Pong(bytes) => self.write(OpCode::Pong, &bytes),
+            Message::Close(frame) => {
+                self.close_sent = true;
+                let payload = frame.map(|f| f.to_payload()).unwrap_or_default();
+                self.write(OpCode::Close, &payload)
+            }
+        }
+    }
+
+    /// Closes the conversation: sends a close with `frame`, if one hasn't been
+    /// sent, then waits for the peer's close. Messages received in the
+    /// meantime are discarded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::io;
+    /// use rocket::websocket::{Socket, CloseFrame};
+    ///
+    /// fn goodbye(mut socket: Socket) -> io::Result<()> {
+    ///     socket.close(Some(CloseFrame::new(1000, "done")))
+    /// }
+    /// ```
+    pub fn close(&mut self, frame: Option<CloseFrame>) -> io::Result<()> {
+        let sent = match self.close_sent {
+            false => self.send(Message::Close(frame)),
+            true => Ok(())
+        };
+
+        // If the peer closed first, failing to send our close is harmless.
+        let mut peer_closed = false;
+        while let Some(message) = self.recv()? {
+            if let Message::Close(_) = message {
+                peer_closed = true;
+            }
+        }
+
+        if peer_closed { Ok(()) } else { sent }
+    }
+
+    /// Closes the connection with status code `code` after a violation by the
+    /// peer and returns the error to report.
+    fn fail(&mut self, code: u16, reason: &'static str) -> io::Error {
+        if !self.close_sent {
+            let _ = self.send(Message::Close(Some(CloseFrame::new(code, reason))));
+        }
+
+        self.close_received = true;
+        self.partial = None;
+        io::Error::new(io::ErrorKind::InvalidData, reason)
+    }
+
+    fn write(&mut self, opcode: OpCode, payload: &[u8]) -> io::Result<()> {
+        let mask = match self.role {
+            Role::Server => None,
+            Role::Client => Some(self.next_mask()),
+        };
+
+        frame::write_frame(&mut self.connection.writer, opcode, payload, mask)
+    }
+
+    fn next_mask(&mut self) -> [u8; 4] {
+        // xorshift32
+        let mut x = self.mask_state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.mask_state = x;
+        x.to_be_bytes()
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        if !self.close_sent {
+            let _ = self.send(Message::Close(Some(CloseFrame::new(1000, ""))));
+        }
+    }
+}
+
+impl fmt::Debug for Socket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Socket")
+            .field("role", &self.role)
+            .field("closed", &self.close_sent)
+            .finish()
+    }
+}
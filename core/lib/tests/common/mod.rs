@@ -0,0 +1,10 @@
+use rocket::config::{Config, ConfigBuilder, Environment, ShutdownConfig};
+
+/// Returns a builder for the configuration of a server spawned by a test: it
+/// binds to a port assigned by the OS, doesn't listen for signals, and shuts
+/// down after a grace period of at most one second.
+pub fn config() -> ConfigBuilder {
+    Config::build(Environment::Development)
+        .port(0)
+        .shutdown(ShutdownConfig { ctrlc: false, grace: 1 })
+}
@@ -0,0 +1,193 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::{State, Response};
+use rocket::http::{Status, EntityTag, HttpDate};
+use rocket::request::Preconditions;
+use rocket::response::NamedFile;
+
+const CONTENTS: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+
+struct FilePath(PathBuf);
+
+struct Version(AtomicUsize);
+
+impl Version {
+    fn etag(&self) -> EntityTag {
+        EntityTag::strong(self.0.load(Ordering::SeqCst).to_string())
+    }
+}
+
+#[get("/file")]
+fn file(path: State<'_, FilePath>) -> Option<NamedFile> {
+    NamedFile::open(&path.0).ok()
+}
+
+#[get("/resource")]
+fn resource(version: State<'_, Version>) -> Response<'static> {
+    Response::build()
+        .etag(version.etag())
+        .last_modified("Sun, 06 Nov 1994 08:49:37 GMT".parse::<HttpDate>().unwrap())
+        .raw_header("Cache-Control", "no-cache")
+        .sized_body(Cursor::new(CONTENTS))
+        .finalize()
+}
+
+#[put("/resource")]
+fn update(pre: Preconditions<'_, '_>, version: State<'_, Version>) -> Result<String, Status> {
+    pre.check(Some(&version.etag()), None)?;
+    Ok(version.0.fetch_add(1, Ordering::SeqCst).to_string())
+}
+
+mod conditional_request_tests {
+    use super::*;
+
+    use std::fs;
+
+    use rocket::local::Client;
+    use rocket::http::Header;
+
+    fn client(name: &str) -> Client {
+        let path = std::env::temp_dir().join(format!("rocket-conditional-{}.txt", name));
+        fs::write(&path, CONTENTS).expect("write test file");
+
+        let rocket = rocket::ignite()
+            .mount("/", routes![file, resource, update])
+            .manage(FilePath(path))
+            .manage(Version(AtomicUsize::new(1)));
+
+        Client::new(rocket).unwrap()
+    }
+
+    #[test]
+    fn file_validators() {
+        let client = client("validators");
+        let response = client.get("/file").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+
+        let etag = response.headers().get_one("ETag").unwrap();
+        let last_modified = response.headers().get_one("Last-Modified").unwrap();
+        assert!(!etag.starts_with("W/"));
+
+        let mut response = client.get("/file")
+            .header(Header::new("If-None-Match", etag.to_string()))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotModified);
+        assert_eq!(response.headers().get_one("ETag"), Some(etag));
+        assert!(response.content_type().is_none());
+        assert!(response.body().is_none());
+
+        let response = client.get("/file")
+            .header(Header::new("If-Modified-Since", last_modified.to_string()))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotModified);
+
+        let mut response = client.get("/file")
+            .header(Header::new("If-Modified-Since", "Thu, 01 Jan 1970 00:00:00 GMT"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some(CONTENTS.into()));
+    }
+
+    #[test]
+    fn not_modified() {
+        let client = client("not-modified");
+        for value in &["\"1\"", "W/\"1\"", "\"0\", \"1\"", "*"] {
+            for request in vec![client.get("/resource"), client.head("/resource")] {
+                let mut response = request
+                    .header(Header::new("If-None-Match", *value))
+                    .dispatch();
+
+                assert_eq!(response.status(), Status::NotModified);
+                assert_eq!(response.headers().get_one("Cache-Control"), Some("no-cache"));
+                assert!(response.body().is_none());
+            }
+        }
+
+        let mut response = client.get("/resource")
+            .header(Header::new("If-None-Match", "\"0\""))
+            .header(Header::new("If-Modified-Since", "Sun, 06 Nov 1994 08:49:37 GMT"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some(CONTENTS.into()));
+    }
+
+    #[test]
+    fn failed_preconditions() {
+        let client = client("failed");
+        let response = client.get("/resource")
+            .header(Header::new("If-Match", "\"0\""))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PreconditionFailed);
+
+        let response = client.get("/resource")
+            .header(Header::new("If-Unmodified-Since", "Sat, 05 Nov 1994 08:49:37 GMT"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PreconditionFailed);
+
+        let response = client.get("/resource")
+            .header(Header::new("If-Match", "\"1\""))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+    }
+
+    #[test]
+    fn unsafe_method_preconditions() {
+        let client = client("unsafe");
+
+        // The representation has changed, so the update is refused.
+        let response = client.put("/resource")
+            .header(Header::new("If-Match", "\"0\""))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PreconditionFailed);
+
+        let mut response = client.put("/resource")
+            .header(Header::new("If-Match", "\"1\""))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("1".into()));
+
+        // The resource exists, so a request to create it is refused.
+        let response = client.put("/resource")
+            .header(Header::new("If-None-Match", "*"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PreconditionFailed);
+    }
+
+    #[test]
+    fn preconditions_are_ignored_for_errors() {
+        let client = client("errors");
+        let response = client.get("/missing")
+            .header(Header::new("If-None-Match", "*"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn ranges_of_modified_representations() {
+        let client = client("ranges");
+        let mut response = client.get("/file")
+            .header(Header::new("If-None-Match", "\"stale\""))
+            .header(Header::new("Range", "bytes=0-1"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PartialContent);
+        assert_eq!(response.body_string(), Some("01".into()));
+    }
+}
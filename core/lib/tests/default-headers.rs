@@ -0,0 +1,114 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::{Request, Response};
+use rocket::http::Header;
+
+#[get("/")]
+fn index() -> &'static str {
+    "Hello, world!"
+}
+
+#[get("/dated")]
+fn dated() -> Response<'static> {
+    Response::build()
+        .header(Header::new("Date", "Sun, 06 Nov 1994 08:49:37 GMT"))
+        .finalize()
+}
+
+#[catch(404)]
+fn not_found(_: &Request<'_>) -> Response<'static> {
+    Response::build()
+        .header(Header::new("Date", "Mon, 07 Nov 1994 08:49:37 GMT"))
+        .finalize()
+}
+
+mod default_headers_tests {
+    use super::*;
+
+    use rocket::Rocket;
+    use rocket::config::{Config, Environment, DefaultHeaders};
+    use rocket::fairing::AdHoc;
+    use rocket::http::Status;
+    use rocket::local::Client;
+
+    fn rocket(headers: DefaultHeaders) -> Rocket {
+        let config = Config::build(Environment::Development)
+            .headers(headers)
+            .unwrap();
+
+        rocket::custom(config)
+            .mount("/", routes![index, dated])
+            .register(catchers![not_found])
+    }
+
+    fn is_http_date(date: &str) -> bool {
+        // For example: "Sun, 06 Nov 1994 08:49:37 GMT".
+        let parts: Vec<_> = date.split(' ').collect();
+        date.len() == 29
+            && parts.len() == 6
+            && parts[0].len() == 4 && parts[0].ends_with(',')
+            && parts[4].len() == 8 && parts[4].split(':').count() == 3
+            && parts[5] == "GMT"
+    }
+
+    #[test]
+    fn date_header_is_set() {
+        let client = Client::new(rocket(DefaultHeaders::new())).unwrap();
+        let response = client.get("/").dispatch();
+        let date = response.headers().get_one("Date").expect("Date header");
+        assert!(is_http_date(date), "bad Date header: {}", date);
+
+        let response = client.post("/").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert!(response.headers().get_one("Date").map_or(false, is_http_date));
+    }
+
+    #[test]
+    fn date_header_can_be_overridden() {
+        let client = Client::new(rocket(DefaultHeaders::new())).unwrap();
+        let response = client.get("/dated").dispatch();
+        let dates: Vec<_> = response.headers().get("Date").collect();
+        assert_eq!(dates, vec!["Sun, 06 Nov 1994 08:49:37 GMT"]);
+
+        let response = client.get("/unknown").dispatch();
+        let dates: Vec<_> = response.headers().get("Date").collect();
+        assert_eq!(dates, vec!["Mon, 07 Nov 1994 08:49:37 GMT"]);
+    }
+
+    #[test]
+    fn default_headers_are_configurable() {
+        let client = Client::new(rocket(DefaultHeaders::new())).unwrap();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.headers().get_one("Server"), Some("Rocket"));
+
+        let headers = DefaultHeaders::new()
+            .header("Server", "MyApp")
+            .header("X-Frame-Options", "DENY");
+
+        let client = Client::new(rocket(headers)).unwrap();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.headers().get_one("Server"), Some("MyApp"));
+        assert_eq!(response.headers().get_one("X-Frame-Options"), Some("DENY"));
+
+        let client = Client::new(rocket(DefaultHeaders::new().remove("Server"))).unwrap();
+        let response = client.get("/").dispatch();
+        assert!(response.headers().get_one("Server").is_none());
+        assert!(response.headers().get_one("Date").is_some());
+    }
+
+    #[test]
+    fn fairings_see_default_headers() {
+        let rocket = rocket(DefaultHeaders::new())
+            .attach(AdHoc::on_response("Check", |_, response| {
+                let date = response.headers().get_one("Date").is_some();
+                let server = response.headers().get_one("Server") == Some("Rocket");
+                response.set_raw_header("X-Seen", format!("{} {}", date, server));
+            }));
+
+        let client = Client::new(rocket).unwrap();
+        let response = client.get("/").dispatch();
+        assert_eq!(response.headers().get_one("X-Seen"), Some("true true"));
+    }
+}
@@ -0,0 +1,133 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use rocket::request::LastEventId;
+use rocket::response::{Event, EventStream};
+
+#[get("/iter")]
+fn iter() -> EventStream<'static> {
+    EventStream::new(vec![
+        Event::data("one"),
+        Event::data("two\nlines").with_event("multi").with_id("2"),
+        Event::default().with_retry(Duration::from_millis(1500)),
+    ])
+}
+
+#[get("/channel")]
+fn channel() -> EventStream<'static> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        tx.send(Event::data("first")).unwrap();
+        thread::sleep(Duration::from_millis(500));
+        tx.send(Event::data("second")).unwrap();
+    });
+
+    EventStream::from(rx).heartbeat(Duration::from_millis(100))
+}
+
+#[get("/slow")]
+fn slow() -> EventStream<'static> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        tx.send(Event::data("now")).unwrap();
+        thread::sleep(Duration::from_secs(5));
+    });
+
+    EventStream::from(rx)
+}
+
+#[get("/last")]
+fn last(last: Option<LastEventId<'_>>) -> String {
+    match last {
+        Some(id) => format!("resume after {}", id.as_str()),
+        None => "start".into(),
+    }
+}
+
+mod event_stream_tests {
+    use super::*;
+
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    use rocket::http::{ContentType, Header};
+    use rocket::local::Client;
+
+    fn client() -> Client {
+        Client::new(rocket::ignite().mount("/", routes![iter, channel, slow, last])).unwrap()
+    }
+
+    #[test]
+    fn events_are_framed() {
+        let client = client();
+        let mut response = client.get("/iter").dispatch();
+        assert_eq!(response.content_type(), Some(ContentType::EventStream));
+        assert_eq!(response.headers().get_one("Cache-Control"), Some("no-cache"));
+        assert_eq!(response.body_string().unwrap(),
+            "data: one\n\nid: 2\nevent: multi\ndata: two\ndata: lines\n\nretry: 1500\n\n");
+    }
+
+    #[test]
+    fn fields_cannot_inject_lines() {
+        let event = Event::data("a\r\nb\rc").with_id("1\nevent: evil").with_event("x\r\n");
+        assert_eq!(event.to_string(), "id: 1event: evil\nevent: x\ndata: a\ndata: b\ndata: c\n\n");
+
+        assert_eq!(Event::data("").to_string(), "data: \n\n");
+    }
+
+    #[test]
+    fn channel_streams_send_heartbeats() {
+        let client = client();
+        let body = client.get("/channel").dispatch().body_string().unwrap();
+        let first = body.find("data: first\n\n").expect("first event");
+        let heartbeat = body.find("\n\n:\n\n").expect("heartbeat");
+        assert!(first < heartbeat);
+        assert!(body.ends_with(":\n\ndata: second\n\n"));
+    }
+
+    #[test]
+    fn last_event_id_guard() {
+        let client = client();
+        let mut response = client.get("/last").dispatch();
+        assert_eq!(response.body_string(), Some("start".into()));
+
+        let mut response = client.get("/last")
+            .header(Header::new("Last-Event-ID", "41"))
+            .dispatch();
+
+        assert_eq!(response.body_string(), Some("resume after 41".into()));
+    }
+
+    #[test]
+    fn events_are_flushed_immediately() {
+        let config = common::config().unwrap();
+
+        let running = rocket::custom(config)
+            .mount("/", routes![slow])
+            .spawn()
+            .expect("launch");
+
+        let mut stream = TcpStream::connect(running.addr()).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(2))).unwrap();
+        stream.write_all(b"GET /slow HTTP/1.1\r\n\r\n").unwrap();
+
+        // The stream stays open for seconds, but the first event arrives now.
+        let mut received = vec![];
+        let mut buf = [0; 1024];
+        while !String::from_utf8_lossy(&received).contains("data: now\n\n") {
+            match stream.read(&mut buf) {
+                Ok(n) if n > 0 => received.extend_from_slice(&buf[..n]),
+                result => panic!("event not received: {:?}", result),
+            }
+        }
+
+        running.stop();
+    }
+}
@@ -0,0 +1,79 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::Data;
+use rocket::http::Method;
+
+#[route(PROPFIND, "/dav/<name>")]
+fn propfind(name: String, data: Data) -> String {
+    let mut body = String::new();
+    std::io::Read::read_to_string(&mut data.open(), &mut body).unwrap();
+    format!("propfind {}: {}", name, body)
+}
+
+#[route(MKCOL, "/dav/<name>")]
+fn mkcol(name: String) -> String {
+    format!("mkcol {}", name)
+}
+
+#[route(VERSION_CONTROL, "/dav/<name>")]
+fn version_control(name: String) -> String {
+    format!("version-control {}", name)
+}
+
+#[route(PURGE, "/cache/<path..>")]
+fn purge(path: std::path::PathBuf, method: Method) -> String {
+    format!("{} {}", method, path.display())
+}
+
+mod extension_methods_tests {
+    use super::*;
+
+    use rocket::local::Client;
+    use rocket::http::Status;
+
+    fn client() -> Client {
+        let rocket = rocket::ignite()
+            .mount("/", routes![propfind, mkcol, version_control, purge]);
+
+        Client::new(rocket).unwrap()
+    }
+
+    #[test]
+    fn webdav_methods_are_routed() {
+        let client = client();
+        let mut response = client.req(Method::PropFind, "/dav/docs")
+            .body("<propfind/>")
+            .dispatch();
+
+        assert_eq!(response.body_string(), Some("propfind docs: <propfind/>".into()));
+
+        let mut response = client.req(Method::MkCol, "/dav/new").dispatch();
+        assert_eq!(response.body_string(), Some("mkcol new".into()));
+
+        let mut response = client.req(Method::VersionControl, "/dav/file").dispatch();
+        assert_eq!(response.body_string(), Some("version-control file".into()));
+    }
+
+    #[test]
+    fn extension_methods_are_routed() {
+        let client = client();
+        let method: Method = "purge".parse().unwrap();
+        let mut response = client.req(method, "/cache/a/b").dispatch();
+        assert_eq!(response.body_string(), Some("PURGE a/b".into()));
+
+        let other: Method = "BREW".parse().unwrap();
+        let response = client.req(other, "/cache/a/b").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("OPTIONS, PURGE"));
+    }
+
+    #[test]
+    fn route_methods_are_generated() {
+        let routes = routes![mkcol, purge];
+        assert_eq!(routes[0].method, Method::MkCol);
+        assert_eq!(routes[1].method.as_str(), "PURGE");
+        assert!(routes[1].method.is_extension());
+    }
+}
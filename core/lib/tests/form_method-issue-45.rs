@@ -15,6 +15,16 @@ fn bug(form_data: Form<FormData>) -> &'static str {
     "OK"
 }
 
+#[options("/")]
+fn options() -> &'static str {
+    "OPTIONS"
+}
+
+#[route(PROPFIND, "/")]
+fn propfind() -> &'static str {
+    "PROPFIND"
+}
+
 mod tests {
     use super::*;
     use rocket::local::Client;
@@ -39,6 +49,31 @@ mod tests {
             .body("_method=patch&form_data=Form+data")
             .dispatch();
 
-        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("OPTIONS, PATCH"));
+    }
+
+    #[test]
+    fn long_method_names() {
+        let client = Client::new(rocket::ignite().mount("/", routes![options, propfind])).unwrap();
+        for &(body, method) in &[("_method=options", "OPTIONS"), ("_method=propfind", "PROPFIND")] {
+            let mut response = client.post("/")
+                .header(ContentType::Form)
+                .body(body)
+                .dispatch();
+
+            assert_eq!(response.body_string(), Some(method.into()));
+        }
+    }
+
+    #[test]
+    fn extension_methods_are_ignored() {
+        let client = Client::new(rocket::ignite().mount("/", routes![bug])).unwrap();
+        let response = client.post("/")
+            .header(ContentType::Form)
+            .body("_method=purge&form_data=Form+data")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::MethodNotAllowed);
     }
 }
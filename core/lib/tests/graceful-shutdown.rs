@@ -0,0 +1,112 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use rocket::Shutdown;
+use rocket::fairing::AdHoc;
+
+#[get("/shutdown")]
+fn shutdown(handle: Shutdown) -> &'static str {
+    handle.notify();
+    "Shutting down..."
+}
+
+mod graceful_shutdown_tests {
+    use super::*;
+    use rocket::local::Client;
+
+    #[test]
+    fn guard_notifies_rocket_handle() {
+        let rocket = rocket::ignite().mount("/", routes![shutdown]);
+        let handle = rocket.shutdown();
+        let client = Client::new(rocket).unwrap();
+        assert!(!handle.is_notified());
+
+        let mut response = client.get("/shutdown").dispatch();
+        assert_eq!(response.body_string(), Some("Shutting down...".into()));
+        assert!(handle.is_notified());
+    }
+
+    #[test]
+    fn launch_returns_after_shutdown() {
+        use rocket::error::LaunchErrorKind;
+
+        let config = common::config().unwrap();
+        let error = rocket::custom(config)
+            .attach(AdHoc::on_launch("Shutdown", |rocket| rocket.shutdown().notify()))
+            .launch();
+
+        match error.kind() {
+            LaunchErrorKind::Shutdown => { /* expected */ }
+            kind => panic!("unexpected launch error: {}", kind),
+        }
+    }
+
+    #[test]
+    fn join_returns_after_shutdown() {
+        let config = common::config().unwrap();
+
+        rocket::custom(config)
+            .attach(AdHoc::on_launch("Shutdown", |rocket| rocket.shutdown().notify()))
+            .spawn()
+            .expect("launch")
+            .join();
+    }
+
+    #[test]
+    fn spawn_serves_requests_until_stopped() {
+        use std::io::{Read, Write};
+        use std::net::TcpStream;
+
+        let config = common::config()
+            .address("127.0.0.1")
+            .unwrap();
+
+        let running = rocket::custom(config)
+            .mount("/", routes![shutdown])
+            .spawn()
+            .expect("launch");
+
+        assert_ne!(running.addr().port(), 0);
+        assert!(!running.shutdown().is_notified());
+
+        let mut stream = TcpStream::connect(running.addr()).unwrap();
+        stream.write_all(b"GET /shutdown HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("Shutting down..."));
+
+        assert!(running.shutdown().is_notified());
+        running.stop();
+    }
+
+    #[test]
+    fn port_can_be_rebound_after_stop() {
+        use std::io::{Read, Write};
+        use std::net::{TcpListener, TcpStream};
+
+        let config = common::config()
+            .address("127.0.0.1")
+            .workers(4)
+            .unwrap();
+
+        let running = rocket::custom(config).spawn().expect("launch");
+        let addr = running.addr();
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 404"));
+
+        running.stop();
+        let listener = TcpListener::bind(addr).expect("port is free after stop");
+        assert_eq!(listener.local_addr().unwrap(), addr);
+    }
+}
@@ -0,0 +1,195 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use std::io::Read;
+
+use rocket::{Request, Data, Outcome};
+use rocket::http::Status;
+use rocket::http::headers::Authorization;
+use rocket::request::{self, Form, FromRequest, GuardError};
+use rocket::data::{self, FromDataSimple};
+
+#[derive(Debug, PartialEq)]
+enum TokenError {
+    Missing,
+    Invalid(String),
+}
+
+struct Token;
+
+impl FromRequest<'_, '_> for Token {
+    type Error = TokenError;
+
+    fn from_request(request: &Request<'_>) -> request::Outcome<Self, TokenError> {
+        match request.headers().get_one("X-Token") {
+            Some("secret") => Outcome::Success(Token),
+            Some(other) => {
+                Outcome::Failure((Status::BadRequest, TokenError::Invalid(other.into())))
+            }
+            None => Outcome::Failure((Status::Unauthorized, TokenError::Missing)),
+        }
+    }
+
+    fn guard_error(error: TokenError) -> GuardError {
+        GuardError::new(error)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct TooLong(usize);
+
+struct Short(String);
+
+impl FromDataSimple for Short {
+    type Error = TooLong;
+
+    fn from_data(_: &Request<'_>, data: Data) -> data::Outcome<Self, TooLong> {
+        let mut string = String::new();
+        data.open().read_to_string(&mut string).unwrap();
+        match string.len() {
+            n if n > 5 => Outcome::Failure((Status::PayloadTooLarge, TooLong(n))),
+            _ => Outcome::Success(Short(string)),
+        }
+    }
+
+    fn guard_error(error: TooLong) -> GuardError {
+        GuardError::new(error)
+    }
+}
+
+/// An error without a `Debug` implementation.
+struct Opaque;
+
+struct Strict;
+
+impl FromDataSimple for Strict {
+    type Error = Opaque;
+
+    fn from_data(_: &Request<'_>, _: Data) -> data::Outcome<Self, Opaque> {
+        Outcome::Failure((Status::BadRequest, Opaque))
+    }
+}
+
+#[derive(FromForm)]
+struct Login {
+    user: String,
+}
+
+#[get("/")]
+fn index(_token: Token) -> &'static str {
+    "ok"
+}
+
+#[post("/short", data = "<short>")]
+fn short(short: Short) -> String {
+    short.0
+}
+
+#[post("/login", data = "<login>")]
+fn login(login: Form<Login>) -> String {
+    login.into_inner().user
+}
+
+#[post("/strict", data = "<_strict>")]
+fn strict(_strict: Strict) { }
+
+#[get("/auth")]
+fn auth(_auth: Authorization) -> &'static str {
+    "ok"
+}
+
+#[catch(default)]
+fn catcher(req: &Request) -> String {
+    let status = req.error_status().unwrap();
+    let error = match req.guard_error() {
+        Some(error) => error,
+        None => return format!("{}: no error", status.code),
+    };
+
+    if let Some(token_error) = error.downcast_ref::<TokenError>() {
+        format!("{}: token {:?}", status.code, token_error)
+    } else if let Some(TooLong(n)) = error.downcast_ref::<TooLong>() {
+        format!("{}: too long by {}", status.code, n - 5)
+    } else if let Some(message) = error.downcast_ref::<String>() {
+        format!("{}: header {}", status.code, message)
+    } else {
+        format!("{}: {}", status.code, error)
+    }
+}
+
+mod guard_errors_tests {
+    use super::*;
+
+    use rocket::local::Client;
+    use rocket::http::{Header, ContentType};
+
+    fn client() -> Client {
+        let rocket = rocket::ignite()
+            .mount("/", routes![index, short, login, strict, auth])
+            .register(catchers![catcher]);
+
+        Client::new(rocket).unwrap()
+    }
+
+    #[test]
+    fn request_guard_errors_are_typed() {
+        let client = client();
+        let mut response = client.get("/").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(response.body_string(), Some("401: token Missing".into()));
+
+        let mut response = client.get("/").header(Header::new("X-Token", "guess")).dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+        assert_eq!(response.body_string(), Some("400: token Invalid(\"guess\")".into()));
+
+        let mut response = client.get("/").header(Header::new("X-Token", "secret")).dispatch();
+        assert_eq!(response.body_string(), Some("ok".into()));
+    }
+
+    #[test]
+    fn data_guard_errors_are_typed() {
+        let client = client();
+        let mut response = client.post("/short").body("much too long").dispatch();
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+        assert_eq!(response.body_string(), Some("413: too long by 8".into()));
+    }
+
+    #[test]
+    fn builtin_guard_errors_are_typed() {
+        let client = client();
+        let mut response = client.get("/auth")
+            .header(Header::new("Authorization", "Basic !!!"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+        let body = response.body_string().unwrap();
+        assert!(body.starts_with("400: header "), "{}", body);
+    }
+
+    #[test]
+    fn data_guard_errors_keep_type_name() {
+        let client = client();
+        let mut response = client.post("/login")
+            .header(ContentType::Form)
+            .body("name=bob")
+            .dispatch();
+
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+        let body = response.body_string().unwrap();
+        assert!(body.starts_with("422: login: "), "{}", body);
+        assert!(body.contains("FormDataError"), "{}", body);
+
+        let mut response = client.post("/strict").dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+        let body = response.body_string().unwrap();
+        assert!(body.starts_with("400: _strict: ") && body.ends_with("Opaque"), "{}", body);
+    }
+
+    #[test]
+    fn no_guard_error_without_guard_failure() {
+        let client = client();
+        let mut response = client.get("/unknown").dispatch();
+        assert_eq!(response.body_string(), Some("404: no error".into()));
+    }
+}
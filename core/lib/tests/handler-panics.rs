@@ -0,0 +1,93 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::request::{self, FromRequest};
+
+struct Panics;
+
+impl<'a, 'r> FromRequest<'a, 'r> for Panics {
+    type Error = ();
+
+    fn from_request(_: &'a Request<'r>) -> request::Outcome<Self, ()> {
+        panic!("guard panicked")
+    }
+}
+
+#[get("/handler")]
+fn handler() -> &'static str {
+    panic!("handler panicked")
+}
+
+#[get("/guard")]
+fn guard(_guard: Panics) -> &'static str {
+    "unreachable"
+}
+
+#[get("/ok")]
+fn ok() -> &'static str {
+    "ok"
+}
+
+#[catch(500)]
+fn internal_error() -> &'static str {
+    "custom 500"
+}
+
+mod handler_panic_tests {
+    use super::*;
+
+    use rocket::Rocket;
+    use rocket::local::Client;
+    use rocket::fairing::AdHoc;
+    use rocket::http::Status;
+
+    fn rocket() -> Rocket {
+        rocket::ignite()
+            .mount("/", routes![handler, guard, ok])
+            .register(catchers![internal_error])
+    }
+
+    #[test]
+    fn handler_and_guard_panics_use_500_catcher() {
+        let client = Client::new(rocket()).unwrap();
+        for path in &["/handler", "/guard"] {
+            let mut response = client.get(*path).dispatch();
+            assert_eq!(response.status(), Status::InternalServerError);
+            assert_eq!(response.body_string(), Some("custom 500".into()));
+        }
+
+        let mut response = client.get("/ok").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("ok".into()));
+    }
+
+    #[test]
+    fn request_fairing_panics_use_500_catcher() {
+        let rocket = rocket().attach(AdHoc::on_request("Panics", |req, _| {
+            if req.uri().path() == "/ok" {
+                panic!("request fairing panicked");
+            }
+        }));
+
+        let client = Client::new(rocket).unwrap();
+        let mut response = client.get("/ok").dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+        assert_eq!(response.body_string(), Some("custom 500".into()));
+    }
+
+    #[test]
+    fn response_fairing_panics_use_500_catcher() {
+        let rocket = rocket().attach(AdHoc::on_response("Panics", |req, _| {
+            if req.uri().path() == "/ok" {
+                panic!("response fairing panicked");
+            }
+        }));
+
+        let client = Client::new(rocket).unwrap();
+        let mut response = client.get("/ok").dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+        assert_eq!(response.body_string(), Some("custom 500".into()));
+    }
+}
@@ -0,0 +1,89 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+#[get("/")]
+fn api_index() -> &'static str {
+    "api"
+}
+
+#[get("/", host = "<tenant>.example.com")]
+fn tenant_index(tenant: String) -> String {
+    format!("tenant {}", tenant)
+}
+
+#[get("/<id>", host = "<tenant>.<region>.example.com")]
+fn tenant_item(tenant: String, region: String, id: usize) -> String {
+    format!("{} in {}: {}", tenant, region, id)
+}
+
+#[get("/only")]
+fn only() -> &'static str {
+    "only"
+}
+
+mod host_routing_tests {
+    use super::*;
+
+    use rocket::local::Client;
+    use rocket::http::{Header, Status};
+
+    fn client() -> Client {
+        let rocket = rocket::ignite()
+            .mount("/", routes![index, tenant_index, tenant_item])
+            .mount_host("api.example.com", "/", routes![api_index])
+            .mount_host("only.example.com", "/", routes![only])
+            .mount_host("only.example.com", "/own", routes![tenant_index]);
+
+        Client::new(rocket).unwrap()
+    }
+
+    fn get(client: &Client, host: Option<&'static str>, uri: &str) -> (Status, Option<String>) {
+        let mut request = client.get(uri);
+        if let Some(host) = host {
+            request.add_header(Header::new("Host", host));
+        }
+
+        let mut response = request.dispatch();
+        (response.status(), response.body_string())
+    }
+
+    #[test]
+    fn static_hosts_are_preferred() {
+        let client = client();
+        let body = |host| get(&client, host, "/").1;
+        assert_eq!(body(Some("api.example.com")), Some("api".into()));
+        assert_eq!(body(Some("API.Example.com:8000")), Some("api".into()));
+        assert_eq!(body(Some("acme.example.com")), Some("tenant acme".into()));
+        assert_eq!(body(Some("example.com")), Some("index".into()));
+        assert_eq!(body(None), Some("index".into()));
+    }
+
+    #[test]
+    fn host_params_are_captured() {
+        let client = client();
+        let body = |host, uri| get(&client, host, uri).1;
+        assert_eq!(body(Some("acme.eu.example.com"), "/10"), Some("acme in eu: 10".into()));
+        assert_eq!(body(Some("x.us.example.com:80"), "/3"), Some("x in us: 3".into()));
+    }
+
+    #[test]
+    fn routes_keep_their_own_host() {
+        let client = client();
+        assert_eq!(get(&client, Some("only.example.com"), "/only").1, Some("only".into()));
+        assert_eq!(get(&client, Some("acme.example.com"), "/own").1, Some("tenant acme".into()));
+    }
+
+    #[test]
+    fn hosts_that_dont_match_are_not_found() {
+        let client = client();
+        assert_eq!(get(&client, Some("api.example.com"), "/only").0, Status::NotFound);
+        assert_eq!(get(&client, None, "/only").0, Status::NotFound);
+        assert_eq!(get(&client, Some("acme.example.com"), "/10").0, Status::NotFound);
+    }
+}
@@ -0,0 +1,106 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use std::io::Cursor;
+
+use rocket::request::MalformedRequest;
+use rocket::response::{self, Response};
+use rocket::http::Status;
+
+#[get("/")]
+fn index() -> &'static str {
+    "index"
+}
+
+fn malformed(request: &MalformedRequest) -> response::Result<'static> {
+    let agent = request.headers().get_one("User-Agent").unwrap_or("unknown");
+    let body = format!("{}|{}|{}", request.method(), request.target(), agent);
+    Response::build()
+        .status(Status::ImATeapot)
+        .sized_body(Cursor::new(body))
+        .ok()
+}
+
+fn failing(_: &MalformedRequest) -> response::Result<'static> {
+    Err(Status::InternalServerError)
+}
+
+mod malformed_request_tests {
+    use super::*;
+
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::sync::{Arc, Mutex};
+
+    use rocket::local::Client;
+    use rocket::fairing::AdHoc;
+    use rocket::http::{Header, Status};
+
+    #[test]
+    fn malformed_handler_sees_raw_request() {
+        let rocket = rocket::ignite().mount("/", routes![index]).on_malformed(malformed);
+        let client = Client::new(rocket).unwrap();
+
+        let mut response = client.get("/a b").header(Header::new("User-Agent", "test")).dispatch();
+        assert_eq!(response.status(), Status::ImATeapot);
+        assert_eq!(response.body_string(), Some("GET|/a b|test".into()));
+
+        let mut response = client.get("/").dispatch();
+        assert_eq!(response.body_string(), Some("index".into()));
+    }
+
+    #[test]
+    fn default_and_failing_handlers_respond_with_400() {
+        let client = Client::new(rocket::ignite()).unwrap();
+        let response = client.get("/a b").dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let client = Client::new(rocket::ignite().on_malformed(failing)).unwrap();
+        let response = client.get("/a b").dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn malformed_fairings_see_raw_request() {
+        let seen = Arc::new(Mutex::new(vec![]));
+        let record = seen.clone();
+        let rocket = rocket::ignite()
+            .attach(AdHoc::on_request("Request", |_, _| panic!("request fairing ran")))
+            .attach(AdHoc::on_response("Response", |_, _| panic!("response fairing ran")))
+            .attach(AdHoc::on_malformed("Malformed", move |req, res| {
+                record.lock().unwrap().push(req.target().to_string());
+                res.set_raw_header("X-Malformed", "yes");
+            }));
+
+        let client = Client::new(rocket).unwrap();
+        let response = client.get("/a b").dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+        assert_eq!(response.headers().get_one("X-Malformed"), Some("yes"));
+        assert_eq!(*seen.lock().unwrap(), vec!["/a b".to_string()]);
+    }
+
+    #[test]
+    fn unknown_methods_reach_malformed_handler() {
+        let config = common::config().unwrap();
+
+        let running = rocket::custom(config)
+            .mount("/", routes![index])
+            .on_malformed(malformed)
+            .spawn()
+            .expect("launch");
+
+        let mut stream = TcpStream::connect(running.addr()).unwrap();
+        stream.write_all(b"BREW * HTTP/1.1\r\nUser-Agent: pot\r\nConnection: close\r\n\r\n")
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 418"));
+        assert!(response.ends_with("BREW|*|pot"));
+
+        running.stop();
+    }
+}
@@ -0,0 +1,133 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::http::Status;
+
+#[get("/resource")]
+fn get_resource() -> &'static str {
+    "resource"
+}
+
+#[post("/resource")]
+fn post_resource() -> &'static str {
+    "created"
+}
+
+#[delete("/only-delete")]
+fn only_delete() -> &'static str {
+    "deleted"
+}
+
+#[get("/forwarding")]
+fn forwarding() -> Option<&'static str> {
+    None
+}
+
+#[options("/custom-options")]
+fn custom_options() -> Status {
+    Status::Ok
+}
+
+#[get("/custom-options")]
+fn custom_options_get() -> &'static str {
+    "get"
+}
+
+mod method_not_allowed_tests {
+    use super::*;
+
+    use rocket::Rocket;
+    use rocket::config::{Config, Environment};
+    use rocket::local::Client;
+
+    fn rocket() -> Rocket {
+        rocket::ignite().mount("/", routes![
+            get_resource, post_resource, only_delete, forwarding,
+            custom_options, custom_options_get
+        ])
+    }
+
+    fn configured(method_not_allowed: bool, auto_options: bool) -> Rocket {
+        let config = Config::build(Environment::Development)
+            .method_not_allowed(method_not_allowed)
+            .auto_options(auto_options)
+            .unwrap();
+
+        rocket::custom(config).mount("/", routes![get_resource, post_resource])
+    }
+
+    #[test]
+    fn other_method_is_not_allowed() {
+        let client = Client::new(rocket()).unwrap();
+        let response = client.put("/resource").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD, OPTIONS, POST"));
+
+        let response = client.get("/only-delete").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("DELETE, OPTIONS"));
+
+        let response = client.head("/only-delete").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("DELETE, OPTIONS"));
+    }
+
+    #[test]
+    fn unknown_path_is_not_found() {
+        let client = Client::new(rocket()).unwrap();
+        let response = client.put("/unknown").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert!(response.headers().get_one("Allow").is_none());
+
+        let response = client.options("/unknown").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn forwarded_request_is_not_found() {
+        let client = Client::new(rocket()).unwrap();
+        let response = client.get("/forwarding").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert!(response.headers().get_one("Allow").is_none());
+    }
+
+    #[test]
+    fn options_is_autohandled() {
+        let client = Client::new(rocket()).unwrap();
+        let mut response = client.options("/resource").dispatch();
+        assert_eq!(response.status(), Status::NoContent);
+        assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD, OPTIONS, POST"));
+        assert!(response.body_bytes().is_none());
+    }
+
+    #[test]
+    fn options_route_takes_precedence() {
+        let client = Client::new(rocket()).unwrap();
+        let response = client.options("/custom-options").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert!(response.headers().get_one("Allow").is_none());
+    }
+
+    #[test]
+    fn behaviors_can_be_disabled() {
+        let client = Client::new(configured(false, false)).unwrap();
+        let response = client.put("/resource").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.options("/resource").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let client = Client::new(configured(true, false)).unwrap();
+        let response = client.options("/resource").dispatch();
+        assert_eq!(response.status(), Status::MethodNotAllowed);
+        assert_eq!(response.headers().get_one("Allow"), Some("GET, HEAD, POST"));
+
+        let client = Client::new(configured(false, true)).unwrap();
+        let response = client.put("/resource").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.options("/resource").dispatch();
+        assert_eq!(response.status(), Status::NoContent);
+    }
+}
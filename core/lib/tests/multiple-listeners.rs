@@ -0,0 +1,93 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+mod common;
+
+#[get("/")]
+fn index() -> &'static str {
+    "Hello, listeners!"
+}
+
+mod multiple_listeners_tests {
+    use super::*;
+
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpStream};
+
+    use rocket::config::{Config, Environment};
+    use rocket::error::LaunchErrorKind;
+
+    fn get(addr: SocketAddr) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\nConnection: close\r\n\r\n").unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn all_listeners_serve_the_same_routes() {
+        let config = common::config()
+            .listener("127.0.0.1", 0)
+            .listener("127.0.0.1", 0)
+            .unwrap();
+
+        let running = rocket::custom(config)
+            .mount("/", routes![index])
+            .spawn()
+            .expect("launch");
+
+        assert_eq!(running.addrs().len(), 3);
+        assert_eq!(running.addrs()[0], running.addr());
+        for addr in running.addrs() {
+            let response = get(*addr);
+            assert!(response.starts_with("HTTP/1.1 200 OK"));
+            assert!(response.ends_with("Hello, listeners!"));
+        }
+
+        running.stop();
+    }
+
+    #[test]
+    fn redirect_requires_tls_listener() {
+        let config = Config::build(Environment::Development)
+            .port(0)
+            .listener("127.0.0.1", 0)
+            .redirect("127.0.0.1", 0)
+            .unwrap();
+
+        match rocket::custom(config).mount("/", routes![index]).spawn() {
+            Ok(_) => panic!("launched a redirect without a TLS listener"),
+            Err(e) => match e.kind() {
+                LaunchErrorKind::Unknown(_) => { /* expected */ }
+                kind => panic!("unexpected launch error: {}", kind),
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_bind_removes_earlier_socket_files() {
+        let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = taken.local_addr().unwrap().port();
+        let file = format!("rocket-listeners-{}.sock", std::process::id());
+        let path = std::env::temp_dir().join(file);
+
+        let config = common::config()
+            .listener(format!("unix:{}", path.display()), 0)
+            .listener("127.0.0.1", port)
+            .unwrap();
+
+        match rocket::custom(config).mount("/", routes![index]).spawn() {
+            Ok(_) => panic!("launched on a port that is already in use"),
+            Err(e) => match e.kind() {
+                LaunchErrorKind::Bind(_) => { /* expected */ }
+                kind => panic!("unexpected launch error: {}", kind),
+            }
+        }
+
+        assert!(!path.exists());
+    }
+}
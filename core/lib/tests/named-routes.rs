@@ -0,0 +1,92 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use std::path::PathBuf;
+
+use rocket::UriParams;
+use rocket::request::RouteUris;
+use rocket::response::Redirect;
+
+#[get("/<name>?<page>")]
+fn profile(name: String, page: Option<usize>) -> String {
+    format!("{} {:?}", name, page)
+}
+
+#[get("/files/<path..>?sorted")]
+fn files(path: PathBuf) -> String {
+    path.display().to_string()
+}
+
+#[get("/", name = "home")]
+fn index() -> &'static str {
+    "home"
+}
+
+#[get("/me")]
+fn me(uris: RouteUris) -> Redirect {
+    let params = UriParams::new().param("name", &"Jo Doe").param("page", &2);
+    Redirect::to(uris.uri_for("profile", &params).unwrap())
+}
+
+mod named_routes_tests {
+    use super::*;
+
+    use rocket::Rocket;
+    use rocket::local::Client;
+    use rocket::error::UriForError::{self, *};
+    use rocket::http::Status;
+
+    fn rocket() -> Rocket {
+        rocket::ignite()
+            .mount("/", routes![index, me])
+            .mount("/users", routes![profile, files])
+            .mount("/people", routes![profile])
+    }
+
+    fn uri_for(rocket: &Rocket, name: &str, params: &UriParams) -> Result<String, UriForError> {
+        rocket.uri_for(name, params).map(|uri| uri.to_string())
+    }
+
+    #[test]
+    fn uris_include_mount_point() {
+        let rocket = rocket();
+        let params = UriParams::new().param("name", &"bob");
+        assert_eq!(uri_for(&rocket, "profile", &params), Ok("/users/bob".into()));
+        assert_eq!(uri_for(&rocket, "home", &UriParams::new()), Ok("/".into()));
+    }
+
+    #[test]
+    fn params_are_encoded() {
+        let rocket = rocket();
+        let params = UriParams::new().param("name", &"a b/c").param("page", &3);
+        assert_eq!(uri_for(&rocket, "profile", &params), Ok("/users/a%20b%2Fc?page=3".into()));
+
+        let path = PathBuf::from("a dir/file.txt");
+        let params = UriParams::new().path("path", &path);
+        let expected = "/users/files/a%20dir/file.txt?sorted";
+        assert_eq!(uri_for(&rocket, "files", &params), Ok(expected.into()));
+    }
+
+    #[test]
+    fn bad_names_and_params_are_errors() {
+        let rocket = rocket();
+        let none = UriParams::new();
+        assert_eq!(uri_for(&rocket, "index", &none), Err(UnknownRoute("index".into())));
+        assert_eq!(uri_for(&rocket, "profile", &none), Err(MissingParam("name".into())));
+
+        let params = UriParams::new().param("name", &"bob").param("age", &3);
+        assert_eq!(uri_for(&rocket, "profile", &params), Err(UnknownParam("age".into())));
+
+        let params = UriParams::new().query("name", &"bob");
+        assert_eq!(uri_for(&rocket, "profile", &params), Err(MissingParam("name".into())));
+    }
+
+    #[test]
+    fn request_guard_builds_uris() {
+        let client = Client::new(rocket()).unwrap();
+        let response = client.get("/me").dispatch();
+        assert_eq!(response.status(), Status::SeeOther);
+        assert_eq!(response.headers().get_one("Location"), Some("/users/Jo%20Doe?page=2"));
+    }
+}
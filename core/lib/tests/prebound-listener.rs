@@ -0,0 +1,74 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+mod common;
+
+#[get("/")]
+fn index() -> &'static str {
+    "Hello, prebound!"
+}
+
+mod prebound_listener_tests {
+    use super::*;
+
+    use std::env;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    use rocket::Listener;
+
+    #[test]
+    fn launches_on_prebound_tcp_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let config = common::config()
+            .port(1)
+            .unwrap();
+
+        let running = rocket::custom(config)
+            .mount("/", routes![index])
+            .attach(rocket::fairing::AdHoc::on_launch("Check Port", move |rocket| {
+                assert_eq!(rocket.config().port, addr.port());
+            }))
+            .listen_on(listener)
+            .spawn()
+            .expect("launch");
+
+        assert_eq!(running.addr(), addr);
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\nConnection: close\r\n\r\n").unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("Hello, prebound!"));
+
+        running.stop();
+    }
+
+    #[test]
+    fn systemd_listener_requires_matching_pid() {
+        env::remove_var("LISTEN_PID");
+        env::remove_var("LISTEN_FDS");
+        assert!(Listener::systemd().unwrap().is_none());
+
+        if cfg!(unix) {
+            env::set_var("LISTEN_PID", (std::process::id() + 1).to_string());
+            env::set_var("LISTEN_FDS", "1");
+            assert!(Listener::systemd().unwrap().is_none());
+            assert!(env::var("LISTEN_FDS").is_ok());
+
+            env::set_var("LISTEN_PID", std::process::id().to_string());
+            env::set_var("LISTEN_FDS", "one");
+            assert!(Listener::systemd().is_err());
+
+            env::set_var("LISTEN_FDS", "0");
+            assert!(Listener::systemd().unwrap().is_none());
+            assert!(env::var("LISTEN_PID").is_err());
+            assert!(env::var("LISTEN_FDS").is_err());
+        }
+    }
+}
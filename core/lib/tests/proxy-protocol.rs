@@ -0,0 +1,176 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use std::net::SocketAddr;
+
+#[get("/")]
+fn remote(remote: SocketAddr) -> String {
+    remote.to_string()
+}
+
+mod proxy_protocol_tests {
+    use super::*;
+
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::time::Duration;
+
+    use rocket::Running;
+
+    const V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+
+    fn spawn(proxy_protocol: bool) -> Running {
+        let config = common::config()
+            .proxy_protocol(proxy_protocol)
+            .proxy_listener("127.0.0.1", 0)
+            .unwrap();
+
+        rocket::custom(config)
+            .mount("/", routes![remote])
+            .spawn()
+            .expect("launch")
+    }
+
+    /// Sends `header` followed by a request for `/` and returns the response,
+    /// which is empty if the connection was closed without one.
+    fn get(addr: SocketAddr, header: &[u8]) -> String {
+        let stream = TcpStream::connect(addr).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        request(stream, header)
+    }
+
+    /// Like `get()`, but over an already connected `stream`.
+    fn request<S: Read + Write>(mut stream: S, header: &[u8]) -> String {
+        stream.write_all(header).unwrap();
+        let _ = stream.write_all(b"GET / HTTP/1.1\r\nConnection: close\r\n\r\n");
+
+        let mut response = vec![];
+        let _ = stream.read_to_end(&mut response);
+        String::from_utf8_lossy(&response).into_owned()
+    }
+
+    /// Returns the body of `response`: the remote address, if it was served.
+    fn body(response: &str) -> Option<&str> {
+        response.splitn(2, "\r\n\r\n").nth(1)
+    }
+
+    fn v2(command: u8, family: u8, addresses: &[u8]) -> Vec<u8> {
+        let mut header = V2_SIGNATURE.to_vec();
+        header.push(0x20 | command);
+        header.push(family);
+        header.extend_from_slice(&(addresses.len() as u16).to_be_bytes());
+        header.extend_from_slice(addresses);
+        header
+    }
+
+    #[test]
+    fn v1_header_sets_remote() {
+        let running = spawn(true);
+        let response = get(running.addr(), b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert_eq!(body(&response), Some("192.0.2.1:56324"));
+
+        let response = get(running.addr(), b"PROXY TCP6 2001:db8::1 2001:db8::2 4711 443\r\n");
+        assert_eq!(body(&response), Some("[2001:db8::1]:4711"));
+
+        // The load balancer doesn't know the client: keep the peer address.
+        let response = get(running.addr(), b"PROXY UNKNOWN\r\n");
+        assert!(body(&response).unwrap().starts_with("127.0.0.1:"));
+        running.stop();
+    }
+
+    #[test]
+    fn v2_header_sets_remote() {
+        let running = spawn(true);
+        let ipv4 = [192, 0, 2, 7, 198, 51, 100, 1, 0x1F, 0x90, 0x01, 0xBB];
+        let response = get(running.addr(), &v2(0x1, 0x11, &ipv4));
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert_eq!(body(&response), Some("192.0.2.7:8080"));
+
+        // Trailing TLVs are skipped.
+        let mut with_tlvs = ipv4.to_vec();
+        with_tlvs.extend_from_slice(&[0x04, 0x00, 0x02, 0xAB, 0xCD]);
+        let response = get(running.addr(), &v2(0x1, 0x11, &with_tlvs));
+        assert_eq!(body(&response), Some("192.0.2.7:8080"));
+
+        let mut ipv6 = [0u8; 36];
+        ipv6[..2].copy_from_slice(&[0x20, 0x01]);
+        ipv6[15] = 0x09;
+        ipv6[32..34].copy_from_slice(&[0x12, 0x67]);
+        let response = get(running.addr(), &v2(0x1, 0x21, &ipv6));
+        assert_eq!(body(&response), Some("[2001::9]:4711"));
+
+        // A `LOCAL` connection, like a health check, keeps the peer address.
+        let response = get(running.addr(), &v2(0x0, 0x00, &[]));
+        assert!(body(&response).unwrap().starts_with("127.0.0.1:"));
+        running.stop();
+    }
+
+    #[test]
+    fn connections_without_header_are_rejected() {
+        let running = spawn(true);
+        for addr in running.addrs() {
+            assert_eq!(get(*addr, b""), "");
+            assert_eq!(get(*addr, b"PROXY TCP4 not-an-ip 198.51.100.1 1 2\r\n"), "");
+            assert_eq!(get(*addr, &v2(0x1, 0x11, &[192, 0, 2])), "");
+        }
+
+        // The server is still up and serving.
+        let response = get(running.addr(), b"PROXY TCP4 192.0.2.1 198.51.100.1 1 2\r\n");
+        assert_eq!(body(&response), Some("192.0.2.1:1"));
+        running.stop();
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn header_sets_remote_on_unix_socket() {
+        use std::os::unix::net::UnixStream;
+
+        let file = format!("rocket-proxy-{}.sock", std::process::id());
+        let path = std::env::temp_dir().join(file);
+        let config = common::config()
+            .address(format!("unix:{}", path.display()))
+            .proxy_protocol(true)
+            .unwrap();
+
+        let running = rocket::custom(config)
+            .mount("/", routes![remote])
+            .spawn()
+            .expect("launch");
+
+        let connect = || {
+            let stream = UnixStream::connect(&path).unwrap();
+            stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+            stream
+        };
+
+        let header = b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\n";
+        let response = request(connect(), header);
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert_eq!(body(&response), Some("192.0.2.1:56324"));
+
+        // Without a client address, the connection has no remote address.
+        let response = request(connect(), b"PROXY UNKNOWN\r\n");
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+        running.stop();
+    }
+
+    #[test]
+    fn headers_are_only_read_when_enabled() {
+        let running = spawn(false);
+
+        // The main listener doesn't expect a header...
+        let response = get(running.addr(), b"");
+        assert!(body(&response).unwrap().starts_with("127.0.0.1:"));
+
+        // ...but the additional listener does.
+        let proxied = running.addrs()[1];
+        assert_eq!(get(proxied, b""), "");
+        let response = get(proxied, b"PROXY TCP4 192.0.2.1 198.51.100.1 80 443\r\n");
+        assert_eq!(body(&response), Some("192.0.2.1:80"));
+        running.stop();
+    }
+}
@@ -0,0 +1,153 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use rocket::State;
+use rocket::response::{NamedFile, Stream};
+
+const CONTENTS: &str = "0123456789abcdefghijklmnopqrstuvwxyz";
+
+struct FilePath(PathBuf);
+
+#[get("/file")]
+fn file(path: State<'_, FilePath>) -> Option<NamedFile> {
+    NamedFile::open(&path.0).ok()
+}
+
+#[get("/sized")]
+fn sized() -> &'static str {
+    CONTENTS
+}
+
+#[get("/stream")]
+fn stream() -> Stream<Cursor<&'static str>> {
+    Stream::from(Cursor::new(CONTENTS))
+}
+
+mod range_request_tests {
+    use super::*;
+
+    use std::fs;
+
+    use rocket::local::Client;
+    use rocket::http::{Header, Status, Range, ByteRange};
+
+    fn client(name: &str) -> Client {
+        let path = std::env::temp_dir().join(format!("rocket-range-{}.txt", name));
+        fs::write(&path, CONTENTS).expect("write test file");
+
+        let rocket = rocket::ignite()
+            .mount("/", routes![file, sized, stream])
+            .manage(FilePath(path));
+
+        Client::new(rocket).unwrap()
+    }
+
+    #[test]
+    fn file_advertises_ranges() {
+        let client = client("advertises");
+        let mut response = client.get("/file").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.headers().get_one("Accept-Ranges"), Some("bytes"));
+        assert_eq!(response.body_string(), Some(CONTENTS.into()));
+    }
+
+    #[test]
+    fn single_range() {
+        let client = client("single");
+        for uri in &["/file", "/sized"] {
+            let mut response = client.get(*uri)
+                .header(Header::new("Range", "bytes=10-15"))
+                .dispatch();
+
+            assert_eq!(response.status(), Status::PartialContent);
+            assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 10-15/36"));
+            assert_eq!(response.body_string(), Some("abcdef".into()));
+
+            let mut response = client.get(*uri)
+                .header(Range::new(vec![ByteRange::Suffix(3)]))
+                .dispatch();
+
+            assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 33-35/36"));
+            assert_eq!(response.body_string(), Some("xyz".into()));
+        }
+
+        // Ranges past the end are clamped.
+        let mut response = client.get("/file")
+            .header(Header::new("Range", "bytes=30-100"))
+            .dispatch();
+
+        assert_eq!(response.headers().get_one("Content-Range"), Some("bytes 30-35/36"));
+        assert_eq!(response.body_string(), Some("uvwxyz".into()));
+    }
+
+    #[test]
+    fn unsatisfiable_range() {
+        let client = client("unsatisfiable");
+        let response = client.get("/file")
+            .header(Header::new("Range", "bytes=36-"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::RangeNotSatisfiable);
+        assert_eq!(response.headers().get_one("Content-Range"), Some("bytes */36"));
+        assert!(response.content_type().is_none());
+    }
+
+    #[test]
+    fn multiple_ranges() {
+        let client = client("multiple");
+        let mut response = client.get("/file")
+            .header(Header::new("Range", "bytes=0-1, 100-200, -2"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::PartialContent);
+        let content_type = response.headers().get_one("Content-Type").unwrap().to_string();
+        assert!(content_type.starts_with("multipart/byteranges; boundary="));
+
+        let boundary = &content_type["multipart/byteranges; boundary=".len()..];
+        let expected = format!("\r\n--{b}\r\nContent-Type: text/plain; charset=utf-8\r\n\
+            Content-Range: bytes 0-1/36\r\n\r\n01\
+            \r\n--{b}\r\nContent-Type: text/plain; charset=utf-8\r\n\
+            Content-Range: bytes 34-35/36\r\n\r\nyz\
+            \r\n--{b}--\r\n", b = boundary);
+
+        assert_eq!(response.body_string(), Some(expected));
+    }
+
+    #[test]
+    fn ranges_are_ignored_when_inapplicable() {
+        let client = client("ignored");
+        let requests = vec![
+            // Streamed bodies can't be seeked.
+            client.get("/stream").header(Header::new("Range", "bytes=0-1")),
+            // Invalid `Range` headers are ignored.
+            client.get("/file").header(Header::new("Range", "bytes=5-1")),
+            client.get("/file").header(Header::new("Range", "lines=1-2")),
+            // The validator doesn't match, so the representation has changed.
+            client.get("/file")
+                .header(Header::new("Range", "bytes=0-1"))
+                .header(Header::new("If-Range", "\"some-etag\"")),
+        ];
+
+        for request in requests {
+            let mut response = request.dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert!(response.headers().get_one("Content-Range").is_none());
+            assert_eq!(response.body_string(), Some(CONTENTS.into()));
+        }
+    }
+
+    #[test]
+    fn head_requests_for_ranges() {
+        let client = client("head");
+        let mut response = client.head("/file")
+            .header(Header::new("Range", "bytes=0-1"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.body_string(), Some("".into()));
+    }
+}
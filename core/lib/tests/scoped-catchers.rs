@@ -0,0 +1,98 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use rocket::Request;
+use rocket::http::Status;
+
+#[get("/fail/<code>")]
+fn fail(code: u16) -> Status {
+    Status::from_code(code).unwrap_or(Status::new(code, "Unknown"))
+}
+
+#[catch(404)]
+fn not_found(req: &Request) -> String {
+    format!("not found: {}", req.uri())
+}
+
+#[catch(default)]
+fn default(req: &Request) -> String {
+    format!("default: {}", req.error_status().unwrap().code)
+}
+
+#[catch(404)]
+fn api_not_found() -> &'static str {
+    "api not found"
+}
+
+#[catch(default)]
+fn api_default(status: Status, _req: &Request) -> String {
+    format!("api default: {}", status.code)
+}
+
+mod scoped_catchers_tests {
+    use super::*;
+    use rocket::local::Client;
+
+    fn client() -> Client {
+        let rocket = rocket::ignite()
+            .mount("/", routes![fail])
+            .mount("/api", routes![fail])
+            .register(catchers![not_found, default])
+            .register_at("/api/v1", catchers![api_not_found])
+            .register_at("/api", catchers![api_default]);
+
+        Client::new(rocket).unwrap()
+    }
+
+    #[test]
+    fn default_catcher_receives_status() {
+        let client = client();
+        let mut response = client.get("/fail/418").dispatch();
+        assert_eq!(response.status(), Status::ImATeapot);
+        assert_eq!(response.body_string(), Some("default: 418".into()));
+
+        let mut response = client.get("/fail/503").dispatch();
+        assert_eq!(response.status(), Status::ServiceUnavailable);
+        assert_eq!(response.body_string(), Some("default: 503".into()));
+    }
+
+    #[test]
+    fn specific_catcher_beats_default() {
+        let client = client();
+        let mut response = client.get("/unknown").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.body_string(), Some("not found: /unknown".into()));
+    }
+
+    #[test]
+    fn most_specific_base_wins() {
+        let client = client();
+        let mut response = client.get("/api/unknown").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.body_string(), Some("api default: 404".into()));
+
+        let mut response = client.get("/api/fail/500").dispatch();
+        assert_eq!(response.status(), Status::InternalServerError);
+        assert_eq!(response.body_string(), Some("api default: 500".into()));
+
+        let mut response = client.get("/api/v1/unknown").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+        assert_eq!(response.body_string(), Some("api not found".into()));
+
+        let mut response = client.get("/apis").dispatch();
+        assert_eq!(response.body_string(), Some("not found: /apis".into()));
+    }
+
+    #[test]
+    fn builtin_catchers_are_used_without_user_catchers() {
+        let client = Client::new(rocket::ignite().mount("/", routes![fail])).unwrap();
+        let mut response = client.get("/fail/418").dispatch();
+        assert_eq!(response.status(), Status::ImATeapot);
+        assert!(response.body_string().unwrap().contains("418"));
+
+        let mut response = client.get("/fail/599").dispatch();
+        assert_eq!(response.status().code, 599);
+        assert!(response.body_string().unwrap().contains("599"));
+    }
+}
@@ -0,0 +1,113 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use std::io::Read;
+
+use rocket::Data;
+
+#[post("/", data = "<data>")]
+fn body(data: Data) -> String {
+    let mut string = String::new();
+    match data.open().read_to_string(&mut string) {
+        Ok(_) => string,
+        Err(e) => format!("{:?}", e.kind()),
+    }
+}
+
+mod timeout_tests {
+    use super::*;
+
+    use std::io::Write;
+    use std::net::TcpStream;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use rocket::Running;
+
+    fn spawn(read_timeout: u32, header_timeout: u32) -> Running {
+        let config = common::config()
+            .read_timeout(read_timeout)
+            .header_timeout(header_timeout)
+            .unwrap();
+
+        rocket::custom(config)
+            .mount("/", routes![body])
+            .spawn()
+            .expect("launch")
+    }
+
+    #[test]
+    fn trickled_headers_are_cut_off() {
+        let running = spawn(5, 1);
+        let mut stream = TcpStream::connect(running.addr()).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+
+        // Each byte arrives well within the read timeout, but the headers as a
+        // whole take longer than the header deadline.
+        let start = Instant::now();
+        for byte in b"POST / HTTP/1.1\r\nX-Slow: trickle\r\n".iter() {
+            if stream.write_all(&[*byte]).is_err() {
+                break;
+            }
+
+            thread::sleep(Duration::from_millis(100));
+        }
+
+        let mut response = vec![];
+        let _ = stream.read_to_end(&mut response);
+        assert!(response.is_empty());
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        running.stop();
+    }
+
+    #[test]
+    fn stalled_body_fails_with_timeout() {
+        let running = spawn(1, 0);
+        let mut stream = TcpStream::connect(running.addr()).unwrap();
+        stream.write_all(b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc").unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("TimedOut"));
+
+        running.stop();
+    }
+
+    #[test]
+    fn stalled_body_fails_with_timeout_by_default() {
+        let running = spawn(0, 0);
+        let mut stream = TcpStream::connect(running.addr()).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(30))).unwrap();
+
+        let start = Instant::now();
+        stream.write_all(b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc").unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("TimedOut"));
+        assert!(start.elapsed() < Duration::from_secs(15));
+
+        running.stop();
+    }
+
+    #[test]
+    fn complete_requests_are_unaffected() {
+        let running = spawn(1, 1);
+        let mut stream = TcpStream::connect(running.addr()).unwrap();
+        stream.write_all(b"POST / HTTP/1.1\r\nContent-Length: 5\r\n").unwrap();
+        stream.write_all(b"Connection: close\r\n\r\nhello").unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("hello"));
+
+        running.stop();
+    }
+}
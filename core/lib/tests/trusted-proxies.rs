@@ -0,0 +1,127 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use std::net::IpAddr;
+
+use rocket::request::ClientInfo;
+
+#[get("/")]
+fn index(client: ClientInfo) -> String {
+    let ip = client.ip().map(|ip| ip.to_string());
+    format!("{} {} {}", ip.as_deref().unwrap_or("-"), client.scheme(),
+        client.host().unwrap_or("-"))
+}
+
+#[get("/ip")]
+fn ip(ip: IpAddr) -> String {
+    ip.to_string()
+}
+
+mod trusted_proxies_tests {
+    use super::*;
+
+    use rocket::Rocket;
+    use rocket::config::{Config, Environment};
+    use rocket::http::{Header, Status};
+    use rocket::local::{Client, LocalRequest};
+
+    fn rocket(proxies: &[&str]) -> Rocket {
+        let config = Config::build(Environment::Development)
+            .trusted_proxies(proxies.iter().map(|p| p.parse().unwrap()).collect())
+            .unwrap();
+
+        rocket::custom(config).mount("/", routes![index, ip])
+    }
+
+    fn get<'c>(client: &'c Client, remote: &str, headers: &[(&'static str, &'static str)])
+        -> LocalRequest<'c>
+    {
+        let mut request = client.get("/").remote(remote.parse().unwrap());
+        for &(name, value) in headers {
+            request.add_header(Header::new(name, value));
+        }
+
+        request
+    }
+
+    fn resolve(client: &Client, remote: &str, headers: &[(&'static str, &'static str)]) -> String {
+        get(client, remote, headers).dispatch().body_string().unwrap()
+    }
+
+    #[test]
+    fn untrusted_remote_headers_are_ignored() {
+        let client = Client::new(rocket(&[])).unwrap();
+        let headers = [
+            ("Host", "rocket.rs"),
+            ("X-Real-IP", "1.1.1.1"),
+            ("X-Forwarded-For", "2.2.2.2"),
+            ("X-Forwarded-Proto", "https"),
+            ("Forwarded", "for=3.3.3.3;proto=https;host=evil.com"),
+        ];
+
+        assert_eq!(resolve(&client, "10.0.0.1:80", &headers), "10.0.0.1 http rocket.rs");
+        assert!(get(&client, "10.0.0.1:80", &headers).inner().real_ip().is_none());
+    }
+
+    #[test]
+    fn x_forwarded_chain_is_walked_through_trusted_hops() {
+        let client = Client::new(rocket(&["10.0.0.0/8"])).unwrap();
+        let headers = [
+            ("Host", "internal"),
+            ("X-Forwarded-For", "1.1.1.1, 2.2.2.2, 10.0.0.3"),
+            ("X-Forwarded-Proto", "https"),
+            ("X-Forwarded-Host", "rocket.rs"),
+        ];
+
+        // `10.0.0.3` is a trusted proxy, `2.2.2.2` isn't, so it's the client.
+        assert_eq!(resolve(&client, "10.0.0.1:80", &headers), "2.2.2.2 https rocket.rs");
+
+        // Every hop is trusted: the farthest address is the client.
+        let headers = [("X-Forwarded-For", "10.1.1.1, 10.2.2.2")];
+        assert_eq!(resolve(&client, "10.0.0.1:80", &headers), "10.1.1.1 http -");
+    }
+
+    #[test]
+    fn forwarded_header_takes_precedence() {
+        let client = Client::new(rocket(&["127.0.0.1", "::1"])).unwrap();
+        let headers = [
+            ("X-Forwarded-For", "2.2.2.2"),
+            ("Forwarded", r#"for="[2001:db8::1]:4711";proto=HTTPS;host="rocket.rs""#),
+            ("Forwarded", "for=127.0.0.1;proto=http;host=internal"),
+        ];
+
+        assert_eq!(resolve(&client, "[::1]:80", &headers), "2001:db8::1 https rocket.rs");
+    }
+
+    #[test]
+    fn obfuscated_identifier_ends_the_chain() {
+        let client = Client::new(rocket(&["127.0.0.1"])).unwrap();
+        let headers = [("Forwarded", "for=1.1.1.1, for=_hidden, for=unknown")];
+        assert_eq!(resolve(&client, "127.0.0.1:80", &headers), "127.0.0.1 http -");
+    }
+
+    #[test]
+    fn x_real_ip_is_used_from_trusted_proxies() {
+        let client = Client::new(rocket(&["127.0.0.1"])).unwrap();
+        let headers = [("X-Real-IP", "8.8.8.8")];
+        assert_eq!(resolve(&client, "127.0.0.1:80", &headers), "8.8.8.8 http -");
+
+        let request = get(&client, "127.0.0.1:80", &headers);
+        assert_eq!(request.inner().real_ip(), Some("8.8.8.8".parse().unwrap()));
+    }
+
+    #[test]
+    fn ip_guard_uses_client_ip() {
+        let client = Client::new(rocket(&["127.0.0.1"])).unwrap();
+        let mut response = client.get("/ip")
+            .remote("127.0.0.1:80".parse().unwrap())
+            .header(Header::new("X-Forwarded-For", "9.9.9.9"))
+            .dispatch();
+
+        assert_eq!(response.body_string(), Some("9.9.9.9".into()));
+
+        let response = client.get("/ip").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,131 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+use std::time::Duration;
+
+use rocket::Response;
+use rocket::http::Status;
+use rocket::http::headers::*;
+
+#[get("/auth")]
+fn auth(auth: Authorization) -> String {
+    match auth {
+        Authorization::Basic { username, .. } => format!("basic:{}", username),
+        Authorization::Bearer(token) => format!("bearer:{}", token),
+        other => format!("other:{}", other.scheme()),
+    }
+}
+
+#[get("/auth", rank = 2)]
+fn no_auth() -> Status {
+    Status::Unauthorized
+}
+
+#[get("/host")]
+fn host(host: Host, agent: Option<UserAgent>) -> String {
+    let product = agent.as_ref().map(|a| a.product().0).unwrap_or("unknown");
+    format!("{} {:?} {}", host.host(), host.port(), product)
+}
+
+#[get("/cache")]
+fn cache(cache_control: Result<CacheControl, String>) -> String {
+    match cache_control {
+        Ok(cc) => format!("{:?}", cc.max_age()),
+        Err(e) => format!("error: {}", e),
+    }
+}
+
+#[get("/download")]
+fn download() -> Response<'static> {
+    Response::build()
+        .header(ContentDisposition::attachment().with_filename("report.csv"))
+        .header(CacheControl::new(vec![CacheDirective::Private, CacheDirective::MaxAge(60)]))
+        .header(Vary::Fields(vec!["Accept-Encoding".into()]))
+        .header(Link::new(vec![LinkValue::new("/next").with_param("rel", "next")]))
+        .header(RetryAfter::Delay(Duration::from_secs(5)))
+        .finalize()
+}
+
+mod typed_headers_tests {
+    use super::*;
+
+    use rocket::local::Client;
+    use rocket::http::Header;
+
+    fn client() -> Client {
+        let rocket = rocket::ignite().mount("/", routes![auth, no_auth, host, cache, download]);
+        Client::new(rocket).unwrap()
+    }
+
+    #[test]
+    fn typed_header_guards_succeed() {
+        let client = client();
+        let mut response = client.get("/auth")
+            .header(Authorization::basic("alice", "secret"))
+            .dispatch();
+
+        assert_eq!(response.body_string(), Some("basic:alice".into()));
+
+        let mut response = client.get("/auth")
+            .header(Header::new("Authorization", "Bearer abc.def"))
+            .dispatch();
+
+        assert_eq!(response.body_string(), Some("bearer:abc.def".into()));
+
+        let mut response = client.get("/host")
+            .header(Header::new("Host", "Example.com:8000"))
+            .header(Header::new("User-Agent", "curl/7.64.1"))
+            .dispatch();
+
+        assert_eq!(response.body_string(), Some("example.com Some(8000) curl".into()));
+    }
+
+    #[test]
+    fn typed_header_guards_forward_when_missing() {
+        let client = client();
+        let response = client.get("/auth").dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client.get("/host").dispatch();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn typed_header_guards_fail_when_malformed() {
+        let client = client();
+        let response = client.get("/host")
+            .header(Header::new("Host", "example.com:99999"))
+            .dispatch();
+
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let mut response = client.get("/cache")
+            .header(Header::new("Cache-Control", "max-age=soon"))
+            .dispatch();
+
+        let body = response.body_string().unwrap();
+        assert!(body.starts_with("error: "), "{}", body);
+
+        let mut response = client.get("/cache")
+            .header(Header::new("Cache-Control", "no-cache"))
+            .header(Header::new("Cache-Control", "max-age=10"))
+            .dispatch();
+
+        assert_eq!(response.body_string(), Some("Some(10s)".into()));
+    }
+
+    #[test]
+    fn typed_headers_set_on_responses() {
+        let client = client();
+        let response = client.get("/download").dispatch();
+        let headers = response.headers();
+
+        let disposition = ContentDisposition::from_headers(headers).unwrap().unwrap();
+        assert_eq!(disposition.filename().unwrap(), "report.csv");
+        assert_eq!(headers.get_one("Cache-Control"), Some("private, max-age=60"));
+        assert_eq!(headers.get_one("Vary"), Some("Accept-Encoding"));
+        assert_eq!(headers.get_one("Link"), Some("</next>; rel=\"next\""));
+        assert_eq!(headers.get_one("Retry-After"), Some("5"));
+    }
+}
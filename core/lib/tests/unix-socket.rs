@@ -0,0 +1,98 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+#[cfg(unix)] mod common;
+
+#[cfg(unix)]
+mod unix_socket_tests {
+    use std::io::{Read, Write};
+    use std::net::SocketAddr;
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+
+    use rocket::Running;
+    use rocket::config::{Config, Environment};
+    use rocket::error::LaunchErrorKind;
+
+    use crate::common;
+
+    #[get("/")]
+    fn remote(remote: Option<SocketAddr>) -> String {
+        format!("{:?}", remote)
+    }
+
+    fn socket_path(name: &str) -> PathBuf {
+        let file = format!("rocket-{}-{}.sock", name, std::process::id());
+        std::env::temp_dir().join(file)
+    }
+
+    fn spawn(path: &Path) -> Running {
+        let config = common::config()
+            .address(format!("unix:{}", path.display()))
+            .socket_mode(0o600)
+            .unwrap();
+
+        rocket::custom(config)
+            .mount("/", routes![remote])
+            .spawn()
+            .expect("launch")
+    }
+
+    fn get(path: &Path) -> String {
+        let mut stream = UnixStream::connect(path).unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\nConnection: close\r\n\r\n").unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn serves_over_unix_socket_without_remote() {
+        let path = socket_path("serve");
+        let running = spawn(&path);
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let response = get(&path);
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.ends_with("None"));
+
+        running.stop();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn removes_stale_socket_file() {
+        let path = socket_path("stale");
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(path.exists());
+
+        let running = spawn(&path);
+        assert!(get(&path).starts_with("HTTP/1.1 200 OK"));
+        running.stop();
+    }
+
+    #[test]
+    fn refuses_socket_in_use() {
+        let path = socket_path("in-use");
+        let _listener = UnixListener::bind(&path).unwrap();
+
+        let config = Config::build(Environment::Development)
+            .address(format!("unix:{}", path.display()))
+            .unwrap();
+
+        match rocket::custom(config).spawn() {
+            Ok(_) => panic!("bound to a socket that is in use"),
+            Err(e) => match e.kind() {
+                LaunchErrorKind::Bind(_) => { /* expected */ }
+                kind => panic!("unexpected launch error: {}", kind),
+            }
+        }
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
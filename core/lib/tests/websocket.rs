@@ -0,0 +1,228 @@
+#![feature(proc_macro_hygiene)]
+
+#[macro_use] extern crate rocket;
+
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rocket::State;
+use rocket::websocket::{WebSocket, Channel, Message, CloseFrame};
+
+#[get("/echo")]
+fn echo(ws: WebSocket, counter: State<Arc<AtomicUsize>>) -> Channel {
+    let counter = counter.inner().clone();
+    ws.channel(move |mut socket| {
+        while let Some(message) = socket.recv()? {
+            match message {
+                Message::Text(text) => {
+                    let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                    socket.send(Message::text(format!("{}: {}", n, text)))?;
+                }
+                Message::Binary(bytes) => socket.send(Message::Binary(bytes))?,
+                _ => { /* pings are answered automatically */ }
+            }
+        }
+
+        Ok(())
+    })
+}
+
+#[get("/echo", rank = 2)]
+fn plain() -> &'static str {
+    "not a websocket"
+}
+
+#[get("/bye")]
+fn bye(ws: WebSocket) -> Channel {
+    ws.channel(|mut socket| socket.close(Some(CloseFrame::new(1001, "going away"))))
+}
+
+#[get("/chat")]
+fn chat(ws: WebSocket) -> Option<Channel> {
+    let protocol = ws.protocols().iter().find(|p| *p == "chat.v1").cloned()?;
+    Some(ws.channel(|_| Ok(())).protocol(protocol))
+}
+
+mod websocket_tests {
+    use super::*;
+
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::time::Duration;
+
+    use rocket::config::{Config, Environment, Limits};
+    use rocket::http::{Header, Status};
+    use rocket::local::Client;
+
+    fn rocket() -> rocket::Rocket {
+        rocket::ignite()
+            .mount("/", routes![echo, plain, bye, chat])
+            .manage(Arc::new(AtomicUsize::new(0)))
+    }
+
+    #[test]
+    fn echo_conversation() {
+        let client = Client::new(rocket()).unwrap();
+        let mut ws = client.get("/echo").websocket().expect("handshake");
+
+        let response = ws.response();
+        assert_eq!(response.status(), Status::SwitchingProtocols);
+        assert_eq!(response.headers().get_one("Upgrade"), Some("websocket"));
+        assert_eq!(response.headers().get_one("Sec-WebSocket-Accept"),
+            Some("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+        ws.send(Message::text("hello")).unwrap();
+        assert_eq!(ws.recv().unwrap(), Some(Message::text("1: hello")));
+        ws.send(Message::text("again")).unwrap();
+        assert_eq!(ws.recv().unwrap(), Some(Message::text("2: again")));
+
+        let large = vec![7u8; 70_000];
+        ws.send(Message::binary(large.clone())).unwrap();
+        assert_eq!(ws.recv().unwrap(), Some(Message::Binary(large)));
+
+        ws.send(Message::Ping(b"are you there?".to_vec())).unwrap();
+        assert_eq!(ws.recv().unwrap(), Some(Message::Pong(b"are you there?".to_vec())));
+
+        ws.join().unwrap();
+    }
+
+    #[test]
+    fn server_initiated_close() {
+        let client = Client::new(rocket()).unwrap();
+        let mut ws = client.get("/bye").websocket().expect("handshake");
+
+        let close = CloseFrame::new(1001, "going away");
+        assert_eq!(ws.recv().unwrap(), Some(Message::Close(Some(close))));
+        assert_eq!(ws.recv().unwrap(), None);
+        assert!(ws.send(Message::text("too late")).is_err());
+        ws.join().unwrap();
+    }
+
+    #[test]
+    fn plain_requests_are_forwarded() {
+        let client = Client::new(rocket()).unwrap();
+        let mut response = client.get("/echo").dispatch();
+        assert_eq!(response.body_string(), Some("not a websocket".into()));
+    }
+
+    #[test]
+    fn bad_handshakes_are_rejected() {
+        let client = Client::new(rocket()).unwrap();
+        let response = client.post("/echo").websocket().unwrap_err();
+        assert_eq!(response.status(), Status::NotFound);
+
+        let bad_requests = vec![
+            client.get("/echo").header(Header::new("Sec-WebSocket-Version", "8")),
+            client.get("/echo").header(Header::new("Sec-WebSocket-Key", "c2hvcnQ=")),
+            client.get("/echo").header(Header::new("Connection", "keep-alive")),
+        ];
+
+        for request in bad_requests {
+            let response = request.websocket().unwrap_err();
+            assert_eq!(response.status(), Status::BadRequest);
+        }
+    }
+
+    #[test]
+    fn subprotocols_are_negotiated() {
+        let client = Client::new(rocket()).unwrap();
+        let ws = client.get("/chat")
+            .header(Header::new("Sec-WebSocket-Protocol", "chat.v2, chat.v1"))
+            .websocket()
+            .expect("handshake");
+
+        let protocol = ws.response().headers().get_one("Sec-WebSocket-Protocol");
+        assert_eq!(protocol, Some("chat.v1"));
+        ws.join().unwrap();
+
+        let response = client.get("/chat").websocket().unwrap_err();
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[test]
+    fn oversized_messages_close_the_connection() {
+        let config = Config::build(Environment::Development)
+            .limits(Limits::new().limit("websocket", 16))
+            .unwrap();
+
+        let rocket = rocket::custom(config)
+            .mount("/", routes![echo])
+            .manage(Arc::new(AtomicUsize::new(0)));
+
+        let client = Client::new(rocket).unwrap();
+        let mut ws = client.get("/echo").websocket().expect("handshake");
+        ws.send(Message::text("this message is too long")).unwrap();
+        match ws.recv().unwrap() {
+            Some(Message::Close(Some(frame))) => assert_eq!(frame.code, 1009),
+            other => panic!("expected a close, got {:?}", other),
+        }
+
+        ws.join().unwrap();
+    }
+
+    /// Writes a masked client frame with opcode `opcode` and `payload`.
+    fn write_frame(stream: &mut TcpStream, opcode: u8, payload: &[u8]) {
+        let mask = [1, 2, 3, 4];
+        let mut frame = vec![0x80 | opcode, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        stream.write_all(&frame).unwrap();
+    }
+
+    /// Reads an unmasked server frame, returning its opcode and payload.
+    fn read_frame(stream: &mut TcpStream) -> (u8, Vec<u8>) {
+        let mut head = [0u8; 2];
+        stream.read_exact(&mut head).unwrap();
+        assert_eq!(head[1] & 0x80, 0);
+
+        let mut payload = vec![0; (head[1] & 0x7F) as usize];
+        stream.read_exact(&mut payload).unwrap();
+        (head[0] & 0x0F, payload)
+    }
+
+    #[test]
+    fn network_conversation() {
+        let config = common::config().unwrap();
+
+        let running = rocket::custom(config)
+            .mount("/", routes![echo])
+            .manage(Arc::new(AtomicUsize::new(0)))
+            .spawn()
+            .expect("launch");
+
+        let mut stream = TcpStream::connect(running.addr()).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        stream.write_all(b"GET /echo HTTP/1.1\r\n\
+            Host: localhost\r\n\
+            Upgrade: websocket\r\n\
+            Connection: keep-alive, Upgrade\r\n\
+            Sec-WebSocket-Version: 13\r\n\
+            Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n").unwrap();
+
+        // Read the head of the response, byte by byte, to leave frames unread.
+        let mut head = vec![];
+        while !head.ends_with(b"\r\n\r\n") {
+            let mut byte = [0u8];
+            stream.read_exact(&mut byte).unwrap();
+            head.push(byte[0]);
+        }
+
+        let head = String::from_utf8(head).unwrap();
+        assert!(head.starts_with("HTTP/1.1 101 Switching Protocols"));
+        assert!(head.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"));
+
+        write_frame(&mut stream, 0x1, b"over the wire");
+        assert_eq!(read_frame(&mut stream), (0x1, b"1: over the wire".to_vec()));
+
+        write_frame(&mut stream, 0x8, &1000u16.to_be_bytes());
+        assert_eq!(read_frame(&mut stream), (0x8, 1000u16.to_be_bytes().to_vec()));
+
+        // The server closes the connection once the conversation is over.
+        let mut rest = vec![];
+        stream.read_to_end(&mut rest).unwrap();
+        assert!(rest.is_empty());
+        running.stop();
+    }
+}
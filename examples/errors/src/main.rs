@@ -25,6 +25,10 @@ fn main() {
         .register(catchers![not_found])
         .launch();
 
+    if let rocket::error::LaunchErrorKind::Shutdown = e.kind() {
+        return;
+    }
+
     println!("Whoops! Rocket didn't launch!");
     println!("This went wrong: {}", e);
 }
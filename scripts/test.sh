@@ -69,6 +69,7 @@ if [ "$1" = "--contrib" ]; then
     handlebars_templates
     serve
     helmet
+    openapi
     diesel_postgres_pool
     diesel_sqlite_pool
     diesel_mysql_pool